@@ -36,10 +36,22 @@ error_chain! {
             description("The provided value is not a known document format.")
             display("The provided value is not a known document format.")
         }
+        #[doc = "The document version is not compatible with this version of the library."]
+        IncompatibleVersion(version: String) {
+            description("The document version is not compatible with this version of the library.")
+            display("The document version '{}' is not compatible with this version ({}) of the library.", version, env!("CARGO_PKG_VERSION"))
+        }
+        #[doc = "An image file referenced by the document could not be read."]
+        ImageNotReadable(path: String) {
+            description("An image file referenced by the document could not be read.")
+            display("The image file '{}' referenced by the document could not be read.", path)
+        }
     }
     foreign_links {
         Fmt(::std::fmt::Error);
         Io(::std::io::Error);
-        Serde(::serde_json::Error) #[cfg(feature = "fmt_json")];
+        Serde(::serde_json::Error) #[cfg(any(feature = "fmt_json", feature = "fmt_pandoc"))];
+        Utf8(::std::string::FromUtf8Error);
+        Zip(::zip::result::ZipError) #[cfg(any(feature = "fmt_docx", feature = "fmt_epub", feature = "fmt_odf"))];
     }
 }
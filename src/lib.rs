@@ -182,7 +182,6 @@ pub mod error;
 
 pub mod model;
 
-#[cfg(feature = "fmt_json")]
 pub mod read;
 
 pub mod write;
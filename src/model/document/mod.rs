@@ -310,12 +310,12 @@ impl Metadata {
                 value
                     .email
                     .as_ref()
-                    .map(|s| format!("- email: {}\n", s))
+                    .map(|s| format!("\n- email: {}", s))
                     .unwrap_or_default(),
                 value
                     .organization
                     .as_ref()
-                    .map(|s| format!("- organization: {}", s))
+                    .map(|s| format!("\n- organization: {}", s))
                     .unwrap_or_default()
             ),
             Metadata::Copyright(value) => format!(
@@ -324,17 +324,17 @@ impl Metadata {
                 value
                     .organization
                     .as_ref()
-                    .map(|s| format!("- organization: {}\n", s))
+                    .map(|s| format!("\n- organization: {}", s))
                     .unwrap_or_default(),
                 value
                     .comment
                     .as_ref()
-                    .map(|s| format!("- comment: {}", s))
+                    .map(|s| format!("\n- comment: {}", s))
                     .unwrap_or_default()
             ),
             Metadata::Date(value) => self.yaml_one(value),
             Metadata::Keywords(value) => self.yaml_one(&format!("[{}]", value.join(", "))),
-            Metadata::Revision(value) => value.to_string(),
+            Metadata::Revision(value) => self.yaml_one(value),
             Metadata::Status(value) => self.yaml_one(value),
             Metadata::Title(value) => self.yaml_one(value),
             Metadata::Other(value) => self.yaml_one(&value.value),
@@ -315,8 +315,7 @@ fn unformat(s: &mut String, content: &[InlineContent]) -> String {
             },
             InlineContent::LineBreak => s.push('\n'),
             InlineContent::Span(value) => {
-                let s2 = unformat(s, value.inner());
-                s.push_str(&s2)
+                let _ = unformat(s, value.inner());
             }
             _ => {}
         }
@@ -0,0 +1,721 @@
+/*!
+Read a document from simple HTML. This reader is the counterpart to the
+[`html`](../../write/html/index.html) writer, but is also intended to import simple HTML
+fragments from other tools; it is not a general purpose HTML parser and ignores any styling.
+
+* The `title` element and any `meta` elements with `name` and `content` attributes become
+  `Metadata` values.
+* Heading elements `h1`–`h6` become `Heading` values, with the corresponding level.
+* `p` elements become `Paragraph` values, `blockquote` elements become `Quote` values, and `hr`
+  elements become `ThematicBreak` values.
+* `ul` and `ol` elements become (possibly nested) `List` values, and `dl` elements become
+  `DefinitionList` values.
+* A `pre` element containing only a `code` element becomes a `CodeBlock`, the language is taken
+  from the first class name of the `code` element; any other `pre` element becomes `Formatted`.
+* `table` elements become `Table` values, with the header row providing the columns.
+* A `div` containing only an image, or only display math (`\[ ... \]`), becomes an `ImageBlock`
+  or `MathBlock` respectively.
+* `a` and `img` elements become `HyperLink` and `Image` values, links to a fragment such as
+  `#label` are treated as internal.
+* `em`, `strong`, `del`, `ins`, `sup`, `sub`, and `code` elements become styled `Span` values.
+* HTML comments become `BlockContent::Comment` values.
+* Any `id` attribute on a block, list item, definition, or table cell becomes its `Label`.
+
+Any element not in the list above is flattened, that is its content is read as if the element
+were not present.
+
+# Example
+
+```rust
+use somedoc::model::HasInnerContent;
+use somedoc::read::html::from_str;
+
+let doc = from_str("<h1 id=\"intro\">Heading</h1><p>Some <em>styled</em> text.</p>").unwrap();
+assert_eq!(doc.inner().len(), 2);
+```
+
+*/
+
+use crate::error;
+use crate::model::block::{
+    Alignment, BlockContent, Caption, Cell, CodeBlock, Column, Definition, DefinitionList,
+    DefinitionPart, Formatted, HasCaption, HasLabel, Heading, HeadingLevel, ImageBlock, Item,
+    Label, List, ListKind, MathBlock, Paragraph, Quote, Row, Table,
+};
+use crate::model::document::{Author, Copyright, Metadata};
+use crate::model::inline::{Character, Image, InlineContent, Math, Span, SpanStyle};
+use crate::model::{Document, HasInnerContent, HasStyles};
+use crate::read::utils::{block_as_inline, hyper_link, metadata_from, push_text, trim_inline};
+use html5gum::{DefaultEmitter, Token, Tokenizer};
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::io::Read;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+/// Parse a `Document` instance from the HTML provided in the `html` string.
+pub fn from_str(html: &str) -> error::Result<Document> {
+    let nodes = parse(html);
+    let mut doc = Document::default();
+    read_metadata(&nodes, &mut doc);
+    for block in read_blocks(&nodes)? {
+        doc.add_content(block)?;
+    }
+    Ok(doc)
+}
+
+/// Read from the provided `reader` and parse the HTML into a `Document` instance.
+pub fn from_reader(reader: impl Read) -> error::Result<Document> {
+    let mut reader = reader;
+    let mut html = String::new();
+    let _ = reader.read_to_string(&mut html)?;
+    from_str(&html)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug)]
+enum Node {
+    Element(Element),
+    Text(String),
+    Comment(String),
+}
+
+#[derive(Clone, Debug)]
+struct Element {
+    name: String,
+    attributes: BTreeMap<String, String>,
+    children: Vec<Node>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+lazy_static! {
+    static ref WHITESPACE_RE: Regex = Regex::new(r"[ \t\r\n\f]+").unwrap();
+    static ref INLINE_MATH_RE: Regex = Regex::new(r"(?s)\\\((.*?)\\\)").unwrap();
+    static ref BLOCK_MATH_RE: Regex = Regex::new(r"(?s)^\s*\\\[(.*)\\\]\s*$").unwrap();
+    static ref PRE_END_RE: Regex = Regex::new(r"\n[ \t]*$").unwrap();
+    static ref AUTHOR_RE: Regex =
+        Regex::new(r"^([^<]*?)\s*(?:<([^>]*)>)?\s*(?:-\s+(.*))?$").unwrap();
+    static ref COPYRIGHT_RE: Regex =
+        Regex::new(r"^(\d{1,5})(?:\s+([^(]*?))?\s*(?:\((.*)\))?$").unwrap();
+}
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "details",
+    "div",
+    "dl",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hr",
+    "html",
+    "link",
+    "main",
+    "meta",
+    "nav",
+    "noscript",
+    "ol",
+    "p",
+    "pre",
+    "script",
+    "section",
+    "style",
+    "table",
+    "template",
+    "title",
+    "ul",
+];
+
+const IGNORED_ELEMENTS: &[&str] = &[
+    "head", "link", "meta", "noscript", "script", "style", "template", "title",
+];
+
+impl Element {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            attributes: Default::default(),
+            children: Default::default(),
+        }
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(String::as_str)
+    }
+
+    fn label(&self) -> Option<Label> {
+        match self.attribute("id") {
+            Some(id) if Label::is_valid(id) => Label::from_str(id).ok(),
+            _ => None,
+        }
+    }
+
+    fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(element) => Some(element),
+            _ => None,
+        })
+    }
+
+    /// All content, ignoring whitespace-only text.
+    fn significant_children(&self) -> Vec<&Node> {
+        self.children
+            .iter()
+            .filter(|node| !matches!(node, Node::Text(text) if text.trim().is_empty()))
+            .collect()
+    }
+
+    fn text(&self) -> String {
+        let mut text = String::new();
+        for node in &self.children {
+            match node {
+                Node::Element(element) => text.push_str(&element.text()),
+                Node::Text(value) => text.push_str(value),
+                Node::Comment(_) => {}
+            }
+        }
+        text
+    }
+
+    fn add_text(&mut self, text: &str) {
+        if let Some(Node::Text(last)) = self.children.last_mut() {
+            last.push_str(text);
+        } else {
+            self.children.push(Node::Text(text.to_string()));
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Construct a simple tree from the HTML tokens. This does not implement the HTML tree
+/// construction rules, it simply honors void and self-closing elements, closes elements that are
+/// implicitly ended by a following start tag, and ignores unmatched end tags.
+///
+fn parse(html: &str) -> Vec<Node> {
+    let mut emitter = DefaultEmitter::default();
+    emitter.naively_switch_states(true);
+
+    let mut stack: Vec<Element> = vec![Element::new("")];
+    for token in Tokenizer::new_with_emitter(html, emitter).flatten() {
+        match token {
+            Token::StartTag(tag) => {
+                let name = String::from_utf8_lossy(&tag.name).to_lowercase();
+                while stack.len() > 1 && is_implied_end(&stack.last().unwrap().name, &name) {
+                    close_element(&mut stack);
+                }
+                let mut element = Element::new(&name);
+                for (key, value) in tag.attributes.iter() {
+                    let _ = element.attributes.insert(
+                        String::from_utf8_lossy(key).to_lowercase(),
+                        String::from_utf8_lossy(value).to_string(),
+                    );
+                }
+                if tag.self_closing || VOID_ELEMENTS.contains(&name.as_str()) {
+                    stack
+                        .last_mut()
+                        .unwrap()
+                        .children
+                        .push(Node::Element(element));
+                } else {
+                    stack.push(element);
+                }
+            }
+            Token::EndTag(tag) => {
+                let name = String::from_utf8_lossy(&tag.name).to_lowercase();
+                if let Some(index) = stack.iter().rposition(|element| element.name == name) {
+                    while index > 0 && stack.len() > index {
+                        close_element(&mut stack);
+                    }
+                }
+            }
+            Token::String(text) => stack
+                .last_mut()
+                .unwrap()
+                .add_text(&String::from_utf8_lossy(&text)),
+            Token::Comment(text) => stack
+                .last_mut()
+                .unwrap()
+                .children
+                .push(Node::Comment(String::from_utf8_lossy(&text).to_string())),
+            _ => {}
+        }
+    }
+    while stack.len() > 1 {
+        close_element(&mut stack);
+    }
+    stack.pop().unwrap().children
+}
+
+fn close_element(stack: &mut Vec<Element>) {
+    let element = stack.pop().unwrap();
+    stack
+        .last_mut()
+        .unwrap()
+        .children
+        .push(Node::Element(element));
+}
+
+fn is_implied_end(open: &str, starting: &str) -> bool {
+    match open {
+        "li" => starting == "li",
+        "dt" | "dd" => starting == "dt" || starting == "dd",
+        "tr" => starting == "tr",
+        "td" | "th" => starting == "td" || starting == "th" || starting == "tr",
+        "p" => BLOCK_ELEMENTS.contains(&starting),
+        _ => false,
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+fn read_metadata(nodes: &[Node], doc: &mut Document) {
+    for node in nodes {
+        if let Node::Element(element) = node {
+            match element.name.as_str() {
+                "html" | "head" => read_metadata(&element.children, doc),
+                "title" => {
+                    let title = collapse_whitespace(&element.text());
+                    if !title.trim().is_empty() {
+                        let _ = doc.add_metadata(Metadata::Title(title.trim().to_string()));
+                    }
+                }
+                "meta" => {
+                    if let (Some(name), Some(content)) =
+                        (element.attribute("name"), element.attribute("content"))
+                    {
+                        let _ = doc.add_metadata(meta_data(name, content));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+///
+/// The HTML writer uses a slightly different form for author and copyright values than that
+/// provided by `Metadata::value_string`.
+///
+fn meta_data(name: &str, content: &str) -> Metadata {
+    match name.to_lowercase().as_str() {
+        "author" => match AUTHOR_RE.captures(content.trim()) {
+            Some(captures) => Metadata::Author(Author {
+                name: captures.get(1).unwrap().as_str().to_string(),
+                email: captures.get(2).map(|m| m.as_str().trim().to_string()),
+                organization: captures.get(3).map(|m| m.as_str().trim().to_string()),
+            }),
+            None => Metadata::Author(Author {
+                name: content.trim().to_string(),
+                email: None,
+                organization: None,
+            }),
+        },
+        "copyright" => match COPYRIGHT_RE.captures(content.trim()) {
+            Some(captures) => Metadata::Copyright(Copyright {
+                year: captures[1].parse().unwrap_or_default(),
+                organization: captures
+                    .get(2)
+                    .map(|m| m.as_str().trim().to_string())
+                    .filter(|s| !s.is_empty()),
+                comment: captures.get(3).map(|m| m.as_str().trim().to_string()),
+            }),
+            None => metadata_from(name, content),
+        },
+        _ => metadata_from(name, content),
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+fn read_blocks(nodes: &[Node]) -> error::Result<Vec<BlockContent>> {
+    let mut blocks: Vec<BlockContent> = Default::default();
+    let mut loose: Vec<InlineContent> = Default::default();
+    for node in nodes {
+        match node {
+            Node::Element(element) if BLOCK_ELEMENTS.contains(&element.name.as_str()) => {
+                flush_paragraph(&mut loose, &mut blocks);
+                blocks.extend(read_block(element)?);
+            }
+            Node::Comment(comment) => {
+                flush_paragraph(&mut loose, &mut blocks);
+                blocks.push(BlockContent::Comment(comment.trim().to_string()));
+            }
+            _ => read_inline(node, &mut loose)?,
+        }
+    }
+    flush_paragraph(&mut loose, &mut blocks);
+    Ok(blocks)
+}
+
+fn flush_paragraph(inline: &mut Vec<InlineContent>, blocks: &mut Vec<BlockContent>) {
+    let inline = trim_inline(std::mem::take(inline));
+    if !inline.is_empty() {
+        blocks.push(Paragraph::from(inline).into());
+    }
+}
+
+fn read_block(element: &Element) -> error::Result<Vec<BlockContent>> {
+    let label = element.label();
+    let block: BlockContent = match element.name.as_str() {
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+            let mut heading = Heading::from(trim_inline(read_inlines(&element.children)?));
+            let level = element.name[1..].parse::<u8>().unwrap();
+            let _ = heading.set_level(HeadingLevel::try_from(level).unwrap_or_default());
+            labeled(heading, label).into()
+        }
+        "p" => labeled(
+            Paragraph::from(trim_inline(read_inlines(&element.children)?)),
+            label,
+        )
+        .into(),
+        "blockquote" => {
+            let mut quote = Quote::default();
+            for block in read_blocks(&element.children)? {
+                quote.add_content(block)?;
+            }
+            labeled(quote, label).into()
+        }
+        "ul" | "ol" => read_list(element)?.into(),
+        "dl" => read_definition_list(element)?.into(),
+        "pre" => read_pre(element),
+        "table" => read_table(element)?.into(),
+        "hr" => BlockContent::ThematicBreak,
+        "div" | "figure" => match element.significant_children().as_slice() {
+            [Node::Element(image)] if image.name == "img" && image.attribute("src").is_some() => {
+                labeled(ImageBlock::from(read_image(image)), label).into()
+            }
+            [Node::Element(image), Node::Element(caption)]
+                if image.name == "img"
+                    && image.attribute("src").is_some()
+                    && caption.name == "figcaption" =>
+            {
+                let caption = collapse_whitespace(&caption.text());
+                labeled(
+                    ImageBlock::with_caption_str(read_image(image), caption.trim()),
+                    label,
+                )
+                .into()
+            }
+            [Node::Text(text)] if BLOCK_MATH_RE.is_match(text) => {
+                let math = BLOCK_MATH_RE.captures(text).unwrap()[1].trim().to_string();
+                labeled(MathBlock::from(Math::from_str(&math)?), label).into()
+            }
+            _ => return read_blocks(&element.children),
+        },
+        name if IGNORED_ELEMENTS.contains(&name) => return Ok(Default::default()),
+        _ => return read_blocks(&element.children),
+    };
+    Ok(vec![block])
+}
+
+fn read_list(element: &Element) -> error::Result<List> {
+    let mut list = labeled(
+        List::new(if element.name == "ol" {
+            ListKind::Ordered
+        } else {
+            ListKind::Unordered
+        }),
+        element.label(),
+    );
+    for child in element.elements() {
+        match child.name.as_str() {
+            "li" => {
+                let mut inline: Vec<InlineContent> = Default::default();
+                let mut sub_lists: Vec<List> = Default::default();
+                for node in &child.children {
+                    match node {
+                        Node::Element(inner) if inner.name == "ul" || inner.name == "ol" => {
+                            sub_lists.push(read_list(inner)?)
+                        }
+                        Node::Element(inner) if BLOCK_ELEMENTS.contains(&inner.name.as_str()) => {
+                            for block in read_block(inner)? {
+                                let block = trim_inline(block_as_inline(block));
+                                if !block.is_empty() {
+                                    if !trim_inline(inline.clone()).is_empty() {
+                                        inline.push(InlineContent::LineBreak);
+                                    }
+                                    inline.extend(block);
+                                }
+                            }
+                        }
+                        _ => read_inline(node, &mut inline)?,
+                    }
+                }
+                let inline = trim_inline(inline);
+                if !inline.is_empty() || sub_lists.is_empty() {
+                    let _ = list.add_item(labeled(Item::from(inline), child.label()));
+                }
+                for sub_list in sub_lists {
+                    let _ = list.add_sub_list(sub_list);
+                }
+            }
+            "ul" | "ol" => {
+                let _ = list.add_sub_list(read_list(child)?);
+            }
+            _ => {}
+        }
+    }
+    Ok(list)
+}
+
+fn read_definition_list(element: &Element) -> error::Result<DefinitionList> {
+    let mut list = labeled(DefinitionList::default(), element.label());
+    let mut term: Option<(String, Option<Label>)> = None;
+    for child in element.elements() {
+        match child.name.as_str() {
+            "dt" => {
+                if let Some((term, label)) = term.take() {
+                    let _ = list.add_definition(labeled(
+                        Definition::new(&term, DefinitionPart::default()),
+                        label,
+                    ));
+                }
+                term = Some((
+                    collapse_whitespace(&child.text()).trim().to_string(),
+                    child.label(),
+                ));
+            }
+            "dd" => {
+                let (term, label) = term.take().unwrap_or_default();
+                let text = DefinitionPart::from(trim_inline(read_inlines(&child.children)?));
+                let _ = list.add_definition(labeled(Definition::new(&term, text), label));
+            }
+            _ => {}
+        }
+    }
+    if let Some((term, label)) = term {
+        let _ = list.add_definition(labeled(
+            Definition::new(&term, DefinitionPart::default()),
+            label,
+        ));
+    }
+    Ok(list)
+}
+
+fn read_pre(element: &Element) -> BlockContent {
+    match element.significant_children().as_slice() {
+        [Node::Element(code)] if code.name == "code" => {
+            let text = pre_text(&code.text());
+            let language = code
+                .attribute("class")
+                .and_then(|class| class.split_whitespace().next())
+                .map(|class| {
+                    class
+                        .trim_start_matches("language-")
+                        .trim_start_matches("lang-")
+                });
+            let code_block = match language {
+                Some(language) if !language.is_empty() => CodeBlock::with_language(&text, language),
+                _ => CodeBlock::from(text),
+            };
+            labeled(code_block, element.label().or_else(|| code.label())).into()
+        }
+        _ => labeled(Formatted::from(pre_text(&element.text())), element.label()).into(),
+    }
+}
+
+fn read_table(element: &Element) -> error::Result<Table> {
+    let mut table = labeled(Table::default(), element.label());
+    let mut rows: Vec<&Element> = Default::default();
+    for child in element.elements() {
+        match child.name.as_str() {
+            "caption" => {
+                let caption = collapse_whitespace(&child.text());
+                let _ = table.set_caption(Caption::from(caption.trim()));
+            }
+            "thead" | "tbody" | "tfoot" => {
+                rows.extend(child.elements().filter(|row| row.name == "tr"));
+            }
+            "tr" => rows.push(child),
+            _ => {}
+        }
+    }
+    for row in rows {
+        let cells: Vec<&Element> = row
+            .elements()
+            .filter(|cell| cell.name == "th" || cell.name == "td")
+            .collect();
+        if !table.has_columns() && !cells.is_empty() && cells.iter().all(|cell| cell.name == "th") {
+            for cell in cells {
+                table.add_column(Column::with_alignment(
+                    collapse_whitespace(&cell.text()).trim(),
+                    cell_alignment(cell),
+                ));
+            }
+        } else {
+            let mut new_row = Row::new(&[]);
+            for cell in cells {
+                let _ = new_row.add_cell(labeled(
+                    Cell::from(trim_inline(read_inlines(&cell.children)?)),
+                    cell.label(),
+                ));
+            }
+            table.add_row(new_row);
+        }
+    }
+    Ok(table)
+}
+
+// ------------------------------------------------------------------------------------------------
+
+fn read_inlines(nodes: &[Node]) -> error::Result<Vec<InlineContent>> {
+    let mut inline: Vec<InlineContent> = Default::default();
+    for node in nodes {
+        read_inline(node, &mut inline)?;
+    }
+    Ok(inline)
+}
+
+fn read_inline(node: &Node, inline: &mut Vec<InlineContent>) -> error::Result<()> {
+    match node {
+        Node::Text(text) => push_html_text(inline, text)?,
+        Node::Comment(_) => {}
+        Node::Element(element) => match element.name.as_str() {
+            "a" => match element.attribute("href") {
+                Some(href) if !href.is_empty() => {
+                    let caption = collapse_whitespace(&element.text());
+                    inline.push(hyper_link(href, caption.trim()).into());
+                }
+                _ => inline.extend(read_inlines(&element.children)?),
+            },
+            "img" => match element.attribute("src") {
+                Some(src) if !src.is_empty() => inline.push(read_image(element).into()),
+                _ => push_text(inline, element.attribute("alt").unwrap_or_default()),
+            },
+            "br" => inline.push(InlineContent::LineBreak),
+            "em" | "i" | "cite" | "dfn" | "var" => {
+                inline.push(read_span(element, SpanStyle::Italic)?)
+            }
+            "strong" | "b" => inline.push(read_span(element, SpanStyle::Bold)?),
+            "del" | "s" | "strike" => inline.push(read_span(element, SpanStyle::Strikethrough)?),
+            "ins" | "u" => inline.push(read_span(element, SpanStyle::Underline)?),
+            "sup" => inline.push(read_span(element, SpanStyle::Superscript)?),
+            "sub" => inline.push(read_span(element, SpanStyle::Subscript)?),
+            "code" | "kbd" | "samp" | "tt" => {
+                inline.push(Span::with_style(&element.text(), SpanStyle::Code).into())
+            }
+            name if IGNORED_ELEMENTS.contains(&name) => {}
+            _ => inline.extend(read_inlines(&element.children)?),
+        },
+    }
+    Ok(())
+}
+
+fn read_span(element: &Element, style: SpanStyle) -> error::Result<InlineContent> {
+    let mut span = Span::from(read_inlines(&element.children)?);
+    span.add_style(style)?;
+    Ok(span.into())
+}
+
+fn read_image(element: &Element) -> Image {
+    let src = element.attribute("src").unwrap_or_default();
+    match element.attribute("alt") {
+        Some(alt) if !alt.trim().is_empty() => Image::with_alt_text(src, alt.trim()),
+        _ => Image::new(src),
+    }
+}
+
+///
+/// Add text, collapsing whitespace as a browser would, and recognizing non-breaking spaces and
+/// the inline math delimiters `\(` and `\)` written by the HTML writer.
+///
+fn push_html_text(inline: &mut Vec<InlineContent>, text: &str) -> error::Result<()> {
+    let text = collapse_whitespace(text);
+    let mut last = 0;
+    for captures in INLINE_MATH_RE.captures_iter(&text) {
+        let whole = captures.get(0).unwrap();
+        push_plain_text(inline, &text[last..whole.start()]);
+        inline.push(Math::from_str(captures[1].trim())?.into());
+        last = whole.end();
+    }
+    push_plain_text(inline, &text[last..]);
+    Ok(())
+}
+
+fn push_plain_text(inline: &mut Vec<InlineContent>, text: &str) {
+    for (i, part) in text.split('\u{a0}').enumerate() {
+        if i > 0 {
+            inline.push(Character::NonBreakSpace.into());
+        }
+        let part = match inline.last() {
+            Some(InlineContent::Text(last)) if last.inner().ends_with(' ') => {
+                part.trim_start_matches(' ')
+            }
+            _ => part,
+        };
+        push_text(inline, part);
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    WHITESPACE_RE.replace_all(text, " ").to_string()
+}
+
+///
+/// Remove the newline that may follow the opening `pre` tag, and the whitespace that may precede
+/// the closing tag.
+///
+fn pre_text(text: &str) -> String {
+    let text = text.strip_prefix('\n').unwrap_or(text);
+    PRE_END_RE.replace(text, "").to_string()
+}
+
+fn cell_alignment(cell: &Element) -> Alignment {
+    let style = cell.attribute("style").unwrap_or_default().to_lowercase();
+    let align = cell
+        .attribute("align")
+        .map(str::to_lowercase)
+        .or_else(|| {
+            style
+                .split(';')
+                .filter_map(|rule| rule.split_once(':'))
+                .find(|(property, _)| property.trim() == "text-align")
+                .map(|(_, value)| value.trim().to_string())
+        })
+        .unwrap_or_default();
+    match align.as_str() {
+        "right" => Alignment::Right,
+        "center" => Alignment::Centered,
+        "justify" => Alignment::Justified,
+        _ => Alignment::Left,
+    }
+}
+
+fn labeled<T: HasLabel>(value: T, label: Option<Label>) -> T {
+    let mut value = value;
+    if let Some(label) = label {
+        let _ = value.set_label(label);
+    }
+    value
+}
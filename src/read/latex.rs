@@ -0,0 +1,1229 @@
+/*!
+Read a document from LaTeX. This reader understands the subset of LaTeX produced by the
+[`latex`](../../write/latex/index.html) writer, which is also the subset most commonly found in
+simple reports, and rebuilds the document model from it.
+
+* The `\title`, `\author`, and `\date` commands become `Metadata` values; other preamble content
+  is ignored.
+* The `\section`, `\subsection`, ... commands become `Heading` values, with the number of `sub`
+  prefixes determining the level.
+* The `itemize` and `enumerate` environments become (possibly nested) `List` values, and the
+  `description` environment becomes a `DefinitionList`.
+* The `lstlisting` environment becomes a `CodeBlock`, including the `language`, `caption`, and
+  `label` options; the `verbatim` environment becomes a `Formatted` value.
+* The `quote`, `quotation`, and `displayquote` environments become `Quote` values.
+* The `tabular` environment becomes a `Table`, the first row is used for the column headings and
+  the column specification for the column alignment.
+* The `figure`, `table`, and `equfloat` environments provide the caption and label for the
+  image, table, or equation they contain.
+* The `equation`, `displaymath`, and `align` environments, along with `\[ ... \]` and `$$ ... $$`,
+  become `MathBlock` values; inline math (`\( ... \)` or `$ ... $`) becomes `Math` values.
+* A `\label` command preceding (or immediately following a heading) becomes the block's label.
+* Text style commands such as `\textbf` and `\textit`, link commands such as `\href` and
+  `\hyperref`, and `\includegraphics` become the corresponding inline values.
+* The `\tableofcontents` and other list commands become `FrontMatter` values.
+* Comment lines become `BlockContent::Comment` values.
+
+Anything that cannot be mapped is not dropped: unsupported environments are retained, as source,
+in a `BlockContent::Comment` and unsupported inline commands are passed through as text.
+
+# Example
+
+```rust
+use somedoc::model::HasInnerContent;
+use somedoc::read::latex::from_str;
+
+let doc = from_str(r"\section{Heading}
+
+Some \textit{styled} text.
+").unwrap();
+assert_eq!(doc.inner().len(), 2);
+```
+
+*/
+
+use crate::error;
+use crate::model::block::{
+    Alignment, BlockContent, Caption, Cell, CodeBlock, Column, Definition, DefinitionList,
+    DefinitionPart, Formatted, FrontMatter, HasCaption, HasLabel, Heading, HeadingLevel,
+    ImageBlock, Item, Label, List, ListKind, MathBlock, Paragraph, Quote, Row, Table,
+};
+use crate::model::document::{Author, Metadata};
+use crate::model::inline::text::Size;
+use crate::model::inline::{
+    Character, HasInlineContent, HyperLink, Image, InlineContent, Math, Span, SpanStyle,
+};
+use crate::model::{Document, HasInnerContent, HasStyles};
+use crate::read::utils::{block_as_inline, push_text, set_block_label, trim_inline};
+use regex::Regex;
+use std::convert::TryFrom;
+use std::io::Read;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+/// Parse a `Document` instance from the LaTeX provided in the `latex` string.
+pub fn from_str(latex: &str) -> error::Result<Document> {
+    let mut reader = LatexReader::default();
+    let blocks = match (latex.find(BEGIN_DOCUMENT), latex.rfind(END_DOCUMENT)) {
+        (Some(begin), end) => {
+            // the preamble only contributes metadata, its other content is configuration.
+            let _ = reader.read_blocks(&latex[..begin])?;
+            let end = end.filter(|end| *end > begin).unwrap_or(latex.len());
+            reader.read_blocks(&latex[begin + BEGIN_DOCUMENT.len()..end])?
+        }
+        _ => reader.read_blocks(latex)?,
+    };
+    let mut doc = Document::default();
+    for meta_datum in reader.metadata {
+        let _ = doc.add_metadata(meta_datum);
+    }
+    for block in blocks {
+        doc.add_content(block)?;
+    }
+    Ok(doc)
+}
+
+/// Read from the provided `reader` and parse the LaTeX into a `Document` instance.
+pub fn from_reader(reader: impl Read) -> error::Result<Document> {
+    let mut reader = reader;
+    let mut latex = String::new();
+    let _ = reader.read_to_string(&mut latex)?;
+    from_str(&latex)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+struct LatexReader {
+    metadata: Vec<Metadata>,
+}
+
+#[derive(Debug)]
+struct Cursor<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+#[derive(Debug)]
+enum Environment {
+    Block(BlockContent),
+    Blocks(Vec<BlockContent>),
+    Raw,
+}
+
+#[derive(Debug)]
+struct ListEntry {
+    label: Option<Label>,
+    term: Option<String>,
+    content: String,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+const BEGIN_DOCUMENT: &str = "\\begin{document}";
+const END_DOCUMENT: &str = "\\end{document}";
+
+lazy_static! {
+    static ref SECTION_RE: Regex = Regex::new(r"^((?:sub)*)section\*?$").unwrap();
+    static ref BLANK_LINE_RE: Regex = Regex::new(r"\n[ \t]*\n").unwrap();
+    static ref END_WHITESPACE_RE: Regex = Regex::new(r"\n[ \t]*$").unwrap();
+}
+
+/// Commands that are only used to configure the document, they and their arguments are ignored.
+const SETUP_COMMANDS: &[&str] = &[
+    "DeclareCaptionType",
+    "bigskip",
+    "centering",
+    "clearpage",
+    "documentclass",
+    "hspace",
+    "maketitle",
+    "medskip",
+    "newcommand",
+    "newenvironment",
+    "newpage",
+    "noindent",
+    "pagestyle",
+    "raggedleft",
+    "raggedright",
+    "renewcommand",
+    "renewenvironment",
+    "smallskip",
+    "thispagestyle",
+    "usepackage",
+    "vspace",
+];
+
+const MATH_ENVIRONMENTS: &[&str] = &[
+    "align",
+    "align*",
+    "displaymath",
+    "equation",
+    "equation*",
+    "gather",
+    "gather*",
+    "multline",
+    "multline*",
+];
+
+impl LatexReader {
+    fn read_blocks(&mut self, source: &str) -> error::Result<Vec<BlockContent>> {
+        let mut blocks: Vec<BlockContent> = Default::default();
+        let mut label: Option<Label> = None;
+        let mut paragraph_start: Option<usize> = None;
+        let mut cursor = Cursor::new(source);
+
+        macro_rules! flush_paragraph {
+            () => {
+                if let Some(start) = paragraph_start.take() {
+                    self.read_paragraph(&source[start..cursor.position], &mut label, &mut blocks)?;
+                }
+            };
+        }
+        macro_rules! push_block {
+            ($block:expr) => {{
+                let mut block: BlockContent = $block;
+                if let Some(label) = label.take() {
+                    set_block_label(&mut block, label);
+                }
+                blocks.push(block);
+            }};
+        }
+
+        while let Some(c) = cursor.peek() {
+            match c {
+                '%' if paragraph_start.is_none() || cursor.at_line_start() => {
+                    flush_paragraph!();
+                    let mut lines: Vec<String> = Default::default();
+                    while cursor.peek() == Some('%') {
+                        lines.push(comment_text(&cursor.read_line()));
+                        cursor.skip_whitespace_in_line();
+                    }
+                    blocks.push(BlockContent::Comment(lines.join("\n")));
+                }
+                '\n' if BLANK_LINE_RE
+                    .find_at(source, cursor.position)
+                    .map(|m| m.start())
+                    == Some(cursor.position) =>
+                {
+                    flush_paragraph!();
+                    cursor.skip_whitespace();
+                }
+                '$' if cursor.starts_with("$$") => {
+                    flush_paragraph!();
+                    cursor.advance(2);
+                    let math = cursor.read_until("$$");
+                    push_block!(MathBlock::from(Math::from_str(math.trim())?).into());
+                }
+                '\\' => {
+                    let start = cursor.position;
+                    cursor.advance(1);
+                    let name = cursor.read_command_name();
+                    match name.as_str() {
+                        "begin" => {
+                            flush_paragraph!();
+                            let env_name = cursor.read_group('{', '}').unwrap_or_default();
+                            match self.read_environment(&env_name, &mut cursor)? {
+                                Environment::Block(block) => push_block!(block),
+                                Environment::Blocks(inner) => {
+                                    for block in inner {
+                                        push_block!(block);
+                                    }
+                                }
+                                Environment::Raw => blocks.push(BlockContent::Comment(
+                                    source[start..cursor.position].to_string(),
+                                )),
+                            }
+                        }
+                        "[" => {
+                            flush_paragraph!();
+                            let math = cursor.read_until("\\]");
+                            let (math, inner_label) = extract_label(&math);
+                            let mut block: BlockContent =
+                                MathBlock::from(Math::from_str(math.trim())?).into();
+                            if let Some(inner_label) = inner_label {
+                                set_block_label(&mut block, inner_label);
+                            }
+                            push_block!(block);
+                        }
+                        "label" if paragraph_start.is_none() => {
+                            label = cursor.read_group('{', '}').and_then(|s| make_label(&s));
+                        }
+                        "title" | "author" | "date" => {
+                            flush_paragraph!();
+                            let value = cursor.read_group('{', '}').unwrap_or_default();
+                            self.add_metadata(&name, &value);
+                        }
+                        "tableofcontents" | "listoffigures" | "listoftables"
+                        | "listofequfloats" | "lstlistoflistings" => {
+                            flush_paragraph!();
+                            push_block!(BlockContent::FrontMatter(match name.as_str() {
+                                "tableofcontents" => FrontMatter::TableOfContents,
+                                "listoffigures" => FrontMatter::TableOfFigures,
+                                "listoftables" => FrontMatter::TableOfTables,
+                                "listofequfloats" => FrontMatter::TableOfEquations,
+                                _ => FrontMatter::TableOfListings,
+                            }));
+                        }
+                        "thematicbreak" | "hrule" | "hrulefill" => {
+                            flush_paragraph!();
+                            push_block!(BlockContent::ThematicBreak);
+                        }
+                        "par" => flush_paragraph!(),
+                        name if SECTION_RE.is_match(name) => {
+                            flush_paragraph!();
+                            let level = SECTION_RE.captures(name).unwrap()[1].len() / 3 + 1;
+                            let _ = cursor.read_group('[', ']');
+                            let title = cursor.read_group('{', '}').unwrap_or_default();
+                            let mut heading = Heading::from(self.read_inlines(&title)?);
+                            let _ = heading
+                                .set_level(HeadingLevel::try_from(level as u8).unwrap_or_default());
+                            if label.is_none() {
+                                label = cursor.read_following_label();
+                            }
+                            push_block!(heading.into());
+                        }
+                        name if SETUP_COMMANDS.contains(&name) && paragraph_start.is_none() => {
+                            let _ = cursor.read_arguments(true);
+                        }
+                        _ => {
+                            if paragraph_start.is_none() {
+                                paragraph_start = Some(start);
+                            }
+                        }
+                    }
+                }
+                c => {
+                    if paragraph_start.is_none() && !c.is_whitespace() {
+                        paragraph_start = Some(cursor.position);
+                    }
+                    cursor.advance(c.len_utf8());
+                }
+            }
+        }
+        flush_paragraph!();
+        Ok(blocks)
+    }
+
+    fn read_paragraph(
+        &mut self,
+        source: &str,
+        label: &mut Option<Label>,
+        blocks: &mut Vec<BlockContent>,
+    ) -> error::Result<()> {
+        let (text, comments) = split_comments(source);
+        let inline = self.read_inlines(&text)?;
+        if !inline.is_empty() {
+            let mut block: BlockContent = match inline.as_slice() {
+                [InlineContent::Image(image)] => ImageBlock::from(image.clone()).into(),
+                _ => Paragraph::from(inline).into(),
+            };
+            if let Some(label) = label.take() {
+                set_block_label(&mut block, label);
+            }
+            blocks.push(block);
+        }
+        if !comments.is_empty() {
+            blocks.push(BlockContent::Comment(comments.join("\n")));
+        }
+        Ok(())
+    }
+
+    fn read_environment(
+        &mut self,
+        name: &str,
+        cursor: &mut Cursor<'_>,
+    ) -> error::Result<Environment> {
+        let arguments = cursor.read_arguments(false);
+        let body = cursor.read_environment_body(name);
+        Ok(match name {
+            "document" => Environment::Blocks(self.read_blocks(&body)?),
+            "itemize" | "enumerate" => Environment::Block(
+                self.read_list(
+                    &body,
+                    if name == "enumerate" {
+                        ListKind::Ordered
+                    } else {
+                        ListKind::Unordered
+                    },
+                )?
+                .into(),
+            ),
+            "description" => Environment::Block(self.read_definition_list(&body)?.into()),
+            "lstlisting" | "minted" => {
+                let mut code_block = CodeBlock::from(verbatim_text(&body));
+                let mut label: Option<Label> = None;
+                for (kind, value) in arguments {
+                    if kind == '{' {
+                        let _ = code_block.set_language(value.trim());
+                        continue;
+                    }
+                    for option in split_top_level(&value, ',') {
+                        if let Some((key, value)) = option.split_once('=') {
+                            let value = unbrace(value.trim());
+                            match key.trim() {
+                                "language" => {
+                                    let _ = code_block.set_language(value);
+                                }
+                                "caption" => {
+                                    let _ = code_block.set_caption(Caption::from(value));
+                                }
+                                "label" => label = make_label(value),
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                if let Some(label) = label {
+                    let _ = code_block.set_label(label);
+                }
+                Environment::Block(code_block.into())
+            }
+            "verbatim" | "verbatim*" => {
+                Environment::Block(Formatted::from(verbatim_text(&body)).into())
+            }
+            "quote" | "quotation" | "displayquote" => {
+                let mut quote = Quote::default();
+                for block in self.read_blocks(&body)? {
+                    quote.add_content(block)?;
+                }
+                Environment::Block(quote.into())
+            }
+            "tabular" | "tabular*" | "tabularx" => {
+                let spec = arguments
+                    .iter()
+                    .rev()
+                    .find(|(kind, _)| *kind == '{')
+                    .map(|(_, spec)| spec.clone())
+                    .unwrap_or_default();
+                Environment::Block(self.read_table(&spec, &body)?.into())
+            }
+            "figure" | "figure*" | "table" | "table*" | "equfloat" => {
+                Environment::Blocks(self.read_float(&body)?)
+            }
+            "center" | "flushleft" | "flushright" | "minipage" => {
+                Environment::Blocks(self.read_blocks(&body)?)
+            }
+            name if MATH_ENVIRONMENTS.contains(&name) => {
+                let (math, label) = extract_label(&body);
+                let mut math_block = MathBlock::from(Math::from_str(math.trim())?);
+                if let Some(label) = label {
+                    let _ = math_block.set_label(label);
+                }
+                Environment::Block(math_block.into())
+            }
+            _ => Environment::Raw,
+        })
+    }
+
+    fn read_float(&mut self, body: &str) -> error::Result<Vec<BlockContent>> {
+        let mut caption: Option<String> = None;
+        let mut label: Option<Label> = None;
+        let mut remaining = String::new();
+        let mut cursor = Cursor::new(body);
+        let mut last = 0;
+        while let Some(c) = cursor.peek() {
+            if c == '\\' {
+                let start = cursor.position;
+                cursor.advance(1);
+                match cursor.read_command_name().as_str() {
+                    "caption" => {
+                        let _ = cursor.read_group('[', ']');
+                        caption = cursor.read_group('{', '}');
+                    }
+                    "label" => label = cursor.read_group('{', '}').and_then(|s| make_label(&s)),
+                    "begin" => {
+                        let name = cursor.read_group('{', '}').unwrap_or_default();
+                        let _ = cursor.read_arguments(false);
+                        let _ = cursor.read_environment_body(&name);
+                        continue;
+                    }
+                    _ => continue,
+                }
+                remaining.push_str(&body[last..start]);
+                last = cursor.position;
+            } else {
+                cursor.advance(c.len_utf8());
+            }
+        }
+        remaining.push_str(&body[last..]);
+
+        let mut blocks = self.read_blocks(&remaining)?;
+        if let Some(block) = blocks.iter_mut().find(|block| {
+            matches!(
+                block,
+                BlockContent::Table(_)
+                    | BlockContent::ImageBlock(_)
+                    | BlockContent::MathBlock(_)
+                    | BlockContent::CodeBlock(_)
+            )
+        }) {
+            if let Some(caption) = caption {
+                let caption = Span::from(self.read_inlines(&caption)?).unformatted_string();
+                set_block_caption(block, Caption::from(caption));
+            }
+            if let Some(label) = label {
+                set_block_label(block, label);
+            }
+        }
+        Ok(blocks)
+    }
+
+    fn read_list(&mut self, body: &str, kind: ListKind) -> error::Result<List> {
+        let mut list = List::new(kind);
+        for entry in split_items(body) {
+            let mut inline: Vec<InlineContent> = Default::default();
+            let mut sub_lists: Vec<List> = Default::default();
+            for block in self.read_blocks(&entry.content)? {
+                match block {
+                    BlockContent::List(sub_list) => sub_lists.push(sub_list),
+                    block => {
+                        let block = block_as_inline(block);
+                        if !block.is_empty() {
+                            if !inline.is_empty() {
+                                inline.push(InlineContent::LineBreak);
+                            }
+                            inline.extend(block);
+                        }
+                    }
+                }
+            }
+            if !inline.is_empty() || sub_lists.is_empty() {
+                let mut item = Item::from(inline);
+                if let Some(label) = entry.label {
+                    let _ = item.set_label(label);
+                }
+                let _ = list.add_item(item);
+            }
+            for sub_list in sub_lists {
+                let _ = list.add_sub_list(sub_list);
+            }
+        }
+        Ok(list)
+    }
+
+    fn read_definition_list(&mut self, body: &str) -> error::Result<DefinitionList> {
+        let mut list = DefinitionList::default();
+        for entry in split_items(body) {
+            let term = Span::from(self.read_inlines(&entry.term.unwrap_or_default())?)
+                .unformatted_string();
+            let text = DefinitionPart::from(self.read_inlines(&entry.content)?);
+            let mut definition = Definition::new(&term, text);
+            if let Some(label) = entry.label {
+                let _ = definition.set_label(label);
+            }
+            let _ = list.add_definition(definition);
+        }
+        Ok(list)
+    }
+
+    fn read_table(&mut self, spec: &str, body: &str) -> error::Result<Table> {
+        let alignments: Vec<Alignment> = column_alignments(spec);
+        let mut table = Table::default();
+        for row in split_top_level(body, '\\') {
+            let row = strip_rules(&row);
+            if row.trim().is_empty() {
+                continue;
+            }
+            let cells = split_top_level(&row, '&');
+            if !table.has_columns() {
+                for (i, cell) in cells.iter().enumerate() {
+                    let text = Span::from(self.read_inlines(cell)?).unformatted_string();
+                    table.add_column(Column::with_alignment(
+                        &text,
+                        alignments.get(i).cloned().unwrap_or_default(),
+                    ));
+                }
+            } else {
+                let mut new_row = Row::new(&[]);
+                for cell in cells {
+                    let (cell, label) = extract_label(&cell);
+                    let mut new_cell = Cell::from(self.read_inlines(&cell)?);
+                    if let Some(label) = label {
+                        let _ = new_cell.set_label(label);
+                    }
+                    let _ = new_row.add_cell(new_cell);
+                }
+                table.add_row(new_row);
+            }
+        }
+        Ok(table)
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    fn read_inlines(&mut self, source: &str) -> error::Result<Vec<InlineContent>> {
+        let mut inline: Vec<InlineContent> = Default::default();
+        let mut cursor = Cursor::new(source);
+        while let Some(c) = cursor.peek() {
+            match c {
+                '\\' => self.read_inline_command(&mut cursor, &mut inline)?,
+                '{' => {
+                    let group = cursor.read_group('{', '}').unwrap_or_default();
+                    for content in self.read_inlines(&group)? {
+                        match content {
+                            InlineContent::Text(text) => push_text(&mut inline, text.inner()),
+                            content => inline.push(content),
+                        }
+                    }
+                }
+                '}' => cursor.advance(1),
+                '$' => {
+                    let delimiter = if cursor.starts_with("$$") { "$$" } else { "$" };
+                    cursor.advance(delimiter.len());
+                    let math = cursor.read_until(delimiter);
+                    inline.push(Math::from_str(math.trim())?.into());
+                }
+                '%' => {
+                    let _ = cursor.read_line();
+                }
+                '~' => {
+                    cursor.advance(1);
+                    inline.push(Character::NonBreakSpace.into());
+                }
+                '-' if cursor.starts_with("---") => {
+                    cursor.advance(3);
+                    inline.push(Character::EmDash.into());
+                }
+                '-' if cursor.starts_with("--") => {
+                    cursor.advance(2);
+                    inline.push(Character::EnDash.into());
+                }
+                c if c.is_whitespace() => {
+                    cursor.skip_whitespace();
+                    if !matches!(inline.last(), Some(InlineContent::Text(text)) if text.inner().ends_with(' '))
+                    {
+                        push_text(&mut inline, " ");
+                    }
+                }
+                _ => {
+                    let text = cursor.read_text();
+                    push_text(&mut inline, &text);
+                }
+            }
+        }
+        Ok(trim_inline(inline))
+    }
+
+    fn read_inline_command(
+        &mut self,
+        cursor: &mut Cursor<'_>,
+        inline: &mut Vec<InlineContent>,
+    ) -> error::Result<()> {
+        let start = cursor.position;
+        cursor.advance(1);
+        let name = cursor.read_command_name();
+        match name.as_str() {
+            "\\" | "newline" | "linebreak" => {
+                let _ = cursor.read_group('[', ']');
+                cursor.skip_whitespace_in_line();
+                inline.push(InlineContent::LineBreak);
+            }
+            "textit" | "emph" | "textsl" => self.read_span(cursor, SpanStyle::Italic, inline)?,
+            "textbf" => self.read_span(cursor, SpanStyle::Bold, inline)?,
+            "texttt" => self.read_span(cursor, SpanStyle::Mono, inline)?,
+            "sout" => self.read_span(cursor, SpanStyle::Strikethrough, inline)?,
+            "underline" | "uline" => self.read_span(cursor, SpanStyle::Underline, inline)?,
+            "textsc" => self.read_span(cursor, SpanStyle::SmallCaps, inline)?,
+            "textsuperscript" => self.read_span(cursor, SpanStyle::Superscript, inline)?,
+            "textsubscript" => self.read_span(cursor, SpanStyle::Subscript, inline)?,
+            "tiny" | "scriptsize" | "footnotesize" | "small" | "normalsize" | "large" | "Large"
+            | "LARGE" | "huge" | "Huge" => {
+                let size = match name.as_str() {
+                    "tiny" | "scriptsize" => Size::Smallest,
+                    "footnotesize" => Size::Smaller,
+                    "small" => Size::Small,
+                    "normalsize" => Size::Normal,
+                    "large" => Size::Large,
+                    "Large" => Size::Larger,
+                    _ => Size::Largest,
+                };
+                if cursor.peek() == Some('{') {
+                    self.read_span(cursor, SpanStyle::Sized(size), inline)?;
+                } else {
+                    cursor.skip_whitespace_in_line();
+                }
+            }
+            "verb" => {
+                if let Some(delimiter) = cursor.peek() {
+                    cursor.advance(delimiter.len_utf8());
+                    let code = cursor.read_until(&delimiter.to_string());
+                    inline.push(Span::with_style(&code, SpanStyle::Code).into());
+                }
+            }
+            "url" => {
+                let url = cursor.read_group('{', '}').unwrap_or_default();
+                inline.push(HyperLink::external(url.trim()).into());
+            }
+            "href" => {
+                let url = cursor.read_group('{', '}').unwrap_or_default();
+                let text = cursor.read_group('{', '}').unwrap_or_default();
+                let caption = Span::from(self.read_inlines(&text)?).unformatted_string();
+                inline.push(if caption.is_empty() {
+                    HyperLink::external(url.trim()).into()
+                } else {
+                    HyperLink::external_with_caption_str(url.trim(), &caption).into()
+                });
+            }
+            "ref" | "autoref" | "nameref" | "pageref" | "cref" | "Cref" => {
+                let target = cursor.read_group('{', '}').unwrap_or_default();
+                match make_label(&target) {
+                    Some(label) => inline.push(HyperLink::internal(label).into()),
+                    None => push_text(inline, &cursor.source[start..cursor.position]),
+                }
+            }
+            "hyperref" => {
+                let target = cursor.read_group('[', ']').unwrap_or_default();
+                let text = cursor.read_group('{', '}').unwrap_or_default();
+                let caption = Span::from(self.read_inlines(&text)?).unformatted_string();
+                match make_label(&target) {
+                    Some(label) if caption.is_empty() => {
+                        inline.push(HyperLink::internal(label).into())
+                    }
+                    Some(label) => {
+                        inline.push(HyperLink::internal_with_caption_str(label, &caption).into())
+                    }
+                    None => push_text(inline, &caption),
+                }
+            }
+            "includegraphics" => {
+                let _ = cursor.read_group('[', ']');
+                let path = cursor.read_group('{', '}').unwrap_or_default();
+                if !path.trim().is_empty() {
+                    inline.push(Image::new(path.trim()).into());
+                }
+            }
+            "(" => {
+                let math = cursor.read_until("\\)");
+                inline.push(Math::from_str(math.trim())?.into());
+            }
+            "label" => {
+                // labels are only supported at the start of blocks, items, and cells.
+                let _ = cursor.read_group('{', '}');
+            }
+            "%" | "&" | "_" | "#" | "$" | "{" | "}" => push_text(inline, &name),
+            " " | "," | ";" | ":" | "!" => push_text(inline, " "),
+            "ldots" | "dots" | "textbackslash" | "LaTeX" | "TeX" => {
+                if cursor.starts_with("{}") {
+                    cursor.advance(2);
+                } else {
+                    cursor.skip_whitespace_in_line();
+                }
+                push_text(
+                    inline,
+                    match name.as_str() {
+                        "ldots" | "dots" => "\u{2026}",
+                        "textbackslash" => "\\",
+                        other => other,
+                    },
+                );
+            }
+            name if SETUP_COMMANDS.contains(&name) => {
+                let _ = cursor.read_arguments(false);
+            }
+            _ => {
+                // pass anything unknown through, as source, to not lose content.
+                let _ = cursor.read_arguments(false);
+                push_text(inline, &cursor.source[start..cursor.position]);
+            }
+        }
+        Ok(())
+    }
+
+    fn read_span(
+        &mut self,
+        cursor: &mut Cursor<'_>,
+        style: SpanStyle,
+        inline: &mut Vec<InlineContent>,
+    ) -> error::Result<()> {
+        let group = cursor.read_group('{', '}').unwrap_or_default();
+        let mut span = Span::from(self.read_inlines(&group)?);
+        span.add_style(style)?;
+        inline.push(span.into());
+        Ok(())
+    }
+
+    fn add_metadata(&mut self, name: &str, value: &str) {
+        let value = value.trim();
+        if value.is_empty() || value == "\\today" {
+            return;
+        }
+        match name {
+            "title" => self.metadata.push(Metadata::Title(value.to_string())),
+            "date" => self.metadata.push(Metadata::Date(value.to_string())),
+            _ => {
+                for author in value.split("\\and") {
+                    let mut parts = author
+                        .split('\\')
+                        .map(str::trim)
+                        .filter(|part| !part.is_empty());
+                    if let Some(name) = parts.next() {
+                        let mut new_author = Author {
+                            name: name.to_string(),
+                            email: None,
+                            organization: None,
+                        };
+                        for part in parts {
+                            if part.contains('@') && new_author.email.is_none() {
+                                new_author.email = Some(part.to_string());
+                            } else {
+                                new_author.organization = Some(part.to_string());
+                            }
+                        }
+                        self.metadata.push(Metadata::Author(new_author));
+                    }
+                }
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a> Cursor<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            position: 0,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn starts_with(&self, s: &str) -> bool {
+        self.rest().starts_with(s)
+    }
+
+    fn advance(&mut self, bytes: usize) {
+        self.position = (self.position + bytes).min(self.source.len());
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.source[..self.position]
+            .chars()
+            .rev()
+            .take_while(|c| *c != '\n')
+            .all(|c| c == ' ' || c == '\t')
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.advance(c.len_utf8());
+        }
+    }
+
+    fn skip_whitespace_in_line(&mut self) {
+        while let Some(c) = self.peek() {
+            if c != ' ' && c != '\t' {
+                break;
+            }
+            self.advance(1);
+        }
+    }
+
+    /// Read the remainder of the current line, and the line end.
+    fn read_line(&mut self) -> String {
+        let rest = self.rest();
+        let (line, length) = match rest.find('\n') {
+            Some(end) => (&rest[..end], end + 1),
+            None => (rest, rest.len()),
+        };
+        self.advance(length);
+        line.to_string()
+    }
+
+    /// Read a run of text up to the next character with special meaning.
+    fn read_text(&mut self) -> String {
+        let rest = self.rest();
+        let mut length = 0;
+        for c in rest.chars() {
+            if matches!(c, '\\' | '{' | '}' | '$' | '%' | '~' | '-') || c.is_whitespace() {
+                break;
+            }
+            length += c.len_utf8();
+        }
+        if length == 0 {
+            // a single special character that has no other meaning in this position.
+            length = rest.chars().next().map(char::len_utf8).unwrap_or_default();
+        }
+        self.advance(length);
+        rest[..length].to_string()
+    }
+
+    /// Read the text up to `end`, consuming `end` as well.
+    fn read_until(&mut self, end: &str) -> String {
+        let rest = self.rest();
+        match rest.find(end) {
+            Some(index) => {
+                self.advance(index + end.len());
+                rest[..index].to_string()
+            }
+            None => {
+                self.advance(rest.len());
+                rest.to_string()
+            }
+        }
+    }
+
+    /// Read the name of the command that follows a `\`.
+    fn read_command_name(&mut self) -> String {
+        let rest = self.rest();
+        let length: usize = rest
+            .chars()
+            .take_while(|c| c.is_ascii_alphabetic())
+            .map(char::len_utf8)
+            .sum();
+        if length == 0 {
+            let length = rest.chars().next().map(char::len_utf8).unwrap_or_default();
+            self.advance(length);
+            rest[..length].to_string()
+        } else {
+            let length = if rest[length..].starts_with('*') {
+                length + 1
+            } else {
+                length
+            };
+            self.advance(length);
+            rest[..length].to_string()
+        }
+    }
+
+    /// If the next character is `open`, read up to the balancing `close` character and return
+    /// the content between them.
+    fn read_group(&mut self, open: char, close: char) -> Option<String> {
+        if self.peek() != Some(open) {
+            return None;
+        }
+        let rest = self.rest();
+        let mut depth = 0;
+        let mut escaped = false;
+        for (index, c) in rest.char_indices() {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '{' && open != '{' {
+                depth += 1;
+            } else if c == '}' && open != '{' {
+                depth -= 1;
+            } else if c == open {
+                depth += 1;
+            } else if c == close {
+                depth -= 1;
+                if depth == 0 {
+                    self.advance(index + 1);
+                    return Some(rest[1..index].to_string());
+                }
+            }
+        }
+        self.advance(rest.len());
+        Some(rest[1..].to_string())
+    }
+
+    /// Read any number of `{...}` and `[...]` arguments; if `across_lines` is `false` the
+    /// arguments must start on the current line.
+    fn read_arguments(&mut self, across_lines: bool) -> Vec<(char, String)> {
+        let mut arguments: Vec<(char, String)> = Default::default();
+        loop {
+            let start = self.position;
+            if across_lines {
+                self.skip_whitespace();
+            } else {
+                self.skip_whitespace_in_line();
+            }
+            match self.peek() {
+                Some('{') => arguments.push(('{', self.read_group('{', '}').unwrap())),
+                Some('[') => arguments.push(('[', self.read_group('[', ']').unwrap())),
+                _ => {
+                    self.position = start;
+                    break;
+                }
+            }
+        }
+        arguments
+    }
+
+    /// Read the body of the environment `name`, consuming the matching `\end{name}`.
+    fn read_environment_body(&mut self, name: &str) -> String {
+        let begin = format!("\\begin{{{}}}", name);
+        let end = format!("\\end{{{}}}", name);
+        let rest = self.rest();
+        let mut depth = 1;
+        let mut index = 0;
+        while index < rest.len() {
+            if rest[index..].starts_with(&end) {
+                depth -= 1;
+                if depth == 0 {
+                    self.advance(index + end.len());
+                    return rest[..index].to_string();
+                }
+                index += end.len();
+            } else if rest[index..].starts_with(&begin) {
+                depth += 1;
+                index += begin.len();
+            } else {
+                index += rest[index..].chars().next().unwrap().len_utf8();
+            }
+        }
+        self.advance(rest.len());
+        rest.to_string()
+    }
+
+    /// Read a `\label{...}` that follows, on the same or next line.
+    fn read_following_label(&mut self) -> Option<Label> {
+        let start = self.position;
+        self.skip_whitespace_in_line();
+        if self.starts_with("\n") {
+            self.advance(1);
+            self.skip_whitespace_in_line();
+        }
+        if self.starts_with("\\label{") {
+            self.advance(6);
+            self.read_group('{', '}').and_then(|s| make_label(&s))
+        } else {
+            self.position = start;
+            None
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn make_label(value: &str) -> Option<Label> {
+    let value = value.trim();
+    if Label::is_valid(value) {
+        Label::from_str(value).ok()
+    } else {
+        None
+    }
+}
+
+fn comment_text(line: &str) -> String {
+    let line = line.trim_start().trim_start_matches('%');
+    line.strip_prefix(' ')
+        .unwrap_or(line)
+        .trim_end()
+        .to_string()
+}
+
+///
+/// Separate any comments, unescaped `%` to the end of the line, from the text of a paragraph.
+///
+fn split_comments(source: &str) -> (String, Vec<String>) {
+    let mut text = String::new();
+    let mut comments: Vec<String> = Default::default();
+    for line in source.split_inclusive('\n') {
+        let mut escaped = false;
+        let mut comment_start = None;
+        for (index, c) in line.char_indices() {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '%' {
+                comment_start = Some(index);
+                break;
+            }
+        }
+        match comment_start {
+            Some(index) => {
+                text.push_str(&line[..index]);
+                comments.push(comment_text(&line[index..]));
+            }
+            None => text.push_str(line),
+        }
+    }
+    (text, comments)
+}
+
+///
+/// Remove a `\label{...}` from anywhere in `source`, returning the remaining text and the label.
+///
+fn extract_label(source: &str) -> (String, Option<Label>) {
+    match source.find("\\label{") {
+        Some(start) => {
+            let mut cursor = Cursor::new(source);
+            cursor.advance(start + 6);
+            let label = cursor.read_group('{', '}').and_then(|s| make_label(&s));
+            (
+                format!("{}{}", &source[..start], &source[cursor.position..]),
+                label,
+            )
+        }
+        None => (source.to_string(), None),
+    }
+}
+
+///
+/// Split `source` on the character `separator`, ignoring any within groups or environments, or
+/// escaped. A separator of `\` splits on the row separator `\\`.
+///
+fn split_top_level(source: &str, separator: char) -> Vec<String> {
+    let mut parts: Vec<String> = Default::default();
+    let mut depth = 0;
+    let mut last = 0;
+    let mut cursor = Cursor::new(source);
+    while let Some(c) = cursor.peek() {
+        match c {
+            '\\' if separator == '\\' && depth == 0 && cursor.starts_with("\\\\") => {
+                parts.push(source[last..cursor.position].to_string());
+                cursor.advance(2);
+                last = cursor.position;
+            }
+            '\\' => {
+                cursor.advance(1);
+                match cursor.read_command_name().as_str() {
+                    "begin" => depth += 1,
+                    "end" => depth -= 1,
+                    _ => {}
+                }
+            }
+            '{' | '[' if separator != '\\' || c == '{' => {
+                depth += 1;
+                cursor.advance(1);
+            }
+            '}' | ']' if separator != '\\' || c == '}' => {
+                depth -= 1;
+                cursor.advance(1);
+            }
+            c if c == separator && depth == 0 => {
+                parts.push(source[last..cursor.position].to_string());
+                cursor.advance(c.len_utf8());
+                last = cursor.position;
+            }
+            c => cursor.advance(c.len_utf8()),
+        }
+    }
+    parts.push(source[last..].to_string());
+    parts
+}
+
+///
+/// Split the body of a list environment into the content of each `\item`, along with any
+/// label or optional term.
+///
+fn split_items(body: &str) -> Vec<ListEntry> {
+    let mut entries: Vec<ListEntry> = Default::default();
+    let mut depth = 0;
+    let mut cursor = Cursor::new(body);
+    let mut content_start: Option<usize> = None;
+    while let Some(c) = cursor.peek() {
+        if c == '\\' {
+            let start = cursor.position;
+            cursor.advance(1);
+            match cursor.read_command_name().as_str() {
+                "begin" => depth += 1,
+                "end" => depth -= 1,
+                "item" if depth == 0 => {
+                    if let (Some(content_start), Some(last)) = (content_start, entries.last_mut()) {
+                        last.content = body[content_start..start].to_string();
+                    }
+                    let mut entry = ListEntry {
+                        label: None,
+                        term: None,
+                        content: String::new(),
+                    };
+                    loop {
+                        cursor.skip_whitespace_in_line();
+                        if cursor.starts_with("\\label{") {
+                            cursor.advance(6);
+                            entry.label = cursor.read_group('{', '}').and_then(|s| make_label(&s));
+                        } else if cursor.peek() == Some('[') && entry.term.is_none() {
+                            entry.term = cursor.read_group('[', ']');
+                        } else {
+                            break;
+                        }
+                    }
+                    entries.push(entry);
+                    content_start = Some(cursor.position);
+                }
+                _ => {}
+            }
+        } else {
+            cursor.advance(c.len_utf8());
+        }
+    }
+    if let (Some(content_start), Some(last)) = (content_start, entries.last_mut()) {
+        last.content = body[content_start..].to_string();
+    }
+    entries
+}
+
+fn column_alignments(spec: &str) -> Vec<Alignment> {
+    let mut alignments: Vec<Alignment> = Default::default();
+    let mut cursor = Cursor::new(spec);
+    while let Some(c) = cursor.peek() {
+        match c {
+            'l' => alignments.push(Alignment::Left),
+            'r' => alignments.push(Alignment::Right),
+            'c' => alignments.push(Alignment::Centered),
+            'p' | 'm' | 'b' | 'X' => alignments.push(Alignment::Justified),
+            '{' => {
+                let _ = cursor.read_group('{', '}');
+                continue;
+            }
+            _ => {}
+        }
+        cursor.advance(c.len_utf8());
+    }
+    alignments
+}
+
+fn strip_rules(row: &str) -> String {
+    let mut row = row.to_string();
+    for rule in &["\\hline", "\\toprule", "\\midrule", "\\bottomrule"] {
+        row = row.replace(rule, "");
+    }
+    row
+}
+
+///
+/// Remove the line end following the environment start, and the indentation before its end.
+///
+fn verbatim_text(body: &str) -> String {
+    let body = body.strip_prefix('\n').unwrap_or(body);
+    END_WHITESPACE_RE.replace(body, "").to_string()
+}
+
+fn unbrace(value: &str) -> &str {
+    value
+        .strip_prefix('{')
+        .and_then(|value| value.strip_suffix('}'))
+        .unwrap_or(value)
+}
+
+fn set_block_caption(block: &mut BlockContent, caption: Caption) {
+    match block {
+        BlockContent::ImageBlock(v) => {
+            let _ = v.set_caption(caption);
+        }
+        BlockContent::MathBlock(v) => {
+            let _ = v.set_caption(caption);
+        }
+        BlockContent::CodeBlock(v) => {
+            let _ = v.set_caption(caption);
+        }
+        BlockContent::Table(v) => {
+            let _ = v.set_caption(caption);
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,527 @@
+/*!
+Read a document from [CommonMark](https://spec.commonmark.org/0.29/) formatted Markdown. This
+reader is the counterpart to the [`markdown`](../../write/markdown/index.html) writer and maps the
+Markdown constructs back into the document model.
+
+* ATX and setext headings become `Heading` values, with the same level.
+* Fenced code blocks become `CodeBlock` values, retaining the language, if present; indented code
+  blocks become `Formatted` values.
+* Block quotes become (possibly nested) `Quote` values.
+* Lists become (possibly nested) `List` values, with the ordering determined by the list marker.
+* Emphasis, strong emphasis, and code spans become styled `Span` values.
+* Links become `HyperLink` values, links to a fragment such as `#label` are treated as internal.
+* Images become `Image` values, or an `ImageBlock` if the image is the only content in a paragraph.
+* The comment (`[//]: # "..."`) and metadata (`[_metadata_:key]:- "..."`) link references
+  generated by the Markdown writer become `BlockContent::Comment` and `Metadata` values
+  respectively.
+
+The [GitHub Flavored Markdown](https://github.github.com/gfm/) extensions may be enabled by
+using `from_str_with` and `MarkdownFlavor::GitHub`, in which case the following are also
+supported.
+
+* Pipe tables become `Table` values, the delimiter row determines the `Alignment` of each
+  `Column`; a delimiter with no colons is read as `Alignment::Justified`.
+* Strikethrough (`~~text~~`) becomes a `Span` with the `SpanStyle::Strikethrough` style.
+* Task list markers are retained as the text `[ ]` or `[x]` at the start of the list item.
+* A front matter block, delimited by `---` lines, becomes `Metadata` values; the block may
+  contain either the metadata link references above or simple YAML `key: value` pairs.
+
+# Example
+
+```rust
+use somedoc::model::HasInnerContent;
+use somedoc::read::markdown::from_str;
+
+let doc = from_str("# Heading\n\nSome *styled* text.\n").unwrap();
+assert_eq!(doc.inner().len(), 2);
+```
+
+Reading GitHub flavored content, such as a table.
+
+```rust
+use somedoc::model::block::BlockContent;
+use somedoc::model::HasInnerContent;
+use somedoc::read::markdown::from_str_with;
+use somedoc::write::markdown::MarkdownFlavor;
+
+let doc = from_str_with("|Name|Value|\n|:---|---:|\n|one|1|\n", MarkdownFlavor::GitHub).unwrap();
+assert!(matches!(doc.inner().first(), Some(BlockContent::Table(_))));
+```
+
+*/
+
+use crate::error;
+use crate::error::ErrorKind;
+use crate::model::block::{
+    Alignment, BlockContent, Cell, CodeBlock, Column, Formatted, Heading, HeadingLevel, ImageBlock,
+    Item, List, ListKind, Paragraph, Quote, Row, Table,
+};
+use crate::model::document::Metadata;
+use crate::model::inline::{HasInlineContent, Image, InlineContent, Math, Span, SpanStyle};
+use crate::model::{Document, HasInnerContent, HasStyles};
+use crate::read::utils::{
+    block_as_inline, hyper_link, metadata_from, metadata_from_yaml, push_text,
+};
+use crate::read::xwiki;
+use crate::write::markdown::MarkdownFlavor;
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+use regex::Regex;
+use std::convert::TryFrom;
+use std::io::Read;
+use std::ops::Range;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+/// Parse a `Document` instance from the CommonMark provided in the `markdown` string.
+pub fn from_str(markdown: &str) -> error::Result<Document> {
+    from_str_with(markdown, MarkdownFlavor::CommonMark)
+}
+
+///
+/// Parse a `Document` instance from the Markdown provided in the `markdown` string, using the
+/// syntax extensions of the specified `flavor`. Currently only the `Strict`, `CommonMark`,
+/// `GitHub`, and `XWiki` flavors are supported, any other flavor will return an `UnknownFormat`
+/// error. The `XWiki` flavor is read using the [`xwiki`](../xwiki/index.html) reader.
+///
+pub fn from_str_with(markdown: &str, flavor: MarkdownFlavor) -> error::Result<Document> {
+    if flavor == MarkdownFlavor::XWiki {
+        xwiki::from_str(markdown)
+    } else {
+        MarkdownReader::new(markdown, parser_options(&flavor)?).read_document()
+    }
+}
+
+/// Read from the provided `reader` and parse the CommonMark into a `Document` instance.
+pub fn from_reader(reader: impl Read) -> error::Result<Document> {
+    from_reader_with(reader, MarkdownFlavor::CommonMark)
+}
+
+///
+/// Read from the provided `reader` and parse the Markdown into a `Document` instance, using the
+/// syntax extensions of the specified `flavor`.
+///
+pub fn from_reader_with(reader: impl Read, flavor: MarkdownFlavor) -> error::Result<Document> {
+    let mut reader = reader;
+    let mut markdown = String::new();
+    let _ = reader.read_to_string(&mut markdown)?;
+    from_str_with(&markdown, flavor)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+struct MarkdownReader<'a> {
+    source: &'a str,
+    events: Vec<(Event<'a>, Range<usize>)>,
+    position: usize,
+    comments: Vec<(usize, String)>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+lazy_static! {
+    static ref COMMENT_RE: Regex = Regex::new(r#"(?m)^\[//\]: # "(.*)"[ \t]*$"#).unwrap();
+    static ref METADATA_RE: Regex =
+        Regex::new(r#"(?m)^\[_metadata_:([^\]]+)\]:- "(.*)"[ \t]*$"#).unwrap();
+}
+
+impl<'a> MarkdownReader<'a> {
+    fn new(source: &'a str, options: Options) -> Self {
+        let events: Vec<(Event<'a>, Range<usize>)> = Parser::new_ext(source, options)
+            .into_offset_iter()
+            .collect();
+        let comments = Self::comments(source, &code_ranges(&events));
+        Self {
+            source,
+            events,
+            position: 0,
+            comments,
+        }
+    }
+
+    fn read_document(mut self) -> error::Result<Document> {
+        let mut doc = Document::default();
+        let mut body_start = 0;
+        if let Some((Event::Start(Tag::MetadataBlock(_)), range)) = self.events.first().cloned() {
+            let _ = self.next();
+            for meta_datum in front_matter(&self.read_text()) {
+                let _ = doc.add_metadata(meta_datum);
+            }
+            body_start = range.end;
+        }
+        let code_ranges = code_ranges(&self.events);
+        for captures in METADATA_RE.captures_iter(&self.source[body_start..]) {
+            if !in_ranges(body_start + captures.get(0).unwrap().start(), &code_ranges) {
+                let _ = doc.add_metadata(metadata_from(&captures[1], &captures[2]));
+            }
+        }
+        for block in self.read_blocks(true)? {
+            doc.add_content(block)?;
+        }
+        Ok(doc)
+    }
+
+    /// Comments are link reference definitions, and so are not reported as events by the
+    /// parser; they are collected here, with consecutive lines combined, along with their
+    /// offset so that they can be interleaved with the top-level blocks. Matches within code
+    /// blocks are code, not comments, and are skipped.
+    fn comments(source: &str, code_ranges: &[Range<usize>]) -> Vec<(usize, String)> {
+        let mut comments: Vec<(usize, String)> = Default::default();
+        let mut last_end = None;
+        for captures in COMMENT_RE.captures_iter(source) {
+            let whole = captures.get(0).unwrap();
+            if in_ranges(whole.start(), code_ranges) {
+                continue;
+            }
+            let text = &captures[1];
+            match comments.last_mut() {
+                Some((_, comment)) if last_end == Some(whole.start().saturating_sub(1)) => {
+                    comment.push('\n');
+                    comment.push_str(text);
+                }
+                _ => comments.push((whole.start(), text.to_string())),
+            }
+            last_end = Some(whole.end());
+        }
+        comments
+    }
+
+    fn next(&mut self) -> Option<(Event<'a>, Range<usize>)> {
+        let next = self.events.get(self.position).cloned();
+        if next.is_some() {
+            self.position += 1;
+        }
+        next
+    }
+
+    fn flush_comments(&mut self, before: usize, blocks: &mut Vec<BlockContent>) {
+        while !self.comments.is_empty() && self.comments[0].0 < before {
+            blocks.push(BlockContent::Comment(self.comments.remove(0).1));
+        }
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    fn read_blocks(&mut self, top_level: bool) -> error::Result<Vec<BlockContent>> {
+        let mut blocks: Vec<BlockContent> = Default::default();
+        while let Some((event, range)) = self.next() {
+            if top_level {
+                self.flush_comments(range.start, &mut blocks);
+            }
+            match event {
+                Event::Start(tag) => {
+                    if let Some(block) = self.read_block(tag)? {
+                        blocks.push(block);
+                    }
+                }
+                Event::End(_) => break,
+                Event::Rule => blocks.push(BlockContent::ThematicBreak),
+                Event::Html(html) => blocks.push(html_block(&html)),
+                _ => {}
+            }
+        }
+        if top_level {
+            self.flush_comments(usize::MAX, &mut blocks);
+        }
+        Ok(blocks)
+    }
+
+    fn read_block(&mut self, tag: Tag<'a>) -> error::Result<Option<BlockContent>> {
+        Ok(match tag {
+            Tag::Paragraph => {
+                let inline = self.read_inlines()?;
+                match inline.as_slice() {
+                    [InlineContent::Image(image)] => Some(ImageBlock::from(image.clone()).into()),
+                    _ => Some(Paragraph::from(inline).into()),
+                }
+            }
+            Tag::Heading { level, .. } => {
+                let mut heading = Heading::from(self.read_inlines()?);
+                let _ = heading.set_level(HeadingLevel::try_from(level as u8).unwrap_or_default());
+                Some(heading.into())
+            }
+            Tag::BlockQuote(_) => {
+                let mut quote = Quote::default();
+                for block in self.read_blocks(false)? {
+                    quote.add_content(block)?;
+                }
+                Some(quote.into())
+            }
+            Tag::CodeBlock(kind) => {
+                let code = self.read_text();
+                let code = code.strip_suffix('\n').unwrap_or(&code);
+                Some(match kind {
+                    CodeBlockKind::Fenced(language) => match language.split_whitespace().next() {
+                        Some(language) => CodeBlock::with_language(code, language).into(),
+                        None => CodeBlock::from(code).into(),
+                    },
+                    CodeBlockKind::Indented => Formatted::from(code).into(),
+                })
+            }
+            Tag::HtmlBlock => Some(html_block(&self.read_text())),
+            Tag::Table(alignments) => Some(self.read_table(&alignments)?.into()),
+            Tag::MetadataBlock(_) => {
+                // Metadata is only recognized at the start of the document.
+                let _ = self.read_text();
+                None
+            }
+            Tag::List(start) => Some(
+                self.read_list(if start.is_some() {
+                    ListKind::Ordered
+                } else {
+                    ListKind::Unordered
+                })?
+                .into(),
+            ),
+            _ => {
+                // Anything not explicitly supported is flattened into a paragraph of text.
+                let inline = self.read_inlines()?;
+                if inline.is_empty() {
+                    None
+                } else {
+                    Some(Paragraph::from(inline).into())
+                }
+            }
+        })
+    }
+
+    fn read_list(&mut self, kind: ListKind) -> error::Result<List> {
+        let mut list = List::new(kind);
+        while let Some((event, _)) = self.next() {
+            match event {
+                Event::Start(Tag::Item) => self.read_list_item(&mut list)?,
+                Event::End(_) => break,
+                _ => {}
+            }
+        }
+        Ok(list)
+    }
+
+    fn read_list_item(&mut self, list: &mut List) -> error::Result<()> {
+        let mut inline: Vec<InlineContent> = Default::default();
+        let mut sub_lists: Vec<List> = Default::default();
+        while let Some((event, _)) = self.next() {
+            match event {
+                Event::End(TagEnd::Item) => break,
+                Event::Start(Tag::Paragraph) => {
+                    if !inline.is_empty() {
+                        inline.push(InlineContent::LineBreak);
+                    }
+                    inline.extend(self.read_inlines()?);
+                }
+                Event::Start(Tag::List(start)) => {
+                    sub_lists.push(self.read_list(if start.is_some() {
+                        ListKind::Ordered
+                    } else {
+                        ListKind::Unordered
+                    })?)
+                }
+                Event::Start(tag) if is_block_tag(&tag) => {
+                    if let Some(block) = self.read_block(tag)? {
+                        if !inline.is_empty() {
+                            inline.push(InlineContent::LineBreak);
+                        }
+                        inline.extend(block_as_inline(block));
+                    }
+                }
+                event => self.read_inline(event, &mut inline)?,
+            }
+        }
+        if !inline.is_empty() || sub_lists.is_empty() {
+            let _ = list.add_item(Item::from(inline));
+        }
+        for sub_list in sub_lists {
+            let _ = list.add_sub_list(sub_list);
+        }
+        Ok(())
+    }
+
+    fn read_table(&mut self, alignments: &[pulldown_cmark::Alignment]) -> error::Result<Table> {
+        let mut table = Table::default();
+        while let Some((event, _)) = self.next() {
+            match event {
+                Event::Start(Tag::TableHead) => {
+                    for (cell, alignment) in self.read_table_cells()?.iter().zip(alignments) {
+                        table.add_column(Column::with_alignment(
+                            &cell.unformatted_string(),
+                            column_alignment(alignment),
+                        ));
+                    }
+                }
+                Event::Start(Tag::TableRow) => table.add_row(Row::from(self.read_table_cells()?)),
+                Event::End(_) => break,
+                _ => {}
+            }
+        }
+        Ok(table)
+    }
+
+    fn read_table_cells(&mut self) -> error::Result<Vec<Cell>> {
+        let mut cells: Vec<Cell> = Default::default();
+        while let Some((event, _)) = self.next() {
+            match event {
+                Event::Start(Tag::TableCell) => cells.push(Cell::from(self.read_inlines()?)),
+                Event::Start(Tag::TableRow) => cells.extend(self.read_table_cells()?),
+                Event::End(_) => break,
+                _ => {}
+            }
+        }
+        Ok(cells)
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    fn read_inlines(&mut self) -> error::Result<Vec<InlineContent>> {
+        let mut inline: Vec<InlineContent> = Default::default();
+        while let Some((event, _)) = self.next() {
+            if let Event::End(_) = event {
+                break;
+            }
+            self.read_inline(event, &mut inline)?;
+        }
+        Ok(inline)
+    }
+
+    fn read_inline(
+        &mut self,
+        event: Event<'a>,
+        inline: &mut Vec<InlineContent>,
+    ) -> error::Result<()> {
+        match event {
+            Event::Text(text) => push_text(inline, &text),
+            Event::Code(code) => inline.push(Span::with_style(&code, SpanStyle::Code).into()),
+            Event::InlineMath(math) | Event::DisplayMath(math) => {
+                inline.push(Math::from_str(&math)?.into())
+            }
+            Event::Html(html) | Event::InlineHtml(html) => push_text(inline, &html),
+            Event::FootnoteReference(name) => push_text(inline, &format!("[^{}]", name)),
+            Event::SoftBreak => push_text(inline, " "),
+            Event::HardBreak => inline.push(InlineContent::LineBreak),
+            Event::TaskListMarker(checked) => {
+                push_text(inline, if checked { "[x] " } else { "[ ] " })
+            }
+            Event::Start(Tag::Emphasis) => inline.push(self.read_span(SpanStyle::Italic)?),
+            Event::Start(Tag::Strong) => inline.push(self.read_span(SpanStyle::Bold)?),
+            Event::Start(Tag::Strikethrough) => {
+                inline.push(self.read_span(SpanStyle::Strikethrough)?)
+            }
+            Event::Start(Tag::Superscript) => inline.push(self.read_span(SpanStyle::Superscript)?),
+            Event::Start(Tag::Subscript) => inline.push(self.read_span(SpanStyle::Subscript)?),
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                let caption = Span::from(self.read_inlines()?).unformatted_string();
+                inline.push(hyper_link(&dest_url, &caption).into());
+            }
+            Event::Start(Tag::Image { dest_url, .. }) => {
+                let alt_text = Span::from(self.read_inlines()?).unformatted_string();
+                if dest_url.is_empty() {
+                    push_text(inline, &alt_text);
+                } else if alt_text.is_empty() {
+                    inline.push(Image::new(&dest_url).into());
+                } else {
+                    inline.push(Image::with_alt_text(&dest_url, &alt_text).into());
+                }
+            }
+            Event::Start(_) => inline.extend(self.read_inlines()?),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn read_span(&mut self, style: SpanStyle) -> error::Result<InlineContent> {
+        let mut span = Span::from(self.read_inlines()?);
+        span.add_style(style)?;
+        Ok(span.into())
+    }
+
+    fn read_text(&mut self) -> String {
+        let mut text = String::new();
+        while let Some((event, _)) = self.next() {
+            match event {
+                Event::Text(value) | Event::Html(value) => text.push_str(&value),
+                Event::End(_) => break,
+                _ => {}
+            }
+        }
+        text
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn parser_options(flavor: &MarkdownFlavor) -> error::Result<Options> {
+    match flavor {
+        MarkdownFlavor::Strict | MarkdownFlavor::CommonMark => Ok(Options::empty()),
+        MarkdownFlavor::GitHub => Ok(Options::ENABLE_TABLES
+            | Options::ENABLE_STRIKETHROUGH
+            | Options::ENABLE_TASKLISTS
+            | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS),
+        _ => Err(ErrorKind::UnknownFormat.into()),
+    }
+}
+
+fn code_ranges(events: &[(Event<'_>, Range<usize>)]) -> Vec<Range<usize>> {
+    events
+        .iter()
+        .filter_map(|(event, range)| match event {
+            Event::Start(Tag::CodeBlock(_)) => Some(range.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn in_ranges(offset: usize, ranges: &[Range<usize>]) -> bool {
+    ranges.iter().any(|range| range.contains(&offset))
+}
+
+fn front_matter(text: &str) -> Vec<Metadata> {
+    if METADATA_RE.is_match(text) {
+        METADATA_RE
+            .captures_iter(text)
+            .map(|captures| metadata_from(&captures[1], &captures[2]))
+            .collect()
+    } else {
+        metadata_from_yaml(text)
+    }
+}
+
+fn column_alignment(alignment: &pulldown_cmark::Alignment) -> Alignment {
+    match alignment {
+        pulldown_cmark::Alignment::None => Alignment::Justified,
+        pulldown_cmark::Alignment::Left => Alignment::Left,
+        pulldown_cmark::Alignment::Center => Alignment::Centered,
+        pulldown_cmark::Alignment::Right => Alignment::Right,
+    }
+}
+
+fn is_block_tag(tag: &Tag<'_>) -> bool {
+    matches!(
+        tag,
+        Tag::Heading { .. }
+            | Tag::BlockQuote(_)
+            | Tag::CodeBlock(_)
+            | Tag::HtmlBlock
+            | Tag::FootnoteDefinition(_)
+            | Tag::DefinitionList
+            | Tag::Table(_)
+            | Tag::MetadataBlock(_)
+    )
+}
+
+fn html_block(html: &str) -> BlockContent {
+    let html = html.trim();
+    match html
+        .strip_prefix("<!--")
+        .and_then(|html| html.strip_suffix("-->"))
+    {
+        Some(comment) => BlockContent::Comment(comment.trim().to_string()),
+        None => Formatted::from(html).into(),
+    }
+}
@@ -0,0 +1,238 @@
+/*!
+Version checks and migrations for the JSON representation of a document. Each document written
+by the [`json`](../../write/json/index.html) writer records the version of this crate that wrote
+it; when reading, this version is checked against the current crate version and any registered
+migration steps are run to upgrade the JSON layout before it is deserialized.
+
+A document is compatible if it was written by a version with the same major version, and for
+`0.x` versions the same minor version, and not by a later version than this one. A document that
+is not compatible, even after migration, or one written before the JSON representation was
+introduced in `0.2.3`, results in an `ErrorKind::IncompatibleVersion` error.
+
+The default set of migrations includes the following steps.
+
+* `0.2.6`: the `Image` structure changed from a wrapper around a `HyperLink` to a path (or
+  label) with an optional alternative text.
+
+# Example
+
+```rust
+use somedoc::read::from_str_with;
+use somedoc::read::migration::Migrations;
+
+let mut migrations = Migrations::default();
+let _ = migrations
+    .add("0.2.8", |mut json| {
+        json["metadata"] = serde_json::Value::Array(Default::default());
+        Ok(json)
+    })
+    .unwrap();
+
+let doc = from_str_with(r#"{"version":"0.2.7","content":[]}"#, &migrations).unwrap();
+```
+
+*/
+
+use crate::error;
+use crate::error::ErrorKind;
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A single migration step, this takes the JSON value of a document and returns the value
+/// upgraded to the layout of the version the step was registered for.
+///
+pub type MigrationFn = fn(Value) -> error::Result<Value>;
+
+///
+/// The set of migration steps to run when reading a document. Use `Migrations::default()` for the
+/// steps provided by this crate, or `Migrations::empty()` to only check versions.
+///
+#[derive(Clone, Debug)]
+pub struct Migrations {
+    steps: Vec<(Version, MigrationFn)>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Version {
+    major: u64,
+    minor: u64,
+    patch: u64,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+const VERSION_KEY: &str = "version";
+
+/// The JSON representation was introduced in this version, nothing earlier can be migrated.
+const FIRST_VERSION: Version = Version {
+    major: 0,
+    minor: 2,
+    patch: 3,
+};
+
+impl Default for Migrations {
+    fn default() -> Self {
+        let mut migrations = Self::empty();
+        let _ = migrations.add("0.2.6", image_from_hyper_link).unwrap();
+        migrations
+    }
+}
+
+impl Migrations {
+    /// Construct a new, empty, set of migration steps.
+    pub fn empty() -> Self {
+        Self {
+            steps: Default::default(),
+        }
+    }
+
+    ///
+    /// Register a migration `step` that upgrades documents written by any version earlier than
+    /// `version` to the layout used by `version`. Steps are run in version order, regardless of
+    /// the order in which they were added.
+    ///
+    pub fn add(&mut self, version: &str, step: MigrationFn) -> error::Result<&mut Self> {
+        let version = Version::from_str(version)?;
+        let index = self
+            .steps
+            .iter()
+            .position(|(existing, _)| *existing > version)
+            .unwrap_or(self.steps.len());
+        self.steps.insert(index, (version, step));
+        Ok(self)
+    }
+
+    ///
+    /// Check the version of the document in `json`, run any migration steps needed, and return
+    /// the upgraded value. The version recorded in the returned value is the current crate
+    /// version.
+    ///
+    pub fn migrate(&self, json: Value) -> error::Result<Value> {
+        let mut json = json;
+        let found = json
+            .get(VERSION_KEY)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let incompatible = || ErrorKind::IncompatibleVersion(found.clone());
+        let current = Version::current();
+        let mut version = Version::from_str(&found).map_err(|_| incompatible())?;
+        if version < FIRST_VERSION || version > current {
+            return Err(incompatible().into());
+        }
+        for (step_version, step) in &self.steps {
+            if version < *step_version && *step_version <= current {
+                json = step(json)?;
+                version = *step_version;
+            }
+        }
+        if !version.is_compatible_with(&current) {
+            return Err(incompatible().into());
+        }
+        if let Some(object) = json.as_object_mut() {
+            let _ = object.insert(VERSION_KEY.to_string(), Value::String(current.to_string()));
+        }
+        Ok(json)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl FromStr for Version {
+    type Err = error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // ignore any pre-release or build suffix.
+        let s = s.split(['-', '+']).next().unwrap_or_default();
+        let parts: Vec<u64> = s
+            .split('.')
+            .map(|part| part.trim().parse::<u64>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| ErrorKind::IncompatibleVersion(s.to_string()))?;
+        match parts.as_slice() {
+            [major, minor, patch] => Ok(Self {
+                major: *major,
+                minor: *minor,
+                patch: *patch,
+            }),
+            _ => Err(ErrorKind::IncompatibleVersion(s.to_string()).into()),
+        }
+    }
+}
+
+impl Version {
+    fn current() -> Self {
+        Self::from_str(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+
+    fn is_compatible_with(&self, other: &Self) -> bool {
+        self.major == other.major
+            && (self.major > 0 || self.minor == other.minor)
+            && self.cmp(other) != Ordering::Greater
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Prior to `0.2.6` an `Image` was serialized as a `HyperLink`, with a `target` and optional
+/// `caption`, rather than with `path_or_label` and `alt_text` fields.
+///
+fn image_from_hyper_link(json: Value) -> error::Result<Value> {
+    fn convert(image: &mut Value) {
+        if let Some(link) = image.as_object() {
+            if let Some(target) = link.get("target").and_then(Value::as_object) {
+                let mut new_image = Map::new();
+                if let Some(path) = target.values().next() {
+                    let _ = new_image.insert("path_or_label".to_string(), path.clone());
+                }
+                if let Some(caption) = link.get("caption") {
+                    let _ = new_image.insert("alt_text".to_string(), caption.clone());
+                }
+                *image = Value::Object(new_image);
+            }
+        }
+    }
+    fn walk(value: &mut Value) {
+        match value {
+            Value::Object(object) => {
+                if let Some(image) = object.get_mut("Image") {
+                    convert(image);
+                }
+                if let Some(image) = object
+                    .get_mut("ImageBlock")
+                    .and_then(|block| block.get_mut("image"))
+                {
+                    convert(image);
+                }
+                object.values_mut().for_each(walk);
+            }
+            Value::Array(array) => array.iter_mut().for_each(walk),
+            _ => {}
+        }
+    }
+    let mut json = json;
+    walk(&mut json);
+    Ok(json)
+}
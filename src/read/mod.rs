@@ -0,0 +1,99 @@
+/*!
+This module introduces the ability to read documents back into the model. The functions in this
+module read the JSON representation used for external tool integration, while the sub-modules
+provide parsers for some of the markup formats supported by the [`write`](../write/index.html)
+module.
+
+# Example
+
+```rust
+use somedoc::model::HasInnerContent;
+use somedoc::read::from_str;
+
+let doc = from_str(r#"{"version":"0.2.10","content":[{"Comment":"Hello"}]}"#).unwrap();
+assert!(doc.has_inner());
+```
+
+*/
+
+#[cfg(feature = "fmt_json")]
+use crate::model::Document;
+#[cfg(feature = "fmt_json")]
+use crate::read::migration::Migrations;
+#[cfg(feature = "fmt_json")]
+use std::io::Read;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Parse a `Document` instance from the JSON provided in the `json` string. The document version
+/// is checked, and the default set of migrations applied, see the
+/// [`migration`](migration/index.html) module.
+///
+#[cfg(feature = "fmt_json")]
+pub fn from_str(json: &str) -> crate::error::Result<Document> {
+    from_str_with(json, &Migrations::default())
+}
+
+///
+/// Parse a `Document` instance from the JSON provided in the `json` string, using the provided
+/// set of `migrations` to upgrade documents written by earlier versions.
+///
+#[cfg(feature = "fmt_json")]
+pub fn from_str_with(json: &str, migrations: &Migrations) -> crate::error::Result<Document> {
+    let json: serde_json::Value = serde_json::from_str(json)?;
+    let doc: Document = serde_json::from_value(migrations.migrate(json)?)?;
+    Ok(doc)
+}
+
+/// Read from the provided `reader` and parse the JSON into a `Document` instance.
+#[cfg(feature = "fmt_json")]
+pub fn from_reader(reader: impl Read) -> crate::error::Result<Document> {
+    from_reader_with(reader, &Migrations::default())
+}
+
+///
+/// Read from the provided `reader` and parse the JSON into a `Document` instance, using the
+/// provided set of `migrations` to upgrade documents written by earlier versions.
+///
+#[cfg(feature = "fmt_json")]
+pub fn from_reader_with(
+    reader: impl Read,
+    migrations: &Migrations,
+) -> crate::error::Result<Document> {
+    let json: serde_json::Value = serde_json::from_reader(reader)?;
+    let doc: Document = serde_json::from_value(migrations.migrate(json)?)?;
+    Ok(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "fmt_html")]
+pub mod html;
+
+#[cfg(feature = "fmt_latex")]
+pub mod latex;
+
+#[cfg(feature = "fmt_markdown")]
+pub mod markdown;
+
+#[cfg(feature = "fmt_json")]
+pub mod migration;
+
+#[cfg(feature = "fmt_pandoc")]
+pub mod pandoc;
+
+#[cfg(feature = "fmt_markdown")]
+pub mod xwiki;
+
+#[cfg(any(
+    feature = "fmt_html",
+    feature = "fmt_latex",
+    feature = "fmt_markdown",
+    feature = "fmt_pandoc"
+))]
+pub(crate) mod utils;
@@ -0,0 +1,649 @@
+/*!
+Read a document from a Pandoc JSON AST, the format produced by `pandoc -t json`. This reader is
+the counterpart to the [`pandoc`](../../write/pandoc/index.html) writer, and allows any format
+that Pandoc can read to be imported into the model.
+
+* Values in the document `meta` map become `Metadata` values, the `abstract` value becomes the
+  document abstract.
+* `Header` becomes `Heading`, `Para` and `Plain` become `Paragraph`, `BlockQuote` becomes
+  `Quote`, and `HorizontalRule` becomes `ThematicBreak`.
+* `BulletList` and `OrderedList` become (possibly nested) `List` values, and `DefinitionList`
+  becomes `DefinitionList`.
+* `CodeBlock` becomes `CodeBlock`, the language is taken from the first class, and `LineBlock`
+  becomes `Formatted`.
+* A paragraph containing only display `Math`, or only an `Image`, becomes a `MathBlock` or
+  `ImageBlock` respectively; a `Figure` provides the caption and label for its content.
+* `Table` becomes `Table`, the first header row provides the columns.
+* HTML comments in a `RawBlock` become `Comment` values, LaTeX table of contents commands become
+  `FrontMatter` values, and any other `RawBlock` is kept as a `Comment`.
+* `Emph`, `Strong`, `Code`, `Strikeout`, `Underline`, `SmallCaps`, `Superscript`, and
+  `Subscript` become styled `Span` values.
+* `Link` and `Image` become `HyperLink` and `Image` values, links to a fragment such as `#label`
+  are treated as internal, and `Math` becomes `Math`.
+* The identifier of an element, or of a `Div` wrapping a single block, becomes its `Label`.
+
+Any other container, such as `Div`, `Span`, `Quoted`, or `Cite`, is flattened; the content of
+a `Note` is included inline, in brackets.
+
+# Example
+
+```rust
+use somedoc::model::HasInnerContent;
+use somedoc::read::pandoc::from_str;
+
+let doc = from_str(r#"{"pandoc-api-version":[1,23,1],"meta":{},"blocks":[
+    {"t":"Para","c":[{"t":"Str","c":"Hello"},{"t":"Space"},{"t":"Emph","c":[{"t":"Str","c":"world"}]}]}
+]}"#).unwrap();
+assert_eq!(doc.inner().len(), 1);
+```
+
+*/
+
+use crate::error;
+use crate::error::ErrorKind;
+use crate::model::block::{
+    Alignment, BlockContent, Caption, Cell, CodeBlock, Column, Definition, DefinitionList,
+    DefinitionPart, Formatted, FrontMatter, HasCaption, HasLabel, Heading, HeadingLevel,
+    ImageBlock, Item, Label, List, ListKind, MathBlock, Paragraph, Quote, Row, Table,
+};
+use crate::model::document::{Author, Copyright, Metadata};
+use crate::model::inline::text::Size;
+use crate::model::inline::{
+    Character, HasInlineContent, Image, InlineContent, Math, Span, SpanStyle,
+};
+use crate::model::{Document, HasInnerContent, HasStyles};
+use crate::read::utils::{
+    block_as_inline, hyper_link, metadata_from, push_text, set_block_label, trim_inline,
+};
+use serde_json::Value;
+use std::convert::TryFrom;
+use std::io::Read;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+/// Parse a `Document` instance from the Pandoc JSON provided in the `json` string.
+pub fn from_str(json: &str) -> error::Result<Document> {
+    let json: Value = serde_json::from_str(json)?;
+    read_document(&json)
+}
+
+/// Read from the provided `reader` and parse the Pandoc JSON into a `Document` instance.
+pub fn from_reader(reader: impl Read) -> error::Result<Document> {
+    let json: Value = serde_json::from_reader(reader)?;
+    read_document(&json)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn read_document(json: &Value) -> error::Result<Document> {
+    let version = json["pandoc-api-version"]
+        .as_array()
+        .ok_or(ErrorKind::UnknownFormat)?;
+    if version.first().and_then(Value::as_u64) != Some(1) {
+        return Err(ErrorKind::IncompatibleVersion(
+            version
+                .iter()
+                .map(Value::to_string)
+                .collect::<Vec<String>>()
+                .join("."),
+        )
+        .into());
+    }
+    let blocks = json["blocks"].as_array().ok_or(ErrorKind::UnknownFormat)?;
+
+    let mut doc = Document::default();
+    if let Some(meta) = json["meta"].as_object() {
+        for (key, value) in meta {
+            read_meta(&mut doc, key, value)?;
+        }
+    }
+    for block in read_blocks(blocks)? {
+        doc.add_content(block)?;
+    }
+    Ok(doc)
+}
+
+fn read_meta(doc: &mut Document, key: &str, value: &Value) -> error::Result<()> {
+    match key {
+        "abstract" => {
+            let _ = doc.add_abstract(Paragraph::from(match tag(value) {
+                "MetaBlocks" => blocks_as_inline(read_blocks(elements(value))?),
+                _ => read_inlines(elements(value))?,
+            }));
+        }
+        "author" => {
+            for author in meta_list(value) {
+                let _ = doc.add_author(match tag(author) {
+                    "MetaMap" => Author {
+                        name: meta_string(&author["c"]["name"]),
+                        email: meta_field(author, &["email"]),
+                        organization: meta_field(author, &["affiliation", "organization"]),
+                    },
+                    _ => Author {
+                        name: meta_string(author),
+                        email: None,
+                        organization: None,
+                    },
+                });
+            }
+        }
+        "copyright" => {
+            for copyright in meta_list(value) {
+                let _ = doc.add_metadata(match tag(copyright) {
+                    "MetaMap" => Metadata::Copyright(Copyright {
+                        year: meta_string(&copyright["c"]["year"])
+                            .parse()
+                            .unwrap_or_default(),
+                        organization: meta_field(copyright, &["organization"]),
+                        comment: meta_field(copyright, &["comment"]),
+                    }),
+                    _ => metadata_from(key, &meta_string(copyright)),
+                });
+            }
+        }
+        "keywords" if tag(value) == "MetaList" => {
+            let _ = doc.add_metadata(Metadata::Keywords(
+                meta_list(value).into_iter().map(meta_string).collect(),
+            ));
+        }
+        _ => {
+            let _ = doc.add_metadata(metadata_from(key, &meta_string(value)));
+        }
+    }
+    Ok(())
+}
+
+fn meta_list(value: &Value) -> Vec<&Value> {
+    match tag(value) {
+        "MetaList" => elements(value).iter().collect(),
+        _ => vec![value],
+    }
+}
+
+fn meta_field(value: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .map(|key| &value["c"][*key])
+        .find(|field| !field.is_null())
+        .map(meta_string)
+}
+
+fn meta_string(value: &Value) -> String {
+    match tag(value) {
+        "MetaString" => value["c"].as_str().unwrap_or_default().to_string(),
+        "MetaBool" => value["c"].as_bool().unwrap_or_default().to_string(),
+        "MetaInlines" => inline_string(elements(value)),
+        "MetaBlocks" => elements(value)
+            .iter()
+            .map(|block| inline_string(elements(block)))
+            .collect::<Vec<String>>()
+            .join("\n"),
+        "MetaList" => elements(value)
+            .iter()
+            .map(meta_string)
+            .collect::<Vec<String>>()
+            .join(", "),
+        _ => String::new(),
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+fn read_blocks(values: &[Value]) -> error::Result<Vec<BlockContent>> {
+    let mut blocks: Vec<BlockContent> = Default::default();
+    for value in values {
+        read_block(value, &mut blocks)?;
+    }
+    Ok(blocks)
+}
+
+fn read_block(value: &Value, blocks: &mut Vec<BlockContent>) -> error::Result<()> {
+    let content = &value["c"];
+    match tag(value) {
+        "Plain" | "Para" => {
+            let inlines = elements(value);
+            match inlines {
+                [single] if tag(single) == "Image" => {
+                    if let Some(image) = read_image(single) {
+                        blocks.push(ImageBlock::from(image).into());
+                        return Ok(());
+                    }
+                }
+                [single] if tag(single) == "Math" && tag(&single["c"][0]) == "DisplayMath" => {
+                    blocks.push(MathBlock::from(Math::from_str(as_str(&single["c"][1]))?).into());
+                    return Ok(());
+                }
+                _ => {}
+            }
+            let inlines = trim_inline(read_inlines(inlines)?);
+            if !inlines.is_empty() {
+                blocks.push(Paragraph::from(inlines).into());
+            }
+        }
+        "LineBlock" => blocks.push(
+            Formatted::from(
+                elements(value)
+                    .iter()
+                    .map(|line| inline_string(as_array(line)))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            )
+            .into(),
+        ),
+        "CodeBlock" => {
+            let code = as_str(&content[1]);
+            let mut block = match classes(&content[0]).first() {
+                Some(language) => CodeBlock::with_language(code, language),
+                None => CodeBlock::from(code),
+            };
+            blocks.push(labeled(&mut block, &content[0]).clone().into());
+        }
+        "RawBlock" => {
+            let raw = as_str(&content[1]).trim();
+            let format = as_str(&content[0]);
+            blocks.push(
+                match (format, front_matter(raw), raw.strip_prefix("<!--")) {
+                    ("latex" | "tex", Some(front_matter), _) => {
+                        BlockContent::FrontMatter(front_matter)
+                    }
+                    ("html", _, Some(comment)) => {
+                        BlockContent::Comment(comment.trim_end_matches("-->").trim().to_string())
+                    }
+                    _ => BlockContent::Comment(raw.to_string()),
+                },
+            );
+        }
+        "BlockQuote" => {
+            let mut quote = Quote::default();
+            for block in read_blocks(elements(value))? {
+                quote.add_content(block)?;
+            }
+            blocks.push(quote.into());
+        }
+        "OrderedList" => blocks.push(read_list(ListKind::Ordered, as_array(&content[1]))?.into()),
+        "BulletList" => blocks.push(read_list(ListKind::Unordered, elements(value))?.into()),
+        "DefinitionList" => {
+            let mut list = DefinitionList::default();
+            for entry in elements(value) {
+                let mut definition = Definition::new(
+                    &inline_string(as_array(&entry[0])),
+                    DefinitionPart::from(
+                        as_array(&entry[1])
+                            .iter()
+                            .map(|blocks| read_blocks(as_array(blocks)).map(blocks_as_inline))
+                            .collect::<error::Result<Vec<Vec<InlineContent>>>>()?
+                            .join(&InlineContent::LineBreak),
+                    ),
+                );
+                if let [span] = as_array(&entry[0]) {
+                    if tag(span) == "Span" {
+                        let _ = labeled(&mut definition, &span["c"][0]);
+                    }
+                }
+                let _ = list.add_definition(definition);
+            }
+            blocks.push(list.into());
+        }
+        "Header" => {
+            let mut heading = Heading::from(trim_inline(read_inlines(as_array(&content[2]))?));
+            let level = u8::try_from(content[0].as_u64().unwrap_or(1)).unwrap_or_default();
+            let _ = heading.set_level(HeadingLevel::try_from(level).unwrap_or_default());
+            blocks.push(labeled(&mut heading, &content[1]).clone().into());
+        }
+        "HorizontalRule" => blocks.push(BlockContent::ThematicBreak),
+        "Table" => blocks.push(read_table(content)?.into()),
+        "Figure" => {
+            let mut inner = read_blocks(as_array(&content[2]))?;
+            let caption = caption_string(&content[1]);
+            if let [block] = inner.as_mut_slice() {
+                if !caption.is_empty() {
+                    set_block_caption(block, Caption::from(caption.as_str()));
+                }
+            } else if !caption.is_empty() {
+                inner.push(Paragraph::plain_str(&caption).into());
+            }
+            blocks.extend(with_label(inner, &content[0]));
+        }
+        "Div" => blocks.extend(with_label(read_blocks(as_array(&content[1]))?, &content[0])),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn read_list(kind: ListKind, items: &[Value]) -> error::Result<List> {
+    let mut list = List::new(kind);
+    for item in items {
+        let mut inline: Vec<InlineContent> = Default::default();
+        let mut label: Option<Label> = None;
+        let mut sub_lists: Vec<List> = Default::default();
+        for block in as_array(item) {
+            match (tag(block), elements(block)) {
+                ("BulletList", items) => sub_lists.push(read_list(ListKind::Unordered, items)?),
+                ("OrderedList", _) => {
+                    sub_lists.push(read_list(ListKind::Ordered, as_array(&block["c"][1]))?)
+                }
+                ("Plain", [span]) | ("Para", [span])
+                    if tag(span) == "Span" && label.is_none() && inline.is_empty() =>
+                {
+                    label = identifier(&span["c"][0]);
+                    inline = read_inlines(as_array(&span["c"][1]))?;
+                }
+                _ => {
+                    let mut blocks: Vec<BlockContent> = Default::default();
+                    read_block(block, &mut blocks)?;
+                    for block in blocks {
+                        if !inline.is_empty() {
+                            inline.push(InlineContent::LineBreak);
+                        }
+                        inline.extend(block_as_inline(block));
+                    }
+                }
+            }
+        }
+        if !inline.is_empty() || sub_lists.is_empty() {
+            let mut item = Item::from(trim_inline(inline));
+            if let Some(label) = label {
+                let _ = item.set_label(label);
+            }
+            let _ = list.add_item(item);
+        }
+        for sub_list in sub_lists {
+            let _ = list.add_sub_list(sub_list);
+        }
+    }
+    Ok(list)
+}
+
+fn read_table(content: &Value) -> error::Result<Table> {
+    let mut table = Table::default();
+    let _ = labeled(&mut table, &content[0]);
+    let caption = caption_string(&content[1]);
+    if !caption.is_empty() {
+        let _ = table.set_caption(Caption::from(caption.as_str()));
+    }
+    let alignments: Vec<Alignment> = as_array(&content[2])
+        .iter()
+        .map(|spec| match tag(&spec[0]) {
+            "AlignLeft" => Alignment::Left,
+            "AlignRight" => Alignment::Right,
+            "AlignCenter" => Alignment::Centered,
+            _ => Alignment::Justified,
+        })
+        .collect();
+
+    let mut rows: Vec<&Value> = as_array(&content[3][1]).iter().collect();
+    for body in as_array(&content[4]) {
+        rows.extend(as_array(&body[2]));
+        rows.extend(as_array(&body[3]));
+    }
+    rows.extend(as_array(&content[5][1]));
+
+    let mut rows = rows.into_iter();
+    if let Some(head) = rows.next() {
+        for (index, cell) in read_row(head)?.cells().iter().enumerate() {
+            table.add_column(Column::with_alignment(
+                &cell.unformatted_string(),
+                alignments
+                    .get(index)
+                    .cloned()
+                    .unwrap_or(Alignment::Justified),
+            ));
+        }
+    }
+    for row in rows {
+        table.add_row(read_row(row)?);
+    }
+    Ok(table)
+}
+
+fn read_row(row: &Value) -> error::Result<Row> {
+    let mut new_row = Row::new(&[]);
+    for cell in as_array(&row[1]) {
+        let inline = blocks_as_inline(read_blocks(as_array(&cell[4]))?);
+        let mut new_cell = if inline.is_empty() {
+            Cell::skip()
+        } else {
+            Cell::from(inline)
+        };
+        let _ = new_row.add_cell(labeled(&mut new_cell, &cell[0]).clone());
+        for _ in 1..cell[3].as_u64().unwrap_or(1) {
+            let _ = new_row.add_cell(Cell::skip());
+        }
+    }
+    Ok(new_row)
+}
+
+// ------------------------------------------------------------------------------------------------
+
+fn read_inlines(values: &[Value]) -> error::Result<Vec<InlineContent>> {
+    let mut inline: Vec<InlineContent> = Default::default();
+    for value in values {
+        read_inline(value, &mut inline)?;
+    }
+    Ok(inline)
+}
+
+fn read_inline(value: &Value, inline: &mut Vec<InlineContent>) -> error::Result<()> {
+    let content = &value["c"];
+    match tag(value) {
+        "Str" => {
+            let mut text = String::new();
+            for c in as_str(content).chars() {
+                let character = match c {
+                    '\u{a0}' => Character::NonBreakSpace,
+                    '\u{2014}' => Character::EmDash,
+                    '\u{2013}' => Character::EnDash,
+                    _ => {
+                        text.push(c);
+                        continue;
+                    }
+                };
+                push_text(inline, &text);
+                text.clear();
+                inline.push(character.into());
+            }
+            push_text(inline, &text);
+        }
+        "Space" | "SoftBreak" => push_text(inline, " "),
+        "LineBreak" => inline.push(InlineContent::LineBreak),
+        "Emph" => inline.push(read_span(elements(value), SpanStyle::Italic)?),
+        "Strong" => inline.push(read_span(elements(value), SpanStyle::Bold)?),
+        "Strikeout" => inline.push(read_span(elements(value), SpanStyle::Strikethrough)?),
+        "Underline" => inline.push(read_span(elements(value), SpanStyle::Underline)?),
+        "SmallCaps" => inline.push(read_span(elements(value), SpanStyle::SmallCaps)?),
+        "Superscript" => inline.push(read_span(elements(value), SpanStyle::Superscript)?),
+        "Subscript" => inline.push(read_span(elements(value), SpanStyle::Subscript)?),
+        "Code" => inline.push(Span::with_style(as_str(&content[1]), SpanStyle::Code).into()),
+        "Math" => inline.push(Math::from_str(as_str(&content[1]))?.into()),
+        "Link" => {
+            let target = as_str(&content[2][0]);
+            let caption = inline_string(as_array(&content[1]));
+            let is_auto = caption == target
+                || Some(caption.as_str()) == target.strip_prefix('#')
+                || classes(&content[0]).contains(&"uri");
+            inline.push(hyper_link(target, if is_auto { "" } else { &caption }).into());
+        }
+        "Image" => match read_image(value) {
+            Some(image) => inline.push(image.into()),
+            None => inline.extend(read_inlines(as_array(&content[1]))?),
+        },
+        "Span" => {
+            let inner = read_inlines(as_array(&content[1]))?;
+            match classes(&content[0]).iter().find_map(|class| size(class)) {
+                Some(size) => inline.push(read_styled(inner, SpanStyle::Sized(size))?),
+                None => inline.extend(inner),
+            }
+        }
+        "Quoted" => {
+            let (open, close) = match tag(&content[0]) {
+                "SingleQuote" => ("\u{2018}", "\u{2019}"),
+                _ => ("\u{201c}", "\u{201d}"),
+            };
+            push_text(inline, open);
+            for value in as_array(&content[1]) {
+                read_inline(value, inline)?;
+            }
+            push_text(inline, close);
+        }
+        "Cite" => {
+            for value in as_array(&content[1]) {
+                read_inline(value, inline)?;
+            }
+        }
+        "RawInline" => push_text(inline, as_str(&content[1])),
+        "Note" => {
+            push_text(inline, " [");
+            inline.extend(blocks_as_inline(read_blocks(elements(value))?));
+            push_text(inline, "]");
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn read_span(values: &[Value], style: SpanStyle) -> error::Result<InlineContent> {
+    read_styled(read_inlines(values)?, style)
+}
+
+fn read_styled(inline: Vec<InlineContent>, style: SpanStyle) -> error::Result<InlineContent> {
+    let mut span = Span::from(inline);
+    span.add_style(style)?;
+    Ok(span.into())
+}
+
+fn read_image(value: &Value) -> Option<Image> {
+    let path = as_str(&value["c"][2][0]);
+    if path.is_empty() {
+        return None;
+    }
+    let alt_text = inline_string(as_array(&value["c"][1]));
+    Some(if alt_text.is_empty() {
+        Image::new(path)
+    } else {
+        Image::with_alt_text(path, &alt_text)
+    })
+}
+
+fn inline_string(values: &[Value]) -> String {
+    let mut s = String::new();
+    for value in values {
+        let content = &value["c"];
+        match tag(value) {
+            "Str" => s.push_str(as_str(content)),
+            "Space" | "SoftBreak" => s.push(' '),
+            "LineBreak" => s.push('\n'),
+            "Code" | "Math" | "RawInline" => s.push_str(as_str(&content[1])),
+            "Link" | "Image" | "Span" | "Quoted" | "Cite" => {
+                s.push_str(&inline_string(as_array(&content[1])))
+            }
+            "Note" => {}
+            _ => s.push_str(&inline_string(as_array(content))),
+        }
+    }
+    s
+}
+
+fn caption_string(caption: &Value) -> String {
+    as_array(&caption[1])
+        .iter()
+        .map(|block| inline_string(elements(block)))
+        .collect::<Vec<String>>()
+        .join(" ")
+        .trim()
+        .to_string()
+}
+
+fn blocks_as_inline(blocks: Vec<BlockContent>) -> Vec<InlineContent> {
+    blocks
+        .into_iter()
+        .map(block_as_inline)
+        .filter(|inline| !inline.is_empty())
+        .collect::<Vec<Vec<InlineContent>>>()
+        .join(&InlineContent::LineBreak)
+}
+
+fn front_matter(raw: &str) -> Option<FrontMatter> {
+    match raw {
+        "\\tableofcontents" => Some(FrontMatter::TableOfContents),
+        "\\listofequfloats" => Some(FrontMatter::TableOfEquations),
+        "\\listoffigures" => Some(FrontMatter::TableOfFigures),
+        "\\lstlistoflistings" => Some(FrontMatter::TableOfListings),
+        "\\listoftables" => Some(FrontMatter::TableOfTables),
+        "\\printglossary" => Some(FrontMatter::Glossary),
+        _ => None,
+    }
+}
+
+fn size(class: &str) -> Option<Size> {
+    match class {
+        "largest" => Some(Size::Largest),
+        "larger" => Some(Size::Larger),
+        "large" => Some(Size::Large),
+        "normal" => Some(Size::Normal),
+        "small" => Some(Size::Small),
+        "smaller" => Some(Size::Smaller),
+        "smallest" => Some(Size::Smallest),
+        _ => None,
+    }
+}
+
+fn set_block_caption(block: &mut BlockContent, caption: Caption) {
+    let _ = match block {
+        BlockContent::ImageBlock(v) => v.set_caption(caption).has_caption(),
+        BlockContent::MathBlock(v) => v.set_caption(caption).has_caption(),
+        BlockContent::CodeBlock(v) => v.set_caption(caption).has_caption(),
+        BlockContent::Table(v) => v.set_caption(caption).has_caption(),
+        _ => false,
+    };
+}
+
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Apply the identifier from `attr` as the label of the first block, if there is one.
+///
+fn with_label(blocks: Vec<BlockContent>, attr: &Value) -> Vec<BlockContent> {
+    let mut blocks = blocks;
+    if let (Some(first), Some(label)) = (blocks.first_mut(), identifier(attr)) {
+        set_block_label(first, label);
+    }
+    blocks
+}
+
+fn labeled<'a, T: HasLabel>(value: &'a mut T, attr: &Value) -> &'a mut T {
+    if let Some(label) = identifier(attr) {
+        let _ = value.set_label(label);
+    }
+    value
+}
+
+fn identifier(attr: &Value) -> Option<Label> {
+    Label::from_str(as_str(&attr[0])).ok()
+}
+
+fn classes(attr: &Value) -> Vec<&str> {
+    as_array(&attr[1])
+        .iter()
+        .filter_map(Value::as_str)
+        .collect()
+}
+
+fn tag(value: &Value) -> &str {
+    as_str(&value["t"])
+}
+
+fn elements(value: &Value) -> &[Value] {
+    as_array(&value["c"])
+}
+
+fn as_array(value: &Value) -> &[Value] {
+    value.as_array().map(Vec::as_slice).unwrap_or_default()
+}
+
+fn as_str(value: &Value) -> &str {
+    value.as_str().unwrap_or_default()
+}
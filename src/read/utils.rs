@@ -0,0 +1,321 @@
+/*!
+Common functions used by more than one of the document readers.
+*/
+
+use crate::model::block::{BlockContent, HasLabel, Label};
+use crate::model::document::{Author, Copyright, Metadata, SimpleProperty};
+use crate::model::inline::{HyperLink, InlineContent, Span, SpanStyle, Text};
+use crate::model::HasInnerContent;
+use regex::Regex;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+/// A top-level YAML key, its scalar value, and any list items (with an optional key) under it.
+type YamlEntry = (String, String, Vec<(Option<String>, String)>);
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Construct a `Metadata` value from a key and a value string; this is the inverse of the
+/// `Metadata::key` and `Metadata::value_string` methods used by most writers.
+///
+pub(crate) fn metadata_from(key: &str, value: &str) -> Metadata {
+    let key = key.trim();
+    let value = value.trim();
+    match key.to_lowercase().as_str() {
+        "author" => match AUTHOR_RE.captures(value) {
+            Some(captures) => Metadata::Author(Author {
+                name: captures.get(1).unwrap().as_str().trim().to_string(),
+                email: captures.get(2).map(|m| m.as_str().trim().to_string()),
+                organization: captures.get(3).map(|m| m.as_str().trim().to_string()),
+            }),
+            None => Metadata::Author(Author {
+                name: value.to_string(),
+                email: None,
+                organization: None,
+            }),
+        },
+        "copyright" => match COPYRIGHT_RE.captures(value) {
+            Some(captures) => Metadata::Copyright(Copyright {
+                year: captures
+                    .get(1)
+                    .unwrap()
+                    .as_str()
+                    .parse()
+                    .unwrap_or_default(),
+                organization: captures.get(2).map(|m| m.as_str().trim().to_string()),
+                comment: captures.get(3).map(|m| m.as_str().trim().to_string()),
+            }),
+            None => other(key, value),
+        },
+        "date" => Metadata::Date(value.to_string()),
+        "keywords" => Metadata::Keywords(
+            value
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect(),
+        ),
+        "revision" => Metadata::Revision(value.to_string()),
+        "status" => Metadata::Status(value.to_string()),
+        "title" => Metadata::Title(value.to_string()),
+        _ => other(key, value),
+    }
+}
+
+///
+/// Construct a list of `Metadata` values from a simple YAML block, such as the front matter of a
+/// Markdown document. Only scalar values and lists of scalars, or of single-entry mappings, are
+/// supported; this covers the output of `Metadata::yaml_string` as well as common hand-written
+/// front matter. Repeated `name` (or `year`) entries in a list start a new author (or copyright).
+///
+pub(crate) fn metadata_from_yaml(yaml: &str) -> Vec<Metadata> {
+    let mut entries: Vec<YamlEntry> = Default::default();
+    for line in yaml.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed == "---" || trimmed == "..." {
+            continue;
+        }
+        if let Some(item) = trimmed.strip_prefix('-') {
+            if let Some((_, _, items)) = entries.last_mut() {
+                let item = item.trim();
+                items.push(match yaml_pair(item) {
+                    Some((key, value)) => (Some(key.to_lowercase()), value),
+                    None => (None, unquote(item)),
+                });
+            }
+        } else if let Some((key, value)) = yaml_pair(trimmed) {
+            entries.push((key, value, Default::default()));
+        }
+    }
+
+    let mut metadata: Vec<Metadata> = Default::default();
+    for (key, value, items) in entries {
+        if items.is_empty() {
+            if !value.is_empty() {
+                metadata.push(metadata_from(&key, &value));
+            }
+            continue;
+        }
+        match key.to_lowercase().as_str() {
+            "author" | "authors" => {
+                let mut author: Option<Author> = None;
+                for (field, value) in items {
+                    match field.as_deref() {
+                        None | Some("name") => {
+                            if let Some(author) = author.take() {
+                                metadata.push(Metadata::Author(author));
+                            }
+                            author = Some(Author {
+                                name: value,
+                                email: None,
+                                organization: None,
+                            });
+                        }
+                        Some("email") => {
+                            if let Some(author) = &mut author {
+                                author.email = Some(value);
+                            }
+                        }
+                        Some("organization") | Some("affiliation") => {
+                            if let Some(author) = &mut author {
+                                author.organization = Some(value);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                if let Some(author) = author {
+                    metadata.push(Metadata::Author(author));
+                }
+            }
+            "copyright" => {
+                let mut copyright: Option<Copyright> = None;
+                for (field, value) in items {
+                    match field.as_deref() {
+                        None | Some("year") => {
+                            if let Some(copyright) = copyright.take() {
+                                metadata.push(Metadata::Copyright(copyright));
+                            }
+                            copyright = Some(Copyright {
+                                year: value.parse().unwrap_or_default(),
+                                organization: None,
+                                comment: None,
+                            });
+                        }
+                        Some("organization") => {
+                            if let Some(copyright) = &mut copyright {
+                                copyright.organization = Some(value);
+                            }
+                        }
+                        Some("comment") => {
+                            if let Some(copyright) = &mut copyright {
+                                copyright.comment = Some(value);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                if let Some(copyright) = copyright {
+                    metadata.push(Metadata::Copyright(copyright));
+                }
+            }
+            _ => {
+                let values: Vec<String> = items.into_iter().map(|(_, value)| value).collect();
+                metadata.push(metadata_from(&key, &values.join(", ")));
+            }
+        }
+    }
+    metadata
+}
+
+///
+/// Append `text` to the content list, merging it with any immediately preceding `Text` value
+/// so that readers do not produce long runs of small text fragments.
+///
+pub(crate) fn push_text(content: &mut Vec<InlineContent>, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    if let Some(InlineContent::Text(last)) = content.last_mut() {
+        *last = Text::from(format!("{}{}", last.inner(), text));
+    } else {
+        content.push(Text::from(text).into());
+    }
+}
+
+///
+/// Construct a `HyperLink` for the `target`; a target of the form `#label`, where the label is
+/// valid, is an internal link, anything else is external. An empty `caption` is ignored.
+///
+pub(crate) fn hyper_link(target: &str, caption: &str) -> HyperLink {
+    match target.strip_prefix('#') {
+        Some(label) if Label::is_valid(label) => {
+            let label = Label::from_str(label).unwrap();
+            if caption.is_empty() {
+                HyperLink::internal(label)
+            } else {
+                HyperLink::internal_with_caption_str(label, caption)
+            }
+        }
+        _ => {
+            if caption.is_empty() {
+                HyperLink::external(target)
+            } else {
+                HyperLink::external_with_caption_str(target, caption)
+            }
+        }
+    }
+}
+
+///
+/// Convert a block into inline content, for those places such as list items where the model
+/// does not allow blocks. Blocks with no sensible inline form are dropped.
+///
+pub(crate) fn block_as_inline(block: BlockContent) -> Vec<InlineContent> {
+    match block {
+        BlockContent::Heading(v) => v.into_inner(),
+        BlockContent::Paragraph(v) => v.into_inner(),
+        BlockContent::CodeBlock(v) => vec![Span::with_style(v.code(), SpanStyle::Code).into()],
+        BlockContent::Formatted(v) => vec![Span::with_style(v.inner(), SpanStyle::Mono).into()],
+        BlockContent::Comment(_) => Default::default(),
+        BlockContent::ImageBlock(v) => vec![v.inner().clone().into()],
+        _ => Default::default(),
+    }
+}
+
+///
+/// Remove any leading and trailing whitespace from the inline content, dropping any text values
+/// that become empty.
+///
+pub(crate) fn trim_inline(inline: Vec<InlineContent>) -> Vec<InlineContent> {
+    let mut inline = inline;
+    if let Some(InlineContent::Text(first)) = inline.first() {
+        let trimmed = first.inner().trim_start().to_string();
+        if trimmed.is_empty() {
+            let _ = inline.remove(0);
+            return trim_inline(inline);
+        }
+        inline[0] = Text::from(trimmed).into();
+    }
+    if let Some(InlineContent::Text(last)) = inline.last() {
+        let trimmed = last.inner().trim_end().to_string();
+        if trimmed.is_empty() {
+            let _ = inline.pop();
+            return trim_inline(inline);
+        }
+        let index = inline.len() - 1;
+        inline[index] = Text::from(trimmed).into();
+    }
+    inline
+}
+
+///
+/// Set the label on the block, if it is one of the labeled block types; otherwise the label is
+/// ignored.
+///
+pub(crate) fn set_block_label(block: &mut BlockContent, label: Label) {
+    let _ = match block {
+        BlockContent::Heading(v) => v.set_label(label).has_label(),
+        BlockContent::ImageBlock(v) => v.set_label(label).has_label(),
+        BlockContent::MathBlock(v) => v.set_label(label).has_label(),
+        BlockContent::List(v) => v.set_label(label).has_label(),
+        BlockContent::DefinitionList(v) => v.set_label(label).has_label(),
+        BlockContent::Formatted(v) => v.set_label(label).has_label(),
+        BlockContent::CodeBlock(v) => v.set_label(label).has_label(),
+        BlockContent::Paragraph(v) => v.set_label(label).has_label(),
+        BlockContent::Quote(v) => v.set_label(label).has_label(),
+        BlockContent::Table(v) => v.set_label(label).has_label(),
+        _ => false,
+    };
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+lazy_static! {
+    static ref AUTHOR_RE: Regex =
+        Regex::new(r"^([^(]*?)\s*(?:\(([^)]*)\))?\s*(?:-\s+(.*))?$").unwrap();
+    static ref COPYRIGHT_RE: Regex =
+        Regex::new(r"^(\d{1,5})(?:\s+([^-]*?)\.?)?(?:\s+-\s+(.*?)\.?)?$").unwrap();
+}
+
+fn yaml_pair(line: &str) -> Option<(String, String)> {
+    let (key, value) = line.split_once(':')?;
+    let key = key.trim();
+    if key.is_empty()
+        || key.contains(char::is_whitespace)
+        || !(value.is_empty() || value.starts_with(char::is_whitespace))
+    {
+        None
+    } else {
+        Some((key.to_string(), unquote(value.trim())))
+    }
+}
+
+fn unquote(value: &str) -> String {
+    if value.len() >= 2
+        && ((value.starts_with('"') && value.ends_with('"'))
+            || (value.starts_with('\'') && value.ends_with('\'')))
+    {
+        value[1..value.len() - 1].replace("\\\"", "\"")
+    } else {
+        value.to_string()
+    }
+}
+
+fn other(key: &str, value: &str) -> Metadata {
+    Metadata::Other(SimpleProperty {
+        key: key.to_string(),
+        value: value.to_string(),
+    })
+}
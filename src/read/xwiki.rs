@@ -0,0 +1,648 @@
+/*!
+Read a document from [XWiki 2.x](https://www.xwiki.org/xwiki/bin/view/XWiki/XWikiSyntax) syntax.
+This reader is the counterpart to the [`markdown`](../../write/markdown/index.html) writer when
+using `MarkdownFlavor::XWiki`, and so a page written by that writer can be read back into the same
+document model.
+
+* Headings (`= text =` to `====== text ======`) become `Heading` values.
+* The text styles `**bold**`, `//italic//`, `__underline__`, `--strikethrough--`, `##monospace##`,
+  `^^superscript^^`, and `,,subscript,,` become styled `Span` values.
+* Lists (`*`, `1.`, and mixed markers such as `*1.`) become (possibly nested) `List` values.
+* Definition lists (`; term` followed by `: definition`) become `DefinitionList` values.
+* Quotations (`>` prefixed lines) become (possibly nested) `Quote` values.
+* Tables (`|=heading|=heading` followed by `|cell|cell` rows) become `Table` values.
+* The `{{code}}` macro becomes a `CodeBlock`, retaining the `language` parameter, and verbatim
+  blocks (`{{{ ... }}}`) become `Formatted` values.
+* The `{{comment}}` macro becomes a `BlockContent::Comment`, except for a leading comment that
+  contains YAML metadata which becomes `Metadata` values.
+* The `{{toc/}}` macro becomes `FrontMatter::TableOfContents`.
+* The `{{id name="..."/}}` macro becomes the label of the following (or enclosing) block.
+* The `{{formula}}` macro becomes `Math`, or a `MathBlock` if it is the only content in a
+  paragraph; similarly `image:` references become `Image` values, or an `ImageBlock`.
+* Links (`[[label>>reference]]`) become `HyperLink` values, a link with only an anchor
+  (`[[label>>||anchor=name]]`) is treated as internal.
+
+Other macros are not dropped; block macros are retained, as source, in a `BlockContent::Comment`
+and inline macros are passed through as text.
+
+# Example
+
+```rust
+use somedoc::model::HasInnerContent;
+use somedoc::read::xwiki::from_str;
+
+let doc = from_str("= Heading =\n\nSome //styled// text.\n").unwrap();
+assert_eq!(doc.inner().len(), 2);
+```
+
+*/
+
+use crate::error;
+use crate::model::block::{
+    BlockContent, Cell, CodeBlock, Column, Definition, DefinitionList, DefinitionPart, Formatted,
+    FrontMatter, HasLabel, Heading, HeadingLevel, ImageBlock, Item, Label, List, ListKind,
+    MathBlock, Paragraph, Quote, Row, Table,
+};
+use crate::model::inline::{
+    Character, HasInlineContent, Image, InlineContent, Math, Span, SpanStyle,
+};
+use crate::model::{Document, HasInnerContent, HasStyles};
+use crate::read::utils::{hyper_link, metadata_from_yaml, push_text, set_block_label, trim_inline};
+use regex::Regex;
+use std::convert::TryFrom;
+use std::io::Read;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+/// Parse a `Document` instance from the XWiki syntax provided in the `xwiki` string.
+pub fn from_str(xwiki: &str) -> error::Result<Document> {
+    let lines: Vec<&str> = xwiki.lines().collect();
+    let mut blocks = read_blocks(&lines)?;
+    let mut doc = Document::default();
+    if let Some(BlockContent::Comment(comment)) = blocks.first() {
+        if is_metadata(comment) {
+            for meta_datum in metadata_from_yaml(comment) {
+                let _ = doc.add_metadata(meta_datum);
+            }
+            let _ = blocks.remove(0);
+        }
+    }
+    for block in blocks {
+        doc.add_content(block)?;
+    }
+    Ok(doc)
+}
+
+/// Read from the provided `reader` and parse the XWiki syntax into a `Document` instance.
+pub fn from_reader(reader: impl Read) -> error::Result<Document> {
+    let mut reader = reader;
+    let mut xwiki = String::new();
+    let _ = reader.read_to_string(&mut xwiki)?;
+    from_str(&xwiki)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct ListEntry {
+    kinds: Vec<ListKind>,
+    inline: Vec<InlineContent>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+lazy_static! {
+    static ref HEADING_RE: Regex = Regex::new(r"^\s*(={1,6})\s*(.*?)\s*=*\s*$").unwrap();
+    static ref LIST_RE: Regex = Regex::new(r"^\s*([*1]+)(\.)?\s+(.*)$").unwrap();
+    static ref THEMATIC_BREAK_RE: Regex = Regex::new(r"^\s*-{4,}\s*$").unwrap();
+    static ref ID_RE: Regex = Regex::new(r#"\{\{id\s+name="?([^"/}]*)"?\s*/\}\}"#).unwrap();
+    static ref TOC_RE: Regex = Regex::new(r"^\s*\{\{toc(\s[^}]*)?/\}\}\s*$").unwrap();
+    static ref MACRO_START_RE: Regex =
+        Regex::new(r"^\s*\{\{([a-zA-Z][a-zA-Z0-9_-]*)(\s[^}]*?)?\s*(/)?\}\}").unwrap();
+    static ref PARAMETER_RE: Regex = Regex::new(r#"([a-zA-Z]+)\s*=\s*"?([^"\s]*)"?"#).unwrap();
+    static ref METADATA_KEY_RE: Regex = Regex::new(r"^[a-z][a-z0-9_-]*:(\s|$)").unwrap();
+}
+
+/// The inline style markers, in the order they are checked.
+const STYLE_MARKERS: &[(&str, SpanStyle)] = &[
+    ("**", SpanStyle::Bold),
+    ("//", SpanStyle::Italic),
+    ("__", SpanStyle::Underline),
+    ("--", SpanStyle::Strikethrough),
+    ("##", SpanStyle::Mono),
+    ("^^", SpanStyle::Superscript),
+    (",,", SpanStyle::Subscript),
+];
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn read_blocks(lines: &[&str]) -> error::Result<Vec<BlockContent>> {
+    let mut blocks: Vec<BlockContent> = Default::default();
+    let mut label: Option<Label> = None;
+    let mut paragraph: Vec<&str> = Default::default();
+    let mut index = 0;
+
+    macro_rules! flush_paragraph {
+        () => {
+            if !paragraph.is_empty() {
+                let mut block = read_paragraph(&paragraph)?;
+                if let Some(label) = label.take() {
+                    set_block_label(&mut block, label);
+                }
+                blocks.push(block);
+                paragraph.clear();
+            }
+        };
+    }
+    macro_rules! push_block {
+        ($block:expr) => {{
+            flush_paragraph!();
+            let mut block: BlockContent = $block;
+            if let Some(label) = label.take() {
+                set_block_label(&mut block, label);
+            }
+            blocks.push(block);
+        }};
+    }
+
+    while index < lines.len() {
+        let line = lines[index];
+        let trimmed = line.trim();
+        index += 1;
+        if trimmed.is_empty() {
+            flush_paragraph!();
+        } else if let Some(captures) = ID_RE
+            .captures(trimmed)
+            .filter(|c| c[0].len() == trimmed.len())
+        {
+            flush_paragraph!();
+            label = make_label(&captures[1]);
+        } else if TOC_RE.is_match(trimmed) {
+            push_block!(BlockContent::FrontMatter(FrontMatter::TableOfContents));
+        } else if trimmed.starts_with("{{{") {
+            let (body, next) = read_until(lines, index - 1, "{{{", "}}}");
+            index = next;
+            push_block!(Formatted::from(body.as_str()).into());
+        } else if let Some(captures) = MACRO_START_RE.captures(trimmed) {
+            let name = captures[1].to_string();
+            let parameters = captures.get(2).map(|m| m.as_str()).unwrap_or_default();
+            let self_closing = captures.get(3).is_some();
+            let start = index - 1;
+            let (body, next) = if self_closing {
+                (String::new(), index)
+            } else {
+                read_until(lines, start, &captures[0], &format!("{{{{/{}}}}}", name))
+            };
+            if name == "formula" && next == index {
+                // the formula is inline, possibly the only content of a paragraph.
+                paragraph.push(line);
+                continue;
+            }
+            flush_paragraph!();
+            let rest = lines[next - 1].trim();
+            let rest = rest
+                .rfind("}}")
+                .map(|end| rest[end + 2..].trim())
+                .unwrap_or_default();
+            match name.as_str() {
+                "comment" => blocks.push(BlockContent::Comment(body)),
+                "code" => {
+                    let mut code_block = CodeBlock::from(body.as_str());
+                    for parameter in PARAMETER_RE.captures_iter(parameters) {
+                        if &parameter[1] == "language" && !parameter[2].is_empty() {
+                            let _ = code_block.set_language(&parameter[2]);
+                        }
+                    }
+                    push_block!(code_block.into())
+                }
+                "formula" => push_block!(MathBlock::from(Math::from_str(body.trim())?).into()),
+                _ => blocks.push(BlockContent::Comment(lines[start..next].join("\n"))),
+            }
+            index = next;
+            if !rest.is_empty() {
+                paragraph.push(rest);
+            }
+        } else if let Some(captures) = HEADING_RE.captures(line) {
+            let (text, inner_label) = extract_label(&captures[2]);
+            let mut heading = Heading::from(read_inlines(&text)?);
+            let _ = heading
+                .set_level(HeadingLevel::try_from(captures[1].len() as u8).unwrap_or_default());
+            if let Some(inner_label) = inner_label {
+                let _ = heading.set_label(inner_label);
+            }
+            push_block!(heading.into());
+        } else if THEMATIC_BREAK_RE.is_match(line) {
+            push_block!(BlockContent::ThematicBreak);
+        } else if let Some(entry) = list_entry(line)? {
+            let mut entries = vec![entry];
+            while index < lines.len() {
+                match list_entry(lines[index])? {
+                    Some(entry) => entries.push(entry),
+                    None => break,
+                }
+                index += 1;
+            }
+            let mut entries = entries.into_iter().peekable();
+            push_block!(read_list(&mut entries, 1).into());
+        } else if trimmed.starts_with(';') || (trimmed.starts_with(':') && paragraph.is_empty()) {
+            let start = index - 1;
+            while index < lines.len()
+                && (lines[index].trim_start().starts_with(';')
+                    || lines[index].trim_start().starts_with(':'))
+            {
+                index += 1;
+            }
+            push_block!(read_definition_list(&lines[start..index])?.into());
+        } else if trimmed.starts_with('>') {
+            let start = index - 1;
+            while index < lines.len() && lines[index].trim_start().starts_with('>') {
+                index += 1;
+            }
+            let inner: Vec<&str> = lines[start..index]
+                .iter()
+                .map(|line| {
+                    let line = line.trim_start();
+                    let line = &line[1..];
+                    if line.starts_with('>') {
+                        line
+                    } else {
+                        line.strip_prefix(' ').unwrap_or(line)
+                    }
+                })
+                .collect();
+            let mut quote = Quote::default();
+            for block in read_blocks(&inner)? {
+                quote.add_content(block)?;
+            }
+            push_block!(quote.into());
+        } else if trimmed.starts_with('|') {
+            let start = index - 1;
+            while index < lines.len() && lines[index].trim_start().starts_with('|') {
+                index += 1;
+            }
+            push_block!(read_table(&lines[start..index])?.into());
+        } else {
+            paragraph.push(line);
+        }
+    }
+    flush_paragraph!();
+    Ok(blocks)
+}
+
+fn read_paragraph(lines: &[&str]) -> error::Result<BlockContent> {
+    let (text, label) = extract_label(&lines.join("\n"));
+    let mut inline: Vec<InlineContent> = Default::default();
+    for (i, line) in text.lines().enumerate() {
+        // in XWiki syntax a newline within a paragraph is a line break.
+        if i > 0 {
+            inline.push(InlineContent::LineBreak);
+        }
+        inline.extend(read_inlines(line)?);
+    }
+    let mut block: BlockContent = match inline.as_slice() {
+        [InlineContent::Image(image)] => ImageBlock::from(image.clone()).into(),
+        [InlineContent::Math(math)] => MathBlock::from(math.clone()).into(),
+        _ => Paragraph::from(inline).into(),
+    };
+    if let Some(label) = label {
+        set_block_label(&mut block, label);
+    }
+    Ok(block)
+}
+
+fn read_list(
+    entries: &mut std::iter::Peekable<std::vec::IntoIter<ListEntry>>,
+    depth: usize,
+) -> List {
+    let kind = entries
+        .peek()
+        .and_then(|entry| entry.kinds.get(depth - 1).cloned())
+        .unwrap_or(ListKind::Unordered);
+    let mut list = List::new(kind);
+    while let Some(entry) = entries.peek() {
+        if entry.kinds.len() < depth {
+            break;
+        } else if entry.kinds.len() == depth {
+            let entry = entries.next().unwrap();
+            let _ = list.add_item(Item::from(entry.inline));
+        } else {
+            let _ = list.add_sub_list(read_list(entries, depth + 1));
+        }
+    }
+    list
+}
+
+fn read_definition_list(lines: &[&str]) -> error::Result<DefinitionList> {
+    let mut list = DefinitionList::default();
+    let mut term: Option<(String, Option<Label>)> = None;
+    for line in lines {
+        let line = line.trim_start();
+        if let Some(text) = line.strip_prefix(';') {
+            if let Some((term, label)) = term.take() {
+                let _ = list.add_definition(labeled(
+                    Definition::new(&term, DefinitionPart::default()),
+                    label,
+                ));
+            }
+            let (text, label) = extract_label(text);
+            let text = Span::from(read_inlines(&text)?).unformatted_string();
+            term = Some((text, label));
+        } else if let Some(text) = line.strip_prefix(':') {
+            let (term, label) = term.take().unwrap_or_default();
+            let _ = list.add_definition(labeled(
+                Definition::new(&term, DefinitionPart::from(read_inlines(text)?)),
+                label,
+            ));
+        }
+    }
+    if let Some((term, label)) = term {
+        let _ = list.add_definition(labeled(
+            Definition::new(&term, DefinitionPart::default()),
+            label,
+        ));
+    }
+    Ok(list)
+}
+
+fn read_table(lines: &[&str]) -> error::Result<Table> {
+    let mut table = Table::default();
+    for (i, line) in lines.iter().enumerate() {
+        let cells = split_cells(line.trim());
+        if i == 0 && cells.iter().all(|cell| cell.starts_with('=')) {
+            for cell in cells {
+                let text = Span::from(read_inlines(&cell[1..])?).unformatted_string();
+                table.add_column(Column::new(&text));
+            }
+            continue;
+        } else if !table.has_columns() {
+            for _ in &cells {
+                table.add_column(Column::new(""));
+            }
+        }
+        let mut row = Row::new(&[]);
+        for cell in cells {
+            let (text, label) = extract_label(cell.trim_start_matches('='));
+            let _ = row.add_cell(labeled(Cell::from(read_inlines(&text)?), label));
+        }
+        table.add_row(row);
+    }
+    Ok(table)
+}
+
+// ------------------------------------------------------------------------------------------------
+
+fn read_inlines(text: &str) -> error::Result<Vec<InlineContent>> {
+    let mut inline: Vec<InlineContent> = Default::default();
+    let mut position = 0;
+    while position < text.len() {
+        let rest = &text[position..];
+        let c = rest.chars().next().unwrap();
+        if c == '~' && rest.len() > 1 {
+            // the XWiki escape character.
+            let escaped = rest[1..].chars().next().unwrap();
+            push_text(&mut inline, &escaped.to_string());
+            position += 1 + escaped.len_utf8();
+        } else if rest.starts_with("\\\\") {
+            inline.push(InlineContent::LineBreak);
+            position += 2;
+        } else if rest.starts_with("&nbsp;") {
+            inline.push(Character::NonBreakSpace.into());
+            position += 6;
+        } else if let Some(verbatim) = rest.strip_prefix("{{{") {
+            let end = verbatim.find("}}}").unwrap_or(verbatim.len());
+            push_text(&mut inline, &verbatim[..end]);
+            position += (end + 6).min(rest.len());
+        } else if rest.starts_with("{{formula}}") {
+            let end = rest.find("{{/formula}}").unwrap_or(rest.len());
+            inline.push(Math::from_str(rest[11..end].trim())?.into());
+            position += (end + 12).min(rest.len());
+        } else if rest.starts_with("{{") {
+            let end = rest.find("}}").map(|end| end + 2).unwrap_or(rest.len());
+            if !ID_RE.is_match(&rest[..end]) {
+                push_text(&mut inline, &rest[..end]);
+            }
+            position += end;
+        } else if let Some(link) = rest.strip_prefix("[[") {
+            match find_closing(link, "]]") {
+                Some(end) => {
+                    inline.push(read_link(&link[..end])?);
+                    position += end + 4;
+                }
+                None => {
+                    push_text(&mut inline, "[[");
+                    position += 2;
+                }
+            }
+        } else if let Some(end) = image_reference(text, position) {
+            inline.push(Image::new(&rest[6..end]).into());
+            position += end;
+        } else if rest.starts_with("---") && !rest.starts_with("----") {
+            inline.push(Character::EmDash.into());
+            position += 3;
+        } else if let Some((marker, style)) = STYLE_MARKERS
+            .iter()
+            .find(|(marker, _)| rest.starts_with(marker))
+        {
+            let closing = if (*marker == "//" && text[..position].ends_with(':'))
+                || rest[2..].starts_with(char::is_whitespace)
+            {
+                None
+            } else {
+                find_closing(&rest[2..], marker).filter(|end| *end > 0)
+            };
+            match closing {
+                Some(end) => {
+                    let mut span = Span::from(read_inlines(&rest[2..end + 2])?);
+                    span.add_style(style.clone())?;
+                    inline.push(span.into());
+                    position += end + 4;
+                }
+                None if *marker == "--" => {
+                    inline.push(Character::EnDash.into());
+                    position += 2;
+                }
+                None => {
+                    push_text(&mut inline, marker);
+                    position += 2;
+                }
+            }
+        } else {
+            let end = rest
+                .char_indices()
+                .skip(1)
+                .find(|(_, c)| "~\\&{[i-*/_#^,".contains(*c))
+                .map(|(i, _)| i)
+                .unwrap_or(rest.len());
+            push_text(&mut inline, &rest[..end]);
+            position += end;
+        }
+    }
+    Ok(trim_inline(inline))
+}
+
+fn read_link(content: &str) -> error::Result<InlineContent> {
+    if let Some(path) = content.strip_prefix("image:") {
+        let path = path.split("||").next().unwrap_or_default().trim();
+        if !path.is_empty() {
+            return Ok(Image::new(path).into());
+        }
+    }
+    let (caption, reference) = match content.find(">>") {
+        Some(index) => (&content[..index], &content[index + 2..]),
+        None => ("", content),
+    };
+    let caption = Span::from(read_inlines(caption)?).unformatted_string();
+    let (reference, parameters) = match reference.find("||") {
+        Some(index) => (&reference[..index], &reference[index + 2..]),
+        None => (reference, ""),
+    };
+    let anchor = PARAMETER_RE
+        .captures_iter(parameters)
+        .find(|parameter| &parameter[1] == "anchor")
+        .map(|parameter| parameter[2].to_string());
+    let reference = reference.trim();
+    Ok(match anchor {
+        Some(anchor) if reference.is_empty() => hyper_link(&format!("#{}", anchor), &caption),
+        Some(anchor) => hyper_link(&format!("{}#{}", reference, anchor), &caption),
+        None => hyper_link(reference, &caption),
+    }
+    .into())
+}
+
+// ------------------------------------------------------------------------------------------------
+
+fn list_entry(line: &str) -> error::Result<Option<ListEntry>> {
+    Ok(match LIST_RE.captures(line) {
+        Some(captures) if captures[1].ends_with('1') == captures.get(2).is_some() => {
+            let markers = &captures[1];
+            if markers.starts_with("**")
+                && markers.chars().all(|c| c == '*')
+                && find_closing(&line.trim_start()[2..], "**").is_some()
+            {
+                // a run of `*` may be either a nested list or bold text.
+                None
+            } else {
+                Some(ListEntry {
+                    kinds: markers
+                        .chars()
+                        .map(|c| {
+                            if c == '1' {
+                                ListKind::Ordered
+                            } else {
+                                ListKind::Unordered
+                            }
+                        })
+                        .collect(),
+                    inline: read_inlines(&captures[3])?,
+                })
+            }
+        }
+        _ => None,
+    })
+}
+
+///
+/// Read the lines of a block delimited by `start` and `end`, which may be on the same line. This
+/// returns the content between the delimiters and the index of the line following the block.
+///
+fn read_until(lines: &[&str], index: usize, start: &str, end: &str) -> (String, usize) {
+    let first = lines[index].trim_start();
+    let first = &first[start.len().min(first.len())..];
+    if let Some(close) = first.find(end) {
+        return (first[..close].to_string(), index + 1);
+    }
+    let mut body: Vec<&str> = Default::default();
+    if !first.trim().is_empty() {
+        body.push(first);
+    }
+    for (i, line) in lines.iter().enumerate().skip(index + 1) {
+        if let Some(close) = line.find(end) {
+            if !line[..close].trim().is_empty() {
+                body.push(&line[..close]);
+            }
+            return (body.join("\n"), i + 1);
+        }
+        body.push(line);
+    }
+    (body.join("\n"), lines.len())
+}
+
+///
+/// Find the `marker` that closes a construct, skipping any links, macros, and escaped
+/// characters within the text.
+///
+fn find_closing(text: &str, marker: &str) -> Option<usize> {
+    let mut position = 0;
+    while position < text.len() {
+        let rest = &text[position..];
+        if rest.starts_with(marker) {
+            return Some(position);
+        } else if rest.starts_with('~') && rest.len() > 1 {
+            position += 1 + rest[1..].chars().next().unwrap().len_utf8();
+        } else if marker != "]]" && rest.starts_with("[[") {
+            position += rest.find("]]").map(|end| end + 2).unwrap_or(rest.len());
+        } else if rest.starts_with("{{") {
+            position += rest.find("}}").map(|end| end + 2).unwrap_or(rest.len());
+        } else {
+            position += rest.chars().next().unwrap().len_utf8();
+        }
+    }
+    None
+}
+
+fn split_cells(line: &str) -> Vec<String> {
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+    let mut cells: Vec<String> = Default::default();
+    let mut start = 0;
+    while let Some(end) = find_closing(&line[start..], "|") {
+        cells.push(line[start..start + end].to_string());
+        start += end + 1;
+    }
+    cells.push(line[start..].to_string());
+    cells
+}
+
+///
+/// If the text at `position` is an `image:` reference, at the start of a word and with a
+/// non-empty path, return the end of the reference relative to `position`.
+///
+fn image_reference(text: &str, position: usize) -> Option<usize> {
+    let rest = &text[position..];
+    let before = &text[..position];
+    if !rest.starts_with("image:")
+        || !(before.is_empty()
+            || before.ends_with(|c: char| c.is_whitespace() || c == '(')
+            || before.ends_with("&nbsp;"))
+    {
+        return None;
+    }
+    let end = rest
+        .find(char::is_whitespace)
+        .unwrap_or(rest.len())
+        .min(rest.find("&nbsp;").unwrap_or(rest.len()));
+    if end > 6 {
+        Some(end)
+    } else {
+        None
+    }
+}
+
+fn extract_label(text: &str) -> (String, Option<Label>) {
+    match ID_RE.captures(text) {
+        Some(captures) => (
+            ID_RE.replace_all(text, "").to_string(),
+            make_label(&captures[1]),
+        ),
+        None => (text.to_string(), None),
+    }
+}
+
+fn make_label(value: &str) -> Option<Label> {
+    Label::from_str(value.trim()).ok()
+}
+
+fn labeled<T: HasLabel>(value: T, label: Option<Label>) -> T {
+    let mut value = value;
+    if let Some(label) = label {
+        let _ = value.set_label(label);
+    }
+    value
+}
+
+fn is_metadata(comment: &str) -> bool {
+    comment
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .all(|line| METADATA_KEY_RE.is_match(line) || line.starts_with("- "))
+        && comment.lines().any(|line| METADATA_KEY_RE.is_match(line))
+}
@@ -0,0 +1,616 @@
+/*!
+Write a document as [AsciiDoc](https://docs.asciidoctor.org/asciidoc/latest/), suitable for
+processing by Asciidoctor.
+
+# Example
+
+```rust
+# use somedoc::model::Document;
+use somedoc::write::asciidoc::writer;
+
+# fn make_some_document() -> Document { Document::default() }
+let doc = make_some_document();
+
+writer(&doc, &mut std::io::stdout()).unwrap();
+```
+*/
+
+use crate::model::block::{
+    Alignment, BlockContent, Caption, Column, FrontMatter, HasAlignment, HasCaption, HeadingLevel,
+    Label, ListKind,
+};
+use crate::model::document::Metadata;
+use crate::model::inline::text::Size;
+use crate::model::inline::{Character, HyperLink, HyperLinkTarget, Image, Math, SpanStyle, Text};
+use crate::model::visitor::{
+    walk_document, BlockVisitor, DocumentVisitor, InlineVisitor, TableVisitor,
+};
+use crate::model::{Document, HasInnerContent};
+use crate::write::utils::string_of_strings;
+use crate::write::Writer;
+use std::cell::RefCell;
+use std::io::Write;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the AsciiDoc writer structure, usually this is accessed via the `writer`
+/// function, but may be used directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use somedoc::model::Document;
+/// use somedoc::write::asciidoc::AsciiDocWriter;
+/// use somedoc::write::Writer;
+///
+/// # fn make_some_document() -> Document { Document::default() }
+/// let doc = make_some_document();
+/// let mut out = std::io::stdout();
+/// let writer = AsciiDocWriter::new(&mut out);
+/// assert!(writer.write_document(&doc).is_ok());
+/// ```
+///
+#[derive(Debug)]
+pub struct AsciiDocWriter<'a, W: Write> {
+    toc_macro: RefCell<bool>,
+    list_stack: RefCell<Vec<ListKind>>,
+    quote_depth: RefCell<usize>,
+    table_columns: RefCell<Vec<(String, String)>>,
+    in_table: RefCell<bool>,
+    line_open: RefCell<bool>,
+    block_written: RefCell<bool>,
+    blank_pending: RefCell<bool>,
+    w: RefCell<&'a mut W>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the writer function for AsciiDoc.
+///
+/// While this can be called directly it is most often used  by calling either
+/// [`model::write_document`](../fn.write_document.html) or
+/// [`model::write_document_to_string`](../fn.write_document_to_string.html).
+///
+#[inline]
+pub fn writer<W: Write>(doc: &Document, w: &mut W) -> crate::error::Result<()> {
+    let writer = AsciiDocWriter::new(w);
+    writer.write_document(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> Writer<'a, W> for AsciiDocWriter<'a, W> {
+    fn new(w: &'a mut W) -> Self {
+        Self {
+            toc_macro: RefCell::from(false),
+            list_stack: RefCell::from(Vec::default()),
+            quote_depth: RefCell::from(0),
+            table_columns: RefCell::from(Vec::default()),
+            in_table: RefCell::from(false),
+            line_open: RefCell::from(false),
+            block_written: RefCell::from(false),
+            blank_pending: RefCell::from(false),
+            w: RefCell::from(w),
+        }
+    }
+
+    fn write_document(&self, doc: &Document) -> crate::error::Result<()> {
+        // the `toc::[]` macro is only honored if enabled in the document header.
+        let _ = self.toc_macro.replace(doc.inner().iter().any(|block| {
+            matches!(
+                block,
+                BlockContent::FrontMatter(FrontMatter::TableOfContents)
+            )
+        }));
+        if doc.has_metadata() || *self.toc_macro.borrow() {
+            self.write_header(doc.metadata())?;
+        }
+        walk_document(doc, self)?;
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> AsciiDocWriter<'a, W> {
+    fn write(&self, text: &str) -> crate::error::Result<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        if self.blank_pending.replace(false) {
+            writeln!(&mut self.w.borrow_mut())?;
+        }
+        write!(&mut self.w.borrow_mut(), "{}", text)?;
+        let _ = self.line_open.replace(!text.ends_with('\n'));
+        let _ = self.block_written.replace(true);
+        Ok(())
+    }
+
+    fn end_line(&self) -> crate::error::Result<()> {
+        if self.line_open.replace(false) {
+            writeln!(&mut self.w.borrow_mut())?;
+        }
+        Ok(())
+    }
+
+    fn write_line(&self, text: &str) -> crate::error::Result<()> {
+        self.write(text)?;
+        self.end_line()
+    }
+
+    fn write_label(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        if let Some(label) = label {
+            self.write_line(&format!("[[{}]]", label))?;
+        }
+        Ok(())
+    }
+
+    fn write_caption(&self, caption: &Option<Caption>) -> crate::error::Result<()> {
+        if let Some(caption) = caption {
+            self.write_line(&format!(".{}", escape(caption.inner())))?;
+        }
+        Ok(())
+    }
+
+    fn write_header(&self, metadata: &[Metadata]) -> crate::error::Result<()> {
+        let authors: Vec<String> = metadata
+            .iter()
+            .filter_map(|datum| match datum {
+                Metadata::Author(author) => Some(match &author.email {
+                    Some(email) => format!("{} <{}>", author.name, email),
+                    None => author.name.clone(),
+                }),
+                _ => None,
+            })
+            .collect();
+        let title = metadata.iter().find_map(|datum| match datum {
+            Metadata::Title(title) => Some(title),
+            _ => None,
+        });
+        if let Some(title) = title {
+            self.write_line(&format!("= {}", title))?;
+            if !authors.is_empty() {
+                self.write_line(&authors.join("; "))?;
+            }
+        } else if !authors.is_empty() {
+            self.write_line(&format!(":author: {}", authors.join("; ")))?;
+        }
+        for datum in metadata.iter() {
+            let (key, value) = match datum {
+                Metadata::Author(_) | Metadata::Title(_) => continue,
+                Metadata::Date(value) => ("revdate".to_string(), value.clone()),
+                Metadata::Revision(value) => ("revnumber".to_string(), value.clone()),
+                Metadata::Keywords(value) => ("keywords".to_string(), value.join(", ")),
+                _ => (datum.key(), datum.value_string()),
+            };
+            self.write_line(&format!(":{}: {}", key, value))?;
+        }
+        if *self.toc_macro.borrow() {
+            self.write_line(":toc: macro")?;
+        }
+        let _ = self
+            .blank_pending
+            .replace(self.block_written.replace(false));
+        Ok(())
+    }
+
+    fn quote_delimiter(&self) -> String {
+        string_of_strings("_", 4 + *self.quote_depth.borrow())
+    }
+
+    fn start_table_body(&self) -> crate::error::Result<()> {
+        if !self.in_table.replace(true) {
+            self.write_line("|===")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, W: Write> DocumentVisitor for AsciiDocWriter<'a, W> {
+    fn block_visitor(&self) -> Option<&dyn BlockVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> BlockVisitor for AsciiDocWriter<'a, W> {
+    fn start_abstract(&self) -> crate::error::Result<()> {
+        self.write_line("[abstract]")
+    }
+
+    fn comment(&self, value: &str) -> crate::error::Result<()> {
+        if value.contains('\n') {
+            self.write(&format!("////\n{}\n////", value))
+        } else {
+            self.write(&format!("// {}", value))
+        }
+    }
+
+    fn front_matter(&self, value: &FrontMatter) -> crate::error::Result<()> {
+        if matches!(value, FrontMatter::TableOfContents) {
+            self.write("toc::[]")?;
+        }
+        Ok(())
+    }
+
+    fn start_heading(
+        &self,
+        level: &HeadingLevel,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_label(label)?;
+        self.write(&format!(
+            "{} ",
+            string_of_strings("=", (level.clone() as usize + 1).min(6))
+        ))
+    }
+
+    fn image(
+        &self,
+        value: &Image,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_label(label)?;
+        self.write_caption(caption)?;
+        self.write(&format!(
+            "image::{}[{}]",
+            value.inner(),
+            value.alt_text().as_deref().unwrap_or_default()
+        ))
+    }
+
+    fn math(
+        &self,
+        value: &Math,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_label(label)?;
+        self.write_caption(caption)?;
+        self.write(&format!("[latexmath]\n++++\n{}\n++++", value.inner()))
+    }
+
+    fn start_list(&self, kind: &ListKind, label: &Option<Label>) -> crate::error::Result<()> {
+        let mut list_stack = self.list_stack.borrow_mut();
+        if list_stack.is_empty() {
+            self.write_label(label)?;
+        }
+        list_stack.push(kind.clone());
+        Ok(())
+    }
+
+    fn end_list(&self, _: &ListKind, _: &Option<Label>) -> crate::error::Result<()> {
+        let _ = self.list_stack.borrow_mut().pop();
+        Ok(())
+    }
+
+    fn start_list_item(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        let list_stack = self.list_stack.borrow();
+        if let Some(kind) = list_stack.last() {
+            self.write(&format!(
+                "{} ",
+                string_of_strings(
+                    match kind {
+                        ListKind::Ordered => ".",
+                        ListKind::Unordered => "*",
+                    },
+                    list_stack.len()
+                )
+            ))?;
+        }
+        if let Some(label) = label {
+            self.write(&format!("[[{}]]", label))?;
+        }
+        Ok(())
+    }
+
+    fn end_list_item(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_line()
+    }
+
+    fn start_definition_list(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_label(label)
+    }
+
+    fn start_definition(&self, term: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        if let Some(label) = label {
+            self.write(&format!("[[{}]]", label))?;
+        }
+        self.write(&format!("{}::", escape(term)))
+    }
+
+    fn start_definition_list_text(&self) -> crate::error::Result<()> {
+        self.write(" ")
+    }
+
+    fn end_definition(&self, _: &str, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_line()
+    }
+
+    fn formatted(&self, value: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_label(label)?;
+        self.write(&format!("....\n{}\n....", value))
+    }
+
+    fn code_block(
+        &self,
+        code: &str,
+        language: &Option<String>,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_label(label)?;
+        self.write_caption(caption)?;
+        if let Some(language) = language {
+            self.write_line(&format!("[source,{}]", language))?;
+        } else {
+            self.write_line("[source]")?;
+        }
+        self.write(&format!("----\n{}\n----", code))
+    }
+
+    fn start_paragraph(
+        &self,
+        alignment: &Alignment,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_label(label)?;
+        match alignment {
+            Alignment::Left => Ok(()),
+            Alignment::Right => self.write_line("[.text-right]"),
+            Alignment::Centered => self.write_line("[.text-center]"),
+            Alignment::Justified => self.write_line("[.text-justify]"),
+        }
+    }
+
+    fn start_quote(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_label(label)?;
+        self.write_line(&self.quote_delimiter())?;
+        *self.quote_depth.borrow_mut() += 1;
+        Ok(())
+    }
+
+    fn end_quote(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        *self.quote_depth.borrow_mut() -= 1;
+        let _ = self.blank_pending.replace(false);
+        self.end_line()?;
+        self.write(&self.quote_delimiter())
+    }
+
+    fn thematic_break(&self) -> crate::error::Result<()> {
+        self.write("'''")
+    }
+
+    fn end_block(&self) -> crate::error::Result<()> {
+        if self.block_written.replace(false) {
+            self.end_line()?;
+            let _ = self.blank_pending.replace(true);
+        }
+        Ok(())
+    }
+
+    fn table_visitor(&self) -> Option<&dyn TableVisitor> {
+        Some(self)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> TableVisitor for AsciiDocWriter<'a, W> {
+    fn start_table(
+        &self,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_label(label)?;
+        self.write_caption(caption)
+    }
+
+    fn table_header_cell(
+        &self,
+        column_cell: &Column,
+        _column_idx: usize,
+    ) -> crate::error::Result<()> {
+        self.table_columns.borrow_mut().push((
+            match column_cell.alignment() {
+                Alignment::Left => "<",
+                Alignment::Right => ">",
+                Alignment::Centered => "^",
+                Alignment::Justified => "1",
+            }
+            .to_string(),
+            escape(column_cell.text()),
+        ));
+        Ok(())
+    }
+
+    fn end_table_header_row(&self) -> crate::error::Result<()> {
+        let columns: Vec<(String, String)> = self.table_columns.borrow_mut().drain(..).collect();
+        self.write_line(&format!(
+            "[cols=\"{}\",options=\"header\"]",
+            columns
+                .iter()
+                .map(|(spec, _)| spec.as_str())
+                .collect::<Vec<&str>>()
+                .join(",")
+        ))?;
+        self.start_table_body()?;
+        self.write_line(
+            &columns
+                .iter()
+                .map(|(_, text)| format!("|{}", text))
+                .collect::<Vec<String>>()
+                .join(" "),
+        )
+    }
+
+    fn start_table_row(&self, _: usize) -> crate::error::Result<()> {
+        self.start_table_body()
+    }
+
+    fn start_table_cell(
+        &self,
+        column_idx: usize,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        if column_idx > 0 {
+            self.write(" ")?;
+        }
+        self.write("|")?;
+        if let Some(label) = label {
+            self.write(&format!("[[{}]]", label))?;
+        }
+        Ok(())
+    }
+
+    fn end_table_row(&self, _: usize) -> crate::error::Result<()> {
+        self.end_line()
+    }
+
+    fn end_table(
+        &self,
+        _caption: &Option<Caption>,
+        _label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.start_table_body()?;
+        let _ = self.in_table.replace(false);
+        self.write("|===")
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> InlineVisitor for AsciiDocWriter<'a, W> {
+    fn link(&self, value: &HyperLink) -> crate::error::Result<()> {
+        let caption = value.caption().as_ref().map(|caption| caption.inner());
+        match value.target() {
+            HyperLinkTarget::External(target) => self.write(&format!(
+                "link:{}[{}]",
+                target.replace(' ', "%20"),
+                caption.map(|s| s.replace(']', "\\]")).unwrap_or_default()
+            )),
+            HyperLinkTarget::Internal(target) => match caption {
+                Some(caption) => self.write(&format!("<<{},{}>>", target, escape(caption))),
+                None => self.write(&format!("<<{}>>", target)),
+            },
+        }
+    }
+
+    fn image(&self, value: &Image) -> crate::error::Result<()> {
+        self.write(&format!(
+            "image:{}[{}]",
+            value.inner(),
+            value.alt_text().as_deref().unwrap_or_default()
+        ))
+    }
+
+    fn text(&self, value: &Text) -> crate::error::Result<()> {
+        let text = escape(value.inner());
+        // a line starting with `.` would be read as a block title.
+        if !*self.line_open.borrow() && text.starts_with('.') {
+            self.write(&format!("&#46;{}", &text[1..]))
+        } else {
+            self.write(&text)
+        }
+    }
+
+    fn math(&self, value: &Math) -> crate::error::Result<()> {
+        self.write(&format!(
+            "latexmath:[{}]",
+            value.inner().replace(']', "\\]")
+        ))
+    }
+
+    fn character(&self, value: &Character) -> crate::error::Result<()> {
+        self.write(&match value {
+            Character::Space => " ".to_string(),
+            Character::NonBreakSpace => "{nbsp}".to_string(),
+            Character::Hyphen => "-".to_string(),
+            Character::EmDash => "\u{2014}".to_string(),
+            Character::EnDash => "\u{2013}".to_string(),
+            Character::Emoji(name) => format!(":{}:", name.inner()),
+            Character::Other(c) => c.to_string(),
+        })
+    }
+
+    fn line_break(&self) -> crate::error::Result<()> {
+        self.write(" +\n")
+    }
+
+    fn start_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        let (style_stack, role_stack) = make_style_stack(styles);
+        if !role_stack.is_empty() {
+            self.write(&format!("[{}]##", role_stack.join(" ")))?;
+        }
+        self.write(&style_stack.join(""))
+    }
+
+    fn end_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        let (style_stack, role_stack) = make_style_stack(styles);
+        self.write(
+            &style_stack
+                .into_iter()
+                .rev()
+                .collect::<Vec<&str>>()
+                .join(""),
+        )?;
+        if !role_stack.is_empty() {
+            self.write("##")?;
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn escape(text: &str) -> String {
+    text.replace('#', "&#35;")
+        .replace('|', "&#124;")
+        .replace('*', "&#42;")
+        .replace('_', "&#95;")
+        .replace('`', "&#96;")
+        .replace('^', "&#94;")
+        .replace('~', "&#126;")
+        .replace('+', "&#43;")
+}
+
+fn make_style_stack(styles: &[SpanStyle]) -> (Vec<&'static str>, Vec<&'static str>) {
+    let mut style_stack = Vec::new();
+    let mut role_stack = Vec::new();
+    for style in styles {
+        match style {
+            SpanStyle::Plain => {
+                style_stack.clear();
+                role_stack.clear();
+            }
+            SpanStyle::Italic => style_stack.push("__"),
+            SpanStyle::Bold => style_stack.push("**"),
+            SpanStyle::Mono | SpanStyle::Code => style_stack.push("``"),
+            SpanStyle::Strikethrough => role_stack.push("line-through"),
+            SpanStyle::Underline => role_stack.push("underline"),
+            SpanStyle::SmallCaps => role_stack.push("small-caps"),
+            SpanStyle::Superscript => style_stack.push("^"),
+            SpanStyle::Subscript => style_stack.push("~"),
+            SpanStyle::Sized(size) => match size {
+                Size::Largest | Size::Larger | Size::Large => role_stack.push("big"),
+                Size::Normal => {}
+                Size::Small | Size::Smaller | Size::Smallest => role_stack.push("small"),
+            },
+        }
+    }
+    (style_stack, role_stack)
+}
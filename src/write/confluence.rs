@@ -0,0 +1,645 @@
+/*!
+Write a document in the Confluence [storage format](https://confluence.atlassian.com/doc/confluence-storage-format-790796544.html),
+the XHTML-based markup accepted by the Confluence REST API as a page body.
+
+Code blocks and the table of contents are written as `code` and `toc` macros, the abstract as an
+`excerpt` macro, and labels as `anchor` macros that internal links target with `ac:link`. Local
+images are written as references to page attachments with the same file name. Document metadata
+is not written, the page title is set when the page is published.
+
+# Example
+
+```rust
+# use somedoc::model::Document;
+use somedoc::write::confluence::writer;
+
+# fn make_some_document() -> Document { Document::default() }
+let doc = make_some_document();
+
+writer(&doc, &mut std::io::stdout()).unwrap();
+```
+*/
+
+use crate::model::block::{
+    Alignment, Caption, Column, FrontMatter, HasAlignment, HasCaption, HeadingLevel, Label,
+    ListKind,
+};
+use crate::model::inline::text::Size;
+use crate::model::inline::{Character, HyperLink, HyperLinkTarget, Image, Math, SpanStyle, Text};
+use crate::model::visitor::{
+    walk_document, BlockVisitor, DocumentVisitor, InlineVisitor, TableVisitor,
+};
+use crate::model::Document;
+use crate::write::utils::string_of_strings;
+use crate::write::Writer;
+use std::cell::RefCell;
+use std::io::Write;
+use std::path::Path;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the Confluence writer structure, usually this is accessed via the `writer`
+/// function, but may be used directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use somedoc::model::Document;
+/// use somedoc::write::confluence::ConfluenceWriter;
+/// use somedoc::write::Writer;
+///
+/// # fn make_some_document() -> Document { Document::default() }
+/// let doc = make_some_document();
+/// let mut out = std::io::stdout();
+/// let writer = ConfluenceWriter::new(&mut out);
+/// assert!(writer.write_document(&doc).is_ok());
+/// ```
+///
+#[derive(Debug)]
+pub struct ConfluenceWriter<'a, W: Write> {
+    list_stack: RefCell<Vec<bool>>,
+    column_alignments: RefCell<Vec<Alignment>>,
+    indent: RefCell<usize>,
+    w: RefCell<&'a mut W>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the writer function for Confluence.
+///
+/// While this can be called directly it is most often used  by calling either
+/// [`model::write_document`](../fn.write_document.html) or
+/// [`model::write_document_to_string`](../fn.write_document_to_string.html).
+///
+#[inline]
+pub fn writer<W: Write>(doc: &Document, w: &mut W) -> crate::error::Result<()> {
+    let writer = ConfluenceWriter::new(w);
+    writer.write_document(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> Writer<'a, W> for ConfluenceWriter<'a, W> {
+    fn new(w: &'a mut W) -> Self {
+        Self {
+            list_stack: RefCell::from(Vec::default()),
+            column_alignments: RefCell::from(Vec::default()),
+            indent: RefCell::from(0),
+            w: RefCell::from(w),
+        }
+    }
+
+    fn write_document(&self, doc: &Document) -> crate::error::Result<()> {
+        walk_document(doc, self)?;
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> ConfluenceWriter<'a, W> {
+    fn write(&self, text: &str) -> crate::error::Result<()> {
+        write!(&mut self.w.borrow_mut(), "{}", text)?;
+        Ok(())
+    }
+
+    fn start_line(&self) -> crate::error::Result<()> {
+        self.write(&string_of_strings("  ", *self.indent.borrow()))
+    }
+
+    fn end_line(&self) -> crate::error::Result<()> {
+        self.write("\n")
+    }
+
+    fn write_line(&self, text: &str) -> crate::error::Result<()> {
+        self.start_line()?;
+        self.write(text)?;
+        self.end_line()
+    }
+
+    fn open(&self, tag: &str, attributes: &[(&str, String)]) -> crate::error::Result<()> {
+        self.write_line(&start_tag(tag, attributes))?;
+        *self.indent.borrow_mut() += 1;
+        Ok(())
+    }
+
+    fn close(&self, tag: &str) -> crate::error::Result<()> {
+        *self.indent.borrow_mut() -= 1;
+        self.write_line(&format!("</{}>", tag))
+    }
+
+    fn start_inline(
+        &self,
+        tag: &str,
+        attributes: &[(&str, String)],
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.start_line()?;
+        self.write(&start_tag(tag, attributes))?;
+        if let Some(label) = label {
+            self.write(&anchor_macro(label))?;
+        }
+        Ok(())
+    }
+
+    fn end_inline(&self, tag: &str) -> crate::error::Result<()> {
+        self.write(&format!("</{}>", tag))?;
+        self.end_line()
+    }
+
+    fn write_anchor(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        if let Some(label) = label {
+            self.write_line(&anchor_macro(label))?;
+        }
+        Ok(())
+    }
+
+    fn write_caption(&self, caption: &Option<Caption>) -> crate::error::Result<()> {
+        if let Some(caption) = caption {
+            self.write_line(&format!(
+                "{}<em>{}</em></p>",
+                start_tag("p", &alignment_style(&Alignment::Centered)),
+                escape(caption.inner())
+            ))?;
+        }
+        Ok(())
+    }
+
+    fn write_code_macro(
+        &self,
+        code: &str,
+        language: &Option<String>,
+        title: &Option<Caption>,
+    ) -> crate::error::Result<()> {
+        self.open("ac:structured-macro", &[("ac:name", "code".to_string())])?;
+        if let Some(language) = language {
+            self.write_line(&parameter("language", language))?;
+        }
+        if let Some(title) = title {
+            self.write_line(&parameter("title", title.inner()))?;
+        }
+        self.write_line(&format!(
+            "<ac:plain-text-body>{}</ac:plain-text-body>",
+            cdata(code)
+        ))?;
+        self.close("ac:structured-macro")
+    }
+}
+
+impl<'a, W: Write> DocumentVisitor for ConfluenceWriter<'a, W> {
+    fn block_visitor(&self) -> Option<&dyn BlockVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> BlockVisitor for ConfluenceWriter<'a, W> {
+    fn start_abstract(&self) -> crate::error::Result<()> {
+        self.open("ac:structured-macro", &[("ac:name", "excerpt".to_string())])?;
+        self.open("ac:rich-text-body", &[])?;
+        self.start_inline("p", &[], &None)
+    }
+
+    fn end_abstract(&self) -> crate::error::Result<()> {
+        self.end_inline("p")?;
+        self.close("ac:rich-text-body")?;
+        self.close("ac:structured-macro")
+    }
+
+    fn comment(&self, value: &str) -> crate::error::Result<()> {
+        // "--" may not appear within an XML comment.
+        self.write_line(&format!("<!-- {} -->", value.replace("--", "- -")))
+    }
+
+    fn front_matter(&self, value: &FrontMatter) -> crate::error::Result<()> {
+        if *value == FrontMatter::TableOfContents {
+            self.write_line("<ac:structured-macro ac:name=\"toc\"/>")?;
+        }
+        Ok(())
+    }
+
+    fn start_heading(
+        &self,
+        level: &HeadingLevel,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.start_inline(&heading_tag(level), &[], label)
+    }
+
+    fn end_heading(&self, level: &HeadingLevel, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_inline(&heading_tag(level))
+    }
+
+    fn image(
+        &self,
+        value: &Image,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.start_inline("p", &alignment_style(&Alignment::Centered), label)?;
+        self.write(&image_element(value))?;
+        self.end_inline("p")?;
+        self.write_caption(caption)
+    }
+
+    fn math(
+        &self,
+        value: &Math,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.start_inline("p", &alignment_style(&Alignment::Centered), label)?;
+        self.write(&format!("<code>{}</code>", escape(value.inner())))?;
+        self.end_inline("p")?;
+        self.write_caption(caption)
+    }
+
+    fn start_list(&self, kind: &ListKind, label: &Option<Label>) -> crate::error::Result<()> {
+        let wrap = matches!(self.list_stack.borrow().last(), Some(false));
+        if wrap {
+            // a nested list must be within an item, this one has no preceding item to use.
+            self.open("li", &[])?;
+            if let Some(item_open) = self.list_stack.borrow_mut().last_mut() {
+                *item_open = true;
+            }
+        }
+        self.write_anchor(label)?;
+        self.list_stack.borrow_mut().push(false);
+        self.open(list_tag(kind), &[])
+    }
+
+    fn end_list(&self, kind: &ListKind, _: &Option<Label>) -> crate::error::Result<()> {
+        if self.list_stack.borrow_mut().pop() == Some(true) {
+            self.close("li")?;
+        }
+        self.close(list_tag(kind))
+    }
+
+    fn start_list_item(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        // items are closed lazily so that a following nested list is placed within them.
+        let item_open = self.list_stack.borrow_mut().last_mut().map(|open| {
+            let was_open = *open;
+            *open = true;
+            was_open
+        });
+        if item_open == Some(true) {
+            self.close("li")?;
+        }
+        self.open("li", &[])?;
+        self.start_inline("p", &[], label)
+    }
+
+    fn end_list_item(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_inline("p")
+    }
+
+    fn start_definition_list(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_anchor(label)?;
+        self.open("dl", &[])
+    }
+
+    fn end_definition_list(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.close("dl")
+    }
+
+    fn start_definition(&self, term: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.start_inline("dt", &[], label)?;
+        self.write(&escape(term))?;
+        self.end_inline("dt")
+    }
+
+    fn start_definition_list_text(&self) -> crate::error::Result<()> {
+        self.start_inline("dd", &[], &None)
+    }
+
+    fn end_definition_list_text(&self) -> crate::error::Result<()> {
+        self.end_inline("dd")
+    }
+
+    fn formatted(&self, value: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_anchor(label)?;
+        self.start_inline("pre", &[], &None)?;
+        self.write(&escape(value))?;
+        self.end_inline("pre")
+    }
+
+    fn code_block(
+        &self,
+        code: &str,
+        language: &Option<String>,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_anchor(label)?;
+        self.write_code_macro(code, language, caption)
+    }
+
+    fn start_paragraph(
+        &self,
+        alignment: &Alignment,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.start_inline("p", &alignment_style(alignment), label)
+    }
+
+    fn end_paragraph(&self, _: &Alignment, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_inline("p")
+    }
+
+    fn start_quote(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_anchor(label)?;
+        self.open("blockquote", &[])
+    }
+
+    fn end_quote(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.close("blockquote")
+    }
+
+    fn thematic_break(&self) -> crate::error::Result<()> {
+        self.write_line("<hr/>")
+    }
+
+    fn table_visitor(&self) -> Option<&dyn TableVisitor> {
+        Some(self)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> TableVisitor for ConfluenceWriter<'a, W> {
+    fn start_table(&self, _: &Option<Caption>, label: &Option<Label>) -> crate::error::Result<()> {
+        self.column_alignments.borrow_mut().clear();
+        self.write_anchor(label)?;
+        self.open("table", &[])?;
+        self.open("tbody", &[])
+    }
+
+    fn start_table_header_row(&self) -> crate::error::Result<()> {
+        self.open("tr", &[])
+    }
+
+    fn table_header_cell(
+        &self,
+        column_cell: &Column,
+        _column_idx: usize,
+    ) -> crate::error::Result<()> {
+        self.column_alignments
+            .borrow_mut()
+            .push(column_cell.alignment().clone());
+        self.start_inline("th", &alignment_style(column_cell.alignment()), &None)?;
+        self.write(&escape(column_cell.text()))?;
+        self.end_inline("th")
+    }
+
+    fn end_table_header_row(&self) -> crate::error::Result<()> {
+        self.close("tr")
+    }
+
+    fn start_table_row(&self, _: usize) -> crate::error::Result<()> {
+        self.open("tr", &[])
+    }
+
+    fn start_table_cell(&self, cell_idx: usize, label: &Option<Label>) -> crate::error::Result<()> {
+        let alignment = self
+            .column_alignments
+            .borrow()
+            .get(cell_idx)
+            .cloned()
+            .unwrap_or(Alignment::Left);
+        self.start_inline("td", &alignment_style(&alignment), label)
+    }
+
+    fn end_table_cell(&self, _: usize, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_inline("td")
+    }
+
+    fn end_table_row(&self, _: usize) -> crate::error::Result<()> {
+        self.close("tr")
+    }
+
+    fn end_table(&self, caption: &Option<Caption>, _: &Option<Label>) -> crate::error::Result<()> {
+        self.close("tbody")?;
+        self.close("table")?;
+        self.write_caption(caption)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> InlineVisitor for ConfluenceWriter<'a, W> {
+    fn link(&self, value: &HyperLink) -> crate::error::Result<()> {
+        match value.target() {
+            HyperLinkTarget::External(target) => self.write(&format!(
+                "{}{}</a>",
+                start_tag("a", &[("href", target.clone())]),
+                escape(match value.caption() {
+                    Some(caption) => caption.inner(),
+                    None => target,
+                })
+            )),
+            HyperLinkTarget::Internal(target) => {
+                let target = target.to_string();
+                self.write(&format!(
+                    "{}<ac:plain-text-link-body>{}</ac:plain-text-link-body></ac:link>",
+                    start_tag("ac:link", &[("ac:anchor", target.clone())]),
+                    cdata(match value.caption() {
+                        Some(caption) => caption.inner(),
+                        None => &target,
+                    })
+                ))
+            }
+        }
+    }
+
+    fn image(&self, value: &Image) -> crate::error::Result<()> {
+        self.write(&image_element(value))
+    }
+
+    fn text(&self, value: &Text) -> crate::error::Result<()> {
+        self.write(&escape(value.inner()))
+    }
+
+    fn math(&self, value: &Math) -> crate::error::Result<()> {
+        self.write(&format!("<code>{}</code>", escape(value.inner())))
+    }
+
+    fn character(&self, value: &Character) -> crate::error::Result<()> {
+        self.write(&match value {
+            Character::Space => " ".to_string(),
+            Character::NonBreakSpace => "&#160;".to_string(),
+            Character::Hyphen => "-".to_string(),
+            Character::EmDash => "&#8212;".to_string(),
+            Character::EnDash => "&#8211;".to_string(),
+            Character::Emoji(name) => format!(":{}:", escape(name.inner())),
+            Character::Other(c) => escape(&c.to_string()),
+        })
+    }
+
+    fn line_break(&self) -> crate::error::Result<()> {
+        self.write("<br/>")
+    }
+
+    fn start_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        for (tag, attributes) in span_tags(styles) {
+            self.write(&start_tag(tag, &attributes))?;
+        }
+        Ok(())
+    }
+
+    fn end_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        for (tag, _) in span_tags(styles).iter().rev() {
+            self.write(&format!("</{}>", tag))?;
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn span_tags(styles: &[SpanStyle]) -> Vec<(&'static str, Vec<(&'static str, String)>)> {
+    let mut tags = Vec::new();
+    let style = |value: &str| vec![("style", value.to_string())];
+    for span_style in styles {
+        match span_style {
+            SpanStyle::Plain => tags.clear(),
+            SpanStyle::Italic => tags.push(("em", Vec::new())),
+            SpanStyle::Bold => tags.push(("strong", Vec::new())),
+            SpanStyle::Mono | SpanStyle::Code => tags.push(("code", Vec::new())),
+            SpanStyle::Strikethrough => {
+                tags.push(("span", style("text-decoration: line-through;")))
+            }
+            SpanStyle::Underline => tags.push(("u", Vec::new())),
+            SpanStyle::SmallCaps => tags.push(("span", style("font-variant: small-caps;"))),
+            SpanStyle::Superscript => tags.push(("sup", Vec::new())),
+            SpanStyle::Subscript => tags.push(("sub", Vec::new())),
+            SpanStyle::Sized(size) => tags.push((
+                "span",
+                style(match size {
+                    Size::Largest => "font-size: xx-large;",
+                    Size::Larger => "font-size: x-large;",
+                    Size::Large => "font-size: large;",
+                    Size::Normal => "font-size: medium;",
+                    Size::Small => "font-size: small;",
+                    Size::Smaller => "font-size: x-small;",
+                    Size::Smallest => "font-size: xx-small;",
+                }),
+            )),
+        }
+    }
+    tags
+}
+
+fn heading_tag(level: &HeadingLevel) -> String {
+    // Confluence, like XHTML, has no seventh heading level.
+    format!("h{}", (level.clone() as u8).min(6))
+}
+
+fn list_tag(kind: &ListKind) -> &'static str {
+    match kind {
+        ListKind::Ordered => "ol",
+        ListKind::Unordered => "ul",
+    }
+}
+
+fn alignment_style(alignment: &Alignment) -> Vec<(&'static str, String)> {
+    let value = match alignment {
+        Alignment::Left => return Vec::new(),
+        Alignment::Right => "right",
+        Alignment::Centered => "center",
+        Alignment::Justified => "justify",
+    };
+    vec![("style", format!("text-align: {};", value))]
+}
+
+fn anchor_macro(label: &Label) -> String {
+    format!(
+        "<ac:structured-macro ac:name=\"anchor\">{}</ac:structured-macro>",
+        parameter("", &label.to_string())
+    )
+}
+
+fn parameter(name: &str, value: &str) -> String {
+    format!(
+        "{}{}</ac:parameter>",
+        start_tag("ac:parameter", &[("ac:name", name.to_string())]),
+        escape(value)
+    )
+}
+
+///
+/// Images stored with the document are expected to be uploaded as attachments to the page,
+/// anything else is referenced by URL.
+///
+fn image_element(value: &Image) -> String {
+    let mut attributes = Vec::new();
+    if let Some(alt_text) = value.alt_text() {
+        attributes.push(("ac:alt", alt_text.clone()));
+    }
+    let resource = if is_local(value.inner()) {
+        let file_name = Path::new(value.inner())
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| value.inner().to_string());
+        format!(
+            "<ri:attachment ri:filename=\"{}\"/>",
+            escape_attribute(&file_name)
+        )
+    } else {
+        format!("<ri:url ri:value=\"{}\"/>", escape_attribute(value.inner()))
+    };
+    format!(
+        "{}{}</ac:image>",
+        start_tag("ac:image", &attributes),
+        resource
+    )
+}
+
+fn is_local(path: &str) -> bool {
+    !(path.contains("://") || path.starts_with("data:"))
+}
+
+fn start_tag(tag: &str, attributes: &[(&str, String)]) -> String {
+    if attributes.is_empty() {
+        format!("<{}>", tag)
+    } else {
+        format!(
+            "<{} {}>",
+            tag,
+            attributes
+                .iter()
+                .map(|(name, value)| format!("{}=\"{}\"", name, escape_attribute(value)))
+                .collect::<Vec<String>>()
+                .join(" ")
+        )
+    }
+}
+
+///
+/// Macro bodies are written as CDATA sections, which may not contain their own terminator.
+///
+fn cdata(text: &str) -> String {
+    format!("<![CDATA[{}]]>", text.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn escape_attribute(text: &str) -> String {
+    escape(text).replace('"', "&quot;")
+}
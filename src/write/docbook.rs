@@ -0,0 +1,797 @@
+/*!
+Write a document as a [DocBook 5](https://tdg.docbook.org/tdg/5.2/) `<article>`.
+
+Headings are mapped onto nested `<section>` elements, metadata and the abstract into the article's
+`<info>` element. Labels are written as `xml:id` attributes, with any characters that are not valid
+in an XML name replaced by `_`, and links to them use the same form.
+
+# Example
+
+```rust
+# use somedoc::model::Document;
+use somedoc::write::docbook::writer;
+
+# fn make_some_document() -> Document { Document::default() }
+let doc = make_some_document();
+
+writer(&doc, &mut std::io::stdout()).unwrap();
+```
+*/
+
+use crate::model::block::{
+    Alignment, Caption, Column, FrontMatter, HasAlignment, HasCaption, HeadingLevel, Label,
+    ListKind,
+};
+use crate::model::document::Metadata;
+use crate::model::inline::text::Size;
+use crate::model::inline::{Character, HyperLink, HyperLinkTarget, Image, Math, SpanStyle, Text};
+use crate::model::visitor::{
+    walk_document, BlockVisitor, DocumentVisitor, InlineVisitor, TableVisitor,
+};
+use crate::model::Document;
+use crate::write::utils::string_of_strings;
+use crate::write::Writer;
+use std::cell::RefCell;
+use std::io::Write;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the DocBook writer structure, usually this is accessed via the `writer`
+/// function, but may be used directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use somedoc::model::Document;
+/// use somedoc::write::docbook::DocBookWriter;
+/// use somedoc::write::Writer;
+///
+/// # fn make_some_document() -> Document { Document::default() }
+/// let doc = make_some_document();
+/// let mut out = std::io::stdout();
+/// let writer = DocBookWriter::new(&mut out);
+/// assert!(writer.write_document(&doc).is_ok());
+/// ```
+///
+#[derive(Debug)]
+pub struct DocBookWriter<'a, W: Write> {
+    has_abstract: RefCell<bool>,
+    info_open: RefCell<bool>,
+    section_stack: RefCell<Vec<usize>>,
+    list_stack: RefCell<Vec<bool>>,
+    table: RefCell<Option<TableText>>,
+    cell: RefCell<Option<String>>,
+    indent: RefCell<usize>,
+    w: RefCell<&'a mut W>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+struct TableText {
+    columns: Vec<(String, Alignment)>,
+    rows: Vec<Vec<(String, Option<Label>)>>,
+}
+
+const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
+
+const ARTICLE_START: &str = r#"<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">"#;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the writer function for DocBook.
+///
+/// While this can be called directly it is most often used  by calling either
+/// [`model::write_document`](../fn.write_document.html) or
+/// [`model::write_document_to_string`](../fn.write_document_to_string.html).
+///
+#[inline]
+pub fn writer<W: Write>(doc: &Document, w: &mut W) -> crate::error::Result<()> {
+    let writer = DocBookWriter::new(w);
+    writer.write_document(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> Writer<'a, W> for DocBookWriter<'a, W> {
+    fn new(w: &'a mut W) -> Self {
+        Self {
+            has_abstract: RefCell::from(false),
+            info_open: RefCell::from(false),
+            section_stack: RefCell::from(Vec::default()),
+            list_stack: RefCell::from(Vec::default()),
+            table: RefCell::from(None),
+            cell: RefCell::from(None),
+            indent: RefCell::from(0),
+            w: RefCell::from(w),
+        }
+    }
+
+    fn write_document(&self, doc: &Document) -> crate::error::Result<()> {
+        // the abstract is part of `<info>`, which must remain open until it has been written.
+        let _ = self.has_abstract.replace(doc.has_abstract());
+        walk_document(doc, self)?;
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> DocBookWriter<'a, W> {
+    fn write(&self, text: &str) -> crate::error::Result<()> {
+        match self.cell.borrow_mut().as_mut() {
+            Some(cell) => cell.push_str(text),
+            None => write!(&mut self.w.borrow_mut(), "{}", text)?,
+        }
+        Ok(())
+    }
+
+    fn start_line(&self) -> crate::error::Result<()> {
+        self.write(&string_of_strings("  ", *self.indent.borrow()))
+    }
+
+    fn end_line(&self) -> crate::error::Result<()> {
+        self.write("\n")
+    }
+
+    fn write_line(&self, text: &str) -> crate::error::Result<()> {
+        self.start_line()?;
+        self.write(text)?;
+        self.end_line()
+    }
+
+    fn open(&self, tag: &str, attributes: &[(&str, String)]) -> crate::error::Result<()> {
+        self.write_line(&start_tag(tag, attributes))?;
+        *self.indent.borrow_mut() += 1;
+        Ok(())
+    }
+
+    fn close(&self, tag: &str) -> crate::error::Result<()> {
+        *self.indent.borrow_mut() -= 1;
+        self.write_line(&format!("</{}>", tag))
+    }
+
+    fn start_inline(&self, tag: &str, attributes: &[(&str, String)]) -> crate::error::Result<()> {
+        self.start_line()?;
+        self.write(&start_tag(tag, attributes))
+    }
+
+    fn end_inline(&self, tag: &str) -> crate::error::Result<()> {
+        self.write(&format!("</{}>", tag))?;
+        self.end_line()
+    }
+
+    fn element(&self, tag: &str, content: &str) -> crate::error::Result<()> {
+        self.write_line(&format!("<{}>{}</{}>", tag, escape(content), tag))
+    }
+
+    fn close_info(&self) -> crate::error::Result<()> {
+        if self.info_open.replace(false) {
+            self.close("info")?;
+        }
+        Ok(())
+    }
+
+    fn close_sections(&self, level: usize) -> crate::error::Result<()> {
+        loop {
+            let open = matches!(self.section_stack.borrow().last(), Some(last) if *last >= level);
+            if !open {
+                break;
+            }
+            let _ = self.section_stack.borrow_mut().pop();
+            self.close("section")?;
+        }
+        Ok(())
+    }
+
+    fn write_title(&self, caption: &Option<Caption>) -> crate::error::Result<()> {
+        if let Some(caption) = caption {
+            self.element("title", caption.inner())?;
+        }
+        Ok(())
+    }
+
+    fn write_table(
+        &self,
+        table: TableText,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        let tag = if caption.is_some() {
+            "table"
+        } else {
+            "informaltable"
+        };
+        let column_count = table
+            .rows
+            .iter()
+            .map(|row| row.len())
+            .chain(std::iter::once(table.columns.len()))
+            .max()
+            .unwrap_or_default();
+        self.open(tag, &id_attribute(label))?;
+        self.write_title(caption)?;
+        self.open("tgroup", &[("cols", column_count.to_string())])?;
+        for i in 0..column_count {
+            let alignment = table
+                .columns
+                .get(i)
+                .map(|(_, alignment)| alignment.clone())
+                .unwrap_or(Alignment::Left);
+            self.write_line(&format!(
+                "<colspec colname=\"c{}\" align=\"{}\"/>",
+                i + 1,
+                match alignment {
+                    Alignment::Right => "right",
+                    Alignment::Centered => "center",
+                    Alignment::Justified => "justify",
+                    _ => "left",
+                }
+            ))?;
+        }
+        if !table.columns.is_empty() {
+            self.open("thead", &[])?;
+            self.open("row", &[])?;
+            for (text, _) in &table.columns {
+                self.element("entry", text)?;
+            }
+            self.close("row")?;
+            self.close("thead")?;
+        }
+        self.open("tbody", &[])?;
+        if table.rows.is_empty() {
+            // CALS requires at least one row in the body.
+            self.write_line("<row><entry/></row>")?;
+        }
+        for row in &table.rows {
+            self.open("row", &[])?;
+            for (text, label) in row {
+                self.write_line(&format!(
+                    "{}{}</entry>",
+                    start_tag("entry", &id_attribute(label)),
+                    text
+                ))?;
+            }
+            self.close("row")?;
+        }
+        self.close("tbody")?;
+        self.close("tgroup")?;
+        self.close(tag)
+    }
+}
+
+impl<'a, W: Write> DocumentVisitor for DocBookWriter<'a, W> {
+    fn start_document(&self) -> crate::error::Result<()> {
+        self.write_line(XML_DECLARATION)?;
+        self.write_line(ARTICLE_START)?;
+        *self.indent.borrow_mut() += 1;
+        Ok(())
+    }
+
+    fn start_metadata(&self) -> crate::error::Result<()> {
+        let _ = self.info_open.replace(true);
+        self.open("info", &[])
+    }
+
+    fn metadata(&self, meta_datum: &Metadata) -> crate::error::Result<()> {
+        match meta_datum {
+            Metadata::Author(author) => {
+                self.open("author", &[])?;
+                self.element("personname", &author.name)?;
+                if let Some(email) = &author.email {
+                    self.element("email", email)?;
+                }
+                if let Some(organization) = &author.organization {
+                    self.open("affiliation", &[])?;
+                    self.element("orgname", organization)?;
+                    self.close("affiliation")?;
+                }
+                self.close("author")
+            }
+            Metadata::Copyright(copyright) => {
+                self.open("copyright", &[])?;
+                self.element("year", &copyright.year.to_string())?;
+                if let Some(organization) = &copyright.organization {
+                    self.element("holder", organization)?;
+                }
+                self.close("copyright")
+            }
+            Metadata::Date(value) => self.element("date", value),
+            Metadata::Keywords(values) => {
+                self.open("keywordset", &[])?;
+                for value in values {
+                    self.element("keyword", value)?;
+                }
+                self.close("keywordset")
+            }
+            Metadata::Revision(value) => self.element("releaseinfo", value),
+            Metadata::Title(value) => self.element("title", value),
+            _ => self.write_line(&format!(
+                "{}{}</bibliomisc>",
+                start_tag("bibliomisc", &[("role", meta_datum.key())]),
+                escape(&meta_datum.value_string())
+            )),
+        }
+    }
+
+    fn block_visitor(&self) -> Option<&dyn BlockVisitor> {
+        if *self.has_abstract.borrow() && !*self.info_open.borrow() {
+            let _ = self.info_open.replace(true);
+            let _ = self.open("info", &[]);
+        } else if !*self.has_abstract.borrow() {
+            let _ = self.close_info();
+        }
+        Some(self)
+    }
+
+    fn end_document(&self) -> crate::error::Result<()> {
+        self.close_info()?;
+        self.close_sections(0)?;
+        self.close("article")
+    }
+}
+
+impl<'a, W: Write> BlockVisitor for DocBookWriter<'a, W> {
+    fn start_abstract(&self) -> crate::error::Result<()> {
+        self.open("abstract", &[])?;
+        self.start_inline("para", &[])
+    }
+
+    fn end_abstract(&self) -> crate::error::Result<()> {
+        self.end_inline("para")?;
+        self.close("abstract")?;
+        self.close_info()
+    }
+
+    fn comment(&self, value: &str) -> crate::error::Result<()> {
+        // "--" may not appear within an XML comment.
+        self.write_line(&format!("<!-- {} -->", value.replace("--", "- -")))
+    }
+
+    fn front_matter(&self, value: &FrontMatter) -> crate::error::Result<()> {
+        if *value == FrontMatter::TableOfContents {
+            self.write_line("<toc/>")?;
+        }
+        Ok(())
+    }
+
+    fn start_heading(
+        &self,
+        level: &HeadingLevel,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        let level = level.clone() as usize;
+        self.close_sections(level)?;
+        self.section_stack.borrow_mut().push(level);
+        self.open("section", &id_attribute(label))?;
+        self.start_inline("title", &[])
+    }
+
+    fn end_heading(&self, _: &HeadingLevel, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_inline("title")
+    }
+
+    fn image(
+        &self,
+        value: &Image,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        if caption.is_some() {
+            self.open("figure", &id_attribute(label))?;
+            self.write_title(caption)?;
+            self.write_line(&media_object(value, "mediaobject", &[]))?;
+            self.close("figure")
+        } else {
+            self.write_line(&media_object(value, "mediaobject", &id_attribute(label)))
+        }
+    }
+
+    fn math(
+        &self,
+        value: &Math,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        let tag = if caption.is_some() {
+            "equation"
+        } else {
+            "informalequation"
+        };
+        self.open(tag, &id_attribute(label))?;
+        self.write_title(caption)?;
+        self.element("mathphrase", value.inner())?;
+        self.close(tag)
+    }
+
+    fn start_list(&self, kind: &ListKind, label: &Option<Label>) -> crate::error::Result<()> {
+        let wrap = matches!(self.list_stack.borrow().last(), Some(false));
+        if wrap {
+            // a nested list must be within an item, this one has no preceding item to use.
+            self.open("listitem", &[])?;
+            if let Some(item_open) = self.list_stack.borrow_mut().last_mut() {
+                *item_open = true;
+            }
+        }
+        self.list_stack.borrow_mut().push(false);
+        self.open(list_tag(kind), &id_attribute(label))
+    }
+
+    fn end_list(&self, kind: &ListKind, _: &Option<Label>) -> crate::error::Result<()> {
+        if self.list_stack.borrow_mut().pop() == Some(true) {
+            self.close("listitem")?;
+        }
+        self.close(list_tag(kind))
+    }
+
+    fn start_list_item(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        // items are closed lazily so that a following nested list is placed within them.
+        let item_open = self.list_stack.borrow_mut().last_mut().map(|open| {
+            let was_open = *open;
+            *open = true;
+            was_open
+        });
+        if item_open == Some(true) {
+            self.close("listitem")?;
+        }
+        self.open("listitem", &id_attribute(label))?;
+        self.start_inline("para", &[])
+    }
+
+    fn end_list_item(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_inline("para")
+    }
+
+    fn start_definition_list(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.open("variablelist", &id_attribute(label))
+    }
+
+    fn end_definition_list(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.close("variablelist")
+    }
+
+    fn start_definition(&self, term: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.open("varlistentry", &id_attribute(label))?;
+        self.element("term", term)?;
+        self.open("listitem", &[])
+    }
+
+    fn start_definition_list_text(&self) -> crate::error::Result<()> {
+        self.start_inline("para", &[])
+    }
+
+    fn end_definition_list_text(&self) -> crate::error::Result<()> {
+        self.end_inline("para")
+    }
+
+    fn end_definition(&self, _: &str, _: &Option<Label>) -> crate::error::Result<()> {
+        self.close("listitem")?;
+        self.close("varlistentry")
+    }
+
+    fn formatted(&self, value: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        let mut attributes = id_attribute(label);
+        attributes.push(("class", "monospaced".to_string()));
+        self.start_inline("literallayout", &attributes)?;
+        self.write(&escape(value))?;
+        self.end_inline("literallayout")
+    }
+
+    fn code_block(
+        &self,
+        code: &str,
+        language: &Option<String>,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        let mut attributes = Vec::new();
+        if let Some(language) = language {
+            attributes.push(("language", language.clone()));
+        }
+        if caption.is_some() {
+            self.open("example", &id_attribute(label))?;
+            self.write_title(caption)?;
+        } else {
+            attributes.extend(id_attribute(label));
+        }
+        self.start_inline("programlisting", &attributes)?;
+        self.write(&escape(code))?;
+        self.end_inline("programlisting")?;
+        if caption.is_some() {
+            self.close("example")?;
+        }
+        Ok(())
+    }
+
+    fn start_paragraph(
+        &self,
+        alignment: &Alignment,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        let mut attributes = id_attribute(label);
+        match alignment {
+            Alignment::Right => attributes.push(("role", "right".to_string())),
+            Alignment::Centered => attributes.push(("role", "center".to_string())),
+            Alignment::Justified => attributes.push(("role", "justify".to_string())),
+            _ => {}
+        }
+        self.start_inline("para", &attributes)
+    }
+
+    fn end_paragraph(&self, _: &Alignment, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_inline("para")
+    }
+
+    fn start_quote(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.open("blockquote", &id_attribute(label))
+    }
+
+    fn end_quote(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.close("blockquote")
+    }
+
+    fn thematic_break(&self) -> crate::error::Result<()> {
+        self.write_line("<para role=\"separator\"/>")
+    }
+
+    fn table_visitor(&self) -> Option<&dyn TableVisitor> {
+        Some(self)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> TableVisitor for DocBookWriter<'a, W> {
+    fn start_table(&self, _: &Option<Caption>, _: &Option<Label>) -> crate::error::Result<()> {
+        // the CALS column count must be known before any rows are written.
+        let _ = self.table.replace(Some(TableText::default()));
+        Ok(())
+    }
+
+    fn table_header_cell(
+        &self,
+        column_cell: &Column,
+        _column_idx: usize,
+    ) -> crate::error::Result<()> {
+        if let Some(table) = self.table.borrow_mut().as_mut() {
+            table.columns.push((
+                column_cell.text().to_string(),
+                column_cell.alignment().clone(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn start_table_row(&self, _: usize) -> crate::error::Result<()> {
+        if let Some(table) = self.table.borrow_mut().as_mut() {
+            table.rows.push(Vec::default());
+        }
+        Ok(())
+    }
+
+    fn start_table_cell(&self, _: usize, _: &Option<Label>) -> crate::error::Result<()> {
+        let _ = self.cell.replace(Some(String::new()));
+        Ok(())
+    }
+
+    fn end_table_cell(&self, _: usize, label: &Option<Label>) -> crate::error::Result<()> {
+        let text = self.cell.replace(None).unwrap_or_default();
+        if let Some(table) = self.table.borrow_mut().as_mut() {
+            if let Some(row) = table.rows.last_mut() {
+                row.push((text, label.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    fn end_table(
+        &self,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        let table = self.table.replace(None);
+        match table {
+            Some(table) => self.write_table(table, caption, label),
+            None => Ok(()),
+        }
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> InlineVisitor for DocBookWriter<'a, W> {
+    fn link(&self, value: &HyperLink) -> crate::error::Result<()> {
+        match (value.target(), value.caption()) {
+            (HyperLinkTarget::External(target), Some(caption)) => self.write(&format!(
+                "{}{}</link>",
+                start_tag("link", &[("xlink:href", target.clone())]),
+                escape(caption.inner())
+            )),
+            (HyperLinkTarget::External(target), None) => self.write(&format!(
+                "<link xlink:href=\"{}\"/>",
+                escape_attribute(target)
+            )),
+            (HyperLinkTarget::Internal(target), Some(caption)) => self.write(&format!(
+                "{}{}</link>",
+                start_tag("link", &[("linkend", xml_id(&target.to_string()))]),
+                escape(caption.inner())
+            )),
+            (HyperLinkTarget::Internal(target), None) => self.write(&format!(
+                "<xref linkend=\"{}\"/>",
+                xml_id(&target.to_string())
+            )),
+        }
+    }
+
+    fn image(&self, value: &Image) -> crate::error::Result<()> {
+        self.write(&media_object(value, "inlinemediaobject", &[]))
+    }
+
+    fn text(&self, value: &Text) -> crate::error::Result<()> {
+        self.write(&escape(value.inner()))
+    }
+
+    fn math(&self, value: &Math) -> crate::error::Result<()> {
+        self.write(&format!(
+            "<inlineequation><mathphrase>{}</mathphrase></inlineequation>",
+            escape(value.inner())
+        ))
+    }
+
+    fn character(&self, value: &Character) -> crate::error::Result<()> {
+        self.write(&match value {
+            Character::Space => " ".to_string(),
+            Character::NonBreakSpace => "&#160;".to_string(),
+            Character::Hyphen => "-".to_string(),
+            Character::EmDash => "&#8212;".to_string(),
+            Character::EnDash => "&#8211;".to_string(),
+            Character::Emoji(name) => format!(":{}:", escape(name.inner())),
+            Character::Other(c) => escape(&c.to_string()),
+        })
+    }
+
+    fn line_break(&self) -> crate::error::Result<()> {
+        // DocBook has no line break element, this is supported by the DocBook XSL stylesheets.
+        self.write("<?linebreak?>")
+    }
+
+    fn start_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        for (tag, attributes) in span_tags(styles) {
+            self.write(&start_tag(tag, &attributes))?;
+        }
+        Ok(())
+    }
+
+    fn end_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        for (tag, _) in span_tags(styles).iter().rev() {
+            self.write(&format!("</{}>", tag))?;
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn span_tags(styles: &[SpanStyle]) -> Vec<(&'static str, Vec<(&'static str, String)>)> {
+    let mut tags = Vec::new();
+    let role = |value: &str| vec![("role", value.to_string())];
+    for style in styles {
+        match style {
+            SpanStyle::Plain => tags.clear(),
+            SpanStyle::Italic => tags.push(("emphasis", Vec::new())),
+            SpanStyle::Bold => tags.push(("emphasis", role("bold"))),
+            SpanStyle::Mono => tags.push(("literal", Vec::new())),
+            SpanStyle::Code => tags.push(("code", Vec::new())),
+            SpanStyle::Strikethrough => tags.push(("emphasis", role("strikethrough"))),
+            SpanStyle::Underline => tags.push(("emphasis", role("underline"))),
+            SpanStyle::SmallCaps => tags.push(("phrase", role("smallcaps"))),
+            SpanStyle::Superscript => tags.push(("superscript", Vec::new())),
+            SpanStyle::Subscript => tags.push(("subscript", Vec::new())),
+            SpanStyle::Sized(size) => tags.push((
+                "phrase",
+                role(match size {
+                    Size::Largest => "largest",
+                    Size::Larger => "larger",
+                    Size::Large => "large",
+                    Size::Normal => "normal",
+                    Size::Small => "small",
+                    Size::Smaller => "smaller",
+                    Size::Smallest => "smallest",
+                }),
+            )),
+        }
+    }
+    tags
+}
+
+fn list_tag(kind: &ListKind) -> &'static str {
+    match kind {
+        ListKind::Ordered => "orderedlist",
+        ListKind::Unordered => "itemizedlist",
+    }
+}
+
+fn media_object(value: &Image, tag: &str, attributes: &[(&str, String)]) -> String {
+    let mut object = format!(
+        "{}<imageobject><imagedata fileref=\"{}\"/></imageobject>",
+        start_tag(tag, attributes),
+        escape_attribute(value.inner())
+    );
+    if let Some(alt_text) = value.alt_text() {
+        object.push_str(&format!(
+            "<textobject><phrase>{}</phrase></textobject>",
+            escape(alt_text)
+        ));
+    }
+    object.push_str(&format!("</{}>", tag));
+    object
+}
+
+fn start_tag(tag: &str, attributes: &[(&str, String)]) -> String {
+    if attributes.is_empty() {
+        format!("<{}>", tag)
+    } else {
+        format!(
+            "<{} {}>",
+            tag,
+            attributes
+                .iter()
+                .map(|(name, value)| format!("{}=\"{}\"", name, escape_attribute(value)))
+                .collect::<Vec<String>>()
+                .join(" ")
+        )
+    }
+}
+
+fn id_attribute(label: &Option<Label>) -> Vec<(&'static str, String)> {
+    match label {
+        Some(label) => vec![("xml:id", xml_id(&label.to_string()))],
+        None => Vec::new(),
+    }
+}
+
+///
+/// Labels are not constrained to XML names (`NCName`), so replace any invalid characters.
+///
+fn xml_id(label: &str) -> String {
+    let mut id: String = label
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !id.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+        id.insert(0, '_');
+    }
+    id
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn escape_attribute(text: &str) -> String {
+    escape(text).replace('"', "&quot;")
+}
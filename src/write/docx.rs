@@ -0,0 +1,1391 @@
+/*!
+Write a document as an [Office Open XML](https://www.ecma-international.org/publications-and-standards/standards/ecma-376/)
+word processing document, the `.docx` format used by Microsoft Word. The output is a zip package
+containing the document, its styles and list numbering definitions, any local images, and the
+document properties.
+
+Headings use the built-in "heading N" styles so that they appear in the navigation pane and in a
+table of contents, each list is given its own numbering instance so that ordered lists restart
+their numbering. Labels become bookmarks, internal hyperlinks refer to them by name. Local images
+are embedded in the package, remote images are linked as external relationships.
+
+# Example
+
+```rust
+# use somedoc::model::Document;
+use somedoc::write::docx::writer;
+
+# fn make_some_document() -> Document { Document::default() }
+let doc = make_some_document();
+let mut buffer = std::io::Cursor::new(Vec::new());
+
+writer(&doc, &mut buffer).unwrap();
+```
+*/
+
+use crate::error::{ErrorKind, ResultExt};
+use crate::model::block::{
+    Alignment, Caption, Column, FrontMatter, HasAlignment, HasCaption, HeadingLevel, Label,
+    ListKind,
+};
+use crate::model::document::Metadata;
+use crate::model::inline::text::Size;
+use crate::model::inline::{
+    Character, HasInlineContent, HyperLink, HyperLinkTarget, Image, Math, SpanStyle, Text,
+};
+use crate::model::visitor::{
+    walk_document, BlockVisitor, DocumentVisitor, InlineVisitor, TableVisitor,
+};
+use crate::model::Document;
+use crate::write::Writer;
+use std::cell::RefCell;
+use std::io::{Cursor, Write};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the DOCX writer structure, usually this is accessed via the `writer`
+/// function, but may be used directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use somedoc::model::Document;
+/// use somedoc::write::docx::DocxWriter;
+/// use somedoc::write::Writer;
+///
+/// # fn make_some_document() -> Document { Document::default() }
+/// let doc = make_some_document();
+/// let mut buffer = std::io::Cursor::new(Vec::new());
+/// let writer = DocxWriter::new(&mut buffer);
+/// assert!(writer.write_document(&doc).is_ok());
+/// ```
+///
+#[derive(Debug)]
+pub struct DocxWriter<'a, W: Write> {
+    body: RefCell<String>,
+    relationships: RefCell<Vec<Relationship>>,
+    media: RefCell<Vec<(String, String, Vec<u8>)>>,
+    numbering: RefCell<Vec<(ListKind, usize)>>,
+    list_stack: RefCell<Vec<usize>>,
+    span_stack: RefCell<Vec<RunProperties>>,
+    quote_depth: RefCell<usize>,
+    bookmarks: RefCell<Vec<String>>,
+    bookmark_count: RefCell<usize>,
+    drawing_count: RefCell<usize>,
+    table: RefCell<Option<TableState>>,
+    last_was_table: RefCell<bool>,
+    last_run: RefCell<Option<String>>,
+    w: RefCell<&'a mut W>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+struct Relationship {
+    kind: String,
+    target: String,
+    external: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct RunProperties {
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    strikethrough: bool,
+    mono: bool,
+    small_caps: bool,
+    position: Option<&'static str>,
+    size: Option<u8>,
+}
+
+#[derive(Debug)]
+struct TableState {
+    start: usize,
+    columns: usize,
+    alignments: Vec<Alignment>,
+    cell_count: usize,
+}
+
+const WORD_NAMESPACES: &str = r#"xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:pic="http://schemas.openxmlformats.org/drawingml/2006/picture""#;
+
+const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#;
+
+const RELATIONSHIP_BASE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+const CONTENT_TYPE_BASE: &str = "application/vnd.openxmlformats-officedocument";
+
+/// Letter size, with one inch margins, in twentieths of a point.
+const SECTION_PROPERTIES: &str = r#"<w:sectPr><w:pgSz w:w="12240" w:h="15840"/><w:pgMar w:top="1440" w:right="1440" w:bottom="1440" w:left="1440" w:header="720" w:footer="720" w:gutter="0"/></w:sectPr>"#;
+
+/// The width of the text between the page margins, in twentieths of a point.
+const TEXT_WIDTH: usize = 9360;
+
+/// English Metric Units per pixel, at 96 pixels per inch.
+const EMU_PER_PIXEL: u64 = 9525;
+
+/// The text width, in English Metric Units.
+const MAX_IMAGE_WIDTH: u64 = 5_943_600;
+
+const RUN_END: &str = "</w:t></w:r>";
+
+const BULLETS: [&str; 3] = ["•", "◦", "▪"];
+
+const PARAGRAPH_STYLES: &str = r#"  <w:style w:type="paragraph" w:default="1" w:styleId="Normal">
+    <w:name w:val="Normal"/>
+    <w:qFormat/>
+  </w:style>
+  <w:style w:type="paragraph" w:styleId="Title">
+    <w:name w:val="Title"/>
+    <w:basedOn w:val="Normal"/>
+    <w:next w:val="Normal"/>
+    <w:qFormat/>
+    <w:pPr><w:spacing w:after="240"/><w:jc w:val="center"/></w:pPr>
+    <w:rPr><w:b/><w:sz w:val="56"/><w:szCs w:val="56"/></w:rPr>
+  </w:style>
+  <w:style w:type="paragraph" w:styleId="Abstract">
+    <w:name w:val="Abstract"/>
+    <w:basedOn w:val="Normal"/>
+    <w:next w:val="Normal"/>
+    <w:pPr><w:ind w:left="720" w:right="720"/><w:jc w:val="both"/></w:pPr>
+    <w:rPr><w:i/></w:rPr>
+  </w:style>
+  <w:style w:type="paragraph" w:styleId="Quote">
+    <w:name w:val="Quote"/>
+    <w:basedOn w:val="Normal"/>
+    <w:next w:val="Normal"/>
+    <w:qFormat/>
+    <w:pPr><w:ind w:left="720" w:right="720"/></w:pPr>
+    <w:rPr><w:i/></w:rPr>
+  </w:style>
+  <w:style w:type="paragraph" w:styleId="SourceCode">
+    <w:name w:val="Source Code"/>
+    <w:basedOn w:val="Normal"/>
+    <w:pPr><w:spacing w:after="160" w:line="240" w:lineRule="auto"/></w:pPr>
+    <w:rPr><w:rFonts w:ascii="Consolas" w:hAnsi="Consolas" w:cs="Consolas"/><w:sz w:val="20"/><w:szCs w:val="20"/></w:rPr>
+  </w:style>
+  <w:style w:type="paragraph" w:styleId="Caption">
+    <w:name w:val="caption"/>
+    <w:basedOn w:val="Normal"/>
+    <w:next w:val="Normal"/>
+    <w:qFormat/>
+    <w:rPr><w:i/><w:sz w:val="18"/><w:szCs w:val="18"/></w:rPr>
+  </w:style>
+  <w:style w:type="paragraph" w:styleId="ListParagraph">
+    <w:name w:val="List Paragraph"/>
+    <w:basedOn w:val="Normal"/>
+    <w:qFormat/>
+    <w:pPr><w:spacing w:after="60"/><w:ind w:left="720"/></w:pPr>
+  </w:style>
+  <w:style w:type="paragraph" w:styleId="DefinitionTerm">
+    <w:name w:val="Definition Term"/>
+    <w:basedOn w:val="Normal"/>
+    <w:next w:val="Definition"/>
+    <w:pPr><w:keepNext/><w:spacing w:after="0"/></w:pPr>
+    <w:rPr><w:b/></w:rPr>
+  </w:style>
+  <w:style w:type="paragraph" w:styleId="Definition">
+    <w:name w:val="Definition"/>
+    <w:basedOn w:val="Normal"/>
+    <w:pPr><w:ind w:left="720"/></w:pPr>
+  </w:style>
+  <w:style w:type="paragraph" w:styleId="TableContents">
+    <w:name w:val="Table Contents"/>
+    <w:basedOn w:val="Normal"/>
+    <w:pPr><w:spacing w:after="0"/></w:pPr>
+  </w:style>
+  <w:style w:type="paragraph" w:styleId="TableHeading">
+    <w:name w:val="Table Heading"/>
+    <w:basedOn w:val="TableContents"/>
+    <w:rPr><w:b/></w:rPr>
+  </w:style>
+  <w:style w:type="paragraph" w:styleId="TOCHeading">
+    <w:name w:val="TOC Heading"/>
+    <w:basedOn w:val="Heading1"/>
+    <w:next w:val="Normal"/>
+    <w:pPr><w:outlineLvl w:val="9"/></w:pPr>
+  </w:style>
+  <w:style w:type="character" w:styleId="Hyperlink">
+    <w:name w:val="Hyperlink"/>
+    <w:rPr><w:color w:val="0563C1"/><w:u w:val="single"/></w:rPr>
+  </w:style>
+  <w:style w:type="character" w:styleId="VerbatimChar">
+    <w:name w:val="Verbatim Char"/>
+    <w:rPr><w:rFonts w:ascii="Consolas" w:hAnsi="Consolas" w:cs="Consolas"/></w:rPr>
+  </w:style>
+  <w:style w:type="table" w:default="1" w:styleId="TableNormal">
+    <w:name w:val="Normal Table"/>
+    <w:tblPr><w:tblInd w:w="0" w:type="dxa"/><w:tblCellMar><w:top w:w="0" w:type="dxa"/><w:left w:w="108" w:type="dxa"/><w:bottom w:w="0" w:type="dxa"/><w:right w:w="108" w:type="dxa"/></w:tblCellMar></w:tblPr>
+  </w:style>
+  <w:style w:type="table" w:styleId="TableGrid">
+    <w:name w:val="Table Grid"/>
+    <w:basedOn w:val="TableNormal"/>
+    <w:tblPr><w:tblBorders><w:top w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:left w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:bottom w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:right w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:insideH w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:insideV w:val="single" w:sz="4" w:space="0" w:color="auto"/></w:tblBorders></w:tblPr>
+  </w:style>
+"#;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the writer function for DOCX.
+///
+/// While this can be called directly it is most often used  by calling
+/// [`model::write_document`](../fn.write_document.html), the output is a zip archive and so
+/// [`model::write_document_to_string`](../fn.write_document_to_string.html) will return an
+/// error.
+///
+#[inline]
+pub fn writer<W: Write>(doc: &Document, w: &mut W) -> crate::error::Result<()> {
+    let writer = DocxWriter::new(w);
+    writer.write_document(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl RunProperties {
+    fn with_styles(&self, styles: &[SpanStyle]) -> Self {
+        let mut properties = self.clone();
+        for style in styles {
+            match style {
+                SpanStyle::Plain => properties = Default::default(),
+                SpanStyle::Italic => properties.italic = true,
+                SpanStyle::Bold => properties.bold = true,
+                SpanStyle::Mono | SpanStyle::Code => properties.mono = true,
+                SpanStyle::Strikethrough => properties.strikethrough = true,
+                SpanStyle::Underline => properties.underline = true,
+                SpanStyle::SmallCaps => properties.small_caps = true,
+                SpanStyle::Superscript => properties.position = Some("superscript"),
+                SpanStyle::Subscript => properties.position = Some("subscript"),
+                SpanStyle::Sized(size) => {
+                    properties.size = Some(match size {
+                        Size::Largest => 44,
+                        Size::Larger => 32,
+                        Size::Large => 26,
+                        Size::Normal => 22,
+                        Size::Small => 18,
+                        Size::Smaller => 16,
+                        Size::Smallest => 14,
+                    })
+                }
+            }
+        }
+        properties
+    }
+
+    ///
+    /// The elements of `w:rPr` must be written in the order defined by the schema, Word will
+    /// refuse to open a document where they are not.
+    ///
+    fn to_xml(&self, style: Option<&str>) -> String {
+        let mut xml = String::new();
+        match style {
+            Some(style) => xml.push_str(&format!("<w:rStyle w:val=\"{}\"/>", style)),
+            None if self.mono => xml.push_str("<w:rStyle w:val=\"VerbatimChar\"/>"),
+            None => (),
+        }
+        if self.bold {
+            xml.push_str("<w:b/>");
+        }
+        if self.italic {
+            xml.push_str("<w:i/>");
+        }
+        if self.small_caps {
+            xml.push_str("<w:smallCaps/>");
+        }
+        if self.strikethrough {
+            xml.push_str("<w:strike/>");
+        }
+        if let Some(size) = self.size {
+            xml.push_str(&format!(
+                "<w:sz w:val=\"{0}\"/><w:szCs w:val=\"{0}\"/>",
+                size
+            ));
+        }
+        if self.underline {
+            xml.push_str("<w:u w:val=\"single\"/>");
+        }
+        if let Some(position) = self.position {
+            xml.push_str(&format!("<w:vertAlign w:val=\"{}\"/>", position));
+        }
+        if xml.is_empty() {
+            xml
+        } else {
+            format!("<w:rPr>{}</w:rPr>", xml)
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> Writer<'a, W> for DocxWriter<'a, W> {
+    fn new(w: &'a mut W) -> Self {
+        Self {
+            body: RefCell::from(String::new()),
+            relationships: RefCell::from(vec![
+                Relationship::internal("styles", "styles.xml"),
+                Relationship::internal("numbering", "numbering.xml"),
+            ]),
+            media: RefCell::from(Vec::default()),
+            numbering: RefCell::from(Vec::default()),
+            list_stack: RefCell::from(Vec::default()),
+            span_stack: RefCell::from(Vec::default()),
+            quote_depth: RefCell::from(0),
+            bookmarks: RefCell::from(Vec::default()),
+            bookmark_count: RefCell::from(0),
+            drawing_count: RefCell::from(0),
+            table: RefCell::from(None),
+            last_was_table: RefCell::from(false),
+            last_run: RefCell::from(None),
+            w: RefCell::from(w),
+        }
+    }
+
+    fn write_document(&self, doc: &Document) -> crate::error::Result<()> {
+        walk_document(doc, self)?;
+
+        let custom_properties = custom_properties_xml(doc);
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        zip.start_file("[Content_Types].xml", options)?;
+        zip.write_all(
+            self.content_types_xml(custom_properties.is_some())
+                .as_bytes(),
+        )?;
+
+        zip.start_file("_rels/.rels", options)?;
+        let mut package_relationships = vec![
+            Relationship::internal("officeDocument", "word/document.xml"),
+            Relationship::internal("core-properties", "docProps/core.xml"),
+        ];
+        if custom_properties.is_some() {
+            package_relationships.push(Relationship::internal(
+                "custom-properties",
+                "docProps/custom.xml",
+            ));
+        }
+        zip.write_all(relationships_xml(&package_relationships).as_bytes())?;
+
+        zip.start_file("docProps/core.xml", options)?;
+        zip.write_all(core_properties_xml(doc).as_bytes())?;
+
+        if let Some(custom_properties) = custom_properties {
+            zip.start_file("docProps/custom.xml", options)?;
+            zip.write_all(custom_properties.as_bytes())?;
+        }
+
+        zip.start_file("word/document.xml", options)?;
+        zip.write_all(self.document_xml().as_bytes())?;
+
+        zip.start_file("word/_rels/document.xml.rels", options)?;
+        zip.write_all(relationships_xml(&self.relationships.borrow()).as_bytes())?;
+
+        zip.start_file("word/styles.xml", options)?;
+        zip.write_all(styles_xml().as_bytes())?;
+
+        zip.start_file("word/numbering.xml", options)?;
+        zip.write_all(self.numbering_xml().as_bytes())?;
+
+        for (_, name, content) in self.media.borrow().iter() {
+            zip.start_file(format!("word/{}", name), options)?;
+            zip.write_all(content)?;
+        }
+
+        let archive = zip.finish()?.into_inner();
+        self.w.borrow_mut().write_all(&archive)?;
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> DocxWriter<'a, W> {
+    fn write(&self, text: &str) {
+        let _ = self.last_run.replace(None);
+        self.body.borrow_mut().push_str(text);
+    }
+
+    ///
+    /// Text is visited a word, or a character, at a time; consecutive text with the same run
+    /// properties is written as a single run.
+    ///
+    fn write_text(&self, text: &str) {
+        let properties = self.run_properties().to_xml(None);
+        if text.contains('\t') {
+            self.write(&run(&properties, text));
+        } else {
+            let mut body = self.body.borrow_mut();
+            if self.last_run.borrow().as_ref() == Some(&properties) {
+                let end = body.len() - RUN_END.len();
+                body.truncate(end);
+                body.push_str(&escape(text));
+                body.push_str(RUN_END);
+            } else {
+                body.push_str(&run(&properties, text));
+                let _ = self.last_run.replace(Some(properties));
+            }
+        }
+    }
+
+    fn start_paragraph_with(&self, style: &str, alignment: &Alignment, extra: &str) {
+        *self.last_was_table.borrow_mut() = false;
+        let indent = self.indent();
+        self.write(&format!(
+            "{}<w:p><w:pPr><w:pStyle w:val=\"{}\"/>{}{}</w:pPr>",
+            indent,
+            style,
+            extra,
+            justification(alignment)
+        ));
+        self.write_bookmarks();
+    }
+
+    fn end_paragraph_with(&self) {
+        self.write("</w:p>\n");
+    }
+
+    fn paragraph(&self, style: &str, content: &str) {
+        self.start_paragraph_with(style, &Alignment::Left, "");
+        self.write(content);
+        self.end_paragraph_with();
+    }
+
+    fn indent(&self) -> String {
+        let depth = if self.table.borrow().is_some() { 5 } else { 2 };
+        format!("{: ^1$}", "", depth * 2)
+    }
+
+    fn bookmark(&self, label: &Option<Label>) {
+        if let Some(label) = label {
+            self.bookmarks.borrow_mut().push(label.to_string());
+        }
+    }
+
+    fn write_bookmarks(&self) {
+        let bookmarks: Vec<String> = self.bookmarks.borrow_mut().drain(..).collect();
+        for bookmark in bookmarks {
+            let id = *self.bookmark_count.borrow();
+            *self.bookmark_count.borrow_mut() += 1;
+            self.write(&format!(
+                "<w:bookmarkStart w:id=\"{0}\" w:name=\"{1}\"/><w:bookmarkEnd w:id=\"{0}\"/>",
+                id,
+                bookmark_name(&bookmark)
+            ));
+        }
+    }
+
+    fn caption(&self, caption: &Option<Caption>, label: &Option<Label>) {
+        if let Some(caption) = caption {
+            self.bookmark(label);
+            self.paragraph("Caption", &run("", caption.inner()));
+        }
+    }
+
+    fn run_properties(&self) -> RunProperties {
+        self.span_stack.borrow().last().cloned().unwrap_or_default()
+    }
+
+    fn add_relationship(&self, relationship: Relationship) -> String {
+        let mut relationships = self.relationships.borrow_mut();
+        let index = match relationships.iter().position(|r| *r == relationship) {
+            Some(index) => index,
+            None => {
+                relationships.push(relationship);
+                relationships.len() - 1
+            }
+        };
+        format!("rId{}", index + 1)
+    }
+
+    fn preformatted(&self, value: &str, alignment: &Alignment, label: &Option<Label>) {
+        self.bookmark(label);
+        self.start_paragraph_with("SourceCode", alignment, "");
+        for (i, line) in value.lines().enumerate() {
+            if i > 0 {
+                self.write("<w:r><w:br/></w:r>");
+            }
+            self.write(&run("", line));
+        }
+        self.end_paragraph_with();
+    }
+
+    fn drawing(&self, value: &Image) -> crate::error::Result<String> {
+        let path = value.inner();
+        let (relationship, size) = if is_local(path) {
+            // an image used more than once is only embedded once.
+            let existing = self
+                .media
+                .borrow()
+                .iter()
+                .find(|(media_path, _, _)| media_path == path)
+                .map(|(_, name, content)| (name.clone(), image_size(content)));
+            let (name, size) = match existing {
+                Some(existing) => existing,
+                None => {
+                    let content = std::fs::read(path)
+                        .chain_err(|| ErrorKind::ImageNotReadable(path.to_string()))?;
+                    let size = image_size(&content);
+                    let name = format!(
+                        "media/image{}{}",
+                        self.media.borrow().len() + 1,
+                        extension(path)
+                    );
+                    self.media
+                        .borrow_mut()
+                        .push((path.to_string(), name.clone(), content));
+                    (name, size)
+                }
+            };
+            let id = self.add_relationship(Relationship::internal("image", &name));
+            (format!("r:embed=\"{}\"", id), size)
+        } else {
+            let id = self.add_relationship(Relationship::external("image", path));
+            (format!("r:link=\"{}\"", id), None)
+        };
+        let (width, height) = extent(size);
+        *self.drawing_count.borrow_mut() += 1;
+        let id = *self.drawing_count.borrow();
+        Ok(format!(
+            "<w:r><w:drawing><wp:inline distT=\"0\" distB=\"0\" distL=\"0\" distR=\"0\"><wp:extent cx=\"{width}\" cy=\"{height}\"/><wp:docPr id=\"{id}\" name=\"Picture {id}\" descr=\"{alt}\"/><wp:cNvGraphicFramePr><a:graphicFrameLocks noChangeAspect=\"1\"/></wp:cNvGraphicFramePr><a:graphic><a:graphicData uri=\"http://schemas.openxmlformats.org/drawingml/2006/picture\"><pic:pic><pic:nvPicPr><pic:cNvPr id=\"{id}\" name=\"Picture {id}\"/><pic:cNvPicPr/></pic:nvPicPr><pic:blipFill><a:blip {relationship}/><a:stretch><a:fillRect/></a:stretch></pic:blipFill><pic:spPr><a:xfrm><a:off x=\"0\" y=\"0\"/><a:ext cx=\"{width}\" cy=\"{height}\"/></a:xfrm><a:prstGeom prst=\"rect\"><a:avLst/></a:prstGeom></pic:spPr></pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r>",
+            width = width,
+            height = height,
+            id = id,
+            alt = escape(value.alt_text().as_deref().unwrap_or_default()),
+            relationship = relationship
+        ))
+    }
+
+    fn document_xml(&self) -> String {
+        format!(
+            "{}\n<w:document {}>\n  <w:body>\n{}{}    {}\n  </w:body>\n</w:document>\n",
+            XML_DECLARATION,
+            WORD_NAMESPACES,
+            self.body.borrow(),
+            // a table may not be the last element of the body.
+            if *self.last_was_table.borrow() {
+                "    <w:p/>\n"
+            } else {
+                ""
+            },
+            SECTION_PROPERTIES
+        )
+    }
+
+    fn numbering_xml(&self) -> String {
+        let mut xml = format!(
+            "{}\n<w:numbering xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\">\n",
+            XML_DECLARATION
+        );
+        for (id, ordered) in [(0, false), (1, true)].iter() {
+            xml.push_str(&format!(
+                "  <w:abstractNum w:abstractNumId=\"{}\">\n    <w:multiLevelType w:val=\"hybridMultilevel\"/>\n",
+                id
+            ));
+            for level in 0..9 {
+                xml.push_str(&format!(
+                    "    <w:lvl w:ilvl=\"{}\"><w:start w:val=\"1\"/><w:numFmt w:val=\"{}\"/><w:lvlText w:val=\"{}\"/><w:lvlJc w:val=\"left\"/><w:pPr><w:ind w:left=\"{}\" w:hanging=\"360\"/></w:pPr></w:lvl>\n",
+                    level,
+                    if *ordered { "decimal" } else { "bullet" },
+                    if *ordered {
+                        format!("%{}.", level + 1)
+                    } else {
+                        BULLETS[level % BULLETS.len()].to_string()
+                    },
+                    720 * (level + 1)
+                ));
+            }
+            xml.push_str("  </w:abstractNum>\n");
+        }
+        for (i, (kind, level)) in self.numbering.borrow().iter().enumerate() {
+            xml.push_str(&format!(
+                "  <w:num w:numId=\"{}\"><w:abstractNumId w:val=\"{}\"/><w:lvlOverride w:ilvl=\"{}\"><w:startOverride w:val=\"1\"/></w:lvlOverride></w:num>\n",
+                i + 1,
+                match kind {
+                    ListKind::Ordered => 1,
+                    ListKind::Unordered => 0,
+                },
+                level
+            ));
+        }
+        xml.push_str("</w:numbering>\n");
+        xml
+    }
+
+    fn content_types_xml(&self, has_custom_properties: bool) -> String {
+        let mut defaults = vec![
+            (
+                "rels".to_string(),
+                "application/vnd.openxmlformats-package.relationships+xml",
+            ),
+            ("xml".to_string(), "application/xml"),
+        ];
+        for (_, name, _) in self.media.borrow().iter() {
+            let extension = extension(name).trim_start_matches('.').to_string();
+            if !defaults.iter().any(|(e, _)| *e == extension) {
+                defaults.push((extension, media_type(name)));
+            }
+        }
+        let mut overrides = vec![
+            (
+                "/word/document.xml",
+                format!("{}.wordprocessingml.document.main+xml", CONTENT_TYPE_BASE),
+            ),
+            (
+                "/word/styles.xml",
+                format!("{}.wordprocessingml.styles+xml", CONTENT_TYPE_BASE),
+            ),
+            (
+                "/word/numbering.xml",
+                format!("{}.wordprocessingml.numbering+xml", CONTENT_TYPE_BASE),
+            ),
+            (
+                "/docProps/core.xml",
+                "application/vnd.openxmlformats-package.core-properties+xml".to_string(),
+            ),
+        ];
+        if has_custom_properties {
+            overrides.push((
+                "/docProps/custom.xml",
+                format!("{}.custom-properties+xml", CONTENT_TYPE_BASE),
+            ));
+        }
+        let mut xml = format!(
+            "{}\n<Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\n",
+            XML_DECLARATION
+        );
+        for (extension, content_type) in defaults {
+            xml.push_str(&format!(
+                "  <Default Extension=\"{}\" ContentType=\"{}\"/>\n",
+                escape(&extension),
+                content_type
+            ));
+        }
+        for (part, content_type) in overrides {
+            xml.push_str(&format!(
+                "  <Override PartName=\"{}\" ContentType=\"{}\"/>\n",
+                part, content_type
+            ));
+        }
+        xml.push_str("</Types>\n");
+        xml
+    }
+}
+
+impl<'a, W: Write> DocumentVisitor for DocxWriter<'a, W> {
+    fn metadata(&self, meta_datum: &Metadata) -> crate::error::Result<()> {
+        if let Metadata::Title(title) = meta_datum {
+            self.paragraph("Title", &run("", title));
+        }
+        Ok(())
+    }
+
+    fn block_visitor(&self) -> Option<&dyn BlockVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> BlockVisitor for DocxWriter<'a, W> {
+    fn start_abstract(&self) -> crate::error::Result<()> {
+        self.start_paragraph_with("Abstract", &Alignment::Left, "");
+        Ok(())
+    }
+
+    fn end_abstract(&self) -> crate::error::Result<()> {
+        self.end_paragraph_with();
+        Ok(())
+    }
+
+    fn front_matter(&self, value: &FrontMatter) -> crate::error::Result<()> {
+        if *value == FrontMatter::TableOfContents {
+            self.paragraph("TOCHeading", &run("", "Contents"));
+            // the field is marked dirty so that Word will offer to update it when opened.
+            self.paragraph(
+                "Normal",
+                r#"<w:r><w:fldChar w:fldCharType="begin" w:dirty="true"/></w:r><w:r><w:instrText xml:space="preserve"> TOC \o "1-3" \h \z \u </w:instrText></w:r><w:r><w:fldChar w:fldCharType="separate"/></w:r><w:r><w:t>Update this field to show the table of contents.</w:t></w:r><w:r><w:fldChar w:fldCharType="end"/></w:r>"#,
+            );
+        }
+        Ok(())
+    }
+
+    fn start_heading(
+        &self,
+        level: &HeadingLevel,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.bookmark(label);
+        self.start_paragraph_with(
+            &format!("Heading{}", level.clone() as u8),
+            &Alignment::Left,
+            "",
+        );
+        Ok(())
+    }
+
+    fn end_heading(&self, _: &HeadingLevel, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_paragraph_with();
+        Ok(())
+    }
+
+    fn image(
+        &self,
+        value: &Image,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        if caption.is_none() {
+            self.bookmark(label);
+        }
+        let drawing = self.drawing(value)?;
+        self.paragraph("Normal", &drawing);
+        self.caption(caption, label);
+        Ok(())
+    }
+
+    fn math(
+        &self,
+        value: &Math,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.preformatted(
+            value.inner(),
+            &Alignment::Centered,
+            if caption.is_none() { label } else { &None },
+        );
+        self.caption(caption, label);
+        Ok(())
+    }
+
+    fn start_list(&self, kind: &ListKind, label: &Option<Label>) -> crate::error::Result<()> {
+        self.bookmark(label);
+        // each list has its own numbering instance, so that ordered lists restart at one.
+        let level = self.list_stack.borrow().len().min(8);
+        let mut numbering = self.numbering.borrow_mut();
+        numbering.push((kind.clone(), level));
+        self.list_stack.borrow_mut().push(numbering.len());
+        Ok(())
+    }
+
+    fn end_list(&self, _: &ListKind, _: &Option<Label>) -> crate::error::Result<()> {
+        let _ = self.list_stack.borrow_mut().pop();
+        Ok(())
+    }
+
+    fn start_list_item(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.bookmark(label);
+        let (level, id) = {
+            let list_stack = self.list_stack.borrow();
+            (
+                list_stack.len().saturating_sub(1).min(8),
+                list_stack.last().cloned().unwrap_or_default(),
+            )
+        };
+        self.start_paragraph_with(
+            "ListParagraph",
+            &Alignment::Left,
+            &format!(
+                "<w:numPr><w:ilvl w:val=\"{}\"/><w:numId w:val=\"{}\"/></w:numPr>",
+                level, id
+            ),
+        );
+        Ok(())
+    }
+
+    fn end_list_item(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_paragraph_with();
+        Ok(())
+    }
+
+    fn start_definition_list(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.bookmark(label);
+        Ok(())
+    }
+
+    fn start_definition(&self, term: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.bookmark(label);
+        self.paragraph("DefinitionTerm", &run("", term));
+        Ok(())
+    }
+
+    fn start_definition_list_text(&self) -> crate::error::Result<()> {
+        self.start_paragraph_with("Definition", &Alignment::Left, "");
+        Ok(())
+    }
+
+    fn end_definition_list_text(&self) -> crate::error::Result<()> {
+        self.end_paragraph_with();
+        Ok(())
+    }
+
+    fn formatted(&self, value: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.preformatted(value, &Alignment::Left, label);
+        Ok(())
+    }
+
+    fn code_block(
+        &self,
+        code: &str,
+        _: &Option<String>,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.preformatted(
+            code,
+            &Alignment::Left,
+            if caption.is_none() { label } else { &None },
+        );
+        self.caption(caption, label);
+        Ok(())
+    }
+
+    fn start_paragraph(
+        &self,
+        alignment: &Alignment,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.bookmark(label);
+        let style = if *self.quote_depth.borrow() > 0 {
+            "Quote"
+        } else {
+            "Normal"
+        };
+        self.start_paragraph_with(style, alignment, "");
+        Ok(())
+    }
+
+    fn end_paragraph(&self, _: &Alignment, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_paragraph_with();
+        Ok(())
+    }
+
+    fn start_quote(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.bookmark(label);
+        *self.quote_depth.borrow_mut() += 1;
+        Ok(())
+    }
+
+    fn end_quote(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        *self.quote_depth.borrow_mut() -= 1;
+        Ok(())
+    }
+
+    fn thematic_break(&self) -> crate::error::Result<()> {
+        self.start_paragraph_with(
+            "Normal",
+            &Alignment::Left,
+            r#"<w:pBdr><w:bottom w:val="single" w:sz="6" w:space="1" w:color="auto"/></w:pBdr>"#,
+        );
+        self.end_paragraph_with();
+        Ok(())
+    }
+
+    fn table_visitor(&self) -> Option<&dyn TableVisitor> {
+        Some(self)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> TableVisitor for DocxWriter<'a, W> {
+    fn start_table(
+        &self,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        if caption.is_none() {
+            // without a caption the bookmark is placed in the first cell.
+            self.bookmark(label);
+        }
+        self.write("    <w:tbl>\n      <w:tblPr><w:tblStyle w:val=\"TableGrid\"/><w:tblW w:w=\"0\" w:type=\"auto\"/></w:tblPr>\n");
+        // the grid is inserted here once the number of columns is known.
+        let _ = self.table.replace(Some(TableState {
+            start: self.body.borrow().len(),
+            columns: 0,
+            alignments: Default::default(),
+            cell_count: 0,
+        }));
+        Ok(())
+    }
+
+    fn start_table_header_row(&self) -> crate::error::Result<()> {
+        self.write("      <w:tr>\n        <w:trPr><w:tblHeader/></w:trPr>\n");
+        Ok(())
+    }
+
+    fn table_header_cell(
+        &self,
+        column_cell: &Column,
+        _column_idx: usize,
+    ) -> crate::error::Result<()> {
+        if let Some(table) = self.table.borrow_mut().as_mut() {
+            table.alignments.push(column_cell.alignment().clone());
+            table.columns = table.columns.max(table.alignments.len());
+        }
+        self.write("        <w:tc>\n");
+        self.start_paragraph_with("TableHeading", column_cell.alignment(), "");
+        self.write(&run("", column_cell.text()));
+        self.end_paragraph_with();
+        self.write("        </w:tc>\n");
+        Ok(())
+    }
+
+    fn end_table_header_row(&self) -> crate::error::Result<()> {
+        self.write("      </w:tr>\n");
+        Ok(())
+    }
+
+    fn start_table_row(&self, _: usize) -> crate::error::Result<()> {
+        if let Some(table) = self.table.borrow_mut().as_mut() {
+            table.cell_count = 0;
+        }
+        self.write("      <w:tr>\n");
+        Ok(())
+    }
+
+    fn start_table_cell(&self, _: usize, label: &Option<Label>) -> crate::error::Result<()> {
+        let alignment = match self.table.borrow_mut().as_mut() {
+            Some(table) => {
+                table.cell_count += 1;
+                table.columns = table.columns.max(table.cell_count);
+                table
+                    .alignments
+                    .get(table.cell_count - 1)
+                    .cloned()
+                    .unwrap_or_default()
+            }
+            None => Alignment::default(),
+        };
+        self.write("        <w:tc>\n");
+        self.bookmark(label);
+        self.start_paragraph_with("TableContents", &alignment, "");
+        Ok(())
+    }
+
+    fn end_table_cell(&self, _: usize, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_paragraph_with();
+        self.write("        </w:tc>\n");
+        Ok(())
+    }
+
+    fn end_table_row(&self, _: usize) -> crate::error::Result<()> {
+        self.write("      </w:tr>\n");
+        Ok(())
+    }
+
+    fn end_table(
+        &self,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        if let Some(table) = self.table.replace(None) {
+            let columns = table.columns.max(1);
+            let grid = format!(
+                "      <w:tblGrid>{}</w:tblGrid>\n",
+                format!("<w:gridCol w:w=\"{}\"/>", TEXT_WIDTH / columns).repeat(columns)
+            );
+            self.body.borrow_mut().insert_str(table.start, &grid);
+        }
+        self.write("    </w:tbl>\n");
+        *self.last_was_table.borrow_mut() = true;
+        self.caption(caption, label);
+        Ok(())
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> InlineVisitor for DocxWriter<'a, W> {
+    fn link(&self, value: &HyperLink) -> crate::error::Result<()> {
+        let (attribute, text) = match value.target() {
+            HyperLinkTarget::External(target) => (
+                format!(
+                    "r:id=\"{}\"",
+                    self.add_relationship(Relationship::external("hyperlink", target))
+                ),
+                target.clone(),
+            ),
+            HyperLinkTarget::Internal(target) => (
+                format!("w:anchor=\"{}\"", bookmark_name(&target.to_string())),
+                target.to_string(),
+            ),
+        };
+        let properties = self.run_properties().to_xml(Some("Hyperlink"));
+        self.write(&format!(
+            "<w:hyperlink {} w:history=\"1\">{}</w:hyperlink>",
+            attribute,
+            run(
+                &properties,
+                match value.caption() {
+                    Some(caption) => caption.inner(),
+                    None => &text,
+                }
+            )
+        ));
+        Ok(())
+    }
+
+    fn image(&self, value: &Image) -> crate::error::Result<()> {
+        let drawing = self.drawing(value)?;
+        self.write(&drawing);
+        Ok(())
+    }
+
+    fn text(&self, value: &Text) -> crate::error::Result<()> {
+        self.write_text(value.inner());
+        Ok(())
+    }
+
+    fn math(&self, value: &Math) -> crate::error::Result<()> {
+        self.write(&run(
+            &self.run_properties().to_xml(Some("VerbatimChar")),
+            value.inner(),
+        ));
+        Ok(())
+    }
+
+    fn character(&self, value: &Character) -> crate::error::Result<()> {
+        let text = match value {
+            Character::Space => " ".to_string(),
+            Character::NonBreakSpace => "\u{a0}".to_string(),
+            Character::Hyphen => "-".to_string(),
+            Character::EmDash => "\u{2014}".to_string(),
+            Character::EnDash => "\u{2013}".to_string(),
+            Character::Emoji(name) => format!(":{}:", name.inner()),
+            Character::Other(c) => c.to_string(),
+        };
+        self.write_text(&text);
+        Ok(())
+    }
+
+    fn line_break(&self) -> crate::error::Result<()> {
+        self.write("<w:r><w:br/></w:r>");
+        Ok(())
+    }
+
+    fn start_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        let properties = self.run_properties().with_styles(styles);
+        self.span_stack.borrow_mut().push(properties);
+        Ok(())
+    }
+
+    fn end_span(&self, _: &[SpanStyle]) -> crate::error::Result<()> {
+        let _ = self.span_stack.borrow_mut().pop();
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Relationship {
+    fn internal(kind: &str, target: &str) -> Self {
+        Self::new(kind, target, false)
+    }
+
+    fn external(kind: &str, target: &str) -> Self {
+        Self::new(kind, target, true)
+    }
+
+    fn new(kind: &str, target: &str, external: bool) -> Self {
+        Self {
+            kind: match kind {
+                "core-properties" => {
+                    "http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties".to_string()
+                }
+                _ => format!("{}/{}", RELATIONSHIP_BASE, kind),
+            },
+            target: target.to_string(),
+            external,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn run(properties: &str, text: &str) -> String {
+    let mut content = String::new();
+    for (i, part) in text.split('\t').enumerate() {
+        if i > 0 {
+            content.push_str("<w:tab/>");
+        }
+        if !part.is_empty() {
+            content.push_str(&format!(
+                "<w:t xml:space=\"preserve\">{}</w:t>",
+                escape(part)
+            ));
+        }
+    }
+    format!("<w:r>{}{}</w:r>", properties, content)
+}
+
+fn justification(alignment: &Alignment) -> &'static str {
+    match alignment {
+        Alignment::Left => "",
+        Alignment::Right => "<w:jc w:val=\"right\"/>",
+        Alignment::Centered => "<w:jc w:val=\"center\"/>",
+        Alignment::Justified => "<w:jc w:val=\"both\"/>",
+    }
+}
+
+///
+/// Word bookmark names must start with a letter, contain only letters, digits, and underscores,
+/// and be no longer than 40 characters.
+///
+fn bookmark_name(label: &str) -> String {
+    let mut name: String = label
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name.insert(0, 'b');
+    }
+    name.chars().take(40).collect()
+}
+
+fn styles_xml() -> String {
+    let mut xml = format!(
+        "{}\n<w:styles xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\">\n",
+        XML_DECLARATION
+    );
+    xml.push_str("  <w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii=\"Calibri\" w:hAnsi=\"Calibri\" w:eastAsia=\"Calibri\" w:cs=\"Calibri\"/><w:sz w:val=\"22\"/><w:szCs w:val=\"22\"/></w:rPr></w:rPrDefault><w:pPrDefault><w:pPr><w:spacing w:after=\"160\" w:line=\"259\" w:lineRule=\"auto\"/></w:pPr></w:pPrDefault></w:docDefaults>\n");
+    xml.push_str(PARAGRAPH_STYLES);
+    for (level, size) in [32, 28, 26, 24, 22, 22, 22].iter().enumerate() {
+        xml.push_str(&format!(
+            "  <w:style w:type=\"paragraph\" w:styleId=\"Heading{0}\">\n    <w:name w:val=\"heading {0}\"/>\n    <w:basedOn w:val=\"Normal\"/>\n    <w:next w:val=\"Normal\"/>\n    <w:qFormat/>\n    <w:pPr><w:keepNext/><w:spacing w:before=\"240\" w:after=\"120\"/><w:outlineLvl w:val=\"{1}\"/></w:pPr>\n    <w:rPr><w:b/>{2}<w:sz w:val=\"{3}\"/><w:szCs w:val=\"{3}\"/></w:rPr>\n  </w:style>\n",
+            level + 1,
+            level,
+            if level > 2 { "<w:i/>" } else { "" },
+            size
+        ));
+    }
+    xml.push_str("</w:styles>\n");
+    xml
+}
+
+fn relationships_xml(relationships: &[Relationship]) -> String {
+    let mut xml = format!(
+        "{}\n<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\n",
+        XML_DECLARATION
+    );
+    for (i, relationship) in relationships.iter().enumerate() {
+        xml.push_str(&format!(
+            "  <Relationship Id=\"rId{}\" Type=\"{}\" Target=\"{}\"{}/>\n",
+            i + 1,
+            relationship.kind,
+            escape(&relationship.target),
+            if relationship.external {
+                " TargetMode=\"External\""
+            } else {
+                ""
+            }
+        ));
+    }
+    xml.push_str("</Relationships>\n");
+    xml
+}
+
+fn core_properties_xml(doc: &Document) -> String {
+    let mut properties: Vec<String> = Default::default();
+    let mut authors: Vec<&str> = Default::default();
+    for datum in doc.metadata() {
+        match datum {
+            Metadata::Title(title) => {
+                properties.push(format!("<dc:title>{}</dc:title>", escape(title)))
+            }
+            Metadata::Author(author) => authors.push(&author.name),
+            Metadata::Keywords(keywords) => properties.push(format!(
+                "<cp:keywords>{}</cp:keywords>",
+                escape(&keywords.join(", "))
+            )),
+            Metadata::Revision(revision) => {
+                properties.push(format!("<cp:revision>{}</cp:revision>", escape(revision)))
+            }
+            Metadata::Status(status) => properties.push(format!(
+                "<cp:contentStatus>{}</cp:contentStatus>",
+                escape(status)
+            )),
+            Metadata::Date(date) if is_iso_date(date) => properties.push(format!(
+                "<dcterms:created xsi:type=\"dcterms:W3CDTF\">{}</dcterms:created>",
+                date
+            )),
+            Metadata::Other(property) => {
+                if let Some(element) = core_property_element(&property.key) {
+                    properties.push(format!("<{0}>{1}</{0}>", element, escape(&property.value)));
+                }
+            }
+            _ => (),
+        }
+    }
+    if !authors.is_empty() {
+        properties.push(format!(
+            "<dc:creator>{}</dc:creator>",
+            escape(&authors.join("; "))
+        ));
+    }
+    if let Some(abstract_block) = doc.abstract_block() {
+        properties.push(format!(
+            "<dc:description>{}</dc:description>",
+            escape(&abstract_block.unformatted_string())
+        ));
+    }
+    format!(
+        "{}\n<cp:coreProperties xmlns:cp=\"http://schemas.openxmlformats.org/package/2006/metadata/core-properties\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\" xmlns:dcterms=\"http://purl.org/dc/terms/\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\">\n{}</cp:coreProperties>\n",
+        XML_DECLARATION,
+        properties
+            .iter()
+            .map(|property| format!("  {}\n", property))
+            .collect::<String>()
+    )
+}
+
+///
+/// Metadata that has no equivalent core property is written as a custom property.
+///
+fn custom_properties_xml(doc: &Document) -> Option<String> {
+    let properties: Vec<(String, String)> = doc
+        .metadata()
+        .iter()
+        .filter_map(|datum| match datum {
+            Metadata::Copyright(_) => Some((datum.key(), datum.value_string())),
+            Metadata::Date(date) if !is_iso_date(date) => Some((datum.key(), date.clone())),
+            Metadata::Other(property) if core_property_element(&property.key).is_none() => {
+                Some((property.key.clone(), property.value.clone()))
+            }
+            _ => None,
+        })
+        .collect();
+    if properties.is_empty() {
+        None
+    } else {
+        let mut xml = format!(
+            "{}\n<Properties xmlns=\"http://schemas.openxmlformats.org/officeDocument/2006/custom-properties\" xmlns:vt=\"http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes\">\n",
+            XML_DECLARATION
+        );
+        for (i, (name, value)) in properties.iter().enumerate() {
+            xml.push_str(&format!(
+                "  <property fmtid=\"{{D5CDD505-2E9C-101B-9397-08002B2CF9AE}}\" pid=\"{}\" name=\"{}\"><vt:lpwstr>{}</vt:lpwstr></property>\n",
+                i + 2,
+                escape(name),
+                escape(value)
+            ));
+        }
+        xml.push_str("</Properties>\n");
+        Some(xml)
+    }
+}
+
+fn core_property_element(key: &str) -> Option<&'static str> {
+    match key {
+        "identifier" => Some("dc:identifier"),
+        "language" => Some("dc:language"),
+        "subject" => Some("dc:subject"),
+        "category" => Some("cp:category"),
+        _ => None,
+    }
+}
+
+///
+/// Read the pixel dimensions from the header of a PNG, GIF, or JPEG image.
+///
+fn image_size(content: &[u8]) -> Option<(u64, u64)> {
+    let u16_be = |i: usize| Some(u16::from_be_bytes([*content.get(i)?, *content.get(i + 1)?]));
+    if content.starts_with(b"\x89PNG\r\n\x1a\n") && content.len() >= 24 {
+        let u32_be = |i: usize| {
+            u32::from_be_bytes([content[i], content[i + 1], content[i + 2], content[i + 3]])
+        };
+        Some((u32_be(16) as u64, u32_be(20) as u64))
+    } else if content.starts_with(b"GIF8") && content.len() >= 10 {
+        Some((
+            u16::from_le_bytes([content[6], content[7]]) as u64,
+            u16::from_le_bytes([content[8], content[9]]) as u64,
+        ))
+    } else if content.starts_with(&[0xFF, 0xD8]) {
+        let mut i = 2;
+        while i + 9 < content.len() && content[i] == 0xFF {
+            let marker = content[i + 1];
+            if (0xC0..=0xCF).contains(&marker) && ![0xC4, 0xC8, 0xCC].contains(&marker) {
+                return Some((u16_be(i + 7)? as u64, u16_be(i + 5)? as u64));
+            }
+            i += 2 + u16_be(i + 2)? as usize;
+        }
+        None
+    } else {
+        None
+    }
+}
+
+///
+/// The size of an image in English Metric Units, scaled down to fit within the page margins; an
+/// image of unknown size is given a 4:3 box the width of the page.
+///
+fn extent(size: Option<(u64, u64)>) -> (u64, u64) {
+    match size {
+        Some((width, height)) if width > 0 && height > 0 => {
+            let (width, height) = (width * EMU_PER_PIXEL, height * EMU_PER_PIXEL);
+            if width > MAX_IMAGE_WIDTH {
+                (MAX_IMAGE_WIDTH, height * MAX_IMAGE_WIDTH / width)
+            } else {
+                (width, height)
+            }
+        }
+        _ => (MAX_IMAGE_WIDTH, MAX_IMAGE_WIDTH * 3 / 4),
+    }
+}
+
+fn is_local(path: &str) -> bool {
+    !(path.contains("://") || path.starts_with("data:"))
+}
+
+fn extension(path: &str) -> String {
+    match std::path::Path::new(path).extension() {
+        Some(extension) => format!(".{}", extension.to_string_lossy().to_lowercase()),
+        None => String::new(),
+    }
+}
+
+fn media_type(path: &str) -> &'static str {
+    match extension(path).as_str() {
+        ".png" => "image/png",
+        ".jpg" | ".jpeg" => "image/jpeg",
+        ".gif" => "image/gif",
+        ".svg" => "image/svg+xml",
+        ".bmp" => "image/bmp",
+        ".tif" | ".tiff" => "image/tiff",
+        _ => "application/octet-stream",
+    }
+}
+
+fn is_iso_date(value: &str) -> bool {
+    value.len() == 10
+        && value.chars().enumerate().all(|(i, c)| {
+            if i == 4 || i == 7 {
+                c == '-'
+            } else {
+                c.is_ascii_digit()
+            }
+        })
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,723 @@
+/*!
+Write a document as an [EPUB 3](https://www.w3.org/TR/epub-33/) publication. The document is
+split into chapters at its top heading level, each of which is written as an XHTML content
+document using the [`html`](../html/index.html) writer. A navigation document is built from the
+document's headings and the package document from its metadata. Local image files are copied into
+the archive, remote images are left as references.
+
+The publication language is taken from a `language` (or `lang`) metadata property, such as
+`en-GB`; if neither is present the language is recorded as `und`, undetermined.
+
+Note that the output is a zip archive and so is not valid UTF-8; it should be written using
+[`write_document`](../fn.write_document.html) rather than
+[`write_document_to_string`](../fn.write_document_to_string.html).
+
+# Example
+
+```rust,no_run
+# use somedoc::model::Document;
+use somedoc::write::epub::writer;
+use std::fs::File;
+
+# fn make_some_document() -> Document { Document::default() }
+let doc = make_some_document();
+
+writer(&doc, &mut File::create("manual.epub").unwrap()).unwrap();
+```
+*/
+
+use crate::error::{ErrorKind, ResultExt};
+use crate::model::block::{
+    BlockContent, Caption, HasLabel, Heading, HeadingLevel, Label, ListKind,
+};
+use crate::model::document::Metadata;
+use crate::model::inline::{HasInlineContent, Image};
+use crate::model::visitor::{
+    walk_document, BlockVisitor, DocumentVisitor, InlineVisitor, TableVisitor,
+};
+use crate::model::{Document, HasInnerContent};
+use crate::write::html::{HtmlWriter, XhtmlOptions};
+use crate::write::Writer;
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::{Cursor, Write};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the EPUB writer structure, usually this is accessed via the `writer`
+/// function, but may be used directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use somedoc::model::Document;
+/// use somedoc::write::epub::EpubWriter;
+/// use somedoc::write::Writer;
+///
+/// # fn make_some_document() -> Document { Document::default() }
+/// let doc = make_some_document();
+/// let mut out: Vec<u8> = Vec::new();
+/// let writer = EpubWriter::new(&mut out);
+/// assert!(writer.write_document(&doc).is_ok());
+/// ```
+///
+#[derive(Debug)]
+pub struct EpubWriter<'a, W: Write> {
+    w: RefCell<&'a mut W>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct Chapter {
+    title: String,
+    file_name: String,
+    document: Document,
+    has_heading: bool,
+}
+
+#[derive(Debug)]
+struct NavEntry {
+    level: u8,
+    text: String,
+    href: String,
+}
+
+#[derive(Debug)]
+struct PackagedImage {
+    path: String,
+    file_name: String,
+    media_type: &'static str,
+}
+
+///
+/// Collects the labels and images in a chapter, these are used to qualify links between chapters
+/// and to determine which images need to be packaged.
+///
+#[derive(Debug, Default)]
+struct Collector {
+    labels: RefCell<Vec<String>>,
+    images: RefCell<Vec<String>>,
+}
+
+const MIMETYPE: &str = "application/epub+zip";
+
+const CONTAINER_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>
+"#;
+
+const XHTML_MEDIA_TYPE: &str = "application/xhtml+xml";
+
+const UNDETERMINED_LANGUAGE: &str = "und";
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the writer function for EPUB.
+///
+/// While this can be called directly it is most often used  by calling
+/// [`model::write_document`](../fn.write_document.html).
+///
+#[inline]
+pub fn writer<W: Write>(doc: &Document, w: &mut W) -> crate::error::Result<()> {
+    let writer = EpubWriter::new(w);
+    writer.write_document(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> Writer<'a, W> for EpubWriter<'a, W> {
+    fn new(w: &'a mut W) -> Self {
+        Self {
+            w: RefCell::from(w),
+        }
+    }
+
+    fn write_document(&self, doc: &Document) -> crate::error::Result<()> {
+        let chapters = split_chapters(doc);
+
+        let mut options = XhtmlOptions::default();
+        let mut images: Vec<PackagedImage> = Default::default();
+        let mut remote_images: Vec<bool> = Default::default();
+        for chapter in &chapters {
+            let collector = Collector::default();
+            walk_document(&chapter.document, &collector)?;
+            for label in collector.labels.into_inner() {
+                let _ = options.anchors.insert(label, chapter.file_name.clone());
+            }
+            let mut has_remote = false;
+            for path in collector.images.into_inner() {
+                if !is_local(&path) {
+                    has_remote = true;
+                } else if !options.images.contains_key(&path) {
+                    let file_name =
+                        format!("images/image-{}{}", images.len() + 1, extension(&path));
+                    let _ = options.images.insert(path.clone(), file_name.clone());
+                    images.push(PackagedImage {
+                        media_type: media_type(&path),
+                        path,
+                        file_name,
+                    });
+                }
+            }
+            remote_images.push(has_remote);
+        }
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let stored = FileOptions::default().compression_method(CompressionMethod::Stored);
+        let deflated = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        // the mimetype must be the first entry, and must not be compressed.
+        zip.start_file("mimetype", stored)?;
+        zip.write_all(MIMETYPE.as_bytes())?;
+
+        zip.start_file("META-INF/container.xml", deflated)?;
+        zip.write_all(CONTAINER_XML.as_bytes())?;
+
+        zip.start_file("OEBPS/content.opf", deflated)?;
+        zip.write_all(package_document(doc, &chapters, &remote_images, &images).as_bytes())?;
+
+        zip.start_file("OEBPS/nav.xhtml", deflated)?;
+        zip.write_all(navigation_document(doc, &chapters).as_bytes())?;
+
+        for chapter in &chapters {
+            let mut content: Vec<u8> = Vec::new();
+            let writer = HtmlWriter::new_xhtml(&mut content, options.clone());
+            writer.write_document(&chapter.document)?;
+            zip.start_file(format!("OEBPS/{}", chapter.file_name), deflated)?;
+            zip.write_all(&content)?;
+        }
+
+        for image in &images {
+            let content = std::fs::read(&image.path)
+                .chain_err(|| ErrorKind::ImageNotReadable(image.path.clone()))?;
+            zip.start_file(format!("OEBPS/{}", image.file_name), stored)?;
+            zip.write_all(&content)?;
+        }
+
+        let archive = zip.finish()?.into_inner();
+        self.w.borrow_mut().write_all(&archive)?;
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Chapter {
+    fn is_empty(&self) -> bool {
+        // comments and front matter are not rendered, so they do not make a chapter.
+        !(self.has_heading
+            || self.document.has_abstract()
+            || self.document.inner().iter().any(|block| {
+                !matches!(
+                    block,
+                    BlockContent::Comment(_) | BlockContent::FrontMatter(_)
+                )
+            }))
+    }
+}
+
+impl Collector {
+    fn label(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        if let Some(label) = label {
+            self.labels.borrow_mut().push(label.to_string());
+        }
+        Ok(())
+    }
+}
+
+impl DocumentVisitor for Collector {
+    fn block_visitor(&self) -> Option<&dyn BlockVisitor> {
+        Some(self)
+    }
+}
+
+impl BlockVisitor for Collector {
+    fn start_heading(&self, _: &HeadingLevel, label: &Option<Label>) -> crate::error::Result<()> {
+        self.label(label)
+    }
+
+    fn image(
+        &self,
+        value: &Image,
+        _: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.images.borrow_mut().push(value.inner().to_string());
+        self.label(label)
+    }
+
+    fn start_list(&self, _: &ListKind, label: &Option<Label>) -> crate::error::Result<()> {
+        self.label(label)
+    }
+
+    fn start_list_item(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.label(label)
+    }
+
+    fn start_definition_list(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.label(label)
+    }
+
+    fn start_definition(&self, _: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.label(label)
+    }
+
+    fn formatted(&self, _: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.label(label)
+    }
+
+    fn code_block(
+        &self,
+        _: &str,
+        _: &Option<String>,
+        _: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.label(label)
+    }
+
+    fn start_paragraph(
+        &self,
+        _: &crate::model::block::Alignment,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.label(label)
+    }
+
+    fn start_quote(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.label(label)
+    }
+
+    fn table_visitor(&self) -> Option<&dyn TableVisitor> {
+        Some(self)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl TableVisitor for Collector {
+    fn start_table(&self, _: &Option<Caption>, label: &Option<Label>) -> crate::error::Result<()> {
+        self.label(label)
+    }
+
+    fn start_table_cell(&self, _: usize, label: &Option<Label>) -> crate::error::Result<()> {
+        self.label(label)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl InlineVisitor for Collector {
+    fn image(&self, value: &Image) -> crate::error::Result<()> {
+        self.images.borrow_mut().push(value.inner().to_string());
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn split_chapters(doc: &Document) -> Vec<Chapter> {
+    let title = document_title(doc);
+    let top_level = doc
+        .inner()
+        .iter()
+        .filter_map(|block| match block {
+            BlockContent::Heading(heading) => Some(heading.level_as_u8()),
+            _ => None,
+        })
+        .min();
+
+    let mut chapters: Vec<Chapter> = Default::default();
+    let mut current = new_chapter(1, &title, false);
+    if let Some(abstract_block) = doc.abstract_block() {
+        let _ = current.document.add_abstract(abstract_block.clone());
+    }
+    for block in doc.inner() {
+        let mut block = block.clone();
+        if let BlockContent::Heading(heading) = &mut block {
+            if Some(heading.level_as_u8()) == top_level {
+                let carried = if current.is_empty() {
+                    current.document.inner().to_vec()
+                } else {
+                    chapters.push(current);
+                    Vec::default()
+                };
+                current = new_chapter(chapters.len() + 1, &heading.unformatted_string(), true);
+                for block in carried {
+                    let _ = current.document.add_content(block);
+                }
+            }
+            // every heading needs an anchor for the navigation document.
+            if !heading.has_label() {
+                let _ = heading.set_label(
+                    Label::from_str(&format!(
+                        "chapter-{}-heading-{}",
+                        chapters.len() + 1,
+                        current.document.inner().len() + 1
+                    ))
+                    .unwrap(),
+                );
+            }
+        }
+        let _ = current.document.add_content(block);
+    }
+    if !current.is_empty() || chapters.is_empty() {
+        chapters.push(current);
+    }
+    chapters
+}
+
+fn new_chapter(number: usize, title: &str, has_heading: bool) -> Chapter {
+    let mut document = Document::default();
+    let _ = document.set_title(title);
+    Chapter {
+        title: title.to_string(),
+        file_name: format!("chapter-{}.xhtml", number),
+        document,
+        has_heading,
+    }
+}
+
+fn navigation_document(doc: &Document, chapters: &[Chapter]) -> String {
+    let top_level = chapters
+        .iter()
+        .flat_map(|chapter| chapter.document.inner())
+        .filter_map(|block| match block {
+            BlockContent::Heading(heading) => Some(heading.level_as_u8()),
+            _ => None,
+        })
+        .min()
+        .unwrap_or(1);
+    let mut entries: Vec<NavEntry> = Default::default();
+    for chapter in chapters {
+        if !chapter.has_heading {
+            entries.push(NavEntry {
+                level: top_level,
+                text: chapter.title.clone(),
+                href: chapter.file_name.clone(),
+            });
+        }
+        let mut first = true;
+        for block in chapter.document.inner() {
+            if let BlockContent::Heading(heading) = block {
+                entries.push(NavEntry {
+                    level: heading.level_as_u8(),
+                    text: heading.unformatted_string(),
+                    href: if first && chapter.has_heading {
+                        chapter.file_name.clone()
+                    } else {
+                        format!("{}#{}", chapter.file_name, heading_anchor(heading))
+                    },
+                });
+                first = false;
+            }
+        }
+    }
+
+    let mut nav = String::new();
+    let mut depth = 0;
+    for entry in &entries {
+        let level = ((entry.level - top_level) as usize + 1).min(depth + 1);
+        if level > depth {
+            nav.push_str(&format!("\n{}<ol>", indent(depth)));
+            depth = level;
+        } else {
+            nav.push_str("</li>");
+            while depth > level {
+                depth -= 1;
+                nav.push_str(&format!("\n{}</ol></li>", indent(depth)));
+            }
+        }
+        nav.push_str(&format!(
+            "\n{}<li><a href=\"{}\">{}</a>",
+            indent(depth),
+            escape(&entry.href),
+            escape(&entry.text)
+        ));
+    }
+    if depth > 0 {
+        nav.push_str("</li>");
+        while depth > 1 {
+            depth -= 1;
+            nav.push_str(&format!("\n{}</ol></li>", indent(depth)));
+        }
+        nav.push_str(&format!("\n{}</ol>", indent(0)));
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops">
+  <head>
+    <meta charset="UTF-8"/>
+    <title>{}</title>
+  </head>
+  <body>
+    <nav epub:type="toc" id="toc">
+      <h1>Contents</h1>{}
+    </nav>
+  </body>
+</html>
+"#,
+        escape(&document_title(doc)),
+        nav
+    )
+}
+
+fn package_document(
+    doc: &Document,
+    chapters: &[Chapter],
+    remote_images: &[bool],
+    images: &[PackagedImage],
+) -> String {
+    let mut metadata: Vec<String> = Default::default();
+    let property = |key: &str| {
+        doc.metadata().iter().find_map(|datum| match datum {
+            Metadata::Other(property) if property.key == key => Some(property.value.clone()),
+            _ => None,
+        })
+    };
+    let identifier = property("identifier").unwrap_or_else(|| {
+        let mut hasher = DefaultHasher::new();
+        document_title(doc).hash(&mut hasher);
+        for datum in doc.metadata() {
+            datum.value_string().hash(&mut hasher);
+        }
+        format!("urn:somedoc:{:016x}", hasher.finish())
+    });
+    metadata.push(format!(
+        "<dc:identifier id=\"pub-id\">{}</dc:identifier>",
+        escape(&identifier)
+    ));
+    metadata.push(format!(
+        "<dc:title>{}</dc:title>",
+        escape(&document_title(doc))
+    ));
+    metadata.push(format!(
+        "<dc:language>{}</dc:language>",
+        escape(
+            &property("language")
+                .or_else(|| property("lang"))
+                .unwrap_or_else(|| UNDETERMINED_LANGUAGE.to_string())
+        )
+    ));
+    let mut modified = property("modified");
+    for datum in doc.metadata() {
+        match datum {
+            Metadata::Author(author) => {
+                metadata.push(format!("<dc:creator>{}</dc:creator>", escape(&author.name)))
+            }
+            Metadata::Copyright(copyright) => metadata.push(format!(
+                "<dc:rights>Copyright {}{}{}</dc:rights>",
+                copyright.year,
+                match &copyright.organization {
+                    None => String::new(),
+                    Some(v) => format!(" {}", escape(v)),
+                },
+                match &copyright.comment {
+                    None => String::new(),
+                    Some(v) => format!(" ({})", escape(v)),
+                }
+            )),
+            Metadata::Date(date) => {
+                metadata.push(format!("<dc:date>{}</dc:date>", escape(date)));
+                if modified.is_none() && is_iso_date(date) {
+                    modified = Some(format!("{}T00:00:00Z", date));
+                }
+            }
+            Metadata::Keywords(keywords) => {
+                for keyword in keywords {
+                    metadata.push(format!("<dc:subject>{}</dc:subject>", escape(keyword)));
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(abstract_block) = doc.abstract_block() {
+        metadata.push(format!(
+            "<dc:description>{}</dc:description>",
+            escape(&abstract_block.unformatted_string())
+        ));
+    }
+    metadata.push(format!(
+        "<meta property=\"dcterms:modified\">{}</meta>",
+        escape(&modified.unwrap_or_else(now_timestamp))
+    ));
+
+    let mut manifest: Vec<String> = vec![format!(
+        "<item id=\"nav\" href=\"nav.xhtml\" media-type=\"{}\" properties=\"nav\"/>",
+        XHTML_MEDIA_TYPE
+    )];
+    for (chapter, has_remote) in chapters.iter().zip(remote_images) {
+        manifest.push(format!(
+            "<item id=\"{}\" href=\"{}\" media-type=\"{}\"{}/>",
+            item_id(&chapter.file_name),
+            chapter.file_name,
+            XHTML_MEDIA_TYPE,
+            if *has_remote {
+                " properties=\"remote-resources\""
+            } else {
+                ""
+            }
+        ));
+    }
+    for image in images {
+        manifest.push(format!(
+            "<item id=\"{}\" href=\"{}\" media-type=\"{}\"/>",
+            item_id(&image.file_name),
+            image.file_name,
+            image.media_type
+        ));
+    }
+
+    let spine: Vec<String> = chapters
+        .iter()
+        .map(|chapter| format!("<itemref idref=\"{}\"/>", item_id(&chapter.file_name)))
+        .collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="pub-id">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    {}
+  </metadata>
+  <manifest>
+    {}
+  </manifest>
+  <spine>
+    {}
+  </spine>
+</package>
+"#,
+        metadata.join("\n    "),
+        manifest.join("\n    "),
+        spine.join("\n    ")
+    )
+}
+
+fn document_title(doc: &Document) -> String {
+    doc.metadata()
+        .iter()
+        .find_map(|datum| match datum {
+            Metadata::Title(title) => Some(title.clone()),
+            _ => None,
+        })
+        .unwrap_or_else(|| "Untitled".to_string())
+}
+
+fn heading_anchor(heading: &Heading) -> String {
+    match heading.label() {
+        Some(label) => label.to_string(),
+        None => String::new(),
+    }
+}
+
+fn item_id(file_name: &str) -> String {
+    file_name
+        .trim_start_matches("images/")
+        .split('.')
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn is_local(path: &str) -> bool {
+    !(path.contains("://") || path.starts_with("data:"))
+}
+
+fn extension(path: &str) -> String {
+    match std::path::Path::new(path).extension() {
+        Some(extension) => format!(".{}", extension.to_string_lossy().to_lowercase()),
+        None => String::new(),
+    }
+}
+
+fn media_type(path: &str) -> &'static str {
+    match extension(path).as_str() {
+        ".png" => "image/png",
+        ".jpg" | ".jpeg" => "image/jpeg",
+        ".gif" => "image/gif",
+        ".svg" => "image/svg+xml",
+        ".webp" => "image/webp",
+        _ => "application/octet-stream",
+    }
+}
+
+fn is_iso_date(value: &str) -> bool {
+    value.len() == 10
+        && value.chars().enumerate().all(|(i, c)| {
+            if i == 4 || i == 7 {
+                c == '-'
+            } else {
+                c.is_ascii_digit()
+            }
+        })
+}
+
+fn now_timestamp() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    // convert days since the epoch into a civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let days = (seconds / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    let time = seconds % 86_400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        time / 3_600,
+        time % 3_600 / 60,
+        time % 60
+    )
+}
+
+fn indent(depth: usize) -> String {
+    format!("{: ^1$}", "", 6 + depth * 2)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
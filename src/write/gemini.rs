@@ -0,0 +1,415 @@
+/*!
+Write a document as [gemtext](https://geminiprotocol.net/docs/gemtext.gmi), the line-oriented
+markup used by the Gemini protocol.
+
+Gemtext has only headings, text lines, list items, quotes, preformatted blocks, and link lines, so
+the model is mapped onto these as follows.
+
+* The document title is written as a top-level heading, and the abstract as a text line.
+* `Heading` becomes a `#`, `##`, or `###` line, deeper levels are clamped to `###`.
+* `List` items become `*` lines, nested lists are flattened and ordered lists lose their numbers;
+  `DefinitionList` items become `*` lines of the form `term: definition`.
+* `Quote` content becomes `>` lines, nested quotes are flattened.
+* `CodeBlock`, `Formatted`, `MathBlock`, and `Table` become preformatted blocks, with the caption,
+  or the code language, as the alt text; tables are rendered as a text grid.
+* `ImageBlock` becomes a link line to the image.
+
+Gemtext has no inline markup, so text styles are dropped. Hyperlinks and images within a block are
+written as their caption, or alternate text, and the link itself is written as a `=>` line
+following the block; internal links have no target in Gemini and so are only written as text.
+Comments, front matter, and labels are not written.
+
+# Example
+
+```rust
+# use somedoc::model::Document;
+use somedoc::write::{OutputFormat, write_document_to_string};
+
+# fn make_some_document() -> Document { Document::default() }
+let doc = make_some_document();
+
+let doc_str = write_document_to_string(&doc, OutputFormat::Gemini).unwrap();
+println!("{}", doc_str);
+```
+
+*/
+
+use crate::model::block::{
+    Alignment, BlockContent, Caption, DefinitionList, HasAlignment, HasCaption, List, ListItem,
+    Table,
+};
+use crate::model::document::Metadata;
+use crate::model::inline::{Character, HasInlineContent, HyperLinkTarget, InlineContent};
+use crate::model::{Document, HasInnerContent};
+use crate::write::Writer;
+use std::cell::RefCell;
+use std::io::Write;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the Gemini writer structure, usually this is accessed via the `writer`
+/// function, but may be used directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use somedoc::model::Document;
+/// use somedoc::write::gemini::GeminiWriter;
+/// use somedoc::write::Writer;
+///
+/// # fn make_some_document() -> Document { Document::default() }
+/// let doc = make_some_document();
+/// let mut out = std::io::stdout();
+/// let writer = GeminiWriter::new(&mut out);
+/// assert!(writer.write_document(&doc).is_ok());
+/// ```
+///
+#[derive(Debug)]
+pub struct GeminiWriter<'a, W: Write> {
+    w: RefCell<&'a mut W>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A link pulled out of a block, to be written as a link line after it.
+///
+#[derive(Debug)]
+struct Link {
+    url: String,
+    text: Option<String>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the writer function for Gemini gemtext.
+///
+/// While this can be called directly it is most often used  by calling either
+/// [`model::write_document`](../fn.write_document.html) or
+/// [`model::write_document_to_string`](../fn.write_document_to_string.html).
+///
+#[inline]
+pub fn writer<W: Write>(doc: &Document, w: &mut W) -> crate::error::Result<()> {
+    let writer = GeminiWriter::new(w);
+    writer.write_document(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> Writer<'a, W> for GeminiWriter<'a, W> {
+    fn new(w: &'a mut W) -> Self {
+        Self {
+            w: RefCell::from(w),
+        }
+    }
+
+    fn write_document(&self, doc: &Document) -> crate::error::Result<()> {
+        write!(&mut self.w.borrow_mut(), "{}", document(doc))?;
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn document(doc: &Document) -> String {
+    let mut blocks: Vec<String> = Default::default();
+    for datum in doc.metadata() {
+        if let Metadata::Title(title) = datum {
+            blocks.push(format!("# {}", single_line(title)));
+        }
+    }
+    if let Some(abstract_block) = doc.abstract_block() {
+        let mut links: Vec<Link> = Default::default();
+        blocks.push(with_links(
+            text_lines(&inlines(abstract_block.inner(), &mut links)),
+            &links,
+        ));
+    }
+    blocks.extend(doc.inner().iter().filter_map(block));
+    if blocks.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n", blocks.join("\n\n"))
+    }
+}
+
+fn block(content: &BlockContent) -> Option<String> {
+    let mut links: Vec<Link> = Default::default();
+    let text = match content {
+        BlockContent::Comment(_) | BlockContent::FrontMatter(_) => return None,
+        BlockContent::Heading(v) => format!(
+            "{} {}",
+            "#".repeat(v.level_as_u8().min(3) as usize),
+            single_line(&inlines(v.inner(), &mut links))
+        ),
+        BlockContent::ImageBlock(v) => {
+            let image = v.inner();
+            link_line(&Link {
+                url: image.inner().to_string(),
+                text: match (v.caption(), image.alt_text()) {
+                    (Some(caption), _) => Some(caption.to_string()),
+                    (None, alt_text) => alt_text.clone(),
+                },
+            })
+        }
+        BlockContent::MathBlock(v) => preformatted(v.inner(), v.caption(), &None),
+        BlockContent::List(v) => list(v, &mut links).join("\n"),
+        BlockContent::DefinitionList(v) => definition_list(v, &mut links),
+        BlockContent::Formatted(v) => preformatted(v.inner(), &None, &None),
+        BlockContent::CodeBlock(v) => preformatted(v.code(), v.caption(), v.language()),
+        BlockContent::Paragraph(v) => text_lines(&inlines(v.inner(), &mut links)),
+        BlockContent::Quote(v) => quote(v.inner(), &mut links),
+        BlockContent::Table(v) => preformatted(&table(v), v.caption(), &None),
+        BlockContent::ThematicBreak => "---".to_string(),
+    };
+    Some(with_links(text, &links))
+}
+
+fn with_links(text: String, links: &[Link]) -> String {
+    if links.is_empty() {
+        text
+    } else {
+        let mut lines = vec![text];
+        lines.extend(links.iter().map(link_line));
+        lines.join("\n")
+    }
+}
+
+fn link_line(link: &Link) -> String {
+    match &link.text {
+        Some(text) if !text.trim().is_empty() => format!("=> {} {}", link.url, single_line(text)),
+        _ => format!("=> {}", link.url),
+    }
+}
+
+fn preformatted(content: &str, caption: &Option<Caption>, language: &Option<String>) -> String {
+    let alt_text = match (caption, language) {
+        (Some(caption), _) => single_line(caption),
+        (None, Some(language)) => language.to_string(),
+        (None, None) => String::new(),
+    };
+    let mut lines = vec![format!("```{}", alt_text)];
+    lines.extend(
+        content
+            .trim_end_matches('\n')
+            .lines()
+            .map(|line| escape_line(line, true)),
+    );
+    lines.push("```".to_string());
+    lines.join("\n")
+}
+
+fn list(list: &List, links: &mut Vec<Link>) -> Vec<String> {
+    let mut lines: Vec<String> = Default::default();
+    for item in list.inner() {
+        match item {
+            ListItem::Item(item) => {
+                lines.push(format!("* {}", single_line(&inlines(item.inner(), links))))
+            }
+            ListItem::List(sub_list) => lines.extend(self::list(sub_list, links)),
+        }
+    }
+    lines
+}
+
+fn definition_list(list: &DefinitionList, links: &mut Vec<Link>) -> String {
+    list.inner()
+        .iter()
+        .map(|definition| {
+            format!(
+                "* {}: {}",
+                single_line(definition.term()),
+                single_line(&inlines(definition.text().inner(), links))
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+///
+/// Gemtext quote lines do not nest, and cannot contain other line types, so the content of any
+/// quotes within this one is written as quote lines as well.
+///
+fn quote(content: &[BlockContent], links: &mut Vec<Link>) -> String {
+    let mut lines: Vec<String> = Default::default();
+    for block in content {
+        let text = match block {
+            BlockContent::Comment(_) | BlockContent::FrontMatter(_) => continue,
+            BlockContent::Quote(v) => quote(v.inner(), links),
+            BlockContent::Paragraph(v) => text_lines(&inlines(v.inner(), links)),
+            BlockContent::List(v) => list(v, links).join("\n"),
+            _ => match self::block(block) {
+                Some(text) => text,
+                None => continue,
+            },
+        };
+        if !lines.is_empty() {
+            lines.push(">".to_string());
+        }
+        lines.extend(text.lines().map(|line| {
+            if line.starts_with('>') {
+                line.to_string()
+            } else {
+                format!("> {}", line)
+            }
+        }));
+    }
+    lines.join("\n")
+}
+
+///
+/// Render a table as a text grid, for a preformatted block.
+///
+fn table(table: &Table) -> String {
+    let alignments: Vec<&Alignment> = table.columns().iter().map(|c| c.alignment()).collect();
+    let mut rows: Vec<Vec<String>> = vec![table
+        .columns()
+        .iter()
+        .map(|column| column.text().to_string())
+        .collect()];
+    for row in table.rows() {
+        rows.push(
+            row.cells()
+                .iter()
+                .map(|cell| single_line(&cell.unformatted_string()))
+                .collect(),
+        );
+    }
+    let mut widths = vec![0; alignments.len()];
+    for row in &rows {
+        for (i, cell) in row.iter().enumerate().take(widths.len()) {
+            widths[i] = widths[i].max(cell.chars().count());
+        }
+    }
+    let rule = format!(
+        "+{}+",
+        widths
+            .iter()
+            .map(|width| "-".repeat(width + 2))
+            .collect::<Vec<String>>()
+            .join("+")
+    );
+    let line = |row: &[String]| {
+        let cells: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(i, width)| {
+                let cell = row.get(i).map(String::as_str).unwrap_or_default();
+                let padding = width - cell.chars().count();
+                match alignments[i] {
+                    Alignment::Right => format!("{}{}", " ".repeat(padding), cell),
+                    Alignment::Centered => format!(
+                        "{}{}{}",
+                        " ".repeat(padding / 2),
+                        cell,
+                        " ".repeat(padding - padding / 2)
+                    ),
+                    _ => format!("{}{}", cell, " ".repeat(padding)),
+                }
+            })
+            .collect();
+        format!("| {} |", cells.join(" | "))
+    };
+    let mut lines: Vec<String> = vec![rule.clone(), line(&rows[0]), rule.clone()];
+    lines.extend(rows[1..].iter().map(|row| line(row)));
+    lines.push(rule);
+    lines.join("\n")
+}
+
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Write inline content as plain text, collecting any links into `links`.
+///
+fn inlines(content: &[InlineContent], links: &mut Vec<Link>) -> String {
+    let mut s = String::new();
+    for inline in content {
+        match inline {
+            InlineContent::HyperLink(v) => {
+                let text = v.caption().as_ref().map(|caption| caption.to_string());
+                match v.target() {
+                    HyperLinkTarget::External(url) => {
+                        s.push_str(text.as_deref().unwrap_or(url));
+                        links.push(Link {
+                            url: url.to_string(),
+                            text,
+                        });
+                    }
+                    HyperLinkTarget::Internal(label) => match text {
+                        Some(text) => s.push_str(&text),
+                        None => s.push_str(&label.to_string()),
+                    },
+                }
+            }
+            InlineContent::Image(v) => {
+                if let Some(alt_text) = v.alt_text() {
+                    s.push_str(alt_text);
+                }
+                links.push(Link {
+                    url: v.inner().to_string(),
+                    text: v.alt_text().clone(),
+                });
+            }
+            InlineContent::Text(v) => s.push_str(v.inner()),
+            InlineContent::Math(v) => s.push_str(v.inner()),
+            InlineContent::Character(v) => match v {
+                Character::Space => s.push(' '),
+                Character::NonBreakSpace => s.push('\u{a0}'),
+                Character::Hyphen => s.push('-'),
+                Character::EmDash => s.push('\u{2014}'),
+                Character::EnDash => s.push('\u{2013}'),
+                Character::Emoji(name) => s.push_str(&format!(":{}:", name.inner())),
+                Character::Other(c) => s.push(*c),
+            },
+            InlineContent::LineBreak => s.push('\n'),
+            InlineContent::Span(v) => s.push_str(&inlines(v.inner(), links)),
+        }
+    }
+    s
+}
+
+///
+/// Each line of a paragraph becomes a text line, escaped so that it is not read as another line
+/// type.
+///
+fn text_lines(s: &str) -> String {
+    s.lines()
+        .map(|line| escape_line(line, false))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn single_line(s: &str) -> String {
+    s.trim_end_matches('\n').replace('\n', " ")
+}
+
+///
+/// Gemtext has no escape mechanism, lines that would be read as another line type are indented
+/// by a single space instead.
+///
+fn escape_line(line: &str, preformatted: bool) -> String {
+    let is_markup = if preformatted {
+        line.starts_with("```")
+    } else {
+        ["#", "* ", ">", "=>", "```"]
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+    };
+    if is_markup {
+        format!(" {}", line)
+    } else {
+        line.to_string()
+    }
+}
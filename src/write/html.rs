@@ -26,7 +26,9 @@ use crate::model::visitor::{
 use crate::model::Document;
 use crate::write::Writer;
 use regex::Regex;
+use std::borrow::Cow;
 use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
 use std::io::Write;
 
 // ------------------------------------------------------------------------------------------------
@@ -57,9 +59,23 @@ pub struct HtmlWriter<'a, W: Write> {
     state: RefCell<State>,
     indent: RefCell<usize>,
     list_level: RefCell<usize>,
+    table_body: RefCell<bool>,
+    xhtml: Option<XhtmlOptions>,
     w: RefCell<&'a mut W>,
 }
 
+///
+/// Options for writing well-formed XHTML rather than HTML, as used by the EPUB writer for its
+/// content documents. No remote scripts or styles are referenced, image paths may be replaced
+/// with their packaged location, and links to labels may be qualified with the name of the
+/// content document that contains them.
+///
+#[derive(Clone, Debug, Default)]
+pub(crate) struct XhtmlOptions {
+    pub(crate) images: HashMap<String, String>,
+    pub(crate) anchors: HashMap<String, String>,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Private Types
 // ------------------------------------------------------------------------------------------------
@@ -71,6 +87,8 @@ enum State {
     Body,
 }
 
+const XHTML_PROLOG: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE html>";
+
 // ------------------------------------------------------------------------------------------------
 // Public Functions
 // ------------------------------------------------------------------------------------------------
@@ -102,6 +120,8 @@ impl<'a, W: Write> Writer<'a, W> for HtmlWriter<'a, W> {
             state: RefCell::from(State::Empty),
             indent: RefCell::new(0),
             list_level: RefCell::from(0),
+            table_body: RefCell::from(false),
+            xhtml: None,
             w: RefCell::from(w),
         }
     }
@@ -113,14 +133,46 @@ impl<'a, W: Write> Writer<'a, W> for HtmlWriter<'a, W> {
 }
 
 impl<'a, W: Write> HtmlWriter<'a, W> {
+    #[cfg(feature = "fmt_epub")]
+    pub(crate) fn new_xhtml(w: &'a mut W, options: XhtmlOptions) -> Self {
+        Self {
+            xhtml: Some(options),
+            ..Self::new(w)
+        }
+    }
+
     fn meta_tag(
         &self,
         w: &mut RefMut<'_, &'a mut W>,
         name: &str,
         content: &str,
     ) -> crate::error::Result<()> {
-        self.start_tag_with(w, "meta", &[("name", name), ("content", content)], true)?;
-        self.end_line(w)
+        if self.xhtml.is_some() {
+            self.closed_tag_with(
+                w,
+                "meta",
+                &[("name", name), ("content", content)],
+                true,
+                true,
+            )
+        } else {
+            self.start_tag_with(w, "meta", &[("name", name), ("content", content)], true)?;
+            self.end_line(w)
+        }
+    }
+
+    fn escaped<'s>(&self, value: &'s str) -> Cow<'s, str> {
+        if self.xhtml.is_some() && value.contains(['&', '<', '>', '"']) {
+            Cow::Owned(
+                value
+                    .replace('&', "&amp;")
+                    .replace('<', "&lt;")
+                    .replace('>', "&gt;")
+                    .replace('"', "&quot;"),
+            )
+        } else {
+            Cow::Borrowed(value)
+        }
     }
 
     fn start_tag(
@@ -167,7 +219,7 @@ impl<'a, W: Write> HtmlWriter<'a, W> {
                 tag,
                 attributes
                     .iter()
-                    .map(|(k, v)| format!("{}=\"{}\"", k, v))
+                    .map(|(k, v)| format!("{}=\"{}\"", k, self.escaped(v)))
                     .collect::<Vec<String>>()
                     .join(" ")
             ),
@@ -222,7 +274,7 @@ impl<'a, W: Write> HtmlWriter<'a, W> {
                 tag,
                 attributes
                     .iter()
-                    .map(|(k, v)| format!("{}=\"{}\"", k, v))
+                    .map(|(k, v)| format!("{}=\"{}\"", k, self.escaped(v)))
                     .collect::<Vec<String>>()
                     .join(" ")
             ),
@@ -266,6 +318,25 @@ impl<'a, W: Write> HtmlWriter<'a, W> {
 impl<'a, W: Write> DocumentVisitor for HtmlWriter<'a, W> {
     fn start_document(&self) -> crate::error::Result<()> {
         let mut w = self.w.borrow_mut();
+        if self.xhtml.is_some() {
+            self.write(&mut w, XHTML_PROLOG)?;
+            self.end_line(&mut w)?;
+            self.start_tag_with(
+                &mut w,
+                "html",
+                &[
+                    ("xmlns", "http://www.w3.org/1999/xhtml"),
+                    ("xmlns:epub", "http://www.idpf.org/2007/ops"),
+                ],
+                false,
+            )?;
+            self.indent(&mut w)?;
+            self.start_tag(&mut w, "head", true)?;
+            self.indent(&mut w)?;
+            self.closed_tag_with(&mut w, "meta", &[("charset", "UTF-8")], true, true)?;
+            *self.state.borrow_mut() = State::Head;
+            return Ok(());
+        }
         self.start_tag(&mut w, "html", false)?;
         self.indent(&mut w)?;
 
@@ -363,7 +434,7 @@ impl<'a, W: Write> DocumentVisitor for HtmlWriter<'a, W> {
             }
             Metadata::Title(v) => {
                 self.start_tag(&mut w, "title", true)?;
-                self.write(&mut w, v)?;
+                self.write(&mut w, &self.escaped(v))?;
                 self.end_tag(&mut w, "title", true)?;
             }
             Metadata::Other(v) => {
@@ -417,9 +488,26 @@ impl<'a, W: Write> BlockVisitor for HtmlWriter<'a, W> {
         Ok(())
     }
 
+    fn start_abstract(&self) -> crate::error::Result<()> {
+        self.start_tag_with(
+            &mut self.w.borrow_mut(),
+            "p",
+            &[("class", "abstract")],
+            true,
+        )
+    }
+
+    fn end_abstract(&self) -> crate::error::Result<()> {
+        self.end_tag(&mut self.w.borrow_mut(), "p", true)
+    }
+
     fn comment(&self, value: &str) -> crate::error::Result<()> {
         let mut w = self.w.borrow_mut();
-        write!(w, "<!-- {} -->", value)?;
+        if self.xhtml.is_some() {
+            write!(w, "<!-- {} -->", value.replace("--", "- -"))?;
+        } else {
+            write!(w, "<!-- {} -->", value)?;
+        }
         self.end_line(&mut w)
     }
 
@@ -467,7 +555,7 @@ impl<'a, W: Write> BlockVisitor for HtmlWriter<'a, W> {
     ) -> crate::error::Result<()> {
         let mut w = self.w.borrow_mut();
         self.start_tag_labeled(&mut w, "div", label, true)?;
-        self.write(&mut w, &format!("\\[ {} \\]", value.inner()))?;
+        self.write(&mut w, &format!("\\[ {} \\]", self.escaped(value.inner())))?;
         self.end_tag(&mut w, "div", true)
     }
 
@@ -536,7 +624,7 @@ impl<'a, W: Write> BlockVisitor for HtmlWriter<'a, W> {
     fn start_definition(&self, term: &str, label: &Option<Label>) -> crate::error::Result<()> {
         let mut w = self.w.borrow_mut();
         self.start_tag_labeled(&mut w, "dt", label, true)?;
-        self.write(&mut w, term)?;
+        self.write(&mut w, &self.escaped(term))?;
         self.end_tag(&mut w, "dt", true)
     }
 
@@ -551,7 +639,7 @@ impl<'a, W: Write> BlockVisitor for HtmlWriter<'a, W> {
     fn formatted(&self, value: &str, label: &Option<Label>) -> crate::error::Result<()> {
         let mut w = self.w.borrow_mut();
         self.start_tag_labeled(&mut w, "pre", label, true)?;
-        self.write(&mut w, &format!("{}\n", value))?;
+        self.write(&mut w, &format!("{}\n", self.escaped(value)))?;
         self.start_line(&mut w)?;
         self.end_tag(&mut w, "pre", true)
     }
@@ -574,7 +662,7 @@ impl<'a, W: Write> BlockVisitor for HtmlWriter<'a, W> {
             self.start_tag_labeled(&mut w, "code", label, false)?;
         }
 
-        self.write(&mut w, &format!("{}\n", code))?;
+        self.write(&mut w, &format!("{}\n", self.escaped(code)))?;
 
         self.start_line(&mut w)?;
         self.end_tag(&mut w, "code", true)?;
@@ -644,7 +732,7 @@ impl<'a, W: Write> TableVisitor for HtmlWriter<'a, W> {
         self.indent(&mut w)?;
         if let Some(caption) = caption {
             self.start_tag(&mut w, "caption", true)?;
-            self.write(&mut w, caption)?;
+            self.write(&mut w, &self.escaped(caption))?;
             self.end_tag(&mut w, "caption", true)?;
         }
         Ok(())
@@ -661,7 +749,7 @@ impl<'a, W: Write> TableVisitor for HtmlWriter<'a, W> {
     fn table_header_cell(&self, column_cell: &Column, _: usize) -> crate::error::Result<()> {
         self.write(
             &mut self.w.borrow_mut(),
-            &format!("<th>{}</th>", column_cell.text()),
+            &format!("<th>{}</th>", self.escaped(column_cell.text())),
         )
     }
 
@@ -670,13 +758,15 @@ impl<'a, W: Write> TableVisitor for HtmlWriter<'a, W> {
         self.end_tag(&mut w, "tr", true)?;
         self.outdent(&mut w)?;
         self.start_line(&mut w)?;
-        self.end_tag(&mut w, "thead", true)?;
-        self.start_line(&mut w)?;
-        self.end_tag(&mut w, "tbody", true)
+        self.end_tag(&mut w, "thead", true)
     }
 
     fn start_table_row(&self, _: usize) -> crate::error::Result<()> {
         let mut w = self.w.borrow_mut();
+        if !self.table_body.replace(true) {
+            self.start_tag(&mut w, "tbody", true)?;
+            self.end_line(&mut w)?;
+        }
         self.indent_no_newline()?;
         self.start_line(&mut w)?;
         self.start_tag(&mut w, "tr", false)
@@ -698,8 +788,10 @@ impl<'a, W: Write> TableVisitor for HtmlWriter<'a, W> {
 
     fn end_table(&self, _: &Option<Caption>, _label: &Option<Label>) -> crate::error::Result<()> {
         let mut w = self.w.borrow_mut();
-        self.start_line(&mut w)?;
-        self.end_tag(&mut w, "tbody", true)?;
+        if self.table_body.replace(false) {
+            self.start_line(&mut w)?;
+            self.end_tag(&mut w, "tbody", true)?;
+        }
         self.outdent(&mut w)?;
         self.start_line(&mut w)?;
         self.end_tag(&mut w, "table", true)
@@ -712,32 +804,38 @@ impl<'a, W: Write> TableVisitor for HtmlWriter<'a, W> {
 
 impl<'a, W: Write> InlineVisitor for HtmlWriter<'a, W> {
     fn link(&self, value: &HyperLink) -> crate::error::Result<()> {
+        let href = match value.target() {
+            HyperLinkTarget::External(v) => v.to_string(),
+            HyperLinkTarget::Internal(v) => {
+                let anchor = self.anchor_id(v);
+                match self
+                    .xhtml
+                    .as_ref()
+                    .and_then(|options| options.anchors.get(&anchor))
+                {
+                    Some(file_name) => format!("{}#{}", file_name, anchor),
+                    None => format!("#{}", anchor),
+                }
+            }
+        };
         if let Some(caption) = value.caption() {
-            self.start_tag_with(
-                &mut self.w.borrow_mut(),
-                "a",
-                &[(
-                    "href",
-                    &match value.target() {
-                        HyperLinkTarget::External(v) => v.to_string(),
-                        HyperLinkTarget::Internal(v) => format!("#{}", self.anchor_id(v)),
-                    },
-                )],
-                false,
-            )?;
-            self.write(&mut self.w.borrow_mut(), caption.inner())?;
+            self.start_tag_with(&mut self.w.borrow_mut(), "a", &[("href", &href)], false)?;
+            self.write(&mut self.w.borrow_mut(), &self.escaped(caption.inner()))?;
+            self.end_tag(&mut self.w.borrow_mut(), "a", false)
+        } else if self.xhtml.is_some() {
+            // an empty anchor is valid XHTML, but it would not be displayed.
+            self.start_tag_with(&mut self.w.borrow_mut(), "a", &[("href", &href)], false)?;
+            let text = match value.target() {
+                HyperLinkTarget::External(v) => v.to_string(),
+                HyperLinkTarget::Internal(v) => v.to_string(),
+            };
+            self.write(&mut self.w.borrow_mut(), &self.escaped(&text))?;
             self.end_tag(&mut self.w.borrow_mut(), "a", false)
         } else {
             self.closed_tag_with(
                 &mut self.w.borrow_mut(),
                 "a",
-                &[(
-                    "href",
-                    &match value.target() {
-                        HyperLinkTarget::External(v) => v.to_string(),
-                        HyperLinkTarget::Internal(v) => format!("#{}", self.anchor_id(v)),
-                    },
-                )],
+                &[("href", &href)],
                 false,
                 false,
             )
@@ -745,27 +843,60 @@ impl<'a, W: Write> InlineVisitor for HtmlWriter<'a, W> {
     }
 
     fn image(&self, value: &Image) -> crate::error::Result<()> {
-        self.closed_tag_with(
-            &mut self.w.borrow_mut(),
-            "img",
-            &[("src", &value.inner())],
-            false,
-            false,
-        )
+        match &self.xhtml {
+            Some(options) => self.closed_tag_with(
+                &mut self.w.borrow_mut(),
+                "img",
+                &[
+                    (
+                        "src",
+                        options
+                            .images
+                            .get(value.inner())
+                            .unwrap_or_else(|| value.inner()),
+                    ),
+                    ("alt", value.alt_text().as_deref().unwrap_or_default()),
+                ],
+                false,
+                false,
+            ),
+            None => self.closed_tag_with(
+                &mut self.w.borrow_mut(),
+                "img",
+                &[("src", &value.inner())],
+                false,
+                false,
+            ),
+        }
     }
 
     fn text(&self, value: &Text) -> crate::error::Result<()> {
-        self.write(&mut self.w.borrow_mut(), value)
+        self.write(&mut self.w.borrow_mut(), &self.escaped(value))
     }
 
     fn math(&self, value: &Math) -> crate::error::Result<()> {
         self.write(
             &mut self.w.borrow_mut(),
-            &format!("\\( {} \\)", value.inner()),
+            &format!("\\( {} \\)", self.escaped(value.inner())),
         )
     }
 
     fn character(&self, value: &Character) -> crate::error::Result<()> {
+        if self.xhtml.is_some() {
+            // XML only defines the five predefined entities, so use character references.
+            return self.write(
+                &mut self.w.borrow_mut(),
+                &match value {
+                    Character::Space => " ".to_string(),
+                    Character::NonBreakSpace => "&#160;".to_string(),
+                    Character::Hyphen => "-".to_string(),
+                    Character::EmDash => "&#8212;".to_string(),
+                    Character::EnDash => "&#8211;".to_string(),
+                    Character::Emoji(v) => self.escaped(v.inner()).to_string(),
+                    Character::Other(v) => self.escaped(&v.to_string()).to_string(),
+                },
+            );
+        }
         self.write(
             &mut self.w.borrow_mut(),
             &match value {
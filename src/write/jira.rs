@@ -0,0 +1,529 @@
+/*!
+Write a document in the [Jira](https://jira.atlassian.com/secure/WikiRendererHelpAction.jspa)
+wiki markup used by Jira Server and Data Center for descriptions and comments.
+
+Labels are written as `{anchor}` macros, and internal links target them with `[caption|#label]`.
+Jira has no markup for paragraph alignment, definition lists, nested quotes, or table captions,
+so these are approximated. Local images are written as references to issue attachments with the
+same file name. Document metadata is not written.
+
+# Example
+
+```rust
+# use somedoc::model::Document;
+use somedoc::write::jira::writer;
+
+# fn make_some_document() -> Document { Document::default() }
+let doc = make_some_document();
+
+writer(&doc, &mut std::io::stdout()).unwrap();
+```
+*/
+
+use crate::model::block::{Alignment, Caption, Column, HasCaption, HeadingLevel, Label, ListKind};
+use crate::model::inline::{Character, HyperLink, HyperLinkTarget, Image, Math, SpanStyle, Text};
+use crate::model::visitor::{
+    walk_document, BlockVisitor, DocumentVisitor, InlineVisitor, TableVisitor,
+};
+use crate::model::Document;
+use crate::write::Writer;
+use std::cell::RefCell;
+use std::io::Write;
+use std::path::Path;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the Jira writer structure, usually this is accessed via the `writer`
+/// function, but may be used directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use somedoc::model::Document;
+/// use somedoc::write::jira::JiraWriter;
+/// use somedoc::write::Writer;
+///
+/// # fn make_some_document() -> Document { Document::default() }
+/// let doc = make_some_document();
+/// let mut out = std::io::stdout();
+/// let writer = JiraWriter::new(&mut out);
+/// assert!(writer.write_document(&doc).is_ok());
+/// ```
+///
+#[derive(Debug)]
+pub struct JiraWriter<'a, W: Write> {
+    list_stack: RefCell<Vec<ListKind>>,
+    quote_depth: RefCell<usize>,
+    block_written: RefCell<bool>,
+    blank_pending: RefCell<bool>,
+    line_start: RefCell<bool>,
+    w: RefCell<&'a mut W>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the writer function for Jira.
+///
+/// While this can be called directly it is most often used  by calling either
+/// [`model::write_document`](../fn.write_document.html) or
+/// [`model::write_document_to_string`](../fn.write_document_to_string.html).
+///
+#[inline]
+pub fn writer<W: Write>(doc: &Document, w: &mut W) -> crate::error::Result<()> {
+    let writer = JiraWriter::new(w);
+    writer.write_document(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> Writer<'a, W> for JiraWriter<'a, W> {
+    fn new(w: &'a mut W) -> Self {
+        Self {
+            list_stack: RefCell::from(Vec::default()),
+            quote_depth: RefCell::from(0),
+            block_written: RefCell::from(false),
+            blank_pending: RefCell::from(false),
+            line_start: RefCell::from(true),
+            w: RefCell::from(w),
+        }
+    }
+
+    fn write_document(&self, doc: &Document) -> crate::error::Result<()> {
+        walk_document(doc, self)?;
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> JiraWriter<'a, W> {
+    fn write(&self, text: &str) -> crate::error::Result<()> {
+        if self.blank_pending.replace(false) {
+            writeln!(&mut self.w.borrow_mut())?;
+        }
+        write!(&mut self.w.borrow_mut(), "{}", text)?;
+        let _ = self.block_written.replace(true);
+        if !text.is_empty() {
+            let _ = self.line_start.replace(text.ends_with('\n'));
+        }
+        Ok(())
+    }
+
+    fn write_line(&self, text: &str) -> crate::error::Result<()> {
+        self.write(text)?;
+        self.write("\n")
+    }
+
+    fn write_anchor(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        if let Some(label) = label {
+            self.write(&format!("{{anchor:{}}}", label))?;
+        }
+        Ok(())
+    }
+
+    fn write_anchor_line(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        if label.is_some() {
+            self.write_anchor(label)?;
+            self.write("\n")?;
+        }
+        Ok(())
+    }
+
+    fn write_caption(&self, caption: &Option<Caption>) -> crate::error::Result<()> {
+        if let Some(caption) = caption {
+            self.write_line(&format!("_{}_", escape(caption.inner())))?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, W: Write> DocumentVisitor for JiraWriter<'a, W> {
+    fn block_visitor(&self) -> Option<&dyn BlockVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> BlockVisitor for JiraWriter<'a, W> {
+    fn start_block(&self) -> crate::error::Result<()> {
+        // blocks are separated by a blank line, but only between blocks that write something.
+        if self.block_written.replace(false) {
+            let _ = self.blank_pending.replace(true);
+        }
+        Ok(())
+    }
+
+    fn start_abstract(&self) -> crate::error::Result<()> {
+        self.write_line("{panel}")
+    }
+
+    fn end_abstract(&self) -> crate::error::Result<()> {
+        self.write("\n")?;
+        self.write_line("{panel}")
+    }
+
+    fn start_heading(
+        &self,
+        level: &HeadingLevel,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        // Jira, like HTML, has no seventh heading level.
+        self.write(&format!("h{}. ", (level.clone() as u8).min(6)))?;
+        self.write_anchor(label)
+    }
+
+    fn end_heading(&self, _: &HeadingLevel, _: &Option<Label>) -> crate::error::Result<()> {
+        self.write("\n")
+    }
+
+    fn image(
+        &self,
+        value: &Image,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_anchor_line(label)?;
+        self.write_line(&image_text(value))?;
+        self.write_caption(caption)
+    }
+
+    fn math(
+        &self,
+        value: &Math,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_anchor_line(label)?;
+        self.write_line(&format!("{{noformat}}\n{}\n{{noformat}}", value.inner()))?;
+        self.write_caption(caption)
+    }
+
+    fn start_list(&self, kind: &ListKind, label: &Option<Label>) -> crate::error::Result<()> {
+        if self.list_stack.borrow().is_empty() {
+            self.write_anchor_line(label)?;
+        }
+        self.list_stack.borrow_mut().push(kind.clone());
+        Ok(())
+    }
+
+    fn end_list(&self, _: &ListKind, _: &Option<Label>) -> crate::error::Result<()> {
+        let _ = self.list_stack.borrow_mut().pop();
+        Ok(())
+    }
+
+    fn start_list_item(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        let prefix: String = self
+            .list_stack
+            .borrow()
+            .iter()
+            .map(|kind| match kind {
+                ListKind::Ordered => '#',
+                ListKind::Unordered => '*',
+            })
+            .collect();
+        self.write(&format!("{} ", prefix))?;
+        self.write_anchor(label)
+    }
+
+    fn end_list_item(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.write("\n")
+    }
+
+    fn start_definition_list(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_anchor_line(label)
+    }
+
+    fn start_definition(&self, term: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        // there are no definition lists, so the term is bold and the definition indented.
+        self.write_anchor(label)?;
+        self.write_line(&format!("*{}*", escape(term)))
+    }
+
+    fn start_definition_list_text(&self) -> crate::error::Result<()> {
+        self.write("bq. ")
+    }
+
+    fn end_definition_list_text(&self) -> crate::error::Result<()> {
+        self.write("\n")
+    }
+
+    fn formatted(&self, value: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_anchor_line(label)?;
+        self.write_line(&format!("{{noformat}}\n{}\n{{noformat}}", value))
+    }
+
+    fn code_block(
+        &self,
+        code: &str,
+        language: &Option<String>,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_anchor_line(label)?;
+        let parameters = match (language, caption) {
+            (Some(language), None) => format!(":{}", language),
+            (Some(language), Some(caption)) => {
+                format!(":title={}|language={}", macro_parameter(caption), language)
+            }
+            (None, Some(caption)) => format!(":title={}", macro_parameter(caption)),
+            (None, None) => String::new(),
+        };
+        self.write_line(&format!("{{code{}}}\n{}\n{{code}}", parameters, code))
+    }
+
+    fn start_paragraph(&self, _: &Alignment, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_anchor(label)
+    }
+
+    fn end_paragraph(&self, _: &Alignment, _: &Option<Label>) -> crate::error::Result<()> {
+        self.write("\n")
+    }
+
+    fn start_quote(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_anchor_line(label)?;
+        // quotes do not nest, the content of an inner quote is part of the outer one.
+        let depth = self.quote_depth.replace_with(|depth| *depth + 1);
+        if depth == 0 {
+            self.write_line("{quote}")?;
+            let _ = self.block_written.replace(false);
+        }
+        Ok(())
+    }
+
+    fn end_quote(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        let depth = self.quote_depth.replace_with(|depth| *depth - 1);
+        if depth == 1 {
+            self.write_line("{quote}")?;
+        }
+        Ok(())
+    }
+
+    fn thematic_break(&self) -> crate::error::Result<()> {
+        self.write_line("----")
+    }
+
+    fn table_visitor(&self) -> Option<&dyn TableVisitor> {
+        Some(self)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> TableVisitor for JiraWriter<'a, W> {
+    fn start_table(&self, _: &Option<Caption>, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_anchor_line(label)
+    }
+
+    fn start_table_header_row(&self) -> crate::error::Result<()> {
+        self.write("||")
+    }
+
+    fn table_header_cell(
+        &self,
+        column_cell: &Column,
+        _column_idx: usize,
+    ) -> crate::error::Result<()> {
+        self.write(&format!("{}||", escape(column_cell.text())))
+    }
+
+    fn end_table_header_row(&self) -> crate::error::Result<()> {
+        self.write("\n")
+    }
+
+    fn start_table_row(&self, _: usize) -> crate::error::Result<()> {
+        self.write("|")
+    }
+
+    fn start_table_cell(&self, _: usize, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_anchor(label)
+    }
+
+    fn end_table_cell(&self, _: usize, _: &Option<Label>) -> crate::error::Result<()> {
+        self.write("|")
+    }
+
+    fn end_table_row(&self, _: usize) -> crate::error::Result<()> {
+        self.write("\n")
+    }
+
+    fn end_table(&self, caption: &Option<Caption>, _: &Option<Label>) -> crate::error::Result<()> {
+        self.write_caption(caption)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> InlineVisitor for JiraWriter<'a, W> {
+    fn link(&self, value: &HyperLink) -> crate::error::Result<()> {
+        let target = match value.target() {
+            HyperLinkTarget::External(target) => target.clone(),
+            HyperLinkTarget::Internal(target) => format!("#{}", target),
+        };
+        match value.caption() {
+            Some(caption) => self.write(&format!("[{}|{}]", escape(caption.inner()), target)),
+            None => self.write(&format!("[{}]", target)),
+        }
+    }
+
+    fn image(&self, value: &Image) -> crate::error::Result<()> {
+        self.write(&image_text(value))
+    }
+
+    fn text(&self, value: &Text) -> crate::error::Result<()> {
+        let line_start = *self.line_start.borrow();
+        self.write(&escape_line_starts(&escape(value.inner()), line_start))
+    }
+
+    fn math(&self, value: &Math) -> crate::error::Result<()> {
+        self.write(&format!("{{{{{}}}}}", escape(value.inner())))
+    }
+
+    fn character(&self, value: &Character) -> crate::error::Result<()> {
+        self.write(&match value {
+            Character::Space => " ".to_string(),
+            Character::NonBreakSpace => "&nbsp;".to_string(),
+            Character::Hyphen => "-".to_string(),
+            Character::EmDash => "---".to_string(),
+            Character::EnDash => "--".to_string(),
+            Character::Emoji(name) => format!(":{}:", escape(name.inner())),
+            Character::Other(c) => escape(&c.to_string()),
+        })
+    }
+
+    fn line_break(&self) -> crate::error::Result<()> {
+        self.write("\\\\\n")
+    }
+
+    fn start_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        self.write(
+            &make_style_stack(styles)
+                .into_iter()
+                .map(|(start, _)| start)
+                .collect::<Vec<&str>>()
+                .join(""),
+        )
+    }
+
+    fn end_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        self.write(
+            &make_style_stack(styles)
+                .into_iter()
+                .rev()
+                .map(|(_, end)| end)
+                .collect::<Vec<&str>>()
+                .join(""),
+        )
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn make_style_stack(styles: &[SpanStyle]) -> Vec<(&'static str, &'static str)> {
+    let mut style_stack = Vec::new();
+    for style in styles {
+        match style {
+            SpanStyle::Plain => style_stack.clear(),
+            SpanStyle::Italic => style_stack.push(("_", "_")),
+            SpanStyle::Bold => style_stack.push(("*", "*")),
+            SpanStyle::Mono | SpanStyle::Code => style_stack.push(("{{", "}}")),
+            SpanStyle::Strikethrough => style_stack.push(("-", "-")),
+            SpanStyle::Underline => style_stack.push(("+", "+")),
+            SpanStyle::Superscript => style_stack.push(("^", "^")),
+            SpanStyle::Subscript => style_stack.push(("~", "~")),
+            _ => {}
+        }
+    }
+    style_stack
+}
+
+fn image_text(value: &Image) -> String {
+    let source = if is_local(value.inner()) {
+        Path::new(value.inner())
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| value.inner().to_string())
+    } else {
+        value.inner().to_string()
+    };
+    match value.alt_text() {
+        Some(alt_text) => format!("!{}|alt={}!", source, alt_text.replace(['!', '|'], "")),
+        None => format!("!{}!", source),
+    }
+}
+
+fn is_local(path: &str) -> bool {
+    !(path.contains("://") || path.starts_with("data:"))
+}
+
+///
+/// Characters that start or end markup are escaped with a backslash, a backslash itself would
+/// start a line break and so is written as an entity.
+///
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("&#92;"),
+            '*' | '_' | '-' | '+' | '^' | '~' | '{' | '}' | '[' | ']' | '|' | '!' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+///
+/// Escape the markers that only have meaning at the start of a line, headings and quotes by
+/// replacing their `.` with an entity, and numbered lists with a backslash; bulleted lists are
+/// already escaped by `escape`.
+///
+fn escape_line_starts(text: &str, line_start: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            escaped.push('\n');
+        }
+        let bytes = line.as_bytes();
+        if i == 0 && !line_start {
+            escaped.push_str(line);
+        } else if line.starts_with('#') {
+            escaped.push('\\');
+            escaped.push_str(line);
+        } else if line.starts_with("bq.")
+            || (bytes.len() >= 3
+                && bytes[0] == b'h'
+                && (b'1'..=b'6').contains(&bytes[1])
+                && bytes[2] == b'.')
+        {
+            escaped.push_str(&line[..2]);
+            escaped.push_str("&#46;");
+            escaped.push_str(&line[3..]);
+        } else {
+            escaped.push_str(line);
+        }
+    }
+    escaped
+}
+
+///
+/// Macro parameters are separated by `|` and the macro is closed by `}`, neither can be escaped
+/// and so both are removed.
+///
+fn macro_parameter(caption: &Caption) -> String {
+    caption.inner().replace(['|', '}'], "")
+}
@@ -1029,8 +1029,8 @@ impl<'a, W: Write> InlineVisitor for LatexWriter<'a, W> {
             Character::Space => " ".to_string(),
             Character::NonBreakSpace => "~".to_string(),
             Character::Hyphen => "-".to_string(),
-            Character::EmDash => "--".to_string(),
-            Character::EnDash => "---".to_string(),
+            Character::EmDash => "---".to_string(),
+            Character::EnDash => "--".to_string(),
             Character::Emoji(e) => format!("\\texttt{{{}}}", e),
             Character::Other(c) => format!("{}", c),
         })
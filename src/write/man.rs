@@ -0,0 +1,647 @@
+/*!
+Write a document as a Unix manual page, using the [man(7)](https://man7.org/linux/man-pages/man7/man.7.html)
+roff macros.
+
+The `.TH` title line is taken from the document's title, date, and revision metadata; the manual
+section defaults to `1` but may be set with an `Other` metadata property named `section`. Other
+metadata is written as roff comments. Tables use the tbl(1) preprocessor, which is requested by
+a `'\" t` first line.
+
+# Example
+
+```rust
+# use somedoc::model::Document;
+use somedoc::write::man::writer;
+
+# fn make_some_document() -> Document { Document::default() }
+let doc = make_some_document();
+
+writer(&doc, &mut std::io::stdout()).unwrap();
+```
+*/
+
+use crate::model::block::{
+    Alignment, BlockContent, Caption, Column, FrontMatter, HasAlignment, HasCaption, HeadingLevel,
+    Label, ListKind,
+};
+use crate::model::document::Metadata;
+use crate::model::inline::{Character, HyperLink, HyperLinkTarget, Image, Math, SpanStyle, Text};
+use crate::model::visitor::{
+    walk_document, BlockVisitor, DocumentVisitor, InlineVisitor, TableVisitor,
+};
+use crate::model::{Document, HasInnerContent};
+use crate::write::Writer;
+use std::cell::RefCell;
+use std::io::Write;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the man page writer structure, usually this is accessed via the `writer`
+/// function, but may be used directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use somedoc::model::Document;
+/// use somedoc::write::man::ManWriter;
+/// use somedoc::write::Writer;
+///
+/// # fn make_some_document() -> Document { Document::default() }
+/// let doc = make_some_document();
+/// let mut out = std::io::stdout();
+/// let writer = ManWriter::new(&mut out);
+/// assert!(writer.write_document(&doc).is_ok());
+/// ```
+///
+#[derive(Debug)]
+pub struct ManWriter<'a, W: Write> {
+    metadata: RefCell<Vec<Metadata>>,
+    list_stack: RefCell<Vec<(ListKind, usize)>>,
+    font_stack: RefCell<Vec<Font>>,
+    table: RefCell<Option<TableText>>,
+    in_heading: RefCell<bool>,
+    line_open: RefCell<bool>,
+    w: RefCell<&'a mut W>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Font {
+    bold: bool,
+    italic: bool,
+}
+
+///
+/// Table rows are buffered as tbl needs the column formats before any data.
+///
+#[derive(Debug, Default)]
+struct TableText {
+    columns: Vec<(String, Alignment)>,
+    cell_count: usize,
+    rows: String,
+}
+
+const DEFAULT_SECTION: &str = "1";
+
+const CODE_INDENT: &str = "4";
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the writer function for man pages.
+///
+/// While this can be called directly it is most often used  by calling either
+/// [`model::write_document`](../fn.write_document.html) or
+/// [`model::write_document_to_string`](../fn.write_document_to_string.html).
+///
+#[inline]
+pub fn writer<W: Write>(doc: &Document, w: &mut W) -> crate::error::Result<()> {
+    let writer = ManWriter::new(w);
+    writer.write_document(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> Writer<'a, W> for ManWriter<'a, W> {
+    fn new(w: &'a mut W) -> Self {
+        Self {
+            metadata: RefCell::from(Vec::default()),
+            list_stack: RefCell::from(Vec::default()),
+            font_stack: RefCell::from(Vec::default()),
+            table: RefCell::from(None),
+            in_heading: RefCell::from(false),
+            line_open: RefCell::from(false),
+            w: RefCell::from(w),
+        }
+    }
+
+    fn write_document(&self, doc: &Document) -> crate::error::Result<()> {
+        if has_table(doc.inner()) {
+            // tells man(1) to run the page through the tbl(1) preprocessor.
+            self.request("'\\\" t")?;
+        }
+        walk_document(doc, self)?;
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> ManWriter<'a, W> {
+    ///
+    /// Write `text` verbatim, used for requests and macros; control lines must always start on
+    /// a new line, so use `request` for those.
+    ///
+    fn write(&self, text: &str) -> crate::error::Result<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        for (i, segment) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.emit("\n")?;
+                let _ = self.line_open.replace(false);
+            }
+            if !segment.is_empty() {
+                let _ = self.line_open.replace(true);
+                self.emit(segment)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn emit(&self, text: &str) -> crate::error::Result<()> {
+        match self.table.borrow_mut().as_mut() {
+            Some(table) => table.rows.push_str(text),
+            None => write!(&mut self.w.borrow_mut(), "{}", text)?,
+        }
+        Ok(())
+    }
+
+    ///
+    /// Write document text, escaping backslashes, and any text line that would otherwise be read
+    /// as a control line (starting with `.` or `'`). Leading spaces are dropped as roff treats them
+    /// as a break.
+    ///
+    fn write_text(&self, text: &str) -> crate::error::Result<()> {
+        let escaped = escape(text);
+        let escaped = if *self.in_heading.borrow() {
+            escaped.replace('"', "\\(dq")
+        } else if self.table.borrow().is_some() {
+            escaped.replace('|', "\\(ba")
+        } else {
+            escaped
+        };
+        for (i, segment) in escaped.split('\n').enumerate() {
+            if i > 0 {
+                self.end_line()?;
+            }
+            if *self.line_open.borrow() {
+                self.write(segment)?;
+            } else {
+                self.write(&escape_line_start(segment.trim_start_matches(' ')))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn end_line(&self) -> crate::error::Result<()> {
+        if self.line_open.replace(false) {
+            self.emit("\n")?;
+        }
+        Ok(())
+    }
+
+    fn request(&self, text: &str) -> crate::error::Result<()> {
+        self.end_line()?;
+        self.write(text)?;
+        self.end_line()
+    }
+
+    fn write_caption(&self, caption: &Option<Caption>) -> crate::error::Result<()> {
+        if let Some(caption) = caption {
+            self.request(".PP")?;
+            self.write("\\fI")?;
+            self.write_text(caption.inner())?;
+            self.write("\\fR")?;
+        }
+        Ok(())
+    }
+
+    fn write_verbatim(&self, text: &str) -> crate::error::Result<()> {
+        self.request(&format!(".RS {}", CODE_INDENT))?;
+        self.request(".nf")?;
+        for line in text.split('\n') {
+            self.write(&escape_line_start(&escape(line)))?;
+            let _ = self.line_open.replace(true);
+            self.end_line()?;
+        }
+        self.request(".fi")?;
+        self.request(".RE")
+    }
+
+    fn write_header(&self) -> crate::error::Result<()> {
+        let metadata = self.metadata.borrow();
+        let mut title = None;
+        let mut date = None;
+        let mut revision = None;
+        let mut section = None;
+        for datum in metadata.iter() {
+            match datum {
+                Metadata::Title(value) => title = Some(value.clone()),
+                Metadata::Date(value) => date = Some(value.clone()),
+                Metadata::Revision(value) => revision = Some(value.clone()),
+                Metadata::Other(property) if property.key == "section" => {
+                    section = Some(property.value.to_string())
+                }
+                _ => self.request(&format!(
+                    ".\\\" {}: {}",
+                    datum.key(),
+                    datum.value_string().replace('\n', " ")
+                ))?,
+            }
+        }
+        if let Some(title) = title {
+            self.request(&format!(
+                ".TH {} {} {} {}",
+                quote_argument(&title),
+                quote_argument(section.as_deref().unwrap_or(DEFAULT_SECTION)),
+                quote_argument(date.as_deref().unwrap_or_default()),
+                quote_argument(revision.as_deref().unwrap_or_default())
+            ))?;
+        }
+        Ok(())
+    }
+
+    fn current_font(&self) -> Font {
+        self.font_stack.borrow().last().copied().unwrap_or_default()
+    }
+}
+
+impl<'a, W: Write> DocumentVisitor for ManWriter<'a, W> {
+    fn metadata(&self, meta_datum: &Metadata) -> crate::error::Result<()> {
+        self.metadata.borrow_mut().push(meta_datum.clone());
+        Ok(())
+    }
+
+    fn block_visitor(&self) -> Option<&dyn BlockVisitor> {
+        if !self.metadata.borrow().is_empty() {
+            let _ = self.write_header();
+        }
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> BlockVisitor for ManWriter<'a, W> {
+    fn start_abstract(&self) -> crate::error::Result<()> {
+        self.request(".RS")?;
+        self.request(".PP")
+    }
+
+    fn end_abstract(&self) -> crate::error::Result<()> {
+        self.request(".RE")
+    }
+
+    fn comment(&self, value: &str) -> crate::error::Result<()> {
+        for line in value.split('\n') {
+            self.request(&format!(".\\\" {}", line))?;
+        }
+        Ok(())
+    }
+
+    fn front_matter(&self, _: &FrontMatter) -> crate::error::Result<()> {
+        Ok(())
+    }
+
+    fn start_heading(&self, level: &HeadingLevel, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_line()?;
+        match level {
+            HeadingLevel::Section => self.write(".SH ")?,
+            HeadingLevel::SubSection => self.write(".SS ")?,
+            _ => {
+                // man has no deeper sections, so use a bold paragraph.
+                self.request(".PP")?;
+                self.write("\\fB")?;
+            }
+        }
+        let _ = self.in_heading.replace(true);
+        Ok(())
+    }
+
+    fn end_heading(&self, level: &HeadingLevel, _: &Option<Label>) -> crate::error::Result<()> {
+        let _ = self.in_heading.replace(false);
+        if (level.clone() as usize) > (HeadingLevel::SubSection as usize) {
+            self.write("\\fR")?;
+        }
+        self.end_line()
+    }
+
+    fn image(
+        &self,
+        value: &Image,
+        caption: &Option<Caption>,
+        _: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_caption(caption)?;
+        self.request(".PP")?;
+        self.write_text(&image_text(value))
+    }
+
+    fn math(
+        &self,
+        value: &Math,
+        caption: &Option<Caption>,
+        _: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_caption(caption)?;
+        self.write_verbatim(value.inner())
+    }
+
+    fn start_list(&self, kind: &ListKind, _: &Option<Label>) -> crate::error::Result<()> {
+        let mut list_stack = self.list_stack.borrow_mut();
+        if !list_stack.is_empty() {
+            // nested lists are indented relative to the parent item's text.
+            self.request(".RS")?;
+        }
+        list_stack.push((kind.clone(), 0));
+        Ok(())
+    }
+
+    fn end_list(&self, _: &ListKind, _: &Option<Label>) -> crate::error::Result<()> {
+        let mut list_stack = self.list_stack.borrow_mut();
+        let _ = list_stack.pop();
+        if !list_stack.is_empty() {
+            self.request(".RE")?;
+        }
+        Ok(())
+    }
+
+    fn start_list_item(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        let marker = match self.list_stack.borrow_mut().last_mut() {
+            Some((ListKind::Ordered, count)) => {
+                *count += 1;
+                format!(".IP \"{}.\" 4", count)
+            }
+            _ => ".IP \\(bu 2".to_string(),
+        };
+        self.request(&marker)
+    }
+
+    fn end_list_item(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_line()
+    }
+
+    fn start_definition(&self, term: &str, _: &Option<Label>) -> crate::error::Result<()> {
+        self.request(".TP")?;
+        self.write("\\fB")?;
+        self.write_text(term)?;
+        self.write("\\fR")?;
+        self.end_line()
+    }
+
+    fn end_definition(&self, _: &str, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_line()
+    }
+
+    fn formatted(&self, value: &str, _: &Option<Label>) -> crate::error::Result<()> {
+        self.write_verbatim(value)
+    }
+
+    fn code_block(
+        &self,
+        code: &str,
+        _: &Option<String>,
+        caption: &Option<Caption>,
+        _: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_caption(caption)?;
+        self.write_verbatim(code)
+    }
+
+    fn start_paragraph(&self, _: &Alignment, _: &Option<Label>) -> crate::error::Result<()> {
+        self.request(".PP")
+    }
+
+    fn end_paragraph(&self, _: &Alignment, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_line()
+    }
+
+    fn start_quote(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.request(".RS")
+    }
+
+    fn end_quote(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.request(".RE")
+    }
+
+    fn thematic_break(&self) -> crate::error::Result<()> {
+        self.request(".PP")?;
+        self.request(".ce")?;
+        self.write("* * *")?;
+        self.end_line()
+    }
+
+    fn end_block(&self) -> crate::error::Result<()> {
+        self.end_line()
+    }
+
+    fn table_visitor(&self) -> Option<&dyn TableVisitor> {
+        Some(self)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> TableVisitor for ManWriter<'a, W> {
+    fn start_table(
+        &self,
+        caption: &Option<Caption>,
+        _: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_caption(caption)?;
+        self.request(".PP")?;
+        let _ = self.table.replace(Some(TableText::default()));
+        Ok(())
+    }
+
+    fn table_header_cell(
+        &self,
+        column_cell: &Column,
+        _column_idx: usize,
+    ) -> crate::error::Result<()> {
+        if let Some(table) = self.table.borrow_mut().as_mut() {
+            table.columns.push((
+                escape(column_cell.text()).replace('|', "\\(ba"),
+                column_cell.alignment().clone(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn start_table_cell(&self, cell_idx: usize, _: &Option<Label>) -> crate::error::Result<()> {
+        if let Some(table) = self.table.borrow_mut().as_mut() {
+            table.cell_count = table.cell_count.max(cell_idx + 1);
+        }
+        if cell_idx > 0 {
+            self.write("|")?;
+        }
+        Ok(())
+    }
+
+    fn end_table_row(&self, _: usize) -> crate::error::Result<()> {
+        self.end_line()
+    }
+
+    fn end_table(&self, _: &Option<Caption>, _: &Option<Label>) -> crate::error::Result<()> {
+        if let Some(table) = self.table.replace(None) {
+            let column_count = table.columns.len().max(table.cell_count);
+            let format = |suffix: &str| {
+                (0..column_count)
+                    .map(|i| {
+                        format!(
+                            "{}{}",
+                            match table.columns.get(i).map(|(_, alignment)| alignment) {
+                                Some(Alignment::Right) => "r",
+                                Some(Alignment::Centered) => "c",
+                                _ => "l",
+                            },
+                            suffix
+                        )
+                    })
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            };
+            self.request(".TS")?;
+            self.request("tab(|);")?;
+            if !table.columns.is_empty() {
+                self.request(&format("B"))?;
+            }
+            self.request(&format!("{}.", format("")))?;
+            if !table.columns.is_empty() {
+                self.request(
+                    &table
+                        .columns
+                        .iter()
+                        .map(|(text, _)| escape_line_start(text))
+                        .collect::<Vec<String>>()
+                        .join("|"),
+                )?;
+                self.request("_")?;
+            }
+            self.write(&table.rows)?;
+            self.request(".TE")?;
+        }
+        Ok(())
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> InlineVisitor for ManWriter<'a, W> {
+    fn link(&self, value: &HyperLink) -> crate::error::Result<()> {
+        match (value.target(), value.caption()) {
+            (HyperLinkTarget::External(target), Some(caption)) => {
+                self.write_text(&format!("{} <{}>", caption.inner(), target))
+            }
+            (HyperLinkTarget::External(target), None) => self.write_text(target),
+            (HyperLinkTarget::Internal(_), Some(caption)) => self.write_text(caption.inner()),
+            (HyperLinkTarget::Internal(target), None) => self.write_text(&target.to_string()),
+        }
+    }
+
+    fn image(&self, value: &Image) -> crate::error::Result<()> {
+        self.write_text(&image_text(value))
+    }
+
+    fn text(&self, value: &Text) -> crate::error::Result<()> {
+        self.write_text(value.inner())
+    }
+
+    fn math(&self, value: &Math) -> crate::error::Result<()> {
+        self.write_text(value.inner())
+    }
+
+    fn character(&self, value: &Character) -> crate::error::Result<()> {
+        match value {
+            Character::Space => self.write_text(" "),
+            Character::NonBreakSpace => self.write("\\ "),
+            Character::Hyphen => self.write_text("-"),
+            Character::EmDash => self.write("\\(em"),
+            Character::EnDash => self.write("\\(en"),
+            Character::Emoji(name) => self.write_text(&format!(":{}:", name.inner())),
+            Character::Other(c) => self.write_text(&c.to_string()),
+        }
+    }
+
+    fn line_break(&self) -> crate::error::Result<()> {
+        self.request(".br")
+    }
+
+    fn start_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        let outer = self.current_font();
+        let mut font = outer;
+        for style in styles {
+            match style {
+                SpanStyle::Plain => font = Font::default(),
+                SpanStyle::Bold | SpanStyle::Mono | SpanStyle::Code => font.bold = true,
+                SpanStyle::Italic | SpanStyle::Underline => font.italic = true,
+                _ => {}
+            }
+        }
+        self.font_stack.borrow_mut().push(font);
+        if font != outer {
+            self.write(font.escape())?;
+        }
+        Ok(())
+    }
+
+    fn end_span(&self, _: &[SpanStyle]) -> crate::error::Result<()> {
+        let font = self.font_stack.borrow_mut().pop().unwrap_or_default();
+        let outer = self.current_font();
+        if font != outer {
+            self.write(outer.escape())?;
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Font {
+    fn escape(&self) -> &'static str {
+        match (self.bold, self.italic) {
+            (true, true) => "\\f(BI",
+            (true, false) => "\\fB",
+            (false, true) => "\\fI",
+            (false, false) => "\\fR",
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn has_table(blocks: &[BlockContent]) -> bool {
+    blocks.iter().any(|block| match block {
+        BlockContent::Table(_) => true,
+        BlockContent::Quote(quote) => has_table(quote.inner()),
+        _ => false,
+    })
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\e")
+}
+
+fn escape_line_start(line: &str) -> String {
+    if line.starts_with('.') || line.starts_with('\'') {
+        format!("\\&{}", line)
+    } else {
+        line.to_string()
+    }
+}
+
+fn quote_argument(value: &str) -> String {
+    format!("\"{}\"", escape(value).replace('"', "\\(dq"))
+}
+
+fn image_text(value: &Image) -> String {
+    format!(
+        "[image: {}]",
+        value.alt_text().as_deref().unwrap_or_else(|| value.inner())
+    )
+}
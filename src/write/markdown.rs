@@ -58,12 +58,9 @@ pub enum MarkdownFlavor {
 
     // See <https://kramdown.gettalong.org/quickref.html>
     // Kramdown,
+    /// See <https://pandoc.org/MANUAL.html#pandocs-markdown>
+    Pandoc,
 
-    // See <https://pandoc.org/MANUAL.html#pandocs-markdown>
-    // Pandoc,
-
-    // See <https://docutils.sourceforge.io/docs/user/rst/quickref.html>
-    // ReStructuredText,
     /// See <https://rawgit.com/fletcher/MultiMarkdown-6-Syntax-Guide/master/index.html>
     Multi,
 
@@ -102,6 +99,7 @@ pub enum MarkdownFlavor {
 pub struct MarkdownWriter<'a, W: Write> {
     flavor: MarkdownFlavor,
     in_metadata: RefCell<bool>,
+    metadata_block: RefCell<Vec<Metadata>>,
     list_prefix_stack: RefCell<Vec<ListKind>>,
     line_prefix_stack: RefCell<Vec<String>>,
     table_sep_row: RefCell<Vec<String>>,
@@ -162,6 +160,7 @@ impl Display for MarkdownFlavor {
                 MarkdownFlavor::Strict => "strict",
                 MarkdownFlavor::CommonMark => "commonmark",
                 MarkdownFlavor::GitHub => "gfm",
+                MarkdownFlavor::Pandoc => "pandoc",
                 MarkdownFlavor::Multi => "multi",
                 MarkdownFlavor::PhpExtra => "extra",
                 MarkdownFlavor::XWiki => "xwiki",
@@ -184,6 +183,7 @@ impl FromStr for MarkdownFlavor {
             "og" | "strict" => Ok(Self::Strict),
             "cm" | "common" | "commonmark" => Ok(Self::CommonMark),
             "github" | "gfm" => Ok(Self::GitHub),
+            "pandoc" => Ok(Self::Pandoc),
             "mmd" | "multi" => Ok(Self::Multi),
             "php_extra" | "extra" => Ok(Self::PhpExtra),
             "xwiki" => Ok(Self::XWiki),
@@ -208,6 +208,7 @@ impl<'a, W: Write> ConfigurableWriter<'a, W, MarkdownFlavor> for MarkdownWriter<
         Self {
             flavor: config,
             in_metadata: RefCell::from(false),
+            metadata_block: RefCell::from(Vec::default()),
             list_prefix_stack: RefCell::from(Vec::default()),
             line_prefix_stack: RefCell::from(Vec::default()),
             table_sep_row: RefCell::from(Vec::default()),
@@ -289,7 +290,7 @@ impl<'a, W: Write> MarkdownWriter<'a, W> {
         Ok(())
     }
 
-    fn make_style_stack(&self, styles: &[SpanStyle]) -> Vec<&str> {
+    fn make_style_stack(&self, styles: &[SpanStyle]) -> Vec<(&str, &str)> {
         let mut style_stack = Vec::new();
         for style in styles {
             match style {
@@ -298,39 +299,48 @@ impl<'a, W: Write> MarkdownWriter<'a, W> {
                 }
                 SpanStyle::Italic => {
                     if self.flavor == MarkdownFlavor::XWiki {
-                        style_stack.push("//")
+                        style_stack.push(("//", "//"))
                     } else {
-                        style_stack.push("*")
+                        style_stack.push(("*", "*"))
                     }
                 }
-                SpanStyle::Bold => style_stack.push("**"),
+                SpanStyle::Bold => style_stack.push(("**", "**")),
                 SpanStyle::Mono | SpanStyle::Code => {
                     if self.flavor == MarkdownFlavor::XWiki {
-                        style_stack.push("##")
+                        style_stack.push(("##", "##"))
                     } else {
-                        style_stack.push("`")
+                        style_stack.push(("`", "`"))
                     }
                 }
                 SpanStyle::Strikethrough => {
-                    if self.flavor == MarkdownFlavor::GitHub {
-                        style_stack.push("~~")
+                    if matches!(self.flavor, MarkdownFlavor::GitHub | MarkdownFlavor::Pandoc) {
+                        style_stack.push(("~~", "~~"))
                     } else if self.flavor == MarkdownFlavor::XWiki {
-                        style_stack.push("--")
+                        style_stack.push(("--", "--"))
                     }
                 }
                 SpanStyle::Underline => {
                     if self.flavor == MarkdownFlavor::XWiki {
-                        style_stack.push("__")
+                        style_stack.push(("__", "__"))
+                    } else if self.flavor == MarkdownFlavor::Pandoc {
+                        style_stack.push(("[", "]{.underline}"))
                     }
                 }
+                SpanStyle::SmallCaps if self.flavor == MarkdownFlavor::Pandoc => {
+                    style_stack.push(("[", "]{.smallcaps}"))
+                }
                 SpanStyle::Superscript => {
                     if self.flavor == MarkdownFlavor::XWiki {
-                        style_stack.push("^^")
+                        style_stack.push(("^^", "^^"))
+                    } else if self.flavor == MarkdownFlavor::Pandoc {
+                        style_stack.push(("^", "^"))
                     }
                 }
                 SpanStyle::Subscript => {
                     if self.flavor == MarkdownFlavor::XWiki {
-                        style_stack.push(",,")
+                        style_stack.push((",,", ",,"))
+                    } else if self.flavor == MarkdownFlavor::Pandoc {
+                        style_stack.push(("~", "~"))
                     }
                 }
                 _ => {}
@@ -338,10 +348,79 @@ impl<'a, W: Write> MarkdownWriter<'a, W> {
         }
         style_stack
     }
+
+    fn write_metadata_block(&self) -> error::Result<()> {
+        let metadata = self.metadata_block.replace(Vec::default());
+        let mut authors: Vec<String> = Default::default();
+        let mut copyrights: Vec<String> = Default::default();
+        self.write("---")?;
+        self.end_line()?;
+        for datum in &metadata {
+            match datum {
+                Metadata::Author(author) => {
+                    authors.push(if author.email.is_none() && author.organization.is_none() {
+                        yaml_quoted(&author.name)
+                    } else {
+                        let mut lines = vec![format!("name: {}", yaml_quoted(&author.name))];
+                        if let Some(email) = &author.email {
+                            lines.push(format!("email: {}", yaml_quoted(email)));
+                        }
+                        if let Some(organization) = &author.organization {
+                            lines.push(format!("affiliation: {}", yaml_quoted(organization)));
+                        }
+                        lines.join("\n  ")
+                    })
+                }
+                Metadata::Copyright(copyright) => {
+                    let mut lines = vec![format!("year: {}", copyright.year)];
+                    if let Some(organization) = &copyright.organization {
+                        lines.push(format!("organization: {}", yaml_quoted(organization)));
+                    }
+                    if let Some(comment) = &copyright.comment {
+                        lines.push(format!("comment: {}", yaml_quoted(comment)));
+                    }
+                    copyrights.push(lines.join("\n  "))
+                }
+                Metadata::Keywords(keywords) => {
+                    self.write(&format!("{}:", datum.key()))?;
+                    self.end_line()?;
+                    for keyword in keywords {
+                        self.write(&format!("- {}", yaml_quoted(keyword)))?;
+                        self.end_line()?;
+                    }
+                }
+                _ => {
+                    self.write(&format!(
+                        "{}: {}",
+                        datum.key(),
+                        yaml_quoted(&datum.value_string())
+                    ))?;
+                    self.end_line()?;
+                }
+            }
+        }
+        for (key, values) in [("author", authors), ("copyright", copyrights)] {
+            if !values.is_empty() {
+                self.write(&format!("{}:", key))?;
+                self.end_line()?;
+                for value in values {
+                    self.write(&format!("- {}", value))?;
+                    self.end_line()?;
+                }
+            }
+        }
+        self.write("---")?;
+        self.end_line()
+    }
 }
 
 impl<'a, W: Write> DocumentVisitor for MarkdownWriter<'a, W> {
     fn metadata(&self, meta_datum: &Metadata) -> crate::error::Result<()> {
+        if self.flavor == MarkdownFlavor::Pandoc {
+            let _ = self.in_metadata.replace(true);
+            self.metadata_block.borrow_mut().push(meta_datum.clone());
+            return Ok(());
+        }
         if !self.in_metadata.replace(true) {
             match &self.flavor {
                 MarkdownFlavor::GitHub | MarkdownFlavor::Multi => {
@@ -359,6 +438,7 @@ impl<'a, W: Write> DocumentVisitor for MarkdownWriter<'a, W> {
             MarkdownFlavor::Strict
             | MarkdownFlavor::CommonMark
             | MarkdownFlavor::GitHub
+            | MarkdownFlavor::Pandoc
             | MarkdownFlavor::Multi
             | MarkdownFlavor::PhpExtra => {
                 let _ = self.write(&format!(
@@ -386,6 +466,11 @@ impl<'a, W: Write> DocumentVisitor for MarkdownWriter<'a, W> {
                     let _ = self.write("{{/comment}}");
                     let _ = self.end_line();
                 }
+                MarkdownFlavor::Pandoc => {
+                    // the first block will separate itself from the metadata block.
+                    let _ = self.write_metadata_block();
+                    return Some(self);
+                }
                 _ => {}
             }
             let _ = self.end_line();
@@ -407,6 +492,7 @@ impl<'a, W: Write> BlockVisitor for MarkdownWriter<'a, W> {
             MarkdownFlavor::Strict
             | MarkdownFlavor::CommonMark
             | MarkdownFlavor::GitHub
+            | MarkdownFlavor::Pandoc
             | MarkdownFlavor::Multi
             | MarkdownFlavor::PhpExtra => {
                 for line in value.split('\n') {
@@ -468,6 +554,10 @@ impl<'a, W: Write> BlockVisitor for MarkdownWriter<'a, W> {
                 s.push('=');
             }
             self.write(&format!(" {}", s))?;
+        } else if self.flavor == MarkdownFlavor::Pandoc {
+            if let Some(label) = label {
+                self.write(&format!(" {{#{}}}", label))?;
+            }
         }
         self.write_label_after(label)?;
         Ok(())
@@ -476,10 +566,20 @@ impl<'a, W: Write> BlockVisitor for MarkdownWriter<'a, W> {
     fn image(
         &self,
         value: &Image,
-        _caption: &Option<Caption>,
+        caption: &Option<Caption>,
         label: &Option<Label>,
     ) -> crate::error::Result<()> {
-        if let Some(inline_visitor) = BlockVisitor::inline_visitor(self) {
+        if self.flavor == MarkdownFlavor::Pandoc {
+            let caption = match (caption, value.alt_text()) {
+                (Some(caption), _) => caption.to_string(),
+                (None, Some(alt_text)) => alt_text.to_string(),
+                (None, None) => String::new(),
+            };
+            self.write(&format!("![{}]({})", caption, value.inner()))?;
+            if let Some(label) = label {
+                self.write(&format!("{{#{}}}", label))?;
+            }
+        } else if let Some(inline_visitor) = BlockVisitor::inline_visitor(self) {
             self.end_line()?;
             self.start_line()?;
             self.write_label_before(label)?;
@@ -495,7 +595,12 @@ impl<'a, W: Write> BlockVisitor for MarkdownWriter<'a, W> {
         _caption: &Option<Caption>,
         label: &Option<Label>,
     ) -> crate::error::Result<()> {
-        if matches!(self.flavor, MarkdownFlavor::Multi | MarkdownFlavor::XWiki) {
+        if self.flavor == MarkdownFlavor::Pandoc {
+            self.write(&format!("$${}$$", value.inner()))?;
+            if let Some(label) = label {
+                self.write(&format!(" {{#{}}}", label))?;
+            }
+        } else if matches!(self.flavor, MarkdownFlavor::Multi | MarkdownFlavor::XWiki) {
             if let Some(inline_visitor) = BlockVisitor::inline_visitor(self) {
                 self.end_line()?;
                 self.start_line()?;
@@ -568,7 +673,7 @@ impl<'a, W: Write> BlockVisitor for MarkdownWriter<'a, W> {
     fn start_definition(&self, term: &str, label: &Option<Label>) -> crate::error::Result<()> {
         self.write_label_before(label)?;
         match self.flavor {
-            MarkdownFlavor::Multi | MarkdownFlavor::PhpExtra => {
+            MarkdownFlavor::Multi | MarkdownFlavor::PhpExtra | MarkdownFlavor::Pandoc => {
                 self.write(term)?;
                 self.end_line()?;
                 self.start_line()?;
@@ -593,6 +698,9 @@ impl<'a, W: Write> BlockVisitor for MarkdownWriter<'a, W> {
             MarkdownFlavor::Multi | MarkdownFlavor::PhpExtra | MarkdownFlavor::XWiki => {
                 write!(self.w.borrow_mut(), ": ")?;
             }
+            MarkdownFlavor::Pandoc => {
+                write!(self.w.borrow_mut(), ":   ")?;
+            }
             _ => {}
         }
         Ok(())
@@ -604,6 +712,7 @@ impl<'a, W: Write> BlockVisitor for MarkdownWriter<'a, W> {
             MarkdownFlavor::Strict
             | MarkdownFlavor::CommonMark
             | MarkdownFlavor::GitHub
+            | MarkdownFlavor::Pandoc
             | MarkdownFlavor::Multi
             | MarkdownFlavor::PhpExtra => {
                 self.line_prefix_stack.borrow_mut().push("    ".to_string());
@@ -621,11 +730,32 @@ impl<'a, W: Write> BlockVisitor for MarkdownWriter<'a, W> {
         &self,
         code: &str,
         language: &Option<String>,
-        _caption: &Option<Caption>,
+        caption: &Option<Caption>,
         label: &Option<Label>,
     ) -> crate::error::Result<()> {
         self.write_label_before(label)?;
         match self.flavor {
+            MarkdownFlavor::Pandoc => {
+                let mut attributes: Vec<String> = Default::default();
+                if let Some(label) = label {
+                    attributes.push(format!("#{}", label));
+                }
+                if let Some(language) = language {
+                    attributes.push(format!(".{}", language));
+                }
+                if let Some(caption) = caption {
+                    attributes.push(format!(
+                        "caption=\"{}\"",
+                        caption.inner().replace('"', "\\\"")
+                    ));
+                }
+                let fence = match (label, caption, language) {
+                    (None, None, None) => "```".to_string(),
+                    (None, None, Some(language)) => format!("```{}", language),
+                    _ => format!("``` {{{}}}", attributes.join(" ")),
+                };
+                self.write(&format!("{}\n{}\n```", fence, code))?;
+            }
             MarkdownFlavor::Strict => {
                 self.line_prefix_stack.borrow_mut().push("    ".to_string());
                 self.write(&format!("    {}", code))?;
@@ -695,6 +825,7 @@ impl<'a, W: Write> BlockVisitor for MarkdownWriter<'a, W> {
         match self.flavor {
             MarkdownFlavor::CommonMark
             | MarkdownFlavor::GitHub
+            | MarkdownFlavor::Pandoc
             | MarkdownFlavor::Multi
             | MarkdownFlavor::PhpExtra
             | MarkdownFlavor::XWiki => Some(self),
@@ -749,7 +880,7 @@ impl<'a, W: Write> TableVisitor for MarkdownWriter<'a, W> {
                             Alignment::Justified => "-----",
                             Alignment::Left => ":----",
                             Alignment::Right => "----:",
-                            Alignment::Centered => "--:--",
+                            Alignment::Centered => ":---:",
                         }
                         .to_string(),
                     );
@@ -811,11 +942,22 @@ impl<'a, W: Write> TableVisitor for MarkdownWriter<'a, W> {
 
     fn end_table(
         &self,
-        _caption: &Option<Caption>,
+        caption: &Option<Caption>,
         label: &Option<Label>,
     ) -> crate::error::Result<()> {
         self.write_label_after(label)?;
 
+        if self.flavor == MarkdownFlavor::Pandoc {
+            if let Some(caption) = caption {
+                self.end_line()?;
+                self.start_line()?;
+                self.write(&format!("Table: {}", caption.inner()))?;
+                if let Some(label) = label {
+                    self.write(&format!(" {{#{}}}", label))?;
+                }
+            }
+        }
+
         if self.flavor == MarkdownFlavor::CommonMark {
             self.write("</table>")?;
         }
@@ -845,13 +987,22 @@ impl<'a, W: Write> InlineVisitor for MarkdownWriter<'a, W> {
         } else {
             let target = match value.target() {
                 HyperLinkTarget::External(v) => {
+                    if self.flavor == MarkdownFlavor::Pandoc && value.caption().is_none() {
+                        return self.write(&format!("<{}>", v));
+                    }
                     if v.contains(' ') {
                         format!("<{}>", v)
                     } else {
                         v.to_string()
                     }
                 }
-                HyperLinkTarget::Internal(v) => format!("#{}", v),
+                HyperLinkTarget::Internal(v) => {
+                    if self.flavor == MarkdownFlavor::Pandoc && value.caption().is_none() {
+                        // `[@label]` would be read as a citation.
+                        return self.write(&format!("[{}](#{})", v, v));
+                    }
+                    format!("#{}", v)
+                }
             };
             self.write(&format!(
                 "[{}]({})",
@@ -868,6 +1019,12 @@ impl<'a, W: Write> InlineVisitor for MarkdownWriter<'a, W> {
     fn image(&self, value: &Image) -> crate::error::Result<()> {
         self.write(&if self.flavor == MarkdownFlavor::XWiki {
             format!("image:{}", value.inner())
+        } else if self.flavor == MarkdownFlavor::Pandoc {
+            format!(
+                "![{}]({})",
+                value.alt_text().as_deref().unwrap_or_default(),
+                value.inner()
+            )
         } else {
             format!("![]({})", value.inner())
         })
@@ -879,7 +1036,7 @@ impl<'a, W: Write> InlineVisitor for MarkdownWriter<'a, W> {
 
     fn math(&self, value: &Math) -> crate::error::Result<()> {
         match self.flavor {
-            MarkdownFlavor::Multi => {
+            MarkdownFlavor::Multi | MarkdownFlavor::Pandoc => {
                 self.write(&format!("${}$", value.inner()))?;
             }
             MarkdownFlavor::XWiki => {
@@ -919,7 +1076,13 @@ impl<'a, W: Write> InlineVisitor for MarkdownWriter<'a, W> {
     fn start_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
         let style_stack = self.make_style_stack(styles);
         if !style_stack.is_empty() {
-            self.write(&style_stack.join(""))?;
+            self.write(
+                &style_stack
+                    .into_iter()
+                    .map(|(open, _)| open)
+                    .collect::<Vec<&str>>()
+                    .join(""),
+            )?;
         }
         Ok(())
     }
@@ -931,6 +1094,7 @@ impl<'a, W: Write> InlineVisitor for MarkdownWriter<'a, W> {
                 &style_stack
                     .into_iter()
                     .rev()
+                    .map(|(_, close)| close)
                     .collect::<Vec<&str>>()
                     .join(""),
             )?;
@@ -957,3 +1121,11 @@ impl Display for DebugMark {
         )
     }
 }
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn yaml_quoted(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
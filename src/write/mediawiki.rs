@@ -0,0 +1,640 @@
+/*!
+Write a document in the [MediaWiki](https://www.mediawiki.org/wiki/Help:Formatting) markup
+language.
+
+Headings start at `== level 2 ==`, as level 1 is reserved for the page title. Labels are written
+as empty elements with an `id`, so that internal links of the form `[[#label|caption]]` can
+target them. Local images are written as links to uploaded files with the same file name,
+external images as a bare URL. Math uses the `<math>` tag and code blocks the `<syntaxhighlight>`
+tag, both of which require the corresponding extensions. Document metadata is not written, the
+page title is set when the page is created.
+
+# Example
+
+```rust
+# use somedoc::model::Document;
+use somedoc::write::mediawiki::writer;
+
+# fn make_some_document() -> Document { Document::default() }
+let doc = make_some_document();
+
+writer(&doc, &mut std::io::stdout()).unwrap();
+```
+*/
+
+use crate::model::block::{
+    Alignment, Caption, Column, FrontMatter, HasAlignment, HasCaption, HeadingLevel, Label,
+    ListKind,
+};
+use crate::model::inline::text::Size;
+use crate::model::inline::{Character, HyperLink, HyperLinkTarget, Image, Math, SpanStyle, Text};
+use crate::model::visitor::{
+    walk_document, BlockVisitor, DocumentVisitor, InlineVisitor, TableVisitor,
+};
+use crate::model::Document;
+use crate::write::utils::string_of_strings;
+use crate::write::Writer;
+use std::cell::RefCell;
+use std::io::Write;
+use std::path::Path;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the MediaWiki writer structure, usually this is accessed via the `writer`
+/// function, but may be used directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use somedoc::model::Document;
+/// use somedoc::write::mediawiki::MediaWikiWriter;
+/// use somedoc::write::Writer;
+///
+/// # fn make_some_document() -> Document { Document::default() }
+/// let doc = make_some_document();
+/// let mut out = std::io::stdout();
+/// let writer = MediaWikiWriter::new(&mut out);
+/// assert!(writer.write_document(&doc).is_ok());
+/// ```
+///
+#[derive(Debug)]
+pub struct MediaWikiWriter<'a, W: Write> {
+    list_stack: RefCell<Vec<ListKind>>,
+    column_alignments: RefCell<Vec<Alignment>>,
+    in_table: RefCell<bool>,
+    block_written: RefCell<bool>,
+    line_start: RefCell<bool>,
+    w: RefCell<&'a mut W>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the writer function for MediaWiki.
+///
+/// While this can be called directly it is most often used  by calling either
+/// [`model::write_document`](../fn.write_document.html) or
+/// [`model::write_document_to_string`](../fn.write_document_to_string.html).
+///
+#[inline]
+pub fn writer<W: Write>(doc: &Document, w: &mut W) -> crate::error::Result<()> {
+    let writer = MediaWikiWriter::new(w);
+    writer.write_document(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> Writer<'a, W> for MediaWikiWriter<'a, W> {
+    fn new(w: &'a mut W) -> Self {
+        Self {
+            list_stack: RefCell::from(Vec::default()),
+            column_alignments: RefCell::from(Vec::default()),
+            in_table: RefCell::from(false),
+            block_written: RefCell::from(false),
+            line_start: RefCell::from(true),
+            w: RefCell::from(w),
+        }
+    }
+
+    fn write_document(&self, doc: &Document) -> crate::error::Result<()> {
+        walk_document(doc, self)?;
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> MediaWikiWriter<'a, W> {
+    fn write(&self, text: &str) -> crate::error::Result<()> {
+        write!(&mut self.w.borrow_mut(), "{}", text)?;
+        let _ = self.block_written.replace(true);
+        if !text.is_empty() {
+            let _ = self.line_start.replace(text.ends_with('\n'));
+        }
+        Ok(())
+    }
+
+    fn write_line(&self, text: &str) -> crate::error::Result<()> {
+        self.write(text)?;
+        self.write("\n")
+    }
+
+    fn write_text(&self, text: &str) -> crate::error::Result<()> {
+        let text = escape_line_starts(&escape(text), *self.line_start.borrow());
+        if *self.in_table.borrow() {
+            // a single `|` separates cell attributes from content.
+            self.write(&text.replace('|', "&#124;"))
+        } else {
+            self.write(&text)
+        }
+    }
+
+    fn write_span_anchor(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        if let Some(label) = label {
+            self.write(&format!(
+                "<span id=\"{}\"></span>",
+                escape_attribute(&label.to_string())
+            ))?;
+        }
+        Ok(())
+    }
+
+    fn write_div_anchor(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        if let Some(label) = label {
+            self.write_line(&format!(
+                "<div id=\"{}\"></div>",
+                escape_attribute(&label.to_string())
+            ))?;
+        }
+        Ok(())
+    }
+
+    fn write_caption(&self, caption: &Option<Caption>) -> crate::error::Result<()> {
+        if let Some(caption) = caption {
+            self.write_line(&format!(
+                "<div style=\"text-align: center;\">''{}''</div>",
+                escape(caption.inner())
+            ))?;
+        }
+        Ok(())
+    }
+
+    fn write_cell_start(
+        &self,
+        marker: &str,
+        alignment: &Alignment,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        let mut attributes = Vec::new();
+        if let Some(label) = label {
+            attributes.push(format!("id=\"{}\"", escape_attribute(&label.to_string())));
+        }
+        if let Some(style) = alignment_style(alignment) {
+            attributes.push(format!("style=\"{}\"", style));
+        }
+        if attributes.is_empty() {
+            self.write(&format!("{} ", marker))
+        } else {
+            self.write(&format!("{} {} | ", marker, attributes.join(" ")))
+        }
+    }
+}
+
+impl<'a, W: Write> DocumentVisitor for MediaWikiWriter<'a, W> {
+    fn block_visitor(&self) -> Option<&dyn BlockVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> BlockVisitor for MediaWikiWriter<'a, W> {
+    fn start_block(&self) -> crate::error::Result<()> {
+        // blocks are separated by a blank line, but only once a block has written something.
+        if self.block_written.replace(false) {
+            writeln!(&mut self.w.borrow_mut())?;
+        }
+        Ok(())
+    }
+
+    fn start_abstract(&self) -> crate::error::Result<()> {
+        self.write("<div class=\"abstract\">''")
+    }
+
+    fn end_abstract(&self) -> crate::error::Result<()> {
+        self.write_line("''</div>")
+    }
+
+    fn comment(&self, value: &str) -> crate::error::Result<()> {
+        self.write_line(&format!("<!-- {} -->", value.replace("--", "- -")))
+    }
+
+    fn front_matter(&self, value: &FrontMatter) -> crate::error::Result<()> {
+        if *value == FrontMatter::TableOfContents {
+            self.write_line("__TOC__")?;
+        }
+        Ok(())
+    }
+
+    fn start_heading(
+        &self,
+        level: &HeadingLevel,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write(&format!("{} ", heading_marker(level)))?;
+        self.write_span_anchor(label)
+    }
+
+    fn end_heading(&self, level: &HeadingLevel, _: &Option<Label>) -> crate::error::Result<()> {
+        self.write_line(&format!(" {}", heading_marker(level)))
+    }
+
+    fn image(
+        &self,
+        value: &Image,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_div_anchor(label)?;
+        if is_local(value.inner()) {
+            let mut parameters = vec!["File:".to_string() + &file_name(value.inner())];
+            if caption.is_some() {
+                parameters.push("thumb".to_string());
+            }
+            if let Some(alt_text) = value.alt_text() {
+                parameters.push(format!("alt={}", escape(alt_text)));
+            }
+            if let Some(caption) = caption {
+                parameters.push(escape(caption.inner()));
+            }
+            self.write_line(&format!("[[{}]]", parameters.join("|")))
+        } else {
+            self.write_line(&external_image(value))?;
+            self.write_caption(caption)
+        }
+    }
+
+    fn math(
+        &self,
+        value: &Math,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_div_anchor(label)?;
+        self.write_line(&format!("<math display=\"block\">{}</math>", value.inner()))?;
+        self.write_caption(caption)
+    }
+
+    fn start_list(&self, kind: &ListKind, label: &Option<Label>) -> crate::error::Result<()> {
+        if self.list_stack.borrow().is_empty() {
+            self.write_div_anchor(label)?;
+        }
+        self.list_stack.borrow_mut().push(kind.clone());
+        Ok(())
+    }
+
+    fn end_list(&self, _: &ListKind, _: &Option<Label>) -> crate::error::Result<()> {
+        let _ = self.list_stack.borrow_mut().pop();
+        Ok(())
+    }
+
+    fn start_list_item(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        let prefix: String = self
+            .list_stack
+            .borrow()
+            .iter()
+            .map(|kind| match kind {
+                ListKind::Ordered => '#',
+                ListKind::Unordered => '*',
+            })
+            .collect();
+        self.write(&format!("{} ", prefix))?;
+        self.write_span_anchor(label)
+    }
+
+    fn end_list_item(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.write("\n")
+    }
+
+    fn start_definition_list(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_div_anchor(label)
+    }
+
+    fn start_definition(&self, term: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write("; ")?;
+        self.write_span_anchor(label)?;
+        self.write_line(&escape(term))
+    }
+
+    fn start_definition_list_text(&self) -> crate::error::Result<()> {
+        self.write(": ")
+    }
+
+    fn end_definition_list_text(&self) -> crate::error::Result<()> {
+        self.write("\n")
+    }
+
+    fn formatted(&self, value: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_div_anchor(label)?;
+        self.write_line(&format!("<pre>{}</pre>", escape_html(value)))
+    }
+
+    fn code_block(
+        &self,
+        code: &str,
+        language: &Option<String>,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_div_anchor(label)?;
+        self.write_line(&format!(
+            "<syntaxhighlight lang=\"{}\">\n{}\n</syntaxhighlight>",
+            escape_attribute(language.as_deref().unwrap_or("text")),
+            code
+        ))?;
+        self.write_caption(caption)
+    }
+
+    fn start_paragraph(
+        &self,
+        alignment: &Alignment,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        if let Some(style) = alignment_style(alignment) {
+            self.write(&format!("<div style=\"{}\">", style))?;
+        }
+        self.write_span_anchor(label)
+    }
+
+    fn end_paragraph(&self, alignment: &Alignment, _: &Option<Label>) -> crate::error::Result<()> {
+        if alignment_style(alignment).is_some() {
+            self.write("</div>")?;
+        }
+        self.write("\n")
+    }
+
+    fn start_quote(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_div_anchor(label)?;
+        self.write_line("<blockquote>")?;
+        let _ = self.block_written.replace(false);
+        Ok(())
+    }
+
+    fn end_quote(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.write_line("</blockquote>")
+    }
+
+    fn thematic_break(&self) -> crate::error::Result<()> {
+        self.write_line("----")
+    }
+
+    fn table_visitor(&self) -> Option<&dyn TableVisitor> {
+        Some(self)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> TableVisitor for MediaWikiWriter<'a, W> {
+    fn start_table(
+        &self,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.column_alignments.borrow_mut().clear();
+        let _ = self.in_table.replace(true);
+        match label {
+            Some(label) => self.write_line(&format!(
+                "{{| class=\"wikitable\" id=\"{}\"",
+                escape_attribute(&label.to_string())
+            ))?,
+            None => self.write_line("{| class=\"wikitable\"")?,
+        }
+        if let Some(caption) = caption {
+            self.write("|+ ")?;
+            self.write_text(caption.inner())?;
+            self.write("\n")?;
+        }
+        Ok(())
+    }
+
+    fn start_table_header_row(&self) -> crate::error::Result<()> {
+        self.write_line("|-")
+    }
+
+    fn table_header_cell(
+        &self,
+        column_cell: &Column,
+        _column_idx: usize,
+    ) -> crate::error::Result<()> {
+        self.column_alignments
+            .borrow_mut()
+            .push(column_cell.alignment().clone());
+        self.write_cell_start("!", column_cell.alignment(), &None)?;
+        self.write_text(column_cell.text())?;
+        self.write("\n")
+    }
+
+    fn start_table_row(&self, _: usize) -> crate::error::Result<()> {
+        self.write_line("|-")
+    }
+
+    fn start_table_cell(&self, cell_idx: usize, label: &Option<Label>) -> crate::error::Result<()> {
+        let alignment = self
+            .column_alignments
+            .borrow()
+            .get(cell_idx)
+            .cloned()
+            .unwrap_or(Alignment::Left);
+        self.write_cell_start("|", &alignment, label)
+    }
+
+    fn end_table_cell(&self, _: usize, _: &Option<Label>) -> crate::error::Result<()> {
+        self.write("\n")
+    }
+
+    fn end_table(&self, _: &Option<Caption>, _: &Option<Label>) -> crate::error::Result<()> {
+        let _ = self.in_table.replace(false);
+        self.write_line("|}")
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> InlineVisitor for MediaWikiWriter<'a, W> {
+    fn link(&self, value: &HyperLink) -> crate::error::Result<()> {
+        match (value.target(), value.caption()) {
+            (HyperLinkTarget::External(target), Some(caption)) => {
+                self.write(&format!("[{} {}]", target, escape(caption.inner())))
+            }
+            (HyperLinkTarget::External(target), None) => self.write(target),
+            (HyperLinkTarget::Internal(target), Some(caption)) => {
+                self.write(&format!("[[#{}|{}]]", target, escape(caption.inner())))
+            }
+            (HyperLinkTarget::Internal(target), None) => self.write(&format!("[[#{}]]", target)),
+        }
+    }
+
+    fn image(&self, value: &Image) -> crate::error::Result<()> {
+        if is_local(value.inner()) {
+            match value.alt_text() {
+                Some(alt_text) => self.write(&format!(
+                    "[[File:{}|alt={}]]",
+                    file_name(value.inner()),
+                    escape(alt_text)
+                )),
+                None => self.write(&format!("[[File:{}]]", file_name(value.inner()))),
+            }
+        } else {
+            self.write(&external_image(value))
+        }
+    }
+
+    fn text(&self, value: &Text) -> crate::error::Result<()> {
+        self.write_text(value.inner())
+    }
+
+    fn math(&self, value: &Math) -> crate::error::Result<()> {
+        self.write(&format!("<math>{}</math>", value.inner()))
+    }
+
+    fn character(&self, value: &Character) -> crate::error::Result<()> {
+        self.write(&match value {
+            Character::Space => " ".to_string(),
+            Character::NonBreakSpace => "&nbsp;".to_string(),
+            Character::Hyphen => "-".to_string(),
+            Character::EmDash => "&mdash;".to_string(),
+            Character::EnDash => "&ndash;".to_string(),
+            Character::Emoji(name) => format!(":{}:", escape(name.inner())),
+            Character::Other(c) => escape(&c.to_string()),
+        })
+    }
+
+    fn line_break(&self) -> crate::error::Result<()> {
+        self.write("<br />")
+    }
+
+    fn start_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        self.write(
+            &make_style_stack(styles)
+                .into_iter()
+                .map(|(start, _)| start)
+                .collect::<Vec<&str>>()
+                .join(""),
+        )
+    }
+
+    fn end_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        self.write(
+            &make_style_stack(styles)
+                .into_iter()
+                .rev()
+                .map(|(_, end)| end)
+                .collect::<Vec<&str>>()
+                .join(""),
+        )
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn make_style_stack(styles: &[SpanStyle]) -> Vec<(&'static str, &'static str)> {
+    let mut style_stack = Vec::new();
+    for style in styles {
+        match style {
+            SpanStyle::Plain => style_stack.clear(),
+            SpanStyle::Italic => style_stack.push(("''", "''")),
+            SpanStyle::Bold => style_stack.push(("'''", "'''")),
+            SpanStyle::Mono | SpanStyle::Code => style_stack.push(("<code>", "</code>")),
+            SpanStyle::Strikethrough => style_stack.push(("<s>", "</s>")),
+            SpanStyle::Underline => style_stack.push(("<u>", "</u>")),
+            SpanStyle::SmallCaps => {
+                style_stack.push(("<span style=\"font-variant: small-caps;\">", "</span>"))
+            }
+            SpanStyle::Superscript => style_stack.push(("<sup>", "</sup>")),
+            SpanStyle::Subscript => style_stack.push(("<sub>", "</sub>")),
+            SpanStyle::Sized(size) => style_stack.push((
+                match size {
+                    Size::Largest => "<span style=\"font-size: xx-large;\">",
+                    Size::Larger => "<span style=\"font-size: x-large;\">",
+                    Size::Large => "<span style=\"font-size: large;\">",
+                    Size::Normal => "<span style=\"font-size: medium;\">",
+                    Size::Small => "<span style=\"font-size: small;\">",
+                    Size::Smaller => "<span style=\"font-size: x-small;\">",
+                    Size::Smallest => "<span style=\"font-size: xx-small;\">",
+                },
+                "</span>",
+            )),
+        }
+    }
+    style_stack
+}
+
+fn heading_marker(level: &HeadingLevel) -> String {
+    // Level 1 is reserved for the page title and MediaWiki, like HTML, has no seventh level.
+    string_of_strings("=", (level.clone() as usize + 1).min(6))
+}
+
+fn alignment_style(alignment: &Alignment) -> Option<&'static str> {
+    match alignment {
+        Alignment::Left => None,
+        Alignment::Right => Some("text-align: right;"),
+        Alignment::Centered => Some("text-align: center;"),
+        Alignment::Justified => Some("text-align: justify;"),
+    }
+}
+
+///
+/// External images are written as a bare URL, which is shown inline if the wiki allows external
+/// images, and as a plain link otherwise; wikitext has no way to give these alternate text.
+///
+fn external_image(value: &Image) -> String {
+    value.inner().to_string()
+}
+
+fn file_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+fn is_local(path: &str) -> bool {
+    !(path.contains("://") || path.starts_with("data:"))
+}
+
+///
+/// Escape characters that would otherwise start markup, HTML entities are understood everywhere
+/// in wikitext and so these are used in preference to `<nowiki>`.
+///
+fn escape(text: &str) -> String {
+    escape_html(text)
+        .replace('[', "&#91;")
+        .replace(']', "&#93;")
+        .replace('{', "&#123;")
+        .replace('}', "&#125;")
+        .replace("''", "'&#39;")
+}
+
+///
+/// Escape the first character of each line that would otherwise start a list, definition,
+/// heading, or preformatted block.
+///
+fn escape_line_starts(text: &str, line_start: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut line_start = line_start;
+    for c in text.chars() {
+        match c {
+            '*' if line_start => escaped.push_str("&#42;"),
+            '#' if line_start => escaped.push_str("&#35;"),
+            ';' if line_start => escaped.push_str("&#59;"),
+            ':' if line_start => escaped.push_str("&#58;"),
+            '=' if line_start => escaped.push_str("&#61;"),
+            ' ' if line_start => escaped.push_str("&#32;"),
+            _ => escaped.push(c),
+        }
+        line_start = c == '\n';
+    }
+    escaped
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn escape_attribute(value: &str) -> String {
+    escape_html(value).replace('"', "&quot;")
+}
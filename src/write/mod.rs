@@ -54,14 +54,50 @@ use std::str::FromStr;
 
 use crate::error;
 use crate::model::Document;
+#[cfg(feature = "fmt_asciidoc")]
+use crate::write::asciidoc::AsciiDocWriter;
+#[cfg(feature = "fmt_confluence")]
+use crate::write::confluence::ConfluenceWriter;
+#[cfg(feature = "fmt_docbook")]
+use crate::write::docbook::DocBookWriter;
+#[cfg(feature = "fmt_docx")]
+use crate::write::docx::DocxWriter;
+#[cfg(feature = "fmt_epub")]
+use crate::write::epub::EpubWriter;
+#[cfg(feature = "fmt_gemini")]
+use crate::write::gemini::GeminiWriter;
 #[cfg(feature = "fmt_html")]
 use crate::write::html::HtmlWriter;
+#[cfg(feature = "fmt_jira")]
+use crate::write::jira::JiraWriter;
 #[cfg(feature = "fmt_json")]
 use crate::write::json::JsonWriter;
 #[cfg(feature = "fmt_latex")]
 use crate::write::latex::LatexWriter;
+#[cfg(feature = "fmt_man")]
+use crate::write::man::ManWriter;
 #[cfg(feature = "fmt_markdown")]
 use crate::write::markdown::{MarkdownFlavor, MarkdownWriter};
+#[cfg(feature = "fmt_mediawiki")]
+use crate::write::mediawiki::MediaWikiWriter;
+#[cfg(feature = "fmt_odf")]
+use crate::write::odf::{OdfPackage, OdfWriter};
+#[cfg(feature = "fmt_org")]
+use crate::write::org::OrgWriter;
+#[cfg(feature = "fmt_pandoc")]
+use crate::write::pandoc::PandocWriter;
+#[cfg(feature = "fmt_rst")]
+use crate::write::rst::RstWriter;
+#[cfg(feature = "fmt_rtf")]
+use crate::write::rtf::RtfWriter;
+#[cfg(feature = "fmt_slack")]
+use crate::write::slack::{SlackFormat, SlackWriter};
+#[cfg(feature = "fmt_terminal")]
+use crate::write::terminal::{TerminalConfig, TerminalWriter};
+#[cfg(feature = "fmt_text")]
+use crate::write::text::{PlainTextConfig, PlainTextWriter};
+#[cfg(feature = "fmt_typst")]
+use crate::write::typst::TypstWriter;
 
 // ------------------------------------------------------------------------------------------------
 // Public Types
@@ -88,6 +124,82 @@ pub enum OutputFormat {
     /// quotes, images, etc.
     #[cfg(feature = "fmt_latex")]
     Latex,
+
+    /// The Pandoc JSON AST, as read by `pandoc -f json`, for conversion to any format supported
+    /// by Pandoc.
+    #[cfg(feature = "fmt_pandoc")]
+    PandocJson,
+
+    /// AsciiDoc, as processed by Asciidoctor.
+    #[cfg(feature = "fmt_asciidoc")]
+    AsciiDoc,
+
+    /// reStructuredText, using the directives and roles supported by Sphinx.
+    #[cfg(feature = "fmt_rst")]
+    ReStructuredText,
+
+    /// Emacs Org-mode.
+    #[cfg(feature = "fmt_org")]
+    Org,
+
+    /// Plain text, wrapped to the width given in [`text::PlainTextConfig`](text/struct.PlainTextConfig.html).
+    #[cfg(feature = "fmt_text")]
+    PlainText(PlainTextConfig),
+
+    /// Text for display in a terminal, with ANSI styles and box-drawn tables, configured by
+    /// [`terminal::TerminalConfig`](terminal/struct.TerminalConfig.html).
+    #[cfg(feature = "fmt_terminal")]
+    Terminal(TerminalConfig),
+
+    /// A Unix manual page, using the man(7) roff macros.
+    #[cfg(feature = "fmt_man")]
+    Man,
+
+    /// Typst, with captioned blocks as figures.
+    #[cfg(feature = "fmt_typst")]
+    Typst,
+
+    /// DocBook 5, with headings as nested sections.
+    #[cfg(feature = "fmt_docbook")]
+    DocBook,
+
+    /// EPUB 3, a zip archive with a chapter for each top-level heading.
+    #[cfg(feature = "fmt_epub")]
+    Epub,
+
+    /// OpenDocument text, either as a flat XML file or as a zip package, see
+    /// [`odf::OdfPackage`](odf/enum.OdfPackage.html).
+    #[cfg(feature = "fmt_odf")]
+    OpenDocument(OdfPackage),
+
+    /// Office Open XML, the zip package format used by Microsoft Word.
+    #[cfg(feature = "fmt_docx")]
+    Docx,
+
+    /// Rich Text Format, for word processors that predate the XML-based formats.
+    #[cfg(feature = "fmt_rtf")]
+    Rtf,
+
+    /// The Confluence storage format, the XHTML-based markup used for page bodies.
+    #[cfg(feature = "fmt_confluence")]
+    Confluence,
+
+    /// MediaWiki markup, as used by Wikipedia.
+    #[cfg(feature = "fmt_mediawiki")]
+    MediaWiki,
+
+    /// Jira wiki markup, as used by Jira Server and Data Center.
+    #[cfg(feature = "fmt_jira")]
+    Jira,
+
+    /// A Slack message, either as mrkdwn text or as Block Kit JSON, see
+    /// [`slack::SlackFormat`](slack/enum.SlackFormat.html).
+    #[cfg(feature = "fmt_slack")]
+    Slack(SlackFormat),
+
+    /// Gemini gemtext, the line-oriented markup used by Gemini capsules.
+    #[cfg(feature = "fmt_gemini")]
+    Gemini,
 }
 
 ///
@@ -146,6 +258,96 @@ pub fn write_document<W: Write>(
             let writer = LatexWriter::new(w);
             writer.write_document(doc)
         }
+        #[cfg(feature = "fmt_pandoc")]
+        OutputFormat::PandocJson => {
+            let writer = PandocWriter::new(w);
+            writer.write_document(doc)
+        }
+        #[cfg(feature = "fmt_asciidoc")]
+        OutputFormat::AsciiDoc => {
+            let writer = AsciiDocWriter::new(w);
+            writer.write_document(doc)
+        }
+        #[cfg(feature = "fmt_rst")]
+        OutputFormat::ReStructuredText => {
+            let writer = RstWriter::new(w);
+            writer.write_document(doc)
+        }
+        #[cfg(feature = "fmt_org")]
+        OutputFormat::Org => {
+            let writer = OrgWriter::new(w);
+            writer.write_document(doc)
+        }
+        #[cfg(feature = "fmt_text")]
+        OutputFormat::PlainText(config) => {
+            let writer = PlainTextWriter::new_with(w, config);
+            writer.write_document(doc)
+        }
+        #[cfg(feature = "fmt_terminal")]
+        OutputFormat::Terminal(config) => {
+            let writer = TerminalWriter::new_with(w, config);
+            writer.write_document(doc)
+        }
+        #[cfg(feature = "fmt_man")]
+        OutputFormat::Man => {
+            let writer = ManWriter::new(w);
+            writer.write_document(doc)
+        }
+        #[cfg(feature = "fmt_typst")]
+        OutputFormat::Typst => {
+            let writer = TypstWriter::new(w);
+            writer.write_document(doc)
+        }
+        #[cfg(feature = "fmt_docbook")]
+        OutputFormat::DocBook => {
+            let writer = DocBookWriter::new(w);
+            writer.write_document(doc)
+        }
+        #[cfg(feature = "fmt_epub")]
+        OutputFormat::Epub => {
+            let writer = EpubWriter::new(w);
+            writer.write_document(doc)
+        }
+        #[cfg(feature = "fmt_odf")]
+        OutputFormat::OpenDocument(package) => {
+            let writer = OdfWriter::new_with(w, package);
+            writer.write_document(doc)
+        }
+        #[cfg(feature = "fmt_docx")]
+        OutputFormat::Docx => {
+            let writer = DocxWriter::new(w);
+            writer.write_document(doc)
+        }
+        #[cfg(feature = "fmt_rtf")]
+        OutputFormat::Rtf => {
+            let writer = RtfWriter::new(w);
+            writer.write_document(doc)
+        }
+        #[cfg(feature = "fmt_confluence")]
+        OutputFormat::Confluence => {
+            let writer = ConfluenceWriter::new(w);
+            writer.write_document(doc)
+        }
+        #[cfg(feature = "fmt_mediawiki")]
+        OutputFormat::MediaWiki => {
+            let writer = MediaWikiWriter::new(w);
+            writer.write_document(doc)
+        }
+        #[cfg(feature = "fmt_jira")]
+        OutputFormat::Jira => {
+            let writer = JiraWriter::new(w);
+            writer.write_document(doc)
+        }
+        #[cfg(feature = "fmt_slack")]
+        OutputFormat::Slack(format) => {
+            let writer = SlackWriter::new_with(w, format);
+            writer.write_document(doc)
+        }
+        #[cfg(feature = "fmt_gemini")]
+        OutputFormat::Gemini => {
+            let writer = GeminiWriter::new(w);
+            writer.write_document(doc)
+        }
     }
 }
 
@@ -160,7 +362,7 @@ pub fn write_document_to_string(
     use std::io::Cursor;
     let mut buffer = Cursor::new(Vec::new());
     write_document(doc, format, &mut buffer)?;
-    Ok(String::from_utf8(buffer.into_inner()).unwrap())
+    Ok(String::from_utf8(buffer.into_inner())?)
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -187,6 +389,68 @@ impl Display for OutputFormat {
                 Self::Json => "json".to_string(),
                 #[cfg(feature = "fmt_latex")]
                 Self::Latex => "latex".to_string(),
+                #[cfg(feature = "fmt_pandoc")]
+                Self::PandocJson => "pandoc".to_string(),
+                #[cfg(feature = "fmt_asciidoc")]
+                Self::AsciiDoc => "asciidoc".to_string(),
+                #[cfg(feature = "fmt_rst")]
+                Self::ReStructuredText => "rst".to_string(),
+                #[cfg(feature = "fmt_org")]
+                Self::Org => "org".to_string(),
+                #[cfg(feature = "fmt_text")]
+                Self::PlainText(config) => {
+                    if *config == PlainTextConfig::default() {
+                        "text".to_string()
+                    } else {
+                        format!("text+{}", config.width())
+                    }
+                }
+                #[cfg(feature = "fmt_terminal")]
+                Self::Terminal(config) => {
+                    let default = TerminalConfig::default();
+                    let mut options = Vec::new();
+                    if config.width() != default.width() {
+                        options.push(config.width().to_string());
+                    }
+                    if !config.color() {
+                        options.push("nocolor".to_string());
+                    }
+                    if options.is_empty() {
+                        "terminal".to_string()
+                    } else {
+                        format!("terminal+{}", options.join(","))
+                    }
+                }
+                #[cfg(feature = "fmt_man")]
+                Self::Man => "man".to_string(),
+                #[cfg(feature = "fmt_typst")]
+                Self::Typst => "typst".to_string(),
+                #[cfg(feature = "fmt_docbook")]
+                Self::DocBook => "docbook".to_string(),
+                #[cfg(feature = "fmt_epub")]
+                Self::Epub => "epub".to_string(),
+                #[cfg(feature = "fmt_odf")]
+                Self::OpenDocument(package) => package.to_string(),
+                #[cfg(feature = "fmt_docx")]
+                Self::Docx => "docx".to_string(),
+                #[cfg(feature = "fmt_rtf")]
+                Self::Rtf => "rtf".to_string(),
+                #[cfg(feature = "fmt_confluence")]
+                Self::Confluence => "confluence".to_string(),
+                #[cfg(feature = "fmt_mediawiki")]
+                Self::MediaWiki => "mediawiki".to_string(),
+                #[cfg(feature = "fmt_jira")]
+                Self::Jira => "jira".to_string(),
+                #[cfg(feature = "fmt_slack")]
+                Self::Slack(format) => {
+                    if *format == SlackFormat::default() {
+                        "slack".to_string()
+                    } else {
+                        format!("slack+{}", format)
+                    }
+                }
+                #[cfg(feature = "fmt_gemini")]
+                Self::Gemini => "gemini".to_string(),
             }
         )
     }
@@ -217,6 +481,80 @@ impl FromStr for OutputFormat {
                 "json" => Ok(Self::Json),
                 #[cfg(feature = "fmt_latex")]
                 "latex" | "tex" => Ok(Self::Latex),
+                #[cfg(feature = "fmt_pandoc")]
+                "pandoc" | "pandoc-json" => Ok(Self::PandocJson),
+                #[cfg(feature = "fmt_asciidoc")]
+                "asciidoc" | "adoc" => Ok(Self::AsciiDoc),
+                #[cfg(feature = "fmt_rst")]
+                "rst" | "restructuredtext" => Ok(Self::ReStructuredText),
+                #[cfg(feature = "fmt_org")]
+                "org" | "orgmode" => Ok(Self::Org),
+                #[cfg(feature = "fmt_text")]
+                "text" | "txt" | "plain" => {
+                    if let Some(width) = parts.get(1) {
+                        match width.parse::<usize>() {
+                            Ok(width) => Ok(Self::PlainText(PlainTextConfig::with_width(width))),
+                            Err(_) => Err(error::ErrorKind::UnknownFormat.into()),
+                        }
+                    } else {
+                        Ok(Self::PlainText(PlainTextConfig::default()))
+                    }
+                }
+                #[cfg(feature = "fmt_terminal")]
+                "terminal" | "term" | "ansi" => {
+                    let default = TerminalConfig::default();
+                    let (mut width, mut color) = (default.width(), default.color());
+                    if let Some(options) = parts.get(1) {
+                        for option in options.split(',') {
+                            match (option, option.parse::<usize>()) {
+                                ("nocolor", _) => color = false,
+                                (_, Ok(value)) => width = value,
+                                _ => return Err(error::ErrorKind::UnknownFormat.into()),
+                            }
+                        }
+                    }
+                    Ok(Self::Terminal(TerminalConfig::new(width, color)))
+                }
+                #[cfg(feature = "fmt_man")]
+                "man" | "roff" => Ok(Self::Man),
+                #[cfg(feature = "fmt_typst")]
+                "typst" | "typ" => Ok(Self::Typst),
+                #[cfg(feature = "fmt_docbook")]
+                "docbook" | "docbook5" | "dbk" => Ok(Self::DocBook),
+                #[cfg(feature = "fmt_epub")]
+                "epub" | "epub3" => Ok(Self::Epub),
+                #[cfg(feature = "fmt_odf")]
+                "odf" | "opendocument" => {
+                    if let Some(package) = parts.get(1) {
+                        Ok(Self::OpenDocument(OdfPackage::from_str(package)?))
+                    } else {
+                        Ok(Self::OpenDocument(OdfPackage::default()))
+                    }
+                }
+                #[cfg(feature = "fmt_odf")]
+                "fodt" | "odt" => Ok(Self::OpenDocument(OdfPackage::from_str(parts[0])?)),
+                #[cfg(feature = "fmt_docx")]
+                "docx" | "word" => Ok(Self::Docx),
+                #[cfg(feature = "fmt_rtf")]
+                "rtf" => Ok(Self::Rtf),
+                #[cfg(feature = "fmt_confluence")]
+                "confluence" => Ok(Self::Confluence),
+                #[cfg(feature = "fmt_mediawiki")]
+                "mediawiki" | "wikitext" => Ok(Self::MediaWiki),
+                #[cfg(feature = "fmt_jira")]
+                "jira" => Ok(Self::Jira),
+                #[cfg(feature = "fmt_slack")]
+                "slack" => {
+                    if let Some(format) = parts.get(1) {
+                        Ok(Self::Slack(SlackFormat::from_str(format)?))
+                    } else {
+                        Ok(Self::Slack(SlackFormat::default()))
+                    }
+                }
+                #[cfg(feature = "fmt_slack")]
+                "mrkdwn" | "blockkit" => Ok(Self::Slack(SlackFormat::from_str(parts[0])?)),
+                #[cfg(feature = "fmt_gemini")]
+                "gemini" | "gemtext" | "gmi" => Ok(Self::Gemini),
                 _ => Err(error::ErrorKind::UnknownFormat.into()),
             }
         }
@@ -227,16 +565,70 @@ impl FromStr for OutputFormat {
 // Modules
 // ------------------------------------------------------------------------------------------------
 
+#[cfg(feature = "fmt_asciidoc")]
+pub mod asciidoc;
+
+#[cfg(feature = "fmt_confluence")]
+pub mod confluence;
+
+#[cfg(feature = "fmt_docbook")]
+pub mod docbook;
+
+#[cfg(feature = "fmt_docx")]
+pub mod docx;
+
+#[cfg(feature = "fmt_epub")]
+pub mod epub;
+
+#[cfg(feature = "fmt_gemini")]
+pub mod gemini;
+
 #[cfg(feature = "fmt_html")]
 pub mod html;
 
+#[cfg(feature = "fmt_jira")]
+pub mod jira;
+
 #[cfg(feature = "fmt_json")]
 pub mod json;
 
 #[cfg(feature = "fmt_latex")]
 pub mod latex;
 
+#[cfg(feature = "fmt_man")]
+pub mod man;
+
 #[cfg(feature = "fmt_markdown")]
 pub mod markdown;
 
+#[cfg(feature = "fmt_mediawiki")]
+pub mod mediawiki;
+
+#[cfg(feature = "fmt_odf")]
+pub mod odf;
+
+#[cfg(feature = "fmt_org")]
+pub mod org;
+
+#[cfg(feature = "fmt_pandoc")]
+pub mod pandoc;
+
+#[cfg(feature = "fmt_rst")]
+pub mod rst;
+
+#[cfg(feature = "fmt_rtf")]
+pub mod rtf;
+
+#[cfg(feature = "fmt_slack")]
+pub mod slack;
+
+#[cfg(feature = "fmt_terminal")]
+pub mod terminal;
+
+#[cfg(feature = "fmt_text")]
+pub mod text;
+
+#[cfg(feature = "fmt_typst")]
+pub mod typst;
+
 pub(crate) mod utils;
@@ -0,0 +1,1151 @@
+/*!
+Write a document as an [OpenDocument](https://docs.oasis-open.org/office/OpenDocument/v1.3/)
+text document. This may be either a single, flat, XML file (usually with the `.fodt` extension)
+or a zip package (usually with the `.odt` extension) containing separate content, styles, and
+metadata files.
+
+Headings and paragraphs use the common styles defined by LibreOffice (such as "Heading 1" and
+"Text body"), paragraph alignment and span styles are written as automatic styles. Labels are
+written as bookmarks, so that internal links may refer to them.
+
+# Example
+
+```rust
+# use somedoc::model::Document;
+use somedoc::write::odf::{writer, OdfPackage};
+
+# fn make_some_document() -> Document { Document::default() }
+let doc = make_some_document();
+
+writer(&doc, OdfPackage::Flat, &mut std::io::stdout()).unwrap();
+```
+*/
+
+use crate::model::block::{
+    Alignment, Caption, Column, FrontMatter, HasAlignment, HasCaption, HeadingLevel, Label,
+    ListKind,
+};
+use crate::model::document::Metadata;
+use crate::model::inline::text::Size;
+use crate::model::inline::{
+    Character, HasInlineContent, HyperLink, HyperLinkTarget, Image, Math, SpanStyle, Text,
+};
+use crate::model::visitor::{
+    walk_document, BlockVisitor, DocumentVisitor, InlineVisitor, TableVisitor,
+};
+use crate::model::Document;
+use crate::write::{ConfigurableWriter, OutputFormat, Writer};
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::io::{Cursor, Write};
+use std::str::FromStr;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The packaging used for the OpenDocument output.
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum OdfPackage {
+    /// A single XML file, usually with the `.fodt` extension.
+    #[default]
+    Flat,
+    /// A zip archive, usually with the `.odt` extension.
+    Zipped,
+}
+
+///
+/// Implementation of the OpenDocument writer structure, usually this is accessed via the `writer`
+/// function, but may be used directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use somedoc::model::Document;
+/// use somedoc::write::odf::{OdfPackage, OdfWriter};
+/// use somedoc::write::{ConfigurableWriter, Writer};
+///
+/// # fn make_some_document() -> Document { Document::default() }
+/// let doc = make_some_document();
+/// let mut out = std::io::stdout();
+/// let writer = OdfWriter::new_with(&mut out, OdfPackage::Flat);
+/// assert!(writer.write_document(&doc).is_ok());
+/// ```
+///
+#[derive(Debug)]
+pub struct OdfWriter<'a, W: Write> {
+    package: OdfPackage,
+    meta: RefCell<Vec<String>>,
+    body: RefCell<String>,
+    indent: RefCell<usize>,
+    paragraph_styles: RefCell<Vec<(&'static str, Alignment)>>,
+    text_styles: RefCell<Vec<String>>,
+    span_stack: RefCell<Vec<(TextProperties, bool)>>,
+    list_stack: RefCell<Vec<bool>>,
+    list_kinds: RefCell<(bool, bool)>,
+    quote_depth: RefCell<usize>,
+    bookmarks: RefCell<Vec<String>>,
+    table: RefCell<Option<TableState>>,
+    table_count: RefCell<usize>,
+    image_count: RefCell<usize>,
+    w: RefCell<&'a mut W>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct TextProperties {
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    strikethrough: bool,
+    mono: bool,
+    small_caps: bool,
+    position: Option<&'static str>,
+    size: Option<&'static str>,
+}
+
+#[derive(Debug)]
+struct TableState {
+    start: usize,
+    columns: usize,
+    alignments: Vec<Alignment>,
+    cell_count: usize,
+}
+
+const MIMETYPE: &str = "application/vnd.oasis.opendocument.text";
+
+const NAMESPACES: &str = r#"xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:draw="urn:oasis:names:tc:opendocument:xmlns:drawing:1.0" xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0" xmlns:xlink="http://www.w3.org/1999/xlink" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0" xmlns:svg="urn:oasis:names:tc:opendocument:xmlns:svg-compatible:1.0" office:version="1.3""#;
+
+const FONT_FACES: &str = r#"  <office:font-face-decls>
+    <style:font-face style:name="Liberation Mono" svg:font-family="'Liberation Mono'" style:font-family-generic="modern" style:font-pitch="fixed"/>
+  </office:font-face-decls>
+"#;
+
+const COMMON_STYLES: &str = r#"  <office:styles>
+    <style:style style:name="Standard" style:family="paragraph" style:class="text"/>
+    <style:style style:name="Text_20_body" style:display-name="Text body" style:family="paragraph" style:parent-style-name="Standard" style:class="text">
+      <style:paragraph-properties fo:margin-top="0cm" fo:margin-bottom="0.247cm"/>
+    </style:style>
+    <style:style style:name="Title" style:family="paragraph" style:parent-style-name="Standard" style:next-style-name="Text_20_body" style:class="chapter">
+      <style:paragraph-properties fo:text-align="center" fo:margin-bottom="0.423cm"/>
+      <style:text-properties fo:font-size="200%" fo:font-weight="bold"/>
+    </style:style>
+    <style:style style:name="Heading" style:family="paragraph" style:parent-style-name="Standard" style:next-style-name="Text_20_body" style:class="text">
+      <style:paragraph-properties fo:margin-top="0.423cm" fo:margin-bottom="0.212cm" fo:keep-with-next="always"/>
+      <style:text-properties fo:font-weight="bold"/>
+    </style:style>
+    <style:style style:name="Heading_20_1" style:display-name="Heading 1" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="1" style:class="text">
+      <style:text-properties fo:font-size="130%"/>
+    </style:style>
+    <style:style style:name="Heading_20_2" style:display-name="Heading 2" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="2" style:class="text">
+      <style:text-properties fo:font-size="115%"/>
+    </style:style>
+    <style:style style:name="Heading_20_3" style:display-name="Heading 3" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="3" style:class="text">
+      <style:text-properties fo:font-size="101%"/>
+    </style:style>
+    <style:style style:name="Heading_20_4" style:display-name="Heading 4" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="4" style:class="text">
+      <style:text-properties fo:font-size="95%" fo:font-style="italic"/>
+    </style:style>
+    <style:style style:name="Heading_20_5" style:display-name="Heading 5" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="5" style:class="text">
+      <style:text-properties fo:font-size="85%"/>
+    </style:style>
+    <style:style style:name="Heading_20_6" style:display-name="Heading 6" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="6" style:class="text">
+      <style:text-properties fo:font-size="85%" fo:font-style="italic"/>
+    </style:style>
+    <style:style style:name="Heading_20_7" style:display-name="Heading 7" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="7" style:class="text">
+      <style:text-properties fo:font-size="80%"/>
+    </style:style>
+    <style:style style:name="Abstract" style:family="paragraph" style:parent-style-name="Text_20_body" style:class="text">
+      <style:paragraph-properties fo:margin-left="1cm" fo:margin-right="1cm" fo:text-align="justify"/>
+      <style:text-properties fo:font-style="italic"/>
+    </style:style>
+    <style:style style:name="Quotations" style:family="paragraph" style:parent-style-name="Standard" style:class="html">
+      <style:paragraph-properties fo:margin-left="1cm" fo:margin-right="1cm" fo:margin-top="0cm" fo:margin-bottom="0.283cm"/>
+    </style:style>
+    <style:style style:name="Preformatted_20_Text" style:display-name="Preformatted Text" style:family="paragraph" style:parent-style-name="Standard" style:class="html">
+      <style:paragraph-properties fo:margin-top="0cm" fo:margin-bottom="0cm"/>
+      <style:text-properties style:font-name="Liberation Mono" fo:font-size="90%"/>
+    </style:style>
+    <style:style style:name="List_20_Heading" style:display-name="List Heading" style:family="paragraph" style:parent-style-name="Standard" style:next-style-name="List_20_Contents" style:class="html">
+      <style:text-properties fo:font-weight="bold"/>
+    </style:style>
+    <style:style style:name="List_20_Contents" style:display-name="List Contents" style:family="paragraph" style:parent-style-name="Standard" style:class="html">
+      <style:paragraph-properties fo:margin-left="1cm" fo:margin-bottom="0.212cm"/>
+    </style:style>
+    <style:style style:name="List" style:family="paragraph" style:parent-style-name="Text_20_body" style:class="list"/>
+    <style:style style:name="Table_20_Contents" style:display-name="Table Contents" style:family="paragraph" style:parent-style-name="Standard" style:class="extra"/>
+    <style:style style:name="Table_20_Heading" style:display-name="Table Heading" style:family="paragraph" style:parent-style-name="Table_20_Contents" style:class="extra">
+      <style:paragraph-properties fo:text-align="center"/>
+      <style:text-properties fo:font-weight="bold"/>
+    </style:style>
+    <style:style style:name="Caption" style:family="paragraph" style:parent-style-name="Standard" style:class="extra">
+      <style:paragraph-properties fo:margin-top="0.212cm" fo:margin-bottom="0.212cm"/>
+      <style:text-properties fo:font-style="italic"/>
+    </style:style>
+    <style:style style:name="Horizontal_20_Line" style:display-name="Horizontal Line" style:family="paragraph" style:parent-style-name="Standard" style:class="html">
+      <style:paragraph-properties fo:margin-bottom="0.5cm" fo:border-bottom="0.06pt solid #808080" fo:padding="0cm"/>
+    </style:style>
+    <style:style style:name="Source_20_Text" style:display-name="Source Text" style:family="text">
+      <style:text-properties style:font-name="Liberation Mono"/>
+    </style:style>
+  </office:styles>
+"#;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the writer function for OpenDocument text.
+///
+/// While this can be called directly it is most often used  by calling either
+/// [`model::write_document`](../fn.write_document.html) or
+/// [`model::write_document_to_string`](../fn.write_document_to_string.html), note that the
+/// latter is only useful for the flat package.
+///
+#[inline]
+pub fn writer<W: Write>(
+    doc: &Document,
+    package: OdfPackage,
+    w: &mut W,
+) -> crate::error::Result<()> {
+    let writer = OdfWriter::new_with(w, package);
+    writer.write_document(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for OdfPackage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                OdfPackage::Flat => "fodt",
+                OdfPackage::Zipped => "odt",
+            }
+        )
+    }
+}
+
+impl FromStr for OdfPackage {
+    type Err = crate::error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fodt" | "flat" => Ok(Self::Flat),
+            "odt" | "zip" => Ok(Self::Zipped),
+            _ => Err(crate::error::ErrorKind::UnknownFormat.into()),
+        }
+    }
+}
+
+impl From<OdfPackage> for OutputFormat {
+    fn from(package: OdfPackage) -> Self {
+        OutputFormat::OpenDocument(package)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl TextProperties {
+    fn with_styles(&self, styles: &[SpanStyle]) -> Self {
+        let mut properties = self.clone();
+        for style in styles {
+            match style {
+                SpanStyle::Plain => properties = Default::default(),
+                SpanStyle::Italic => properties.italic = true,
+                SpanStyle::Bold => properties.bold = true,
+                SpanStyle::Mono | SpanStyle::Code => properties.mono = true,
+                SpanStyle::Strikethrough => properties.strikethrough = true,
+                SpanStyle::Underline => properties.underline = true,
+                SpanStyle::SmallCaps => properties.small_caps = true,
+                SpanStyle::Superscript => properties.position = Some("super 58%"),
+                SpanStyle::Subscript => properties.position = Some("sub 58%"),
+                SpanStyle::Sized(size) => {
+                    properties.size = Some(match size {
+                        Size::Largest => "200%",
+                        Size::Larger => "150%",
+                        Size::Large => "120%",
+                        Size::Normal => "100%",
+                        Size::Small => "83%",
+                        Size::Smaller => "70%",
+                        Size::Smallest => "58%",
+                    })
+                }
+            }
+        }
+        properties
+    }
+
+    ///
+    /// Spans are nested, so only the properties that differ from the enclosing span are written;
+    /// this is what allows a plain span to turn off the styles of its parent.
+    ///
+    fn attributes(&self, parent: &TextProperties) -> String {
+        let mut attributes: Vec<String> = Default::default();
+        if self.bold != parent.bold {
+            attributes.push(format!(
+                "fo:font-weight=\"{}\"",
+                if self.bold { "bold" } else { "normal" }
+            ));
+        }
+        if self.italic != parent.italic {
+            attributes.push(format!(
+                "fo:font-style=\"{}\"",
+                if self.italic { "italic" } else { "normal" }
+            ));
+        }
+        if self.underline != parent.underline {
+            if self.underline {
+                attributes.push("style:text-underline-style=\"solid\" style:text-underline-width=\"auto\" style:text-underline-color=\"font-color\"".to_string());
+            } else {
+                attributes.push("style:text-underline-style=\"none\"".to_string());
+            }
+        }
+        if self.strikethrough != parent.strikethrough {
+            attributes.push(format!(
+                "style:text-line-through-style=\"{}\"",
+                if self.strikethrough { "solid" } else { "none" }
+            ));
+        }
+        if self.mono != parent.mono && self.mono {
+            attributes.push("style:font-name=\"Liberation Mono\"".to_string());
+        }
+        if self.small_caps != parent.small_caps {
+            attributes.push(format!(
+                "fo:font-variant=\"{}\"",
+                if self.small_caps {
+                    "small-caps"
+                } else {
+                    "normal"
+                }
+            ));
+        }
+        if self.position != parent.position {
+            attributes.push(format!(
+                "style:text-position=\"{}\"",
+                self.position.unwrap_or("0% 100%")
+            ));
+        }
+        if self.size != parent.size {
+            attributes.push(format!("fo:font-size=\"{}\"", self.size.unwrap_or("100%")));
+        }
+        attributes.join(" ")
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> Writer<'a, W> for OdfWriter<'a, W> {
+    fn new(w: &'a mut W) -> Self {
+        Self::new_with(w, Default::default())
+    }
+
+    fn write_document(&self, doc: &Document) -> crate::error::Result<()> {
+        if let Some(abstract_block) = doc.abstract_block() {
+            self.meta.borrow_mut().push(format!(
+                "<dc:description>{}</dc:description>",
+                escape(&abstract_block.unformatted_string())
+            ));
+        }
+        walk_document(doc, self)?;
+        match self.package {
+            OdfPackage::Flat => {
+                let mut w = self.w.borrow_mut();
+                writeln!(w, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+                writeln!(
+                    w,
+                    "<office:document {} office:mimetype=\"{}\">",
+                    NAMESPACES, MIMETYPE
+                )?;
+                write!(w, "{}", self.meta_xml())?;
+                write!(w, "{}", FONT_FACES)?;
+                write!(w, "{}", COMMON_STYLES)?;
+                write!(w, "{}", self.automatic_styles())?;
+                write!(w, "{}", self.body_xml())?;
+                writeln!(w, "</office:document>")?;
+            }
+            OdfPackage::Zipped => {
+                let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+                let stored = FileOptions::default().compression_method(CompressionMethod::Stored);
+                let deflated =
+                    FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+                // the mimetype must be the first entry, and must not be compressed.
+                zip.start_file("mimetype", stored)?;
+                zip.write_all(MIMETYPE.as_bytes())?;
+
+                zip.start_file("META-INF/manifest.xml", deflated)?;
+                zip.write_all(manifest_xml().as_bytes())?;
+
+                zip.start_file("meta.xml", deflated)?;
+                zip.write_all(package_part("office:document-meta", &self.meta_xml()).as_bytes())?;
+
+                zip.start_file("styles.xml", deflated)?;
+                zip.write_all(
+                    package_part(
+                        "office:document-styles",
+                        &format!("{}{}", FONT_FACES, COMMON_STYLES),
+                    )
+                    .as_bytes(),
+                )?;
+
+                zip.start_file("content.xml", deflated)?;
+                zip.write_all(
+                    package_part(
+                        "office:document-content",
+                        &format!(
+                            "{}{}{}",
+                            FONT_FACES,
+                            self.automatic_styles(),
+                            self.body_xml()
+                        ),
+                    )
+                    .as_bytes(),
+                )?;
+
+                let archive = zip.finish()?.into_inner();
+                self.w.borrow_mut().write_all(&archive)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a, W: Write> ConfigurableWriter<'a, W, OdfPackage> for OdfWriter<'a, W> {
+    fn new_with(w: &'a mut W, package: OdfPackage) -> Self {
+        Self {
+            package,
+            meta: RefCell::from(Vec::default()),
+            body: RefCell::from(String::new()),
+            indent: RefCell::from(3),
+            paragraph_styles: RefCell::from(Vec::default()),
+            text_styles: RefCell::from(Vec::default()),
+            span_stack: RefCell::from(Vec::default()),
+            list_stack: RefCell::from(Vec::default()),
+            list_kinds: RefCell::from((false, false)),
+            quote_depth: RefCell::from(0),
+            bookmarks: RefCell::from(Vec::default()),
+            table: RefCell::from(None),
+            table_count: RefCell::from(0),
+            image_count: RefCell::from(0),
+            w: RefCell::from(w),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> OdfWriter<'a, W> {
+    fn write(&self, text: &str) {
+        self.body.borrow_mut().push_str(text);
+    }
+
+    fn start_line(&self) {
+        self.write(&format!("{: ^1$}", "", *self.indent.borrow() * 2));
+    }
+
+    fn write_line(&self, text: &str) {
+        self.start_line();
+        self.write(text);
+        self.write("\n");
+    }
+
+    fn open(&self, tag: &str) {
+        self.write_line(tag);
+        *self.indent.borrow_mut() += 1;
+    }
+
+    fn close(&self, tag: &str) {
+        *self.indent.borrow_mut() -= 1;
+        self.write_line(tag);
+    }
+
+    fn start_paragraph_with(&self, tag: &str, style: &str) {
+        self.start_line();
+        self.write(&format!("<{} text:style-name=\"{}\">", tag, style));
+        self.write_bookmarks();
+    }
+
+    fn end_paragraph_with(&self, tag: &str) {
+        self.write(&format!("</{}>\n", tag));
+    }
+
+    fn paragraph(&self, style: &str, content: &str) {
+        self.start_paragraph_with("text:p", style);
+        self.write(content);
+        self.end_paragraph_with("text:p");
+    }
+
+    fn bookmark(&self, label: &Option<Label>) {
+        if let Some(label) = label {
+            self.bookmarks.borrow_mut().push(label.to_string());
+        }
+    }
+
+    fn write_bookmarks(&self) {
+        let bookmarks: Vec<String> = self.bookmarks.borrow_mut().drain(..).collect();
+        for bookmark in bookmarks {
+            self.write(&format!(
+                "<text:bookmark text:name=\"{}\"/>",
+                escape(&bookmark)
+            ));
+        }
+    }
+
+    fn caption(&self, caption: &Option<Caption>, label: &Option<Label>) {
+        if let Some(caption) = caption {
+            self.bookmark(label);
+            self.paragraph("Caption", &escape(caption.inner()));
+        }
+    }
+
+    fn paragraph_style(&self, parent: &'static str, alignment: &Alignment) -> String {
+        if *alignment == Alignment::Left {
+            return parent.to_string();
+        }
+        let mut styles = self.paragraph_styles.borrow_mut();
+        let key = (parent, alignment.clone());
+        let index = match styles.iter().position(|style| *style == key) {
+            Some(index) => index,
+            None => {
+                styles.push(key);
+                styles.len() - 1
+            }
+        };
+        format!("P{}", index + 1)
+    }
+
+    fn text_style(&self, attributes: String) -> String {
+        let mut styles = self.text_styles.borrow_mut();
+        let index = match styles.iter().position(|style| *style == attributes) {
+            Some(index) => index,
+            None => {
+                styles.push(attributes);
+                styles.len() - 1
+            }
+        };
+        format!("T{}", index + 1)
+    }
+
+    fn preformatted(&self, value: &str, label: &Option<Label>) {
+        self.bookmark(label);
+        for line in value.lines() {
+            self.paragraph("Preformatted_20_Text", &preserve_spaces(line));
+        }
+    }
+
+    fn meta_xml(&self) -> String {
+        let mut meta = String::from("  <office:meta>\n");
+        for line in self.meta.borrow().iter() {
+            meta.push_str(&format!("    {}\n", line));
+        }
+        meta.push_str("  </office:meta>\n");
+        meta
+    }
+
+    fn automatic_styles(&self) -> String {
+        let mut styles = String::from("  <office:automatic-styles>\n");
+        for (i, (parent, alignment)) in self.paragraph_styles.borrow().iter().enumerate() {
+            styles.push_str(&format!(
+                "    <style:style style:name=\"P{}\" style:family=\"paragraph\" style:parent-style-name=\"{}\">\n      <style:paragraph-properties fo:text-align=\"{}\"/>\n    </style:style>\n",
+                i + 1,
+                parent,
+                text_align(alignment)
+            ));
+        }
+        for (i, attributes) in self.text_styles.borrow().iter().enumerate() {
+            styles.push_str(&format!(
+                "    <style:style style:name=\"T{}\" style:family=\"text\">\n      <style:text-properties {}/>\n    </style:style>\n",
+                i + 1,
+                attributes
+            ));
+        }
+        let (unordered, ordered) = *self.list_kinds.borrow();
+        if unordered {
+            styles.push_str("    <text:list-style style:name=\"L1\">\n");
+            for level in 1..=10 {
+                styles.push_str(&format!(
+                    "      <text:list-level-style-bullet text:level=\"{}\" text:bullet-char=\"{}\">\n{}      </text:list-level-style-bullet>\n",
+                    level,
+                    ["•", "◦", "▪"][(level - 1) % 3],
+                    list_level_properties(level)
+                ));
+            }
+            styles.push_str("    </text:list-style>\n");
+        }
+        if ordered {
+            styles.push_str("    <text:list-style style:name=\"L2\">\n");
+            for level in 1..=10 {
+                styles.push_str(&format!(
+                    "      <text:list-level-style-number text:level=\"{}\" style:num-suffix=\".\" style:num-format=\"1\">\n{}      </text:list-level-style-number>\n",
+                    level,
+                    list_level_properties(level)
+                ));
+            }
+            styles.push_str("    </text:list-style>\n");
+        }
+        styles.push_str("  </office:automatic-styles>\n");
+        styles
+    }
+
+    fn body_xml(&self) -> String {
+        format!(
+            "  <office:body>\n    <office:text>\n{}    </office:text>\n  </office:body>\n",
+            self.body.borrow()
+        )
+    }
+}
+
+impl<'a, W: Write> DocumentVisitor for OdfWriter<'a, W> {
+    fn metadata(&self, meta_datum: &Metadata) -> crate::error::Result<()> {
+        let mut meta = self.meta.borrow_mut();
+        match meta_datum {
+            Metadata::Author(author) => {
+                if meta
+                    .iter()
+                    .any(|line| line.starts_with("<meta:initial-creator>"))
+                {
+                    meta.push(user_defined("author", &author.name));
+                } else {
+                    meta.push(format!(
+                        "<meta:initial-creator>{}</meta:initial-creator>",
+                        escape(&author.name)
+                    ));
+                }
+            }
+            Metadata::Keywords(keywords) => {
+                for keyword in keywords {
+                    meta.push(format!("<meta:keyword>{}</meta:keyword>", escape(keyword)));
+                }
+            }
+            Metadata::Title(title) => {
+                meta.push(format!("<dc:title>{}</dc:title>", escape(title)));
+                drop(meta);
+                self.paragraph("Title", &escape(title));
+            }
+            _ => meta.push(user_defined(&meta_datum.key(), &meta_datum.value_string())),
+        }
+        Ok(())
+    }
+
+    fn block_visitor(&self) -> Option<&dyn BlockVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> BlockVisitor for OdfWriter<'a, W> {
+    fn start_abstract(&self) -> crate::error::Result<()> {
+        self.start_paragraph_with("text:p", "Abstract");
+        Ok(())
+    }
+
+    fn end_abstract(&self) -> crate::error::Result<()> {
+        self.end_paragraph_with("text:p");
+        Ok(())
+    }
+
+    fn comment(&self, value: &str) -> crate::error::Result<()> {
+        // "--" may not appear within an XML comment.
+        self.write_line(&format!("<!-- {} -->", value.replace("--", "- -")));
+        Ok(())
+    }
+
+    fn front_matter(&self, value: &FrontMatter) -> crate::error::Result<()> {
+        if *value == FrontMatter::TableOfContents {
+            self.open("<text:table-of-content text:name=\"Table of Contents\">");
+            self.write_line("<text:table-of-content-source text:outline-level=\"10\"/>");
+            self.write_line("<text:index-body/>");
+            self.close("</text:table-of-content>");
+        }
+        Ok(())
+    }
+
+    fn start_heading(
+        &self,
+        level: &HeadingLevel,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        let level = level.clone() as u8;
+        self.bookmark(label);
+        self.start_line();
+        self.write(&format!(
+            "<text:h text:style-name=\"Heading_20_{}\" text:outline-level=\"{}\">",
+            level, level
+        ));
+        self.write_bookmarks();
+        Ok(())
+    }
+
+    fn end_heading(&self, _: &HeadingLevel, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_paragraph_with("text:h");
+        Ok(())
+    }
+
+    fn image(
+        &self,
+        value: &Image,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        if caption.is_none() {
+            self.bookmark(label);
+        }
+        self.start_paragraph_with("text:p", "Standard");
+        InlineVisitor::image(self, value)?;
+        self.end_paragraph_with("text:p");
+        self.caption(caption, label);
+        Ok(())
+    }
+
+    fn math(
+        &self,
+        value: &Math,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        if caption.is_none() {
+            self.bookmark(label);
+        }
+        let style = self.paragraph_style("Preformatted_20_Text", &Alignment::Centered);
+        self.paragraph(&style, &escape(value.inner()));
+        self.caption(caption, label);
+        Ok(())
+    }
+
+    fn start_list(&self, kind: &ListKind, label: &Option<Label>) -> crate::error::Result<()> {
+        let wrap = matches!(self.list_stack.borrow().last(), Some(false));
+        if wrap {
+            // a nested list must be within an item, this one has no preceding item to use.
+            self.open("<text:list-item>");
+            if let Some(item_open) = self.list_stack.borrow_mut().last_mut() {
+                *item_open = true;
+            }
+        }
+        self.bookmark(label);
+        let style = match kind {
+            ListKind::Ordered => {
+                self.list_kinds.borrow_mut().1 = true;
+                "L2"
+            }
+            ListKind::Unordered => {
+                self.list_kinds.borrow_mut().0 = true;
+                "L1"
+            }
+        };
+        self.list_stack.borrow_mut().push(false);
+        self.open(&format!("<text:list text:style-name=\"{}\">", style));
+        Ok(())
+    }
+
+    fn end_list(&self, _: &ListKind, _: &Option<Label>) -> crate::error::Result<()> {
+        if self.list_stack.borrow_mut().pop() == Some(true) {
+            self.close("</text:list-item>");
+        }
+        self.close("</text:list>");
+        Ok(())
+    }
+
+    fn start_list_item(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        // items are closed lazily so that a following nested list is placed within them.
+        let item_open = self.list_stack.borrow_mut().last_mut().map(|open| {
+            let was_open = *open;
+            *open = true;
+            was_open
+        });
+        if item_open == Some(true) {
+            self.close("</text:list-item>");
+        }
+        self.open("<text:list-item>");
+        self.bookmark(label);
+        self.start_paragraph_with("text:p", "List");
+        Ok(())
+    }
+
+    fn end_list_item(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_paragraph_with("text:p");
+        Ok(())
+    }
+
+    fn start_definition_list(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.bookmark(label);
+        Ok(())
+    }
+
+    fn start_definition(&self, term: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.bookmark(label);
+        self.paragraph("List_20_Heading", &escape(term));
+        Ok(())
+    }
+
+    fn start_definition_list_text(&self) -> crate::error::Result<()> {
+        self.start_paragraph_with("text:p", "List_20_Contents");
+        Ok(())
+    }
+
+    fn end_definition_list_text(&self) -> crate::error::Result<()> {
+        self.end_paragraph_with("text:p");
+        Ok(())
+    }
+
+    fn formatted(&self, value: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.preformatted(value, label);
+        Ok(())
+    }
+
+    fn code_block(
+        &self,
+        code: &str,
+        _: &Option<String>,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        if caption.is_some() {
+            self.preformatted(code, &None);
+        } else {
+            self.preformatted(code, label);
+        }
+        self.caption(caption, label);
+        Ok(())
+    }
+
+    fn start_paragraph(
+        &self,
+        alignment: &Alignment,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        let parent = if *self.quote_depth.borrow() > 0 {
+            "Quotations"
+        } else {
+            "Text_20_body"
+        };
+        self.bookmark(label);
+        let style = self.paragraph_style(parent, alignment);
+        self.start_paragraph_with("text:p", &style);
+        Ok(())
+    }
+
+    fn end_paragraph(&self, _: &Alignment, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_paragraph_with("text:p");
+        Ok(())
+    }
+
+    fn start_quote(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.bookmark(label);
+        *self.quote_depth.borrow_mut() += 1;
+        Ok(())
+    }
+
+    fn end_quote(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        *self.quote_depth.borrow_mut() -= 1;
+        Ok(())
+    }
+
+    fn thematic_break(&self) -> crate::error::Result<()> {
+        self.write_line("<text:p text:style-name=\"Horizontal_20_Line\"/>");
+        Ok(())
+    }
+
+    fn table_visitor(&self) -> Option<&dyn TableVisitor> {
+        Some(self)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> TableVisitor for OdfWriter<'a, W> {
+    fn start_table(&self, _: &Option<Caption>, label: &Option<Label>) -> crate::error::Result<()> {
+        *self.table_count.borrow_mut() += 1;
+        let name = match label {
+            Some(label) => label.to_string(),
+            None => format!("Table{}", self.table_count.borrow()),
+        };
+        self.open(&format!("<table:table table:name=\"{}\">", escape(&name)));
+        // the column definitions are inserted here once the number of columns is known.
+        let _ = self.table.replace(Some(TableState {
+            start: self.body.borrow().len(),
+            columns: 0,
+            alignments: Default::default(),
+            cell_count: 0,
+        }));
+        Ok(())
+    }
+
+    fn start_table_header_row(&self) -> crate::error::Result<()> {
+        self.open("<table:table-header-rows>");
+        self.open("<table:table-row>");
+        Ok(())
+    }
+
+    fn table_header_cell(
+        &self,
+        column_cell: &Column,
+        _column_idx: usize,
+    ) -> crate::error::Result<()> {
+        if let Some(table) = self.table.borrow_mut().as_mut() {
+            table.alignments.push(column_cell.alignment().clone());
+            table.columns = table.columns.max(table.alignments.len());
+        }
+        self.open("<table:table-cell office:value-type=\"string\">");
+        self.paragraph("Table_20_Heading", &escape(column_cell.text()));
+        self.close("</table:table-cell>");
+        Ok(())
+    }
+
+    fn end_table_header_row(&self) -> crate::error::Result<()> {
+        self.close("</table:table-row>");
+        self.close("</table:table-header-rows>");
+        Ok(())
+    }
+
+    fn start_table_row(&self, _: usize) -> crate::error::Result<()> {
+        if let Some(table) = self.table.borrow_mut().as_mut() {
+            table.cell_count = 0;
+        }
+        self.open("<table:table-row>");
+        Ok(())
+    }
+
+    fn start_table_cell(&self, _: usize, label: &Option<Label>) -> crate::error::Result<()> {
+        let alignment = match self.table.borrow_mut().as_mut() {
+            Some(table) => {
+                table.cell_count += 1;
+                table.columns = table.columns.max(table.cell_count);
+                table
+                    .alignments
+                    .get(table.cell_count - 1)
+                    .cloned()
+                    .unwrap_or_default()
+            }
+            None => Alignment::default(),
+        };
+        self.open("<table:table-cell office:value-type=\"string\">");
+        self.bookmark(label);
+        let style = self.paragraph_style("Table_20_Contents", &alignment);
+        self.start_paragraph_with("text:p", &style);
+        Ok(())
+    }
+
+    fn end_table_cell(&self, _: usize, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_paragraph_with("text:p");
+        self.close("</table:table-cell>");
+        Ok(())
+    }
+
+    fn end_table_row(&self, _: usize) -> crate::error::Result<()> {
+        self.close("</table:table-row>");
+        Ok(())
+    }
+
+    fn end_table(&self, caption: &Option<Caption>, _: &Option<Label>) -> crate::error::Result<()> {
+        if let Some(table) = self.table.replace(None) {
+            let columns = format!(
+                "{: ^1$}<table:table-column table:number-columns-repeated=\"{2}\"/>\n",
+                "",
+                *self.indent.borrow() * 2,
+                table.columns.max(1)
+            );
+            self.body.borrow_mut().insert_str(table.start, &columns);
+        }
+        self.close("</table:table>");
+        if caption.is_some() {
+            // the table's label is its name, and so is not needed as a bookmark.
+            self.caption(caption, &None);
+        }
+        Ok(())
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> InlineVisitor for OdfWriter<'a, W> {
+    fn link(&self, value: &HyperLink) -> crate::error::Result<()> {
+        let (href, text) = match value.target() {
+            HyperLinkTarget::External(target) => (target.clone(), target.clone()),
+            HyperLinkTarget::Internal(target) => (format!("#{}", target), target.to_string()),
+        };
+        self.write(&format!(
+            "<text:a xlink:type=\"simple\" xlink:href=\"{}\">{}</text:a>",
+            escape(&href),
+            escape(match value.caption() {
+                Some(caption) => caption.inner(),
+                None => &text,
+            })
+        ));
+        Ok(())
+    }
+
+    fn image(&self, value: &Image) -> crate::error::Result<()> {
+        *self.image_count.borrow_mut() += 1;
+        self.write(&format!(
+            "<draw:frame draw:name=\"Image{}\" text:anchor-type=\"as-char\"><draw:image xlink:type=\"simple\" xlink:href=\"{}\" xlink:show=\"embed\" xlink:actuate=\"onLoad\"/>{}</draw:frame>",
+            self.image_count.borrow(),
+            escape(value.inner()),
+            match value.alt_text() {
+                Some(alt_text) => format!("<svg:title>{}</svg:title>", escape(alt_text)),
+                None => String::new(),
+            }
+        ));
+        Ok(())
+    }
+
+    fn text(&self, value: &Text) -> crate::error::Result<()> {
+        self.write(&escape(value.inner()));
+        Ok(())
+    }
+
+    fn math(&self, value: &Math) -> crate::error::Result<()> {
+        self.write(&format!(
+            "<text:span text:style-name=\"Source_20_Text\">{}</text:span>",
+            escape(value.inner())
+        ));
+        Ok(())
+    }
+
+    fn character(&self, value: &Character) -> crate::error::Result<()> {
+        self.write(&match value {
+            Character::Space => " ".to_string(),
+            Character::NonBreakSpace => "&#160;".to_string(),
+            Character::Hyphen => "-".to_string(),
+            Character::EmDash => "&#8212;".to_string(),
+            Character::EnDash => "&#8211;".to_string(),
+            Character::Emoji(name) => format!(":{}:", escape(name.inner())),
+            Character::Other(c) => escape(&c.to_string()),
+        });
+        Ok(())
+    }
+
+    fn line_break(&self) -> crate::error::Result<()> {
+        self.write("<text:line-break/>");
+        Ok(())
+    }
+
+    fn start_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        let parent = self
+            .span_stack
+            .borrow()
+            .last()
+            .map(|(properties, _)| properties.clone())
+            .unwrap_or_default();
+        let properties = parent.with_styles(styles);
+        let attributes = properties.attributes(&parent);
+        // spans that change nothing are not written at all.
+        let written = !attributes.is_empty();
+        if written {
+            let style = self.text_style(attributes);
+            self.write(&format!("<text:span text:style-name=\"{}\">", style));
+        }
+        self.span_stack.borrow_mut().push((properties, written));
+        Ok(())
+    }
+
+    fn end_span(&self, _: &[SpanStyle]) -> crate::error::Result<()> {
+        if let Some((_, true)) = self.span_stack.borrow_mut().pop() {
+            self.write("</text:span>");
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn package_part(root: &str, content: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<{} {}>\n{}</{}>\n",
+        root, NAMESPACES, content, root
+    )
+}
+
+fn manifest_xml() -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="1.3">
+  <manifest:file-entry manifest:full-path="/" manifest:version="1.3" manifest:media-type="{}"/>
+  <manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/>
+  <manifest:file-entry manifest:full-path="styles.xml" manifest:media-type="text/xml"/>
+  <manifest:file-entry manifest:full-path="meta.xml" manifest:media-type="text/xml"/>
+</manifest:manifest>
+"#,
+        MIMETYPE
+    )
+}
+
+fn list_level_properties(level: usize) -> String {
+    format!(
+        "        <style:list-level-properties text:list-level-position-and-space-mode=\"label-alignment\">\n          <style:list-level-label-alignment text:label-followed-by=\"listtab\" text:list-tab-stop-position=\"{0:.3}cm\" fo:text-indent=\"-0.635cm\" fo:margin-left=\"{0:.3}cm\"/>\n        </style:list-level-properties>\n",
+        0.635 * level as f32 + 0.635
+    )
+}
+
+fn user_defined(name: &str, value: &str) -> String {
+    format!(
+        "<meta:user-defined meta:name=\"{}\">{}</meta:user-defined>",
+        escape(name),
+        escape(value)
+    )
+}
+
+fn text_align(alignment: &Alignment) -> &'static str {
+    match alignment {
+        Alignment::Right => "end",
+        Alignment::Centered => "center",
+        Alignment::Justified => "justify",
+        _ => "start",
+    }
+}
+
+///
+/// Runs of spaces, and leading spaces, are collapsed by ODF consumers unless written as
+/// `text:s` elements.
+///
+fn preserve_spaces(line: &str) -> String {
+    let mut result = String::new();
+    let mut spaces = 0;
+    let flush = |result: &mut String, spaces: usize| {
+        if spaces == 1 && !result.is_empty() {
+            result.push(' ');
+        } else if spaces == 1 {
+            result.push_str("<text:s/>");
+        } else if spaces > 1 {
+            result.push_str(&format!("<text:s text:c=\"{}\"/>", spaces));
+        }
+    };
+    for c in line.chars() {
+        if c == ' ' {
+            spaces += 1;
+        } else {
+            flush(&mut result, spaces);
+            spaces = 0;
+            if c == '\t' {
+                result.push_str("<text:tab/>");
+            } else {
+                result.push_str(&escape(&c.to_string()));
+            }
+        }
+    }
+    flush(&mut result, spaces);
+    result
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,718 @@
+/*!
+Write a document in the [Emacs Org-mode](https://orgmode.org/manual/) format.
+
+# Example
+
+```rust
+# use somedoc::model::Document;
+use somedoc::write::org::writer;
+
+# fn make_some_document() -> Document { Document::default() }
+let doc = make_some_document();
+
+writer(&doc, &mut std::io::stdout()).unwrap();
+```
+*/
+
+use crate::model::block::{
+    Alignment, BlockContent, Caption, Column, FrontMatter, HasAlignment, HasCaption, HasLabel,
+    HeadingLevel, Label, ListKind,
+};
+use crate::model::document::Metadata;
+use crate::model::inline::{Character, HyperLink, HyperLinkTarget, Image, Math, SpanStyle, Text};
+use crate::model::visitor::{
+    walk_document, BlockVisitor, DocumentVisitor, InlineVisitor, TableVisitor,
+};
+use crate::model::{Document, HasInnerContent};
+use crate::write::utils::string_of_strings;
+use crate::write::Writer;
+use std::cell::RefCell;
+use std::io::Write;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the Org-mode writer structure, usually this is accessed via the `writer`
+/// function, but may be used directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use somedoc::model::Document;
+/// use somedoc::write::org::OrgWriter;
+/// use somedoc::write::Writer;
+///
+/// # fn make_some_document() -> Document { Document::default() }
+/// let doc = make_some_document();
+/// let mut out = std::io::stdout();
+/// let writer = OrgWriter::new(&mut out);
+/// assert!(writer.write_document(&doc).is_ok());
+/// ```
+///
+#[derive(Debug)]
+pub struct OrgWriter<'a, W: Write> {
+    metadata: RefCell<Vec<Metadata>>,
+    heading_labels: RefCell<Vec<String>>,
+    list_stack: RefCell<Vec<ListKind>>,
+    indent_stack: RefCell<Vec<String>>,
+    table_alignments: RefCell<Vec<Alignment>>,
+    in_table: RefCell<bool>,
+    last_char: RefCell<Option<char>>,
+    span_opened: RefCell<bool>,
+    span_closed: RefCell<bool>,
+    line_open: RefCell<bool>,
+    block_written: RefCell<bool>,
+    blank_pending: RefCell<bool>,
+    w: RefCell<&'a mut W>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the writer function for Org-mode.
+///
+/// While this can be called directly it is most often used  by calling either
+/// [`model::write_document`](../fn.write_document.html) or
+/// [`model::write_document_to_string`](../fn.write_document_to_string.html).
+///
+#[inline]
+pub fn writer<W: Write>(doc: &Document, w: &mut W) -> crate::error::Result<()> {
+    let writer = OrgWriter::new(w);
+    writer.write_document(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> Writer<'a, W> for OrgWriter<'a, W> {
+    fn new(w: &'a mut W) -> Self {
+        Self {
+            metadata: RefCell::from(Vec::default()),
+            heading_labels: RefCell::from(Vec::default()),
+            list_stack: RefCell::from(Vec::default()),
+            indent_stack: RefCell::from(Vec::default()),
+            table_alignments: RefCell::from(Vec::default()),
+            in_table: RefCell::from(false),
+            last_char: RefCell::from(None),
+            span_opened: RefCell::from(false),
+            span_closed: RefCell::from(false),
+            line_open: RefCell::from(false),
+            block_written: RefCell::from(false),
+            blank_pending: RefCell::from(false),
+            w: RefCell::from(w),
+        }
+    }
+
+    fn write_document(&self, doc: &Document) -> crate::error::Result<()> {
+        // links to headings target the `CUSTOM_ID` property, all others a name or target.
+        let _ = self.heading_labels.replace(
+            doc.inner()
+                .iter()
+                .filter_map(|block| match block {
+                    BlockContent::Heading(heading) => heading.label().as_ref(),
+                    _ => None,
+                })
+                .map(|label| label.to_string())
+                .collect(),
+        );
+        walk_document(doc, self)?;
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> OrgWriter<'a, W> {
+    fn write(&self, text: &str) -> crate::error::Result<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        let _ = self.span_opened.replace(false);
+        if self.span_closed.replace(false) && !text.starts_with(closes_markup) {
+            // emphasis must be followed by whitespace or punctuation to be closed.
+            self.write(ZERO_WIDTH_SPACE)?;
+        }
+        let mut w = self.w.borrow_mut();
+        if self.blank_pending.replace(false) {
+            writeln!(w)?;
+        }
+        let prefix = self.indent_stack.borrow().join("");
+        for (i, segment) in text.split('\n').enumerate() {
+            if i > 0 {
+                writeln!(w)?;
+                let _ = self.line_open.replace(false);
+            }
+            if !segment.is_empty() {
+                if !self.line_open.replace(true) {
+                    write!(w, "{}", prefix)?;
+                }
+                write!(w, "{}", segment)?;
+            }
+        }
+        let _ = self
+            .last_char
+            .replace(text.chars().last().filter(|c| *c != '\n'));
+        let _ = self.block_written.replace(true);
+        Ok(())
+    }
+
+    fn end_line(&self) -> crate::error::Result<()> {
+        let _ = self.span_opened.replace(false);
+        let _ = self.span_closed.replace(false);
+        if self.line_open.replace(false) {
+            writeln!(&mut self.w.borrow_mut())?;
+            let _ = self.last_char.replace(None);
+        }
+        Ok(())
+    }
+
+    fn write_text(&self, text: &str) -> crate::error::Result<()> {
+        let previous = if *self.line_open.borrow() {
+            *self.last_char.borrow()
+        } else {
+            None
+        };
+        self.write(&escape(text, previous, *self.in_table.borrow()))
+    }
+
+    fn write_line(&self, text: &str) -> crate::error::Result<()> {
+        self.write(text)?;
+        self.end_line()
+    }
+
+    fn write_affiliated(
+        &self,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        if let Some(caption) = caption {
+            self.write("#+CAPTION: ")?;
+            self.write_text(caption.inner())?;
+            self.end_line()?;
+        }
+        if let Some(label) = label {
+            self.write_line(&format!("#+NAME: {}", label))?;
+        }
+        Ok(())
+    }
+
+    fn write_target(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        if let Some(label) = label {
+            self.write(&format!("<<{}>>", label))?;
+        }
+        Ok(())
+    }
+
+    fn write_header(&self) -> crate::error::Result<()> {
+        let metadata = self.metadata.borrow();
+        let authors: Vec<&str> = metadata
+            .iter()
+            .filter_map(|datum| match datum {
+                Metadata::Author(author) => Some(author.name.as_str()),
+                _ => None,
+            })
+            .collect();
+        let email = metadata.iter().find_map(|datum| match datum {
+            Metadata::Author(author) => author.email.as_ref(),
+            _ => None,
+        });
+        if let Some(title) = metadata.iter().find_map(|datum| match datum {
+            Metadata::Title(title) => Some(title),
+            _ => None,
+        }) {
+            self.write_line(&format!("#+TITLE: {}", title))?;
+        }
+        if !authors.is_empty() {
+            self.write_line(&format!("#+AUTHOR: {}", authors.join(", ")))?;
+        }
+        if let Some(email) = email {
+            self.write_line(&format!("#+EMAIL: {}", email))?;
+        }
+        for datum in metadata.iter() {
+            match datum {
+                Metadata::Author(_) | Metadata::Title(_) => continue,
+                Metadata::Date(value) => self.write_line(&format!("#+DATE: {}", value))?,
+                Metadata::Keywords(value) => {
+                    self.write_line(&format!("#+KEYWORDS: {}", value.join(", ")))?
+                }
+                _ => self.write_line(&format!(
+                    "#+{}: {}",
+                    datum.key().to_uppercase(),
+                    datum.value_string()
+                ))?,
+            }
+        }
+        let _ = self
+            .blank_pending
+            .replace(self.block_written.replace(false));
+        Ok(())
+    }
+
+    fn write_greater_block(
+        &self,
+        name: &str,
+        parameters: Option<&str>,
+        content: &str,
+    ) -> crate::error::Result<()> {
+        match parameters {
+            Some(parameters) => self.write_line(&format!("#+BEGIN_{} {}", name, parameters))?,
+            None => self.write_line(&format!("#+BEGIN_{}", name))?,
+        }
+        self.write_line(content)?;
+        self.write(&format!("#+END_{}", name))
+    }
+}
+
+impl<'a, W: Write> DocumentVisitor for OrgWriter<'a, W> {
+    fn metadata(&self, meta_datum: &Metadata) -> crate::error::Result<()> {
+        self.metadata.borrow_mut().push(meta_datum.clone());
+        Ok(())
+    }
+
+    fn block_visitor(&self) -> Option<&dyn BlockVisitor> {
+        if !self.metadata.borrow().is_empty() {
+            let _ = self.write_header();
+        }
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> BlockVisitor for OrgWriter<'a, W> {
+    fn start_abstract(&self) -> crate::error::Result<()> {
+        self.write_line("#+BEGIN_abstract")
+    }
+
+    fn end_abstract(&self) -> crate::error::Result<()> {
+        self.end_line()?;
+        self.write("#+END_abstract")
+    }
+
+    fn comment(&self, value: &str) -> crate::error::Result<()> {
+        self.write(
+            &value
+                .split('\n')
+                .map(|line| format!("# {}", line))
+                .collect::<Vec<String>>()
+                .join("\n"),
+        )
+    }
+
+    fn front_matter(&self, value: &FrontMatter) -> crate::error::Result<()> {
+        match value {
+            FrontMatter::TableOfContents => self.write("#+TOC: headlines"),
+            FrontMatter::TableOfListings => self.write("#+TOC: listings"),
+            FrontMatter::TableOfTables => self.write("#+TOC: tables"),
+            _ => Ok(()),
+        }
+    }
+
+    fn start_heading(&self, level: &HeadingLevel, _: &Option<Label>) -> crate::error::Result<()> {
+        self.write(&format!(
+            "{} ",
+            string_of_strings("*", level.clone() as usize)
+        ))
+    }
+
+    fn end_heading(&self, _: &HeadingLevel, label: &Option<Label>) -> crate::error::Result<()> {
+        if let Some(label) = label {
+            self.end_line()?;
+            self.write(&format!(":PROPERTIES:\n:CUSTOM_ID: {}\n:END:", label))?;
+        }
+        Ok(())
+    }
+
+    fn image(
+        &self,
+        value: &Image,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_affiliated(caption, label)?;
+        if let Some(alt_text) = value.alt_text() {
+            self.write_line(&format!("#+ATTR_HTML: :alt {}", alt_text))?;
+        }
+        self.write(&format!("[[{}]]", value.inner()))
+    }
+
+    fn math(
+        &self,
+        value: &Math,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_affiliated(caption, label)?;
+        self.write(&format!(
+            "\\begin{{equation}}\n{}\n\\end{{equation}}",
+            value.inner()
+        ))
+    }
+
+    fn start_list(&self, kind: &ListKind, label: &Option<Label>) -> crate::error::Result<()> {
+        let mut list_stack = self.list_stack.borrow_mut();
+        match list_stack.last() {
+            None => self.write_affiliated(&None, label)?,
+            Some(parent) => {
+                // nested lists are indented to the parent item's text.
+                self.indent_stack.borrow_mut().push(
+                    match parent {
+                        ListKind::Ordered => "   ",
+                        ListKind::Unordered => "  ",
+                    }
+                    .to_string(),
+                );
+            }
+        }
+        list_stack.push(kind.clone());
+        Ok(())
+    }
+
+    fn end_list(&self, _: &ListKind, _: &Option<Label>) -> crate::error::Result<()> {
+        let mut list_stack = self.list_stack.borrow_mut();
+        let _ = list_stack.pop();
+        if !list_stack.is_empty() {
+            let _ = self.indent_stack.borrow_mut().pop();
+        }
+        Ok(())
+    }
+
+    fn start_list_item(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        match self.list_stack.borrow().last() {
+            Some(ListKind::Ordered) => self.write("1. ")?,
+            Some(ListKind::Unordered) => self.write("- ")?,
+            None => {}
+        }
+        self.write_target(label)
+    }
+
+    fn end_list_item(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_line()
+    }
+
+    fn start_definition_list(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_affiliated(&None, label)
+    }
+
+    fn start_definition(&self, term: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write("- ")?;
+        self.write_target(label)?;
+        self.write_text(term)?;
+        self.write(" ::")
+    }
+
+    fn start_definition_list_text(&self) -> crate::error::Result<()> {
+        self.write(" ")
+    }
+
+    fn end_definition(&self, _: &str, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_line()
+    }
+
+    fn formatted(&self, value: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_affiliated(&None, label)?;
+        self.write_greater_block("EXAMPLE", None, value)
+    }
+
+    fn code_block(
+        &self,
+        code: &str,
+        language: &Option<String>,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_affiliated(caption, label)?;
+        match language {
+            Some(language) => self.write_greater_block("SRC", Some(language), code),
+            None => self.write_greater_block("EXAMPLE", None, code),
+        }
+    }
+
+    fn start_paragraph(
+        &self,
+        alignment: &Alignment,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_affiliated(&None, label)?;
+        if *alignment == Alignment::Centered {
+            self.write_line("#+BEGIN_CENTER")?;
+        }
+        Ok(())
+    }
+
+    fn end_paragraph(&self, alignment: &Alignment, _: &Option<Label>) -> crate::error::Result<()> {
+        if *alignment == Alignment::Centered {
+            self.end_line()?;
+            self.write("#+END_CENTER")?;
+        }
+        Ok(())
+    }
+
+    fn start_quote(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_affiliated(&None, label)?;
+        self.write_line("#+BEGIN_QUOTE")
+    }
+
+    fn end_quote(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        let _ = self.blank_pending.replace(false);
+        self.end_line()?;
+        self.write("#+END_QUOTE")
+    }
+
+    fn thematic_break(&self) -> crate::error::Result<()> {
+        self.write("-----")
+    }
+
+    fn end_block(&self) -> crate::error::Result<()> {
+        if self.block_written.replace(false) {
+            self.end_line()?;
+            let _ = self.blank_pending.replace(true);
+        }
+        Ok(())
+    }
+
+    fn table_visitor(&self) -> Option<&dyn TableVisitor> {
+        Some(self)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> TableVisitor for OrgWriter<'a, W> {
+    fn start_table(
+        &self,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        let _ = self.in_table.replace(true);
+        self.write_affiliated(caption, label)
+    }
+
+    fn start_table_header_row(&self) -> crate::error::Result<()> {
+        self.write("|")
+    }
+
+    fn table_header_cell(
+        &self,
+        column_cell: &Column,
+        _column_idx: usize,
+    ) -> crate::error::Result<()> {
+        self.table_alignments
+            .borrow_mut()
+            .push(column_cell.alignment().clone());
+        self.write(" ")?;
+        self.write_text(column_cell.text())?;
+        self.write(" |")
+    }
+
+    fn end_table_header_row(&self) -> crate::error::Result<()> {
+        self.end_line()?;
+        let alignments: Vec<Alignment> = self.table_alignments.borrow_mut().drain(..).collect();
+        self.write_line(&format!(
+            "|{}|",
+            alignments
+                .iter()
+                .map(|_| "---")
+                .collect::<Vec<&str>>()
+                .join("+")
+        ))?;
+        if alignments
+            .iter()
+            .any(|alignment| matches!(alignment, Alignment::Right | Alignment::Centered))
+        {
+            // alignment cookies are not exported, but are honored by all the exporters.
+            self.write_line(&format!(
+                "|{}|",
+                alignments
+                    .iter()
+                    .map(|alignment| match alignment {
+                        Alignment::Right => " <r> ",
+                        Alignment::Centered => " <c> ",
+                        _ => " <l> ",
+                    })
+                    .collect::<Vec<&str>>()
+                    .join("|")
+            ))?;
+        }
+        Ok(())
+    }
+
+    fn start_table_row(&self, _: usize) -> crate::error::Result<()> {
+        self.write("|")
+    }
+
+    fn start_table_cell(&self, _: usize, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write(" ")?;
+        self.write_target(label)
+    }
+
+    fn end_table_cell(&self, _: usize, _: &Option<Label>) -> crate::error::Result<()> {
+        self.write(" |")
+    }
+
+    fn end_table_row(&self, _: usize) -> crate::error::Result<()> {
+        self.end_line()
+    }
+
+    fn end_table(&self, _: &Option<Caption>, _: &Option<Label>) -> crate::error::Result<()> {
+        let _ = self.in_table.replace(false);
+        Ok(())
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> InlineVisitor for OrgWriter<'a, W> {
+    fn link(&self, value: &HyperLink) -> crate::error::Result<()> {
+        let target = match value.target() {
+            HyperLinkTarget::External(target) => target.clone(),
+            HyperLinkTarget::Internal(target) => {
+                let target = target.to_string();
+                if self.heading_labels.borrow().contains(&target) {
+                    format!("#{}", target)
+                } else {
+                    target
+                }
+            }
+        };
+        match value.caption() {
+            Some(caption) => self.write(&format!("[[{}][{}]]", target, caption.inner())),
+            None => self.write(&format!("[[{}]]", target)),
+        }
+    }
+
+    fn image(&self, value: &Image) -> crate::error::Result<()> {
+        self.write(&format!("[[{}]]", value.inner()))
+    }
+
+    fn text(&self, value: &Text) -> crate::error::Result<()> {
+        self.write_text(value.inner())
+    }
+
+    fn math(&self, value: &Math) -> crate::error::Result<()> {
+        self.write(&format!("\\({}\\)", value.inner()))
+    }
+
+    fn character(&self, value: &Character) -> crate::error::Result<()> {
+        self.write(&match value {
+            Character::Space => " ".to_string(),
+            Character::NonBreakSpace => "\\nbsp{}".to_string(),
+            Character::Hyphen => "-".to_string(),
+            Character::EmDash => "---".to_string(),
+            Character::EnDash => "--".to_string(),
+            Character::Emoji(name) => format!(":{}:", name.inner()),
+            Character::Other(c) => c.to_string(),
+        })
+    }
+
+    fn line_break(&self) -> crate::error::Result<()> {
+        self.write("\\\\\n")
+    }
+
+    fn start_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        let start = make_style_stack(styles)
+            .into_iter()
+            .map(|(start, _)| start)
+            .collect::<Vec<&str>>()
+            .join("");
+        if start.is_empty() {
+            return Ok(());
+        }
+        let opened = self.span_opened.replace(false);
+        let closed = self.span_closed.replace(false);
+        if *self.line_open.borrow()
+            && (closed || (!opened && !opens_markup(*self.last_char.borrow())))
+        {
+            // emphasis must be preceded by whitespace or punctuation to be opened.
+            self.write(ZERO_WIDTH_SPACE)?;
+        }
+        self.write(&start)?;
+        let _ = self.span_opened.replace(true);
+        Ok(())
+    }
+
+    fn end_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        let end = make_style_stack(styles)
+            .into_iter()
+            .rev()
+            .map(|(_, end)| end)
+            .collect::<Vec<&str>>()
+            .join("");
+        if end.is_empty() {
+            return Ok(());
+        }
+        let _ = self.span_closed.replace(false);
+        self.write(&end)?;
+        let _ = self.span_closed.replace(true);
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+const ZERO_WIDTH_SPACE: &str = "\u{200B}";
+
+fn opens_markup(previous: Option<char>) -> bool {
+    match previous {
+        None => true,
+        Some(c) => c.is_whitespace() || "-('\"{\u{200B}".contains(c),
+    }
+}
+
+fn closes_markup(next: char) -> bool {
+    next.is_whitespace() || "-.,:!?;'\")}\\[\u{200B}".contains(next)
+}
+
+///
+/// Org has no escape character; markup characters that could open emphasis, start a heading, or
+/// start a keyword are preceded by a zero-width space instead.
+///
+fn escape(text: &str, previous: Option<char>, in_table: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut previous = previous;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '|' if in_table => escaped.push_str("\\vert{}"),
+            '*' | '/' | '=' | '~' | '+' | '_' if opens_markup(previous) => {
+                escaped.push_str(ZERO_WIDTH_SPACE);
+                escaped.push(c);
+            }
+            '#' if previous.is_none() && chars.peek() == Some(&'+') => {
+                escaped.push_str(ZERO_WIDTH_SPACE);
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+        previous = if c == '\n' { None } else { Some(c) };
+    }
+    escaped
+}
+
+fn make_style_stack(styles: &[SpanStyle]) -> Vec<(&'static str, &'static str)> {
+    let mut style_stack = Vec::new();
+    for style in styles {
+        match style {
+            SpanStyle::Plain => style_stack.clear(),
+            SpanStyle::Italic => style_stack.push(("/", "/")),
+            SpanStyle::Bold => style_stack.push(("*", "*")),
+            SpanStyle::Mono => style_stack.push(("=", "=")),
+            SpanStyle::Code => style_stack.push(("~", "~")),
+            SpanStyle::Strikethrough => style_stack.push(("+", "+")),
+            SpanStyle::Underline => style_stack.push(("_", "_")),
+            SpanStyle::Superscript => style_stack.push(("^{", "}")),
+            SpanStyle::Subscript => style_stack.push(("_{", "}")),
+            _ => {}
+        }
+    }
+    style_stack
+}
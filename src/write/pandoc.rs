@@ -0,0 +1,584 @@
+/*!
+Write a document as a Pandoc JSON AST, the format produced by `pandoc -t json`. The output may
+be passed to `pandoc -f json` to convert the document into any of the formats that Pandoc
+supports.
+
+The model is mapped onto the Pandoc AST as follows.
+
+* `Metadata` values are written to the document `meta` map, authors and keywords as lists; the
+  document abstract is written as the `abstract` value.
+* `Heading` becomes `Header`, `Paragraph` becomes `Para`, `Quote` becomes `BlockQuote`, and
+  `ThematicBreak` becomes `HorizontalRule`.
+* `List` becomes `BulletList` or `OrderedList`, with sub-lists nested in the preceding item, and
+  `DefinitionList` becomes `DefinitionList`.
+* `CodeBlock` and `Formatted` become `CodeBlock`, with the language as the first class.
+* `MathBlock` becomes a `Para` containing display `Math`, and `ImageBlock` becomes a `Figure`.
+* `Table` becomes `Table`, the columns are written as a single header row.
+* `Comment` becomes an HTML `RawBlock`, and `FrontMatter` becomes a LaTeX `RawBlock`.
+* Span styles become `Emph`, `Strong`, `Code`, `Strikeout`, `Underline`, `SmallCaps`,
+  `Superscript`, and `Subscript`; sizes become a `Span` with the size as a class.
+* `HyperLink` becomes `Link`, internal links use a `#label` target, `Image` becomes `Image`, and
+  `Math` becomes inline `Math`.
+
+Labels are written as the identifier of the Pandoc element where it has attributes, otherwise
+the block is wrapped in a `Div` with the label as its identifier. Captioned code and math blocks
+are wrapped in a `Figure`. Paragraph alignment is not represented.
+
+# Example
+
+```rust
+# use somedoc::model::Document;
+use somedoc::write::{OutputFormat, write_document_to_string};
+
+# fn make_some_document() -> Document { Document::default() }
+let doc = make_some_document();
+
+let doc_str = write_document_to_string(&doc, OutputFormat::PandocJson).unwrap();
+println!("{}", doc_str);
+```
+
+*/
+
+use crate::model::block::{
+    Alignment, BlockContent, Caption, DefinitionList, FrontMatter, HasAlignment, HasCaption,
+    HasLabel, Label, List, ListItem, Table,
+};
+use crate::model::document::Metadata;
+use crate::model::inline::text::Size;
+use crate::model::inline::{
+    Character, HasInlineContent, HyperLinkTarget, Image, InlineContent, Span, SpanStyle,
+};
+use crate::model::{Document, HasInnerContent, HasStyles};
+use crate::write::Writer;
+use serde_json::{json, Map, Value};
+use std::cell::RefCell;
+use std::io::Write;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the Pandoc JSON writer structure, usually this is accessed via the `writer`
+/// function, but may be used directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use somedoc::model::Document;
+/// use somedoc::write::pandoc::PandocWriter;
+/// use somedoc::write::Writer;
+///
+/// # fn make_some_document() -> Document { Document::default() }
+/// let doc = make_some_document();
+/// let mut out = std::io::stdout();
+/// let writer = PandocWriter::new(&mut out);
+/// assert!(writer.write_document(&doc).is_ok());
+/// ```
+///
+#[derive(Debug)]
+pub struct PandocWriter<'a, W: Write> {
+    w: RefCell<&'a mut W>,
+}
+
+///
+/// The version of the `pandoc-types` API that documents are written for.
+///
+pub const PANDOC_API_VERSION: [u32; 3] = [1, 23, 1];
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the writer function for Pandoc JSON.
+///
+/// While this can be called directly it is most often used  by calling either
+/// [`model::write_document`](../fn.write_document.html) or
+/// [`model::write_document_to_string`](../fn.write_document_to_string.html).
+///
+#[inline]
+pub fn writer<W: Write>(doc: &Document, w: &mut W) -> crate::error::Result<()> {
+    let writer = PandocWriter::new(w);
+    writer.write_document(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> Writer<'a, W> for PandocWriter<'a, W> {
+    fn new(w: &'a mut W) -> Self {
+        Self {
+            w: RefCell::from(w),
+        }
+    }
+
+    fn write_document(&self, doc: &Document) -> crate::error::Result<()> {
+        let stringified = serde_json::to_string(&document(doc))?;
+        write!(&mut self.w.borrow_mut(), "{}", stringified)?;
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn document(doc: &Document) -> Value {
+    json!({
+        "pandoc-api-version": PANDOC_API_VERSION,
+        "meta": meta(doc),
+        "blocks": doc.inner().iter().map(block).collect::<Vec<Value>>(),
+    })
+}
+
+fn meta(doc: &Document) -> Value {
+    let mut meta: Map<String, Value> = Default::default();
+    let mut authors: Vec<Value> = Default::default();
+    let mut copyrights: Vec<Value> = Default::default();
+    for datum in doc.metadata() {
+        match datum {
+            Metadata::Author(author) => {
+                if author.email.is_none() && author.organization.is_none() {
+                    authors.push(meta_inlines(&author.name));
+                } else {
+                    let mut map: Map<String, Value> = Default::default();
+                    let _ = map.insert("name".to_string(), meta_inlines(&author.name));
+                    if let Some(email) = &author.email {
+                        let _ = map.insert("email".to_string(), meta_inlines(email));
+                    }
+                    if let Some(organization) = &author.organization {
+                        let _ = map.insert("affiliation".to_string(), meta_inlines(organization));
+                    }
+                    authors.push(node("MetaMap", Value::Object(map)));
+                }
+            }
+            Metadata::Copyright(copyright) => {
+                let mut map: Map<String, Value> = Default::default();
+                let _ = map.insert(
+                    "year".to_string(),
+                    node("MetaString", json!(copyright.year.to_string())),
+                );
+                if let Some(organization) = &copyright.organization {
+                    let _ = map.insert("organization".to_string(), meta_inlines(organization));
+                }
+                if let Some(comment) = &copyright.comment {
+                    let _ = map.insert("comment".to_string(), meta_inlines(comment));
+                }
+                copyrights.push(node("MetaMap", Value::Object(map)));
+            }
+            Metadata::Keywords(keywords) => {
+                let _ = meta.insert(
+                    datum.key(),
+                    node(
+                        "MetaList",
+                        Value::Array(keywords.iter().map(|k| meta_inlines(k)).collect()),
+                    ),
+                );
+            }
+            _ => {
+                let _ = meta.insert(datum.key(), meta_inlines(&datum.value_string()));
+            }
+        }
+    }
+    if !authors.is_empty() {
+        let _ = meta.insert(
+            "author".to_string(),
+            node("MetaList", Value::Array(authors)),
+        );
+    }
+    if copyrights.len() == 1 {
+        let _ = meta.insert("copyright".to_string(), copyrights.remove(0));
+    } else if !copyrights.is_empty() {
+        let _ = meta.insert(
+            "copyright".to_string(),
+            node("MetaList", Value::Array(copyrights)),
+        );
+    }
+    if let Some(abstract_block) = doc.abstract_block() {
+        let _ = meta.insert(
+            "abstract".to_string(),
+            node(
+                "MetaBlocks",
+                json!([node("Para", inlines(abstract_block.inner()))]),
+            ),
+        );
+    }
+    Value::Object(meta)
+}
+
+fn meta_inlines(s: &str) -> Value {
+    node("MetaInlines", Value::Array(text(s)))
+}
+
+// ------------------------------------------------------------------------------------------------
+
+fn block(content: &BlockContent) -> Value {
+    match content {
+        BlockContent::Comment(v) => node("RawBlock", json!(["html", format!("<!-- {} -->", v)])),
+        BlockContent::FrontMatter(v) => node(
+            "RawBlock",
+            json!([
+                "latex",
+                match v {
+                    FrontMatter::TableOfContents => "\\tableofcontents",
+                    FrontMatter::TableOfEquations => "\\listofequfloats",
+                    FrontMatter::TableOfFigures => "\\listoffigures",
+                    FrontMatter::TableOfListings => "\\lstlistoflistings",
+                    FrontMatter::TableOfTables => "\\listoftables",
+                    FrontMatter::Glossary => "\\printglossary",
+                }
+            ]),
+        ),
+        BlockContent::Heading(v) => node(
+            "Header",
+            json!([v.level_as_u8(), attr(v.label(), &[]), inlines(v.inner())]),
+        ),
+        BlockContent::ImageBlock(v) => node(
+            "Figure",
+            json!([
+                attr(v.label(), &[]),
+                caption(v.caption()),
+                [node("Plain", json!([image(v.inner())]))]
+            ]),
+        ),
+        BlockContent::MathBlock(v) => figure(
+            v.label(),
+            v.caption(),
+            node(
+                "Para",
+                json!([node(
+                    "Math",
+                    json!([leaf("DisplayMath"), v.inner().to_string()])
+                )]),
+            ),
+        ),
+        BlockContent::List(v) => labeled(v.label(), list(v)),
+        BlockContent::DefinitionList(v) => labeled(v.label(), definition_list(v)),
+        BlockContent::Formatted(v) => node("CodeBlock", json!([attr(v.label(), &[]), v.inner()])),
+        BlockContent::CodeBlock(v) => {
+            let classes: Vec<&str> = v.language().iter().map(String::as_str).collect();
+            if v.has_caption() {
+                figure(
+                    v.label(),
+                    v.caption(),
+                    node("CodeBlock", json!([attr(&None, &classes), v.code()])),
+                )
+            } else {
+                node("CodeBlock", json!([attr(v.label(), &classes), v.code()]))
+            }
+        }
+        BlockContent::Paragraph(v) => labeled(v.label(), node("Para", inlines(v.inner()))),
+        BlockContent::Quote(v) => labeled(
+            v.label(),
+            node(
+                "BlockQuote",
+                Value::Array(v.inner().iter().map(block).collect()),
+            ),
+        ),
+        BlockContent::Table(v) => table(v),
+        BlockContent::ThematicBreak => leaf("HorizontalRule"),
+    }
+}
+
+fn list(list: &List) -> Value {
+    let mut items: Vec<Vec<Value>> = Default::default();
+    for item in list.inner() {
+        match item {
+            ListItem::Item(item) => {
+                let content = inlines(item.inner());
+                items.push(vec![node(
+                    "Plain",
+                    if item.has_label() {
+                        json!([node("Span", json!([attr(item.label(), &[]), content]))])
+                    } else {
+                        content
+                    },
+                )]);
+            }
+            ListItem::List(sub_list) => match items.last_mut() {
+                Some(last) => last.push(self::list(sub_list)),
+                None => items.push(vec![self::list(sub_list)]),
+            },
+        }
+    }
+    if list.is_ordered() {
+        node(
+            "OrderedList",
+            json!([[1, leaf("Decimal"), leaf("Period")], items]),
+        )
+    } else {
+        node("BulletList", json!(items))
+    }
+}
+
+fn definition_list(list: &DefinitionList) -> Value {
+    node(
+        "DefinitionList",
+        Value::Array(
+            list.inner()
+                .iter()
+                .map(|definition| {
+                    let term = text(definition.term());
+                    json!([
+                        if definition.has_label() {
+                            json!([node("Span", json!([attr(definition.label(), &[]), term]))])
+                        } else {
+                            Value::Array(term)
+                        },
+                        [[node("Plain", inlines(definition.text().inner()))]]
+                    ])
+                })
+                .collect(),
+        ),
+    )
+}
+
+fn table(table: &Table) -> Value {
+    let cell = |label: &Option<Label>, content: Value| {
+        let blocks = if content.as_array().map(Vec::is_empty).unwrap_or(true) {
+            json!([])
+        } else {
+            json!([node("Plain", content)])
+        };
+        json!([attr(label, &[]), leaf("AlignDefault"), 1, 1, blocks])
+    };
+    let row = |cells: Vec<Value>| json!([attr(&None, &[]), cells]);
+    let specs: Vec<Value> = table
+        .columns()
+        .iter()
+        .map(|column| json!([alignment(column.alignment()), leaf("ColWidthDefault")]))
+        .collect();
+    let head = row(table
+        .columns()
+        .iter()
+        .map(|column| cell(&None, Value::Array(text(column.text()))))
+        .collect());
+    let rows: Vec<Value> = table
+        .rows()
+        .iter()
+        .map(|r| {
+            row(r
+                .cells()
+                .iter()
+                .map(|c| cell(c.label(), inlines(c.inner())))
+                .collect())
+        })
+        .collect();
+    node(
+        "Table",
+        json!([
+            attr(table.label(), &[]),
+            caption(table.caption()),
+            specs,
+            [attr(&None, &[]), [head]],
+            [[attr(&None, &[]), 0, [], rows]],
+            [attr(&None, &[]), []]
+        ]),
+    )
+}
+
+fn alignment(alignment: &Alignment) -> Value {
+    leaf(match alignment {
+        Alignment::Left => "AlignLeft",
+        Alignment::Right => "AlignRight",
+        Alignment::Centered => "AlignCenter",
+        Alignment::Justified => "AlignDefault",
+    })
+}
+
+fn figure(label: &Option<Label>, caption: &Option<Caption>, content: Value) -> Value {
+    if caption.is_some() {
+        node(
+            "Figure",
+            json!([attr(label, &[]), self::caption(caption), [content]]),
+        )
+    } else {
+        labeled(label, content)
+    }
+}
+
+fn caption(caption: &Option<Caption>) -> Value {
+    match caption {
+        None => json!([null, []]),
+        Some(caption) => json!([null, [node("Plain", Value::Array(text(caption)))]]),
+    }
+}
+
+fn labeled(label: &Option<Label>, content: Value) -> Value {
+    match label {
+        None => content,
+        Some(_) => node("Div", json!([attr(label, &[]), [content]])),
+    }
+}
+
+fn attr(label: &Option<Label>, classes: &[&str]) -> Value {
+    json!([
+        label.as_ref().map(Label::to_string).unwrap_or_default(),
+        classes,
+        []
+    ])
+}
+
+// ------------------------------------------------------------------------------------------------
+
+fn inlines(content: &[InlineContent]) -> Value {
+    let mut values: Vec<Value> = Default::default();
+    for inline in content {
+        for value in self::inline(inline) {
+            push_inline(&mut values, value);
+        }
+    }
+    Value::Array(values)
+}
+
+fn inline(content: &InlineContent) -> Vec<Value> {
+    match content {
+        InlineContent::HyperLink(v) => {
+            let (target, text_or_label, classes) = match v.target() {
+                HyperLinkTarget::External(url) => (url.to_string(), url.to_string(), vec!["uri"]),
+                HyperLinkTarget::Internal(label) => {
+                    (format!("#{}", label), label.to_string(), vec![])
+                }
+            };
+            let (caption, classes) = match v.caption() {
+                Some(caption) => (text(caption), vec![]),
+                None => (text(&text_or_label), classes),
+            };
+            vec![node(
+                "Link",
+                json!([attr(&None, &classes), caption, [target, ""]]),
+            )]
+        }
+        InlineContent::Image(v) => vec![image(v)],
+        InlineContent::Text(v) => text(v),
+        InlineContent::Math(v) => vec![node("Math", json!([leaf("InlineMath"), v.to_string()]))],
+        InlineContent::Character(v) => vec![match v {
+            Character::Space => leaf("Space"),
+            Character::NonBreakSpace => str_node("\u{a0}"),
+            Character::Hyphen => str_node("-"),
+            Character::EmDash => str_node("\u{2014}"),
+            Character::EnDash => str_node("\u{2013}"),
+            Character::Emoji(emoji) => str_node(emoji),
+            Character::Other(c) => str_node(&c.to_string()),
+        }],
+        InlineContent::LineBreak => vec![leaf("LineBreak")],
+        InlineContent::Span(v) => span(v),
+    }
+}
+
+fn span(span: &Span) -> Vec<Value> {
+    let mut content = inlines(span.inner());
+    for style in span.styles().iter().rev() {
+        content = match style {
+            SpanStyle::Plain => content,
+            SpanStyle::Italic => json!([node("Emph", content)]),
+            SpanStyle::Bold => json!([node("Strong", content)]),
+            SpanStyle::Mono | SpanStyle::Code => json!([node(
+                "Code",
+                json!([attr(&None, &[]), span.unformatted_string()])
+            )]),
+            SpanStyle::Strikethrough => json!([node("Strikeout", content)]),
+            SpanStyle::Underline => json!([node("Underline", content)]),
+            SpanStyle::SmallCaps => json!([node("SmallCaps", content)]),
+            SpanStyle::Superscript => json!([node("Superscript", content)]),
+            SpanStyle::Subscript => json!([node("Subscript", content)]),
+            SpanStyle::Sized(size) => json!([node(
+                "Span",
+                json!([attr(&None, &[size_class(size)]), content])
+            )]),
+        };
+    }
+    match content {
+        Value::Array(values) => values,
+        _ => Default::default(),
+    }
+}
+
+fn size_class(size: &Size) -> &'static str {
+    match size {
+        Size::Largest => "largest",
+        Size::Larger => "larger",
+        Size::Large => "large",
+        Size::Normal => "normal",
+        Size::Small => "small",
+        Size::Smaller => "smaller",
+        Size::Smallest => "smallest",
+    }
+}
+
+fn image(image: &Image) -> Value {
+    node(
+        "Image",
+        json!([
+            attr(&None, &[]),
+            image.alt_text().as_deref().map(text).unwrap_or_default(),
+            [image.inner(), ""]
+        ]),
+    )
+}
+
+///
+/// Split text into `Str` values separated by `Space`, or `SoftBreak` where the whitespace
+/// includes a newline, as Pandoc does.
+///
+fn text(s: &str) -> Vec<Value> {
+    let mut values: Vec<Value> = Default::default();
+    let mut word = String::new();
+    let mut space: Option<bool> = None;
+    for c in s.chars() {
+        if c.is_whitespace() && c != '\u{a0}' {
+            if !word.is_empty() {
+                values.push(str_node(&word));
+                word.clear();
+            }
+            space = Some(space.unwrap_or_default() || c == '\n');
+        } else {
+            if let Some(soft) = space.take() {
+                values.push(leaf(if soft { "SoftBreak" } else { "Space" }));
+            }
+            word.push(c);
+        }
+    }
+    if !word.is_empty() {
+        values.push(str_node(&word));
+    }
+    if let Some(soft) = space {
+        values.push(leaf(if soft { "SoftBreak" } else { "Space" }));
+    }
+    values
+}
+
+///
+/// Append the value, merging adjacent `Str` values as Pandoc does.
+///
+fn push_inline(values: &mut Vec<Value>, value: Value) {
+    if let (Some(last), Some(s)) = (values.last_mut(), str_value(&value)) {
+        if let Some(last_s) = str_value(last) {
+            *last = str_node(&format!("{}{}", last_s, s));
+            return;
+        }
+    }
+    values.push(value);
+}
+
+fn str_value(value: &Value) -> Option<&str> {
+    if value["t"] == "Str" {
+        value["c"].as_str()
+    } else {
+        None
+    }
+}
+
+fn str_node(s: &str) -> Value {
+    node("Str", json!(s))
+}
+
+fn node(tag: &str, content: Value) -> Value {
+    json!({"t": tag, "c": content})
+}
+
+fn leaf(tag: &str) -> Value {
+    json!({ "t": tag })
+}
@@ -0,0 +1,736 @@
+/*!
+Write a document as [reStructuredText](https://docutils.sourceforge.io/rst.html), using the
+directives and roles supported by Sphinx.
+
+# Example
+
+```rust
+# use somedoc::model::Document;
+use somedoc::write::rst::writer;
+
+# fn make_some_document() -> Document { Document::default() }
+let doc = make_some_document();
+
+writer(&doc, &mut std::io::stdout()).unwrap();
+```
+*/
+
+use crate::model::block::{
+    Alignment, Caption, Column, FrontMatter, HasCaption, HeadingLevel, Label, ListKind,
+};
+use crate::model::document::Metadata;
+use crate::model::inline::{Character, HyperLink, HyperLinkTarget, Image, Math, SpanStyle, Text};
+use crate::model::visitor::{
+    walk_document, BlockVisitor, DocumentVisitor, InlineVisitor, TableVisitor,
+};
+use crate::model::Document;
+use crate::write::utils::string_of_strings;
+use crate::write::Writer;
+use std::cell::RefCell;
+use std::io::Write;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the reStructuredText writer structure, usually this is accessed via the
+/// `writer` function, but may be used directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use somedoc::model::Document;
+/// use somedoc::write::rst::RstWriter;
+/// use somedoc::write::Writer;
+///
+/// # fn make_some_document() -> Document { Document::default() }
+/// let doc = make_some_document();
+/// let mut out = std::io::stdout();
+/// let writer = RstWriter::new(&mut out);
+/// assert!(writer.write_document(&doc).is_ok());
+/// ```
+///
+#[derive(Debug)]
+pub struct RstWriter<'a, W: Write> {
+    metadata: RefCell<Vec<Metadata>>,
+    list_stack: RefCell<Vec<ListKind>>,
+    indent_stack: RefCell<Vec<String>>,
+    span_stack: RefCell<Vec<Option<(&'static str, &'static str)>>>,
+    heading: RefCell<Option<String>>,
+    substitutions: RefCell<Vec<(String, String)>>,
+    substitution_count: RefCell<usize>,
+    table_caption: RefCell<Option<String>>,
+    in_table: RefCell<bool>,
+    last_char: RefCell<Option<char>>,
+    markup_opened: RefCell<bool>,
+    markup_closed: RefCell<bool>,
+    line_open: RefCell<bool>,
+    block_written: RefCell<bool>,
+    blank_pending: RefCell<bool>,
+    w: RefCell<&'a mut W>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+const TITLE_ADORNMENT: &str = "#";
+
+const HEADING_ADORNMENTS: [&str; 7] = ["=", "-", "^", "\"", "'", "~", "+"];
+
+const DIRECTIVE_INDENT: &str = "   ";
+
+const MARKUP_SEPARATOR: &str = "\\ ";
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the writer function for reStructuredText.
+///
+/// While this can be called directly it is most often used  by calling either
+/// [`model::write_document`](../fn.write_document.html) or
+/// [`model::write_document_to_string`](../fn.write_document_to_string.html).
+///
+#[inline]
+pub fn writer<W: Write>(doc: &Document, w: &mut W) -> crate::error::Result<()> {
+    let writer = RstWriter::new(w);
+    writer.write_document(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> Writer<'a, W> for RstWriter<'a, W> {
+    fn new(w: &'a mut W) -> Self {
+        Self {
+            metadata: RefCell::from(Vec::default()),
+            list_stack: RefCell::from(Vec::default()),
+            indent_stack: RefCell::from(Vec::default()),
+            span_stack: RefCell::from(Vec::default()),
+            heading: RefCell::from(None),
+            substitutions: RefCell::from(Vec::default()),
+            substitution_count: RefCell::from(0),
+            table_caption: RefCell::from(None),
+            in_table: RefCell::from(false),
+            last_char: RefCell::from(None),
+            markup_opened: RefCell::from(false),
+            markup_closed: RefCell::from(false),
+            line_open: RefCell::from(false),
+            block_written: RefCell::from(false),
+            blank_pending: RefCell::from(false),
+            w: RefCell::from(w),
+        }
+    }
+
+    fn write_document(&self, doc: &Document) -> crate::error::Result<()> {
+        walk_document(doc, self)?;
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> RstWriter<'a, W> {
+    fn write(&self, text: &str) -> crate::error::Result<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        let _ = self.markup_opened.replace(false);
+        if self.markup_closed.replace(false) && !text.starts_with(ends_markup) {
+            // inline markup must be followed by whitespace or punctuation to be recognized.
+            self.write(MARKUP_SEPARATOR)?;
+        }
+        let _ = self
+            .last_char
+            .replace(text.chars().last().filter(|c| *c != '\n'));
+        if let Some(heading) = self.heading.borrow_mut().as_mut() {
+            heading.push_str(text);
+            return Ok(());
+        }
+        let mut w = self.w.borrow_mut();
+        if self.blank_pending.replace(false) {
+            writeln!(w)?;
+        }
+        let prefix = self.indent_stack.borrow().join("");
+        for (i, segment) in text.split('\n').enumerate() {
+            if i > 0 {
+                writeln!(w)?;
+                let _ = self.line_open.replace(false);
+            }
+            if !segment.is_empty() {
+                if !self.line_open.replace(true) {
+                    write!(w, "{}", prefix)?;
+                }
+                write!(w, "{}", segment)?;
+            }
+        }
+        let _ = self.block_written.replace(true);
+        Ok(())
+    }
+
+    fn end_line(&self) -> crate::error::Result<()> {
+        let _ = self.last_char.replace(None);
+        let _ = self.markup_opened.replace(false);
+        let _ = self.markup_closed.replace(false);
+        if self.line_open.replace(false) {
+            writeln!(&mut self.w.borrow_mut())?;
+        }
+        Ok(())
+    }
+
+    fn start_markup(&self) -> crate::error::Result<()> {
+        let opened = self.markup_opened.replace(false);
+        let closed = self.markup_closed.replace(false);
+        if closed || (!opened && !starts_markup(*self.last_char.borrow())) {
+            // inline markup must be preceded by whitespace or punctuation to be recognized.
+            self.write(MARKUP_SEPARATOR)?;
+        }
+        Ok(())
+    }
+
+    fn write_markup(&self, markup: &str) -> crate::error::Result<()> {
+        self.start_markup()?;
+        self.write(markup)?;
+        let _ = self.markup_closed.replace(true);
+        Ok(())
+    }
+
+    fn write_line(&self, text: &str) -> crate::error::Result<()> {
+        self.write(text)?;
+        self.end_line()
+    }
+
+    fn write_label(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        if let Some(label) = label {
+            let label = label.to_string();
+            if label.contains(':') {
+                self.write_line(&format!(".. _`{}`:", label))?;
+            } else {
+                self.write_line(&format!(".. _{}:", label))?;
+            }
+            let _ = self.blank_pending.replace(true);
+        }
+        Ok(())
+    }
+
+    fn write_directive_body(&self, body: &str) -> crate::error::Result<()> {
+        let _ = self.blank_pending.replace(true);
+        self.indent_stack
+            .borrow_mut()
+            .push(DIRECTIVE_INDENT.to_string());
+        self.write(body)?;
+        self.end_line()?;
+        let _ = self.indent_stack.borrow_mut().pop();
+        Ok(())
+    }
+
+    fn write_header(&self) -> crate::error::Result<()> {
+        let metadata = self.metadata.borrow();
+        if let Some(title) = metadata.iter().find_map(|datum| match datum {
+            Metadata::Title(title) => Some(title),
+            _ => None,
+        }) {
+            let adornment = string_of_strings(TITLE_ADORNMENT, title.chars().count());
+            self.write_line(&adornment)?;
+            self.write_line(title)?;
+            self.write_line(&adornment)?;
+            let _ = self.blank_pending.replace(true);
+        }
+        let authors: Vec<String> = metadata
+            .iter()
+            .filter_map(|datum| match datum {
+                Metadata::Author(author) => Some(match &author.email {
+                    Some(email) => format!("{} <{}>", author.name, email),
+                    None => author.name.clone(),
+                }),
+                _ => None,
+            })
+            .collect();
+        if authors.len() == 1 {
+            self.write_line(&format!(":Author: {}", authors.first().unwrap()))?;
+        } else if !authors.is_empty() {
+            self.write_line(&format!(":Authors: {}", authors.join("; ")))?;
+        }
+        for datum in metadata.iter() {
+            let (key, value) = match datum {
+                Metadata::Author(_) | Metadata::Title(_) => continue,
+                Metadata::Copyright(_) => ("Copyright".to_string(), datum.value_string()),
+                Metadata::Date(value) => ("Date".to_string(), value.clone()),
+                Metadata::Keywords(value) => ("Keywords".to_string(), value.join(", ")),
+                Metadata::Revision(value) => ("Revision".to_string(), value.clone()),
+                Metadata::Status(value) => ("Status".to_string(), value.clone()),
+                Metadata::Other(property) => (property.key.clone(), property.value.clone()),
+            };
+            self.write_line(&format!(":{}: {}", key, value))?;
+        }
+        let _ = self
+            .blank_pending
+            .replace(self.block_written.replace(false));
+        Ok(())
+    }
+
+    fn write_substitutions(&self) -> crate::error::Result<()> {
+        let substitutions: Vec<(String, String)> =
+            self.substitutions.borrow_mut().drain(..).collect();
+        if !substitutions.is_empty() {
+            let _ = self.blank_pending.replace(true);
+            for (name, path) in substitutions {
+                self.write_line(&format!(".. |{}| image:: {}", name, path))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn start_table_directive(&self, header_row: bool) -> crate::error::Result<()> {
+        if !self.in_table.replace(true) {
+            match self.table_caption.borrow_mut().take() {
+                Some(caption) => self.write_line(&format!(".. list-table:: {}", caption))?,
+                None => self.write_line(".. list-table::")?,
+            }
+            if header_row {
+                self.write_line(&format!("{}:header-rows: 1", DIRECTIVE_INDENT))?;
+            }
+            let _ = self.blank_pending.replace(true);
+            self.indent_stack
+                .borrow_mut()
+                .push(DIRECTIVE_INDENT.to_string());
+        }
+        Ok(())
+    }
+
+    fn start_table_cell_text(&self, column_idx: usize) -> crate::error::Result<()> {
+        self.write(if column_idx == 0 { "* - " } else { "  - " })?;
+        self.indent_stack.borrow_mut().push("    ".to_string());
+        Ok(())
+    }
+
+    fn end_table_cell_text(&self) -> crate::error::Result<()> {
+        self.end_line()?;
+        let _ = self.indent_stack.borrow_mut().pop();
+        Ok(())
+    }
+}
+
+impl<'a, W: Write> DocumentVisitor for RstWriter<'a, W> {
+    fn metadata(&self, meta_datum: &Metadata) -> crate::error::Result<()> {
+        self.metadata.borrow_mut().push(meta_datum.clone());
+        Ok(())
+    }
+
+    fn block_visitor(&self) -> Option<&dyn BlockVisitor> {
+        if !self.metadata.borrow().is_empty() {
+            let _ = self.write_header();
+        }
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> BlockVisitor for RstWriter<'a, W> {
+    fn start_abstract(&self) -> crate::error::Result<()> {
+        self.write_line(".. topic:: Abstract")?;
+        let _ = self.blank_pending.replace(true);
+        self.indent_stack
+            .borrow_mut()
+            .push(DIRECTIVE_INDENT.to_string());
+        Ok(())
+    }
+
+    fn end_abstract(&self) -> crate::error::Result<()> {
+        self.end_line()?;
+        let _ = self.indent_stack.borrow_mut().pop();
+        Ok(())
+    }
+
+    fn comment(&self, value: &str) -> crate::error::Result<()> {
+        self.write("..")?;
+        if !value.is_empty() {
+            self.write(" ")?;
+            self.indent_stack
+                .borrow_mut()
+                .push(DIRECTIVE_INDENT.to_string());
+            self.write(value)?;
+            let _ = self.indent_stack.borrow_mut().pop();
+        }
+        Ok(())
+    }
+
+    fn front_matter(&self, value: &FrontMatter) -> crate::error::Result<()> {
+        if matches!(value, FrontMatter::TableOfContents) {
+            self.write(".. contents::")?;
+        }
+        Ok(())
+    }
+
+    fn start_heading(&self, _: &HeadingLevel, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_label(label)?;
+        let _ = self.last_char.replace(None);
+        let _ = self.heading.replace(Some(String::new()));
+        Ok(())
+    }
+
+    fn end_heading(&self, level: &HeadingLevel, _: &Option<Label>) -> crate::error::Result<()> {
+        if let Some(heading) = self.heading.replace(None) {
+            self.write_line(&heading)?;
+            self.write(&string_of_strings(
+                HEADING_ADORNMENTS[level.clone() as usize - 1],
+                heading.chars().count(),
+            ))?;
+        }
+        Ok(())
+    }
+
+    fn image(
+        &self,
+        value: &Image,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_label(label)?;
+        if caption.is_some() {
+            self.write_line(&format!(".. figure:: {}", value.inner()))?;
+        } else {
+            self.write_line(&format!(".. image:: {}", value.inner()))?;
+        }
+        if let Some(alt_text) = value.alt_text() {
+            self.write_line(&format!("{}:alt: {}", DIRECTIVE_INDENT, alt_text))?;
+        }
+        if let Some(caption) = caption {
+            self.write_directive_body(&escape(caption.inner()))?;
+        }
+        Ok(())
+    }
+
+    fn math(
+        &self,
+        value: &Math,
+        _caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_label(label)?;
+        self.write_line(".. math::")?;
+        self.write_directive_body(value.inner())
+    }
+
+    fn start_list(&self, kind: &ListKind, label: &Option<Label>) -> crate::error::Result<()> {
+        let mut list_stack = self.list_stack.borrow_mut();
+        match list_stack.last() {
+            None => self.write_label(label)?,
+            Some(parent) => {
+                // nested lists are indented to the parent item's text, with blank lines around.
+                self.end_line()?;
+                let _ = self.blank_pending.replace(true);
+                self.indent_stack.borrow_mut().push(
+                    match parent {
+                        ListKind::Ordered => "   ",
+                        ListKind::Unordered => "  ",
+                    }
+                    .to_string(),
+                );
+            }
+        }
+        list_stack.push(kind.clone());
+        Ok(())
+    }
+
+    fn end_list(&self, _: &ListKind, _: &Option<Label>) -> crate::error::Result<()> {
+        let mut list_stack = self.list_stack.borrow_mut();
+        let _ = list_stack.pop();
+        if !list_stack.is_empty() {
+            self.end_line()?;
+            let _ = self.indent_stack.borrow_mut().pop();
+            let _ = self.blank_pending.replace(true);
+        }
+        Ok(())
+    }
+
+    fn start_list_item(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        match self.list_stack.borrow().last() {
+            Some(ListKind::Ordered) => self.write("#. "),
+            Some(ListKind::Unordered) => self.write("* "),
+            None => Ok(()),
+        }
+    }
+
+    fn end_list_item(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_line()
+    }
+
+    fn start_definition_list(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_label(label)
+    }
+
+    fn start_definition(&self, term: &str, _: &Option<Label>) -> crate::error::Result<()> {
+        self.write_line(&escape(term))?;
+        self.indent_stack
+            .borrow_mut()
+            .push(DIRECTIVE_INDENT.to_string());
+        Ok(())
+    }
+
+    fn end_definition(&self, _: &str, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_line()?;
+        let _ = self.indent_stack.borrow_mut().pop();
+        Ok(())
+    }
+
+    fn formatted(&self, value: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_label(label)?;
+        self.write_line("::")?;
+        self.write_directive_body(value)
+    }
+
+    fn code_block(
+        &self,
+        code: &str,
+        language: &Option<String>,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_label(label)?;
+        match language {
+            Some(language) => self.write_line(&format!(".. code-block:: {}", language))?,
+            None => self.write_line(".. code-block::")?,
+        }
+        if let Some(caption) = caption {
+            self.write_line(&format!(
+                "{}:caption: {}",
+                DIRECTIVE_INDENT,
+                caption.inner()
+            ))?;
+        }
+        self.write_directive_body(code)
+    }
+
+    fn start_paragraph(
+        &self,
+        alignment: &Alignment,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_label(label)?;
+        let class = match alignment {
+            Alignment::Right => "align-right",
+            Alignment::Centered => "align-center",
+            _ => return Ok(()),
+        };
+        self.write_line(&format!(".. class:: {}", class))?;
+        let _ = self.blank_pending.replace(true);
+        Ok(())
+    }
+
+    fn start_quote(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_label(label)?;
+        self.indent_stack
+            .borrow_mut()
+            .push(DIRECTIVE_INDENT.to_string());
+        Ok(())
+    }
+
+    fn end_quote(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_line()?;
+        let _ = self.indent_stack.borrow_mut().pop();
+        Ok(())
+    }
+
+    fn thematic_break(&self) -> crate::error::Result<()> {
+        self.write("----")
+    }
+
+    fn end_block(&self) -> crate::error::Result<()> {
+        if self.block_written.replace(false) {
+            self.end_line()?;
+            self.write_substitutions()?;
+            let _ = self.block_written.replace(false);
+            let _ = self.blank_pending.replace(true);
+        }
+        Ok(())
+    }
+
+    fn table_visitor(&self) -> Option<&dyn TableVisitor> {
+        Some(self)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> TableVisitor for RstWriter<'a, W> {
+    fn start_table(
+        &self,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_label(label)?;
+        let _ = self
+            .table_caption
+            .replace(caption.as_ref().map(|caption| caption.inner().to_string()));
+        Ok(())
+    }
+
+    fn start_table_header_row(&self) -> crate::error::Result<()> {
+        self.start_table_directive(true)
+    }
+
+    fn table_header_cell(
+        &self,
+        column_cell: &Column,
+        column_idx: usize,
+    ) -> crate::error::Result<()> {
+        self.start_table_cell_text(column_idx)?;
+        self.write(&escape(column_cell.text()))?;
+        self.end_table_cell_text()
+    }
+
+    fn start_table_row(&self, _: usize) -> crate::error::Result<()> {
+        self.start_table_directive(false)
+    }
+
+    fn start_table_cell(&self, column_idx: usize, _: &Option<Label>) -> crate::error::Result<()> {
+        self.start_table_cell_text(column_idx)
+    }
+
+    fn end_table_cell(&self, _: usize, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_table_cell_text()
+    }
+
+    fn end_table(
+        &self,
+        _caption: &Option<Caption>,
+        _label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.start_table_directive(false)?;
+        let _ = self.in_table.replace(false);
+        let _ = self.indent_stack.borrow_mut().pop();
+        Ok(())
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> InlineVisitor for RstWriter<'a, W> {
+    fn link(&self, value: &HyperLink) -> crate::error::Result<()> {
+        let caption = value.caption().as_ref().map(|caption| caption.inner());
+        match (value.target(), caption) {
+            (HyperLinkTarget::External(target), Some(caption)) => {
+                self.write_markup(&format!("`{} <{}>`__", caption, target))
+            }
+            (HyperLinkTarget::External(target), None) => self.write(target),
+            (HyperLinkTarget::Internal(target), Some(caption)) => {
+                self.write_markup(&format!(":ref:`{} <{}>`", caption, target))
+            }
+            (HyperLinkTarget::Internal(target), None) => {
+                self.write_markup(&format!(":ref:`{}`", target))
+            }
+        }
+    }
+
+    fn image(&self, value: &Image) -> crate::error::Result<()> {
+        // inline images are substitution references, defined after the enclosing block.
+        let mut substitutions = self.substitutions.borrow_mut();
+        let name = match value.alt_text() {
+            Some(alt_text) if !substitutions.iter().any(|(name, _)| name == alt_text) => {
+                alt_text.clone()
+            }
+            _ => {
+                *self.substitution_count.borrow_mut() += 1;
+                format!("image-{}", self.substitution_count.borrow())
+            }
+        };
+        substitutions.push((name.clone(), value.inner().to_string()));
+        self.write_markup(&format!("|{}|", name))
+    }
+
+    fn text(&self, value: &Text) -> crate::error::Result<()> {
+        self.write(&escape(value.inner()))
+    }
+
+    fn math(&self, value: &Math) -> crate::error::Result<()> {
+        self.write_markup(&format!(":math:`{}`", value.inner()))
+    }
+
+    fn character(&self, value: &Character) -> crate::error::Result<()> {
+        self.write(&match value {
+            Character::Space => " ".to_string(),
+            Character::NonBreakSpace => "\u{a0}".to_string(),
+            Character::Hyphen => "-".to_string(),
+            Character::EmDash => "\u{2014}".to_string(),
+            Character::EnDash => "\u{2013}".to_string(),
+            Character::Emoji(name) => format!(":{}:", name.inner()),
+            Character::Other(c) => c.to_string(),
+        })
+    }
+
+    fn line_break(&self) -> crate::error::Result<()> {
+        self.write("\n")
+    }
+
+    fn start_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        // inline markup may not be nested, so only the outermost styled span is marked up.
+        let mut span_stack = self.span_stack.borrow_mut();
+        let markup = if span_stack.iter().any(Option::is_some) {
+            None
+        } else {
+            span_markup(styles)
+        };
+        span_stack.push(markup);
+        if let Some((start, _)) = markup {
+            self.start_markup()?;
+            self.write(start)?;
+            let _ = self.markup_opened.replace(true);
+        }
+        Ok(())
+    }
+
+    fn end_span(&self, _: &[SpanStyle]) -> crate::error::Result<()> {
+        if let Some(Some((_, end))) = self.span_stack.borrow_mut().pop() {
+            self.write(end)?;
+            let _ = self.markup_closed.replace(true);
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('*', "\\*")
+        .replace('`', "\\`")
+        .replace('|', "\\|")
+        .replace('_', "\\_")
+}
+
+fn starts_markup(previous: Option<char>) -> bool {
+    match previous {
+        None => true,
+        Some(c) => c.is_whitespace() || "-:/'\"<([{".contains(c),
+    }
+}
+
+fn ends_markup(next: char) -> bool {
+    next.is_whitespace() || "-.,:;!?\\/'\")]}>".contains(next)
+}
+
+fn span_markup(styles: &[SpanStyle]) -> Option<(&'static str, &'static str)> {
+    let mut markup = None;
+    for style in styles {
+        match style {
+            SpanStyle::Plain => markup = None,
+            SpanStyle::Italic => markup = markup.or(Some(("*", "*"))),
+            SpanStyle::Bold => markup = markup.or(Some(("**", "**"))),
+            SpanStyle::Mono | SpanStyle::Code => markup = markup.or(Some(("``", "``"))),
+            SpanStyle::Superscript => markup = markup.or(Some((":sup:`", "`"))),
+            SpanStyle::Subscript => markup = markup.or(Some((":sub:`", "`"))),
+            _ => {}
+        }
+    }
+    markup
+}
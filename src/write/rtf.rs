@@ -0,0 +1,709 @@
+/*!
+Write a document in the [Rich Text Format](https://en.wikipedia.org/wiki/Rich_Text_Format), as
+understood by word processors back to the earliest versions of Microsoft Word.
+
+The document title, authors, keywords, date, and abstract are written to the `\info` group;
+characters outside of ASCII are written as `\uN?` escapes so that Unicode content survives any
+code page. Images are not embedded, they are written as hyperlinks to the image source.
+
+# Example
+
+```rust
+# use somedoc::model::Document;
+use somedoc::write::rtf::writer;
+
+# fn make_some_document() -> Document { Document::default() }
+let doc = make_some_document();
+
+writer(&doc, &mut std::io::stdout()).unwrap();
+```
+*/
+
+use crate::model::block::{
+    Alignment, Caption, Column, FrontMatter, HasAlignment, HasCaption, HeadingLevel, Label,
+    ListKind,
+};
+use crate::model::document::Metadata;
+use crate::model::inline::text::Size;
+use crate::model::inline::{
+    Character, HasInlineContent, HyperLink, HyperLinkTarget, Image, Math, SpanStyle, Text,
+};
+use crate::model::visitor::{
+    walk_document, BlockVisitor, DocumentVisitor, InlineVisitor, TableVisitor,
+};
+use crate::model::Document;
+use crate::write::Writer;
+use std::cell::RefCell;
+use std::io::Write;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the RTF writer structure, usually this is accessed via the `writer`
+/// function, but may be used directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use somedoc::model::Document;
+/// use somedoc::write::rtf::RtfWriter;
+/// use somedoc::write::Writer;
+///
+/// # fn make_some_document() -> Document { Document::default() }
+/// let doc = make_some_document();
+/// let mut out = std::io::stdout();
+/// let writer = RtfWriter::new(&mut out);
+/// assert!(writer.write_document(&doc).is_ok());
+/// ```
+///
+#[derive(Debug)]
+pub struct RtfWriter<'a, W: Write> {
+    list_stack: RefCell<Vec<(ListKind, usize)>>,
+    quote_depth: RefCell<usize>,
+    bookmarks: RefCell<Vec<String>>,
+    table: RefCell<Option<TableRow>>,
+    w: RefCell<&'a mut W>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Table rows are buffered as each row must define its cell boundaries before any cell content.
+///
+#[derive(Debug, Default)]
+struct TableRow {
+    alignments: Vec<Alignment>,
+    cells: Vec<String>,
+    in_cell: bool,
+}
+
+const HEADER: &str = r"{\rtf1\ansi\ansicpg1252\deff0\uc1
+{\fonttbl{\f0\froman\fcharset0 Times New Roman;}{\f1\fswiss\fcharset0 Arial;}{\f2\fmodern\fcharset0 Courier New;}}
+{\colortbl;\red0\green0\blue0;\red5\green99\blue193;}
+";
+
+const PAGE: &str = r"\paperw12240\paperh15840\margl1440\margr1440\margt1440\margb1440\widowctrl
+";
+
+/// The width of the text between the page margins, in twentieths of a point.
+const TEXT_WIDTH: usize = 9360;
+
+/// Paragraph and list indentation, in twentieths of a point.
+const INDENT: usize = 720;
+
+const PARAGRAPH: &str = r"\pard\plain\sa180\f0\fs24";
+
+const HEADING_SIZES: [u8; 7] = [36, 32, 28, 26, 24, 24, 24];
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the writer function for RTF.
+///
+/// While this can be called directly it is most often used  by calling either
+/// [`model::write_document`](../fn.write_document.html) or
+/// [`model::write_document_to_string`](../fn.write_document_to_string.html).
+///
+#[inline]
+pub fn writer<W: Write>(doc: &Document, w: &mut W) -> crate::error::Result<()> {
+    let writer = RtfWriter::new(w);
+    writer.write_document(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> Writer<'a, W> for RtfWriter<'a, W> {
+    fn new(w: &'a mut W) -> Self {
+        Self {
+            list_stack: RefCell::from(Vec::default()),
+            quote_depth: RefCell::from(0),
+            bookmarks: RefCell::from(Vec::default()),
+            table: RefCell::from(None),
+            w: RefCell::from(w),
+        }
+    }
+
+    fn write_document(&self, doc: &Document) -> crate::error::Result<()> {
+        self.write(HEADER)?;
+        self.write(&info_group(doc))?;
+        self.write(PAGE)?;
+        walk_document(doc, self)?;
+        self.write("}\n")
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> RtfWriter<'a, W> {
+    fn write(&self, text: &str) -> crate::error::Result<()> {
+        if let Some(row) = self.table.borrow_mut().as_mut() {
+            if row.in_cell {
+                if let Some(cell) = row.cells.last_mut() {
+                    cell.push_str(text);
+                    return Ok(());
+                }
+            }
+        }
+        self.w.borrow_mut().write_all(text.as_bytes())?;
+        Ok(())
+    }
+
+    fn start_paragraph_with(&self, controls: &str) -> crate::error::Result<()> {
+        let quote_depth = *self.quote_depth.borrow();
+        let quote = if quote_depth > 0 {
+            format!("\\li{0}\\ri{1}", INDENT * quote_depth, INDENT)
+        } else {
+            String::new()
+        };
+        self.write(&format!("{}{}{} ", PARAGRAPH, quote, controls))?;
+        self.write_bookmarks()
+    }
+
+    fn end_paragraph_with(&self) -> crate::error::Result<()> {
+        self.write("\\par\n")
+    }
+
+    fn bookmark(&self, label: &Option<Label>) {
+        if let Some(label) = label {
+            self.bookmarks.borrow_mut().push(label.to_string());
+        }
+    }
+
+    fn write_bookmarks(&self) -> crate::error::Result<()> {
+        let bookmarks: Vec<String> = self.bookmarks.borrow_mut().drain(..).collect();
+        for bookmark in bookmarks {
+            let name = bookmark_name(&bookmark);
+            self.write(&format!("{{\\*\\bkmkstart {0}}}{{\\*\\bkmkend {0}}}", name))?;
+        }
+        Ok(())
+    }
+
+    fn caption(
+        &self,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        if let Some(caption) = caption {
+            self.bookmark(label);
+            self.start_paragraph_with("\\qc\\i\\fs20")?;
+            self.write(&escape(caption.inner()))?;
+            self.end_paragraph_with()?;
+        }
+        Ok(())
+    }
+
+    fn preformatted(&self, value: &str, controls: &str) -> crate::error::Result<()> {
+        self.start_paragraph_with(&format!("{}\\f2\\fs20", controls))?;
+        for (i, line) in value.lines().enumerate() {
+            if i > 0 {
+                self.write("\\line\n")?;
+            }
+            self.write(&escape(line))?;
+        }
+        self.end_paragraph_with()
+    }
+
+    fn field(&self, instruction: &str, result: &str) -> crate::error::Result<()> {
+        self.write(&format!(
+            "{{\\field{{\\*\\fldinst{{{}}}}}{{\\fldrslt{{{}}}}}}}",
+            instruction, result
+        ))
+    }
+}
+
+impl<'a, W: Write> DocumentVisitor for RtfWriter<'a, W> {
+    fn metadata(&self, meta_datum: &Metadata) -> crate::error::Result<()> {
+        if let Metadata::Title(title) = meta_datum {
+            self.start_paragraph_with("\\qc\\sa360\\b\\fs48")?;
+            self.write(&escape(title))?;
+            self.end_paragraph_with()?;
+        }
+        Ok(())
+    }
+
+    fn block_visitor(&self) -> Option<&dyn BlockVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> BlockVisitor for RtfWriter<'a, W> {
+    fn start_abstract(&self) -> crate::error::Result<()> {
+        self.start_paragraph_with(&format!("\\li{0}\\ri{0}\\qj\\i", INDENT))
+    }
+
+    fn end_abstract(&self) -> crate::error::Result<()> {
+        self.end_paragraph_with()
+    }
+
+    fn front_matter(&self, value: &FrontMatter) -> crate::error::Result<()> {
+        if *value == FrontMatter::TableOfContents {
+            self.start_paragraph_with("")?;
+            self.field(
+                "TOC \\\\o \"1-3\" \\\\h",
+                "Update this field to show the table of contents.",
+            )?;
+            self.end_paragraph_with()?;
+        }
+        Ok(())
+    }
+
+    fn start_heading(
+        &self,
+        level: &HeadingLevel,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        let level = level.clone() as usize;
+        self.bookmark(label);
+        self.start_paragraph_with(&format!(
+            "\\sb240\\sa120\\keepn\\outlinelevel{}\\b\\fs{}",
+            level - 1,
+            HEADING_SIZES[level - 1]
+        ))
+    }
+
+    fn end_heading(&self, _: &HeadingLevel, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_paragraph_with()
+    }
+
+    fn image(
+        &self,
+        value: &Image,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        if caption.is_none() {
+            self.bookmark(label);
+        }
+        self.start_paragraph_with("\\qc")?;
+        InlineVisitor::image(self, value)?;
+        self.end_paragraph_with()?;
+        self.caption(caption, label)
+    }
+
+    fn math(
+        &self,
+        value: &Math,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        if caption.is_none() {
+            self.bookmark(label);
+        }
+        self.preformatted(value.inner(), "\\qc")?;
+        self.caption(caption, label)
+    }
+
+    fn start_list(&self, kind: &ListKind, label: &Option<Label>) -> crate::error::Result<()> {
+        self.bookmark(label);
+        self.list_stack.borrow_mut().push((kind.clone(), 0));
+        Ok(())
+    }
+
+    fn end_list(&self, _: &ListKind, _: &Option<Label>) -> crate::error::Result<()> {
+        let _ = self.list_stack.borrow_mut().pop();
+        Ok(())
+    }
+
+    fn start_list_item(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.bookmark(label);
+        let (depth, marker) = {
+            let mut list_stack = self.list_stack.borrow_mut();
+            let depth = list_stack.len();
+            match list_stack.last_mut() {
+                Some((ListKind::Ordered, count)) => {
+                    *count += 1;
+                    (depth, format!("{}.", count))
+                }
+                _ => (depth, "\\bullet".to_string()),
+            }
+        };
+        self.start_paragraph_with(&format!(
+            "\\sa60\\fi-360\\li{}\\tx{}",
+            INDENT * depth,
+            INDENT * depth
+        ))?;
+        self.write(&format!("{}\\tab ", marker))
+    }
+
+    fn end_list_item(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_paragraph_with()
+    }
+
+    fn start_definition_list(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.bookmark(label);
+        Ok(())
+    }
+
+    fn start_definition(&self, term: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.bookmark(label);
+        self.start_paragraph_with("\\sa0\\keepn\\b")?;
+        self.write(&escape(term))?;
+        self.end_paragraph_with()
+    }
+
+    fn start_definition_list_text(&self) -> crate::error::Result<()> {
+        self.start_paragraph_with(&format!("\\li{}", INDENT))
+    }
+
+    fn end_definition_list_text(&self) -> crate::error::Result<()> {
+        self.end_paragraph_with()
+    }
+
+    fn formatted(&self, value: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.bookmark(label);
+        self.preformatted(value, "")
+    }
+
+    fn code_block(
+        &self,
+        code: &str,
+        _: &Option<String>,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        if caption.is_none() {
+            self.bookmark(label);
+        }
+        self.preformatted(code, "")?;
+        self.caption(caption, label)
+    }
+
+    fn start_paragraph(
+        &self,
+        alignment: &Alignment,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.bookmark(label);
+        self.start_paragraph_with(justification(alignment))
+    }
+
+    fn end_paragraph(&self, _: &Alignment, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_paragraph_with()
+    }
+
+    fn start_quote(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        self.bookmark(label);
+        *self.quote_depth.borrow_mut() += 1;
+        Ok(())
+    }
+
+    fn end_quote(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        *self.quote_depth.borrow_mut() -= 1;
+        Ok(())
+    }
+
+    fn thematic_break(&self) -> crate::error::Result<()> {
+        self.start_paragraph_with("\\brdrb\\brdrs\\brdrw10\\brsp20")?;
+        self.end_paragraph_with()
+    }
+
+    fn table_visitor(&self) -> Option<&dyn TableVisitor> {
+        Some(self)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> TableVisitor for RtfWriter<'a, W> {
+    fn start_table(
+        &self,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        if caption.is_none() {
+            self.bookmark(label);
+        }
+        let _ = self.table.replace(Some(TableRow::default()));
+        Ok(())
+    }
+
+    fn start_table_header_row(&self) -> crate::error::Result<()> {
+        Ok(())
+    }
+
+    fn table_header_cell(
+        &self,
+        column_cell: &Column,
+        _column_idx: usize,
+    ) -> crate::error::Result<()> {
+        if let Some(row) = self.table.borrow_mut().as_mut() {
+            row.alignments.push(column_cell.alignment().clone());
+            row.cells
+                .push(format!("{{\\b {}}}", escape(column_cell.text())));
+        }
+        Ok(())
+    }
+
+    fn end_table_header_row(&self) -> crate::error::Result<()> {
+        self.end_row(true)
+    }
+
+    fn start_table_row(&self, _: usize) -> crate::error::Result<()> {
+        Ok(())
+    }
+
+    fn start_table_cell(&self, _: usize, label: &Option<Label>) -> crate::error::Result<()> {
+        if let Some(row) = self.table.borrow_mut().as_mut() {
+            row.cells.push(String::new());
+            row.in_cell = true;
+        }
+        self.bookmark(label);
+        self.write_bookmarks()
+    }
+
+    fn end_table_cell(&self, _: usize, _: &Option<Label>) -> crate::error::Result<()> {
+        if let Some(row) = self.table.borrow_mut().as_mut() {
+            row.in_cell = false;
+        }
+        Ok(())
+    }
+
+    fn end_table_row(&self, _: usize) -> crate::error::Result<()> {
+        self.end_row(false)
+    }
+
+    fn end_table(
+        &self,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        let _ = self.table.replace(None);
+        self.caption(caption, label)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> RtfWriter<'a, W> {
+    fn end_row(&self, header: bool) -> crate::error::Result<()> {
+        let (alignments, cells) = match self.table.borrow_mut().as_mut() {
+            Some(row) => (row.alignments.clone(), std::mem::take(&mut row.cells)),
+            None => return Ok(()),
+        };
+        let width = TEXT_WIDTH / cells.len().max(1);
+        let mut text = format!(
+            "\\trowd\\trgaph108\\trleft0{}\n",
+            if header { "\\trhdr" } else { "" }
+        );
+        for i in 0..cells.len() {
+            text.push_str(&format!(
+                "\\clbrdrt\\brdrs\\brdrw10\\clbrdrl\\brdrs\\brdrw10\\clbrdrb\\brdrs\\brdrw10\\clbrdrr\\brdrs\\brdrw10\\cellx{}\n",
+                width * (i + 1)
+            ));
+        }
+        for (i, cell) in cells.iter().enumerate() {
+            text.push_str(&format!(
+                "\\pard\\plain\\intbl\\f0\\fs24{} {}\\cell\n",
+                justification(alignments.get(i).unwrap_or(&Alignment::Left)),
+                cell
+            ));
+        }
+        text.push_str("\\row\n");
+        self.write(&text)
+    }
+}
+
+impl<'a, W: Write> InlineVisitor for RtfWriter<'a, W> {
+    fn link(&self, value: &HyperLink) -> crate::error::Result<()> {
+        let (instruction, text) = match value.target() {
+            HyperLinkTarget::External(target) => {
+                (format!("HYPERLINK \"{}\"", escape(target)), target.clone())
+            }
+            HyperLinkTarget::Internal(target) => (
+                format!("HYPERLINK \\\\l \"{}\"", bookmark_name(&target.to_string())),
+                target.to_string(),
+            ),
+        };
+        self.field(
+            &instruction,
+            &format!(
+                "\\ul\\cf2 {}",
+                escape(match value.caption() {
+                    Some(caption) => caption.inner(),
+                    None => &text,
+                })
+            ),
+        )
+    }
+
+    fn image(&self, value: &Image) -> crate::error::Result<()> {
+        self.field(
+            &format!("HYPERLINK \"{}\"", escape(value.inner())),
+            &format!(
+                "\\ul\\cf2 {}",
+                escape(match value.alt_text() {
+                    Some(alt_text) => alt_text,
+                    None => value.inner(),
+                })
+            ),
+        )
+    }
+
+    fn text(&self, value: &Text) -> crate::error::Result<()> {
+        self.write(&escape(value.inner()))
+    }
+
+    fn math(&self, value: &Math) -> crate::error::Result<()> {
+        self.write(&format!("{{\\f2 {}}}", escape(value.inner())))
+    }
+
+    fn character(&self, value: &Character) -> crate::error::Result<()> {
+        self.write(&match value {
+            Character::Space => " ".to_string(),
+            Character::NonBreakSpace => "\\~".to_string(),
+            Character::Hyphen => "-".to_string(),
+            Character::EmDash => "\\emdash ".to_string(),
+            Character::EnDash => "\\endash ".to_string(),
+            Character::Emoji(name) => format!(":{}:", escape(name.inner())),
+            Character::Other(c) => escape(&c.to_string()),
+        })
+    }
+
+    fn line_break(&self) -> crate::error::Result<()> {
+        self.write("\\line ")
+    }
+
+    fn start_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        let controls = span_controls(styles);
+        if controls.is_empty() {
+            self.write("{")
+        } else {
+            self.write(&format!("{{{} ", controls))
+        }
+    }
+
+    fn end_span(&self, _: &[SpanStyle]) -> crate::error::Result<()> {
+        self.write("}")
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn info_group(doc: &Document) -> String {
+    let mut info: Vec<String> = Default::default();
+    let mut authors: Vec<&str> = Default::default();
+    let mut keywords: Vec<&str> = Default::default();
+    for datum in doc.metadata() {
+        match datum {
+            Metadata::Title(title) => info.push(format!("{{\\title {}}}", escape(title))),
+            Metadata::Author(author) => authors.push(&author.name),
+            Metadata::Keywords(values) => keywords.extend(values.iter().map(String::as_str)),
+            Metadata::Date(date) => {
+                let parts: Vec<&str> = date.split('-').collect();
+                if let [year, month, day] = parts.as_slice() {
+                    if let (Ok(year), Ok(month), Ok(day)) =
+                        (year.parse::<u16>(), month.parse::<u8>(), day.parse::<u8>())
+                    {
+                        info.push(format!("{{\\creatim\\yr{}\\mo{}\\dy{}}}", year, month, day));
+                    }
+                }
+            }
+            Metadata::Other(property) if property.key == "subject" => {
+                info.push(format!("{{\\subject {}}}", escape(&property.value)))
+            }
+            _ => (),
+        }
+    }
+    if !authors.is_empty() {
+        info.push(format!("{{\\author {}}}", escape(&authors.join("; "))));
+    }
+    if !keywords.is_empty() {
+        info.push(format!("{{\\keywords {}}}", escape(&keywords.join(", "))));
+    }
+    if let Some(abstract_block) = doc.abstract_block() {
+        info.push(format!(
+            "{{\\doccomm {}}}",
+            escape(&abstract_block.unformatted_string())
+        ));
+    }
+    if info.is_empty() {
+        String::new()
+    } else {
+        format!("{{\\info{}}}\n", info.join(""))
+    }
+}
+
+fn span_controls(styles: &[SpanStyle]) -> String {
+    styles
+        .iter()
+        .map(|style| match style {
+            SpanStyle::Plain => "\\plain\\f0\\fs24",
+            SpanStyle::Italic => "\\i",
+            SpanStyle::Bold => "\\b",
+            SpanStyle::Mono | SpanStyle::Code => "\\f2",
+            SpanStyle::Strikethrough => "\\strike",
+            SpanStyle::Underline => "\\ul",
+            SpanStyle::SmallCaps => "\\scaps",
+            SpanStyle::Superscript => "\\super",
+            SpanStyle::Subscript => "\\sub",
+            SpanStyle::Sized(size) => match size {
+                Size::Largest => "\\fs48",
+                Size::Larger => "\\fs36",
+                Size::Large => "\\fs28",
+                Size::Normal => "\\fs24",
+                Size::Small => "\\fs20",
+                Size::Smaller => "\\fs18",
+                Size::Smallest => "\\fs14",
+            },
+        })
+        .collect()
+}
+
+fn justification(alignment: &Alignment) -> &'static str {
+    match alignment {
+        Alignment::Left => "\\ql",
+        Alignment::Right => "\\qr",
+        Alignment::Centered => "\\qc",
+        Alignment::Justified => "\\qj",
+    }
+}
+
+///
+/// Bookmark names are limited to letters, digits, and underscores so that they need no escaping
+/// in either the bookmark destination or a field instruction.
+///
+fn bookmark_name(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+///
+/// Escape the RTF special characters, and write any character outside of ASCII as one, or for
+/// characters outside the basic multilingual plane two, signed 16-bit `\uN?` escapes.
+///
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '{' => escaped.push_str("\\{"),
+            '}' => escaped.push_str("\\}"),
+            '\t' => escaped.push_str("\\tab "),
+            '\n' => escaped.push_str("\\line "),
+            c if c.is_ascii() => escaped.push(c),
+            c => {
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    escaped.push_str(&format!("\\u{}?", *unit as i16));
+                }
+            }
+        }
+    }
+    escaped
+}
@@ -0,0 +1,559 @@
+/*!
+Write a document as a Slack message, either as
+[mrkdwn](https://api.slack.com/reference/surfaces/formatting) text or as
+[Block Kit](https://api.slack.com/block-kit) JSON, see [`SlackFormat`](enum.SlackFormat.html).
+
+The mrkdwn format is a single text value suitable for the `text` field of a message. Headings and
+definition terms are written in bold, lists are indented with bullets or numbers, quotes are
+prefixed with `>`, and code, formatted, and math blocks are written as fenced preformatted text.
+
+The Block Kit format is a JSON object with a `blocks` array, mapped as follows.
+
+* The document title and each `Heading` becomes a `header` block, truncated to the 150 characters
+  that Slack allows.
+* `ThematicBreak` becomes a `divider` block.
+* `Paragraph`, `List`, `DefinitionList`, and `Quote` become `section` blocks with mrkdwn text, as
+  does the document abstract.
+* `CodeBlock`, `Formatted`, and `MathBlock` become `rich_text` blocks with preformatted content.
+* `ImageBlock` becomes an `image` block, with the caption as the title; local images cannot be
+  referenced by Slack and are written as a `section` with the caption or alternate text instead.
+
+Slack has no table, anchor, or comment constructs; tables are written as a monospaced text grid,
+labels are used as the `block_id` of the corresponding block, and comments and front matter are
+ignored. Captions on code, math, and tables are written in a following `context` block.
+
+# Example
+
+```rust
+# use somedoc::model::Document;
+use somedoc::write::{OutputFormat, write_document_to_string};
+use somedoc::write::slack::SlackFormat;
+
+# fn make_some_document() -> Document { Document::default() }
+let doc = make_some_document();
+
+let doc_str = write_document_to_string(&doc, OutputFormat::Slack(SlackFormat::BlockKit)).unwrap();
+println!("{}", doc_str);
+```
+
+*/
+
+use crate::model::block::{
+    Alignment, BlockContent, Caption, DefinitionList, HasAlignment, HasCaption, HasLabel, Label,
+    List, ListItem, Table,
+};
+use crate::model::document::Metadata;
+use crate::model::inline::{
+    Character, HasInlineContent, HyperLinkTarget, Image, InlineContent, Span, SpanStyle,
+};
+use crate::model::{Document, HasInnerContent, HasStyles};
+use crate::write::{ConfigurableWriter, OutputFormat, Writer};
+use serde_json::{json, Map, Value};
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The form of Slack message to produce.
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum SlackFormat {
+    /// A single mrkdwn formatted text value.
+    #[default]
+    Mrkdwn,
+    /// A Block Kit JSON object, with a `blocks` array.
+    BlockKit,
+}
+
+///
+/// Implementation of the Slack writer structure, usually this is accessed via the `writer`
+/// function, but may be used directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use somedoc::model::Document;
+/// use somedoc::write::slack::{SlackFormat, SlackWriter};
+/// use somedoc::write::{ConfigurableWriter, Writer};
+///
+/// # fn make_some_document() -> Document { Document::default() }
+/// let doc = make_some_document();
+/// let mut out = std::io::stdout();
+/// let writer = SlackWriter::new_with(&mut out, SlackFormat::BlockKit);
+/// assert!(writer.write_document(&doc).is_ok());
+/// ```
+///
+#[derive(Debug)]
+pub struct SlackWriter<'a, W: Write> {
+    format: SlackFormat,
+    w: RefCell<&'a mut W>,
+}
+
+///
+/// The maximum number of characters Slack allows in the text of a `header` block.
+///
+pub const MAX_HEADER_LENGTH: usize = 150;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the writer function for Slack messages.
+///
+/// While this can be called directly it is most often used  by calling either
+/// [`model::write_document`](../fn.write_document.html) or
+/// [`model::write_document_to_string`](../fn.write_document_to_string.html).
+///
+#[inline]
+pub fn writer<W: Write>(
+    doc: &Document,
+    format: SlackFormat,
+    w: &mut W,
+) -> crate::error::Result<()> {
+    let writer = SlackWriter::new_with(w, format);
+    writer.write_document(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for SlackFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                SlackFormat::Mrkdwn => "mrkdwn",
+                SlackFormat::BlockKit => "blockkit",
+            }
+        )
+    }
+}
+
+impl FromStr for SlackFormat {
+    type Err = crate::error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mrkdwn" | "text" => Ok(Self::Mrkdwn),
+            "blockkit" | "blocks" | "json" => Ok(Self::BlockKit),
+            _ => Err(crate::error::ErrorKind::UnknownFormat.into()),
+        }
+    }
+}
+
+impl From<SlackFormat> for OutputFormat {
+    fn from(format: SlackFormat) -> Self {
+        OutputFormat::Slack(format)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> Writer<'a, W> for SlackWriter<'a, W> {
+    fn new(w: &'a mut W) -> Self {
+        Self::new_with(w, Default::default())
+    }
+
+    fn write_document(&self, doc: &Document) -> crate::error::Result<()> {
+        match self.format {
+            SlackFormat::Mrkdwn => {
+                write!(&mut self.w.borrow_mut(), "{}", mrkdwn_document(doc))?;
+            }
+            SlackFormat::BlockKit => {
+                let stringified = serde_json::to_string(&block_kit_document(doc))?;
+                write!(&mut self.w.borrow_mut(), "{}", stringified)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a, W: Write> ConfigurableWriter<'a, W, SlackFormat> for SlackWriter<'a, W> {
+    fn new_with(w: &'a mut W, format: SlackFormat) -> Self {
+        Self {
+            format,
+            w: RefCell::from(w),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn title(doc: &Document) -> Option<&String> {
+    doc.metadata().iter().find_map(|datum| match datum {
+        Metadata::Title(title) => Some(title),
+        _ => None,
+    })
+}
+
+fn mrkdwn_document(doc: &Document) -> String {
+    let mut blocks: Vec<String> = Default::default();
+    if let Some(title) = title(doc) {
+        blocks.push(format!("*{}*", escape(title)));
+    }
+    if let Some(abstract_block) = doc.abstract_block() {
+        blocks.push(format!("_{}_", inlines(abstract_block.inner())));
+    }
+    blocks.extend(doc.inner().iter().filter_map(mrkdwn_block));
+    if blocks.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n", blocks.join("\n\n"))
+    }
+}
+
+fn mrkdwn_block(content: &BlockContent) -> Option<String> {
+    match content {
+        BlockContent::Comment(_) | BlockContent::FrontMatter(_) => None,
+        BlockContent::Heading(v) => Some(format!("*{}*", escape(&v.unformatted_string()))),
+        BlockContent::ImageBlock(v) => Some(with_caption(image(v.inner()), v.caption())),
+        BlockContent::MathBlock(v) => Some(with_caption(fenced(v.inner()), v.caption())),
+        BlockContent::List(v) => Some(list(v, 0).join("\n")),
+        BlockContent::DefinitionList(v) => Some(definition_list(v)),
+        BlockContent::Formatted(v) => Some(fenced(v.inner())),
+        BlockContent::CodeBlock(v) => Some(with_caption(fenced(v.code()), v.caption())),
+        BlockContent::Paragraph(v) => Some(inlines(v.inner())),
+        BlockContent::Quote(v) => Some(quote(v.inner())),
+        BlockContent::Table(v) => Some(with_caption(fenced(&table(v)), v.caption())),
+        BlockContent::ThematicBreak => Some("---".to_string()),
+    }
+}
+
+fn with_caption(content: String, caption: &Option<Caption>) -> String {
+    match caption {
+        None => content,
+        Some(caption) => format!("{}\n_{}_", content, escape(caption)),
+    }
+}
+
+fn fenced(s: &str) -> String {
+    format!("```\n{}\n```", escape(s.trim_end_matches('\n')))
+}
+
+fn list(list: &List, depth: usize) -> Vec<String> {
+    let indent = "    ".repeat(depth);
+    let mut lines: Vec<String> = Default::default();
+    let mut number = 0;
+    for item in list.inner() {
+        match item {
+            ListItem::Item(item) => {
+                number += 1;
+                lines.push(if list.is_ordered() {
+                    format!("{}{}. {}", indent, number, inlines(item.inner()))
+                } else {
+                    format!("{}\u{2022} {}", indent, inlines(item.inner()))
+                });
+            }
+            ListItem::List(sub_list) => lines.extend(self::list(sub_list, depth + 1)),
+        }
+    }
+    lines
+}
+
+fn definition_list(list: &DefinitionList) -> String {
+    list.inner()
+        .iter()
+        .map(|definition| {
+            format!(
+                "*{}*: {}",
+                escape(definition.term()),
+                inlines(definition.text().inner())
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+///
+/// Slack does not nest quotes, so any quotes within this one are flattened into it.
+///
+fn quote(content: &[BlockContent]) -> String {
+    let mut blocks: Vec<String> = Default::default();
+    for block in content {
+        match block {
+            BlockContent::Quote(v) => blocks.push(quote(v.inner())),
+            _ => blocks.extend(mrkdwn_block(block)),
+        }
+    }
+    blocks
+        .join("\n\n")
+        .lines()
+        .map(|line| {
+            if line.starts_with('>') {
+                line.to_string()
+            } else if line.is_empty() {
+                ">".to_string()
+            } else {
+                format!("> {}", line)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+///
+/// Render a table as a monospaced text grid, used as Slack has no table support.
+///
+fn table(table: &Table) -> String {
+    let alignments: Vec<&Alignment> = table.columns().iter().map(|c| c.alignment()).collect();
+    let mut rows: Vec<Vec<String>> = vec![table
+        .columns()
+        .iter()
+        .map(|column| column.text().to_string())
+        .collect()];
+    for row in table.rows() {
+        rows.push(
+            row.cells()
+                .iter()
+                .map(|cell| cell.unformatted_string().replace('\n', " "))
+                .collect(),
+        );
+    }
+    let mut widths = vec![0; alignments.len()];
+    for row in &rows {
+        for (i, cell) in row.iter().enumerate().take(widths.len()) {
+            widths[i] = widths[i].max(cell.chars().count());
+        }
+    }
+    let line = |row: &[String]| {
+        let cells: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(i, width)| {
+                let cell = row.get(i).map(String::as_str).unwrap_or_default();
+                let padding = width - cell.chars().count();
+                match alignments[i] {
+                    Alignment::Right => format!("{}{}", " ".repeat(padding), cell),
+                    Alignment::Centered => format!(
+                        "{}{}{}",
+                        " ".repeat(padding / 2),
+                        cell,
+                        " ".repeat(padding - padding / 2)
+                    ),
+                    _ => format!("{}{}", cell, " ".repeat(padding)),
+                }
+            })
+            .collect();
+        format!("| {} |", cells.join(" | "))
+    };
+    let mut lines: Vec<String> = vec![line(&rows[0])];
+    lines.push(format!(
+        "|{}|",
+        widths
+            .iter()
+            .map(|width| "-".repeat(width + 2))
+            .collect::<Vec<String>>()
+            .join("|")
+    ));
+    lines.extend(rows[1..].iter().map(|row| line(row)));
+    lines.join("\n")
+}
+
+// ------------------------------------------------------------------------------------------------
+
+fn block_kit_document(doc: &Document) -> Value {
+    let mut blocks: Vec<Value> = Default::default();
+    if let Some(title) = title(doc) {
+        blocks.push(header(title, &None));
+    }
+    if let Some(abstract_block) = doc.abstract_block() {
+        blocks.push(section(
+            &format!("_{}_", inlines(abstract_block.inner())),
+            &None,
+        ));
+    }
+    for content in doc.inner() {
+        block_kit_block(&mut blocks, content);
+    }
+    json!({ "blocks": blocks })
+}
+
+fn block_kit_block(blocks: &mut Vec<Value>, content: &BlockContent) {
+    match content {
+        BlockContent::Comment(_) | BlockContent::FrontMatter(_) => {}
+        BlockContent::Heading(v) => blocks.push(header(&v.unformatted_string(), v.label())),
+        BlockContent::ImageBlock(v) => {
+            let image = v.inner();
+            let path = image.inner();
+            if is_local(path) {
+                let text = match (v.caption(), image.alt_text()) {
+                    (Some(caption), _) => caption.to_string(),
+                    (None, Some(alt_text)) => alt_text.to_string(),
+                    (None, None) => path.to_string(),
+                };
+                blocks.push(section(&escape(&text), v.label()));
+            } else {
+                let alt_text = match (image.alt_text(), v.caption()) {
+                    (Some(alt_text), _) => alt_text.to_string(),
+                    (None, Some(caption)) => caption.to_string(),
+                    (None, None) => path.to_string(),
+                };
+                let mut map = block("image", v.label());
+                let _ = map.insert("image_url".to_string(), json!(path));
+                let _ = map.insert("alt_text".to_string(), json!(alt_text));
+                if let Some(caption) = v.caption() {
+                    let _ = map.insert("title".to_string(), plain_text(caption));
+                }
+                blocks.push(Value::Object(map));
+            }
+        }
+        BlockContent::MathBlock(v) => {
+            blocks.push(preformatted(v.inner(), v.label()));
+            blocks.extend(context(v.caption()));
+        }
+        BlockContent::Formatted(v) => blocks.push(preformatted(v.inner(), v.label())),
+        BlockContent::CodeBlock(v) => {
+            blocks.push(preformatted(v.code(), v.label()));
+            blocks.extend(context(v.caption()));
+        }
+        BlockContent::Table(v) => {
+            blocks.push(preformatted(&table(v), v.label()));
+            blocks.extend(context(v.caption()));
+        }
+        BlockContent::ThematicBreak => blocks.push(json!({ "type": "divider" })),
+        BlockContent::List(v) => blocks.extend(mrkdwn_section(content, v.label())),
+        BlockContent::DefinitionList(v) => blocks.extend(mrkdwn_section(content, v.label())),
+        BlockContent::Paragraph(v) => blocks.extend(mrkdwn_section(content, v.label())),
+        BlockContent::Quote(v) => blocks.extend(mrkdwn_section(content, v.label())),
+    }
+}
+
+fn block(block_type: &str, label: &Option<Label>) -> Map<String, Value> {
+    let mut map: Map<String, Value> = Default::default();
+    let _ = map.insert("type".to_string(), json!(block_type));
+    if let Some(label) = label {
+        let _ = map.insert("block_id".to_string(), json!(label.to_string()));
+    }
+    map
+}
+
+fn header(text: &str, label: &Option<Label>) -> Value {
+    let text: String = text.chars().take(MAX_HEADER_LENGTH).collect();
+    let mut map = block("header", label);
+    let _ = map.insert("text".to_string(), plain_text(&text));
+    Value::Object(map)
+}
+
+fn mrkdwn_section(content: &BlockContent, label: &Option<Label>) -> Option<Value> {
+    mrkdwn_block(content)
+        .filter(|text| !text.trim().is_empty())
+        .map(|text| section(&text, label))
+}
+
+fn section(text: &str, label: &Option<Label>) -> Value {
+    let mut map = block("section", label);
+    let _ = map.insert(
+        "text".to_string(),
+        json!({ "type": "mrkdwn", "text": text }),
+    );
+    Value::Object(map)
+}
+
+fn preformatted(text: &str, label: &Option<Label>) -> Value {
+    let mut map = block("rich_text", label);
+    let _ = map.insert(
+        "elements".to_string(),
+        json!([{
+            "type": "rich_text_preformatted",
+            "elements": [{ "type": "text", "text": text.trim_end_matches('\n') }]
+        }]),
+    );
+    Value::Object(map)
+}
+
+fn context(caption: &Option<Caption>) -> Option<Value> {
+    caption.as_ref().map(|caption| {
+        json!({
+            "type": "context",
+            "elements": [{ "type": "mrkdwn", "text": format!("_{}_", escape(caption)) }]
+        })
+    })
+}
+
+fn plain_text(text: &str) -> Value {
+    json!({ "type": "plain_text", "text": text, "emoji": true })
+}
+
+// ------------------------------------------------------------------------------------------------
+
+fn inlines(content: &[InlineContent]) -> String {
+    content.iter().map(inline).collect()
+}
+
+fn inline(content: &InlineContent) -> String {
+    match content {
+        InlineContent::HyperLink(v) => match v.target() {
+            HyperLinkTarget::External(url) => match v.caption() {
+                Some(caption) => format!("<{}|{}>", url, escape(caption)),
+                None => format!("<{}>", url),
+            },
+            HyperLinkTarget::Internal(label) => match v.caption() {
+                Some(caption) => escape(caption),
+                None => escape(&label.to_string()),
+            },
+        },
+        InlineContent::Image(v) => image(v),
+        InlineContent::Text(v) => escape(v),
+        InlineContent::Math(v) => format!("`{}`", escape(v.inner())),
+        InlineContent::Character(v) => match v {
+            Character::Space => " ".to_string(),
+            Character::NonBreakSpace => "\u{a0}".to_string(),
+            Character::Hyphen => "-".to_string(),
+            Character::EmDash => "\u{2014}".to_string(),
+            Character::EnDash => "\u{2013}".to_string(),
+            Character::Emoji(name) => format!(":{}:", name.inner()),
+            Character::Other(c) => escape(&c.to_string()),
+        },
+        InlineContent::LineBreak => "\n".to_string(),
+        InlineContent::Span(v) => span(v),
+    }
+}
+
+fn span(span: &Span) -> String {
+    let mut content = inlines(span.inner());
+    for style in span.styles().iter().rev() {
+        content = match style {
+            SpanStyle::Italic => format!("_{}_", content),
+            SpanStyle::Bold => format!("*{}*", content),
+            SpanStyle::Mono | SpanStyle::Code => {
+                format!("`{}`", escape(&span.unformatted_string()))
+            }
+            SpanStyle::Strikethrough => format!("~{}~", content),
+            _ => content,
+        };
+    }
+    content
+}
+
+fn image(image: &Image) -> String {
+    let path = image.inner();
+    match (is_local(path), image.alt_text()) {
+        (false, Some(alt_text)) => format!("<{}|{}>", path, escape(alt_text)),
+        (false, None) => format!("<{}>", path),
+        (true, Some(alt_text)) => escape(alt_text),
+        (true, None) => escape(path),
+    }
+}
+
+fn is_local(path: &str) -> bool {
+    !(path.contains("://") || path.starts_with("data:"))
+}
+
+///
+/// Slack requires only the three control characters to be escaped, everywhere in mrkdwn text.
+///
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
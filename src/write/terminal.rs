@@ -0,0 +1,149 @@
+/*!
+Write a document for display in a terminal. This builds on the [`text`](../text/index.html)
+writer, adding ANSI SGR styles for spans and headings, box-drawn tables, and OSC 8 hyperlinks.
+Colors and escape sequences may be turned off, for example when output is not a TTY, in which case
+only the box-drawing characters distinguish the output from plain text.
+
+# Example
+
+```rust
+# use somedoc::model::Document;
+use somedoc::write::terminal::{writer, TerminalConfig};
+
+# fn make_some_document() -> Document { Document::default() }
+let doc = make_some_document();
+
+writer(&doc, TerminalConfig::new(100, false), &mut std::io::stdout()).unwrap();
+```
+*/
+
+use crate::model::Document;
+use crate::write::text::{Decoration, PlainTextConfig, PlainTextWriter};
+use crate::write::{ConfigurableWriter, OutputFormat, Writer};
+use std::io::Write;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Configuration for the terminal writer; the width at which paragraphs are wrapped, where `0`
+/// disables wrapping, and whether colors and other escape sequences are written.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct TerminalConfig {
+    width: usize,
+    color: bool,
+}
+
+///
+/// Implementation of the terminal writer structure, usually this is accessed via the `writer`
+/// function, but may be used directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use somedoc::model::Document;
+/// use somedoc::write::terminal::{TerminalConfig, TerminalWriter};
+/// use somedoc::write::{ConfigurableWriter, Writer};
+///
+/// # fn make_some_document() -> Document { Document::default() }
+/// let doc = make_some_document();
+/// let mut out = std::io::stdout();
+/// let writer = TerminalWriter::new_with(&mut out, TerminalConfig::new(100, true));
+/// assert!(writer.write_document(&doc).is_ok());
+/// ```
+///
+#[derive(Debug)]
+pub struct TerminalWriter<'a, W: Write> {
+    inner: PlainTextWriter<'a, W>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+const DEFAULT_WIDTH: usize = 80;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the writer function for terminals.
+///
+/// While this can be called directly it is most often used  by calling either
+/// [`model::write_document`](../fn.write_document.html) or
+/// [`model::write_document_to_string`](../fn.write_document_to_string.html).
+///
+#[inline]
+pub fn writer<W: Write>(
+    doc: &Document,
+    config: TerminalConfig,
+    w: &mut W,
+) -> crate::error::Result<()> {
+    let writer = TerminalWriter::new_with(w, config);
+    writer.write_document(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Default for TerminalConfig {
+    fn default() -> Self {
+        Self::new(DEFAULT_WIDTH, true)
+    }
+}
+
+impl From<TerminalConfig> for OutputFormat {
+    fn from(config: TerminalConfig) -> Self {
+        OutputFormat::Terminal(config)
+    }
+}
+
+impl TerminalConfig {
+    /// Construct a configuration that wraps paragraphs at `width` characters, and only writes
+    /// colors and escape sequences if `color` is `true`.
+    pub fn new(width: usize, color: bool) -> Self {
+        Self { width, color }
+    }
+
+    /// Return the width at which paragraphs are wrapped, `0` if wrapping is disabled.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Return `true` if colors and escape sequences are written.
+    pub fn color(&self) -> bool {
+        self.color
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> Writer<'a, W> for TerminalWriter<'a, W> {
+    fn new(w: &'a mut W) -> Self {
+        Self::new_with(w, Default::default())
+    }
+
+    fn write_document(&self, doc: &Document) -> crate::error::Result<()> {
+        self.inner.write_document(doc)
+    }
+}
+
+impl<'a, W: Write> ConfigurableWriter<'a, W, TerminalConfig> for TerminalWriter<'a, W> {
+    fn new_with(w: &'a mut W, config: TerminalConfig) -> Self {
+        Self {
+            inner: PlainTextWriter::new_decorated(
+                w,
+                PlainTextConfig::with_width(config.width),
+                if config.color {
+                    Decoration::Ansi
+                } else {
+                    Decoration::Boxes
+                },
+            ),
+        }
+    }
+}
@@ -0,0 +1,973 @@
+/*!
+Write a document as plain text, with paragraphs wrapped to a configurable width, suitable for
+terminals and log files.
+
+# Example
+
+```rust
+# use somedoc::model::Document;
+use somedoc::write::text::{writer, PlainTextConfig};
+
+# fn make_some_document() -> Document { Document::default() }
+let doc = make_some_document();
+
+writer(&doc, PlainTextConfig::with_width(72), &mut std::io::stdout()).unwrap();
+```
+*/
+
+use crate::model::block::{
+    Alignment, Caption, Column, FrontMatter, HasAlignment, HasCaption, HeadingLevel, Label,
+    ListKind,
+};
+use crate::model::document::Metadata;
+use crate::model::inline::{Character, HyperLink, HyperLinkTarget, Image, Math, SpanStyle, Text};
+use crate::model::visitor::{
+    walk_document, BlockVisitor, DocumentVisitor, InlineVisitor, TableVisitor,
+};
+use crate::model::Document;
+use crate::write::utils::string_of_strings;
+use crate::write::{ConfigurableWriter, OutputFormat, Writer};
+use std::cell::RefCell;
+use std::io::Write;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Configuration for the plain text writer, currently this is the width at which paragraphs
+/// are wrapped. A width of `0` disables wrapping.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlainTextConfig {
+    width: usize,
+}
+
+///
+/// Implementation of the plain text writer structure, usually this is accessed via the `writer`
+/// function, but may be used directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use somedoc::model::Document;
+/// use somedoc::write::text::{PlainTextConfig, PlainTextWriter};
+/// use somedoc::write::{ConfigurableWriter, Writer};
+///
+/// # fn make_some_document() -> Document { Document::default() }
+/// let doc = make_some_document();
+/// let mut out = std::io::stdout();
+/// let writer = PlainTextWriter::new_with(&mut out, PlainTextConfig::with_width(100));
+/// assert!(writer.write_document(&doc).is_ok());
+/// ```
+///
+#[derive(Debug)]
+pub struct PlainTextWriter<'a, W: Write> {
+    config: PlainTextConfig,
+    decoration: Decoration,
+    text: RefCell<Option<String>>,
+    indent_stack: RefCell<Vec<String>>,
+    list_stack: RefCell<Vec<(ListKind, usize)>>,
+    table: RefCell<Option<TableText>>,
+    links: RefCell<Vec<String>>,
+    styles: RefCell<Vec<String>>,
+    line_open: RefCell<bool>,
+    block_written: RefCell<bool>,
+    blank_pending: RefCell<bool>,
+    w: RefCell<&'a mut W>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// How much of the terminal's capabilities the writer may use beyond plain characters; see the
+/// [`terminal`](../terminal/index.html) module.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Decoration {
+    /// Plain ASCII output only.
+    None,
+    /// Box-drawing characters for tables and rules.
+    #[cfg(feature = "fmt_terminal")]
+    Boxes,
+    /// Box-drawing characters, plus ANSI SGR styles and OSC 8 hyperlinks.
+    #[cfg(feature = "fmt_terminal")]
+    Ansi,
+}
+
+#[derive(Debug, Default)]
+struct TableText {
+    columns: Vec<(String, Alignment)>,
+    rows: Vec<Vec<String>>,
+}
+
+const DEFAULT_WIDTH: usize = 80;
+
+const BLOCK_INDENT: &str = "    ";
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the writer function for plain text.
+///
+/// While this can be called directly it is most often used  by calling either
+/// [`model::write_document`](../fn.write_document.html) or
+/// [`model::write_document_to_string`](../fn.write_document_to_string.html).
+///
+#[inline]
+pub fn writer<W: Write>(
+    doc: &Document,
+    config: PlainTextConfig,
+    w: &mut W,
+) -> crate::error::Result<()> {
+    let writer = PlainTextWriter::new_with(w, config);
+    writer.write_document(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Default for PlainTextConfig {
+    fn default() -> Self {
+        Self::with_width(DEFAULT_WIDTH)
+    }
+}
+
+impl From<PlainTextConfig> for OutputFormat {
+    fn from(config: PlainTextConfig) -> Self {
+        OutputFormat::PlainText(config)
+    }
+}
+
+impl PlainTextConfig {
+    /// Construct a configuration that wraps paragraphs at `width` characters.
+    pub fn with_width(width: usize) -> Self {
+        Self { width }
+    }
+
+    /// Return the width at which paragraphs are wrapped, `0` if wrapping is disabled.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> Writer<'a, W> for PlainTextWriter<'a, W> {
+    fn new(w: &'a mut W) -> Self {
+        Self::new_with(w, Default::default())
+    }
+
+    fn write_document(&self, doc: &Document) -> crate::error::Result<()> {
+        walk_document(doc, self)?;
+        Ok(())
+    }
+}
+
+impl<'a, W: Write> ConfigurableWriter<'a, W, PlainTextConfig> for PlainTextWriter<'a, W> {
+    fn new_with(w: &'a mut W, config: PlainTextConfig) -> Self {
+        Self::new_decorated(w, config, Decoration::None)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> PlainTextWriter<'a, W> {
+    pub(crate) fn new_decorated(
+        w: &'a mut W,
+        config: PlainTextConfig,
+        decoration: Decoration,
+    ) -> Self {
+        Self {
+            config,
+            decoration,
+            text: RefCell::from(None),
+            indent_stack: RefCell::from(Vec::default()),
+            list_stack: RefCell::from(Vec::default()),
+            table: RefCell::from(None),
+            links: RefCell::from(Vec::default()),
+            styles: RefCell::from(Vec::default()),
+            line_open: RefCell::from(false),
+            block_written: RefCell::from(false),
+            blank_pending: RefCell::from(false),
+            w: RefCell::from(w),
+        }
+    }
+
+    fn write(&self, text: &str) -> crate::error::Result<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        let mut w = self.w.borrow_mut();
+        if self.blank_pending.replace(false) {
+            writeln!(w)?;
+        }
+        let prefix = self.indent_stack.borrow().join("");
+        for (i, segment) in text.split('\n').enumerate() {
+            if i > 0 {
+                writeln!(w)?;
+                let _ = self.line_open.replace(false);
+            }
+            if !segment.is_empty() {
+                if !self.line_open.replace(true) {
+                    write!(w, "{}", prefix)?;
+                }
+                write!(w, "{}", segment)?;
+            }
+        }
+        let _ = self.block_written.replace(true);
+        Ok(())
+    }
+
+    fn end_line(&self) -> crate::error::Result<()> {
+        if self.line_open.replace(false) {
+            writeln!(&mut self.w.borrow_mut())?;
+        }
+        Ok(())
+    }
+
+    fn write_line(&self, text: &str) -> crate::error::Result<()> {
+        self.write(text)?;
+        self.end_line()
+    }
+
+    fn write_inline(&self, text: &str) -> crate::error::Result<()> {
+        match self.text.borrow_mut().as_mut() {
+            Some(buffer) => {
+                buffer.push_str(text);
+                Ok(())
+            }
+            None => self.write(text),
+        }
+    }
+
+    fn start_text(&self) {
+        let _ = self.text.replace(Some(String::new()));
+    }
+
+    fn end_text(&self) -> String {
+        self.text.replace(None).unwrap_or_default()
+    }
+
+    #[cfg(feature = "fmt_terminal")]
+    fn is_ansi(&self) -> bool {
+        self.decoration == Decoration::Ansi
+    }
+
+    #[cfg(not(feature = "fmt_terminal"))]
+    fn is_ansi(&self) -> bool {
+        false
+    }
+
+    fn styled(&self, codes: &str, text: &str) -> String {
+        if self.is_ansi() && !codes.is_empty() {
+            format!("{}{}{}", sgr(codes), text, sgr("0"))
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn write_caption(&self, caption: &Option<Caption>) -> crate::error::Result<()> {
+        if let Some(caption) = caption {
+            self.write_line(caption.inner())?;
+        }
+        Ok(())
+    }
+
+    fn write_verbatim(&self, text: &str) -> crate::error::Result<()> {
+        self.indent_stack
+            .borrow_mut()
+            .push(BLOCK_INDENT.to_string());
+        self.write_line(text)?;
+        let _ = self.indent_stack.borrow_mut().pop();
+        Ok(())
+    }
+
+    ///
+    /// Wrap `text` to the configured width, less any current indentation and the width of the
+    /// first line's prefix; subsequent lines are indented to match the first.
+    ///
+    fn write_wrapped(
+        &self,
+        text: &str,
+        first_prefix: &str,
+        alignment: &Alignment,
+    ) -> crate::error::Result<()> {
+        let prefix_width = display_width(first_prefix);
+        let width = if self.config.width == 0 {
+            0
+        } else {
+            let indent_width = display_width(&self.indent_stack.borrow().join(""));
+            (self
+                .config
+                .width
+                .saturating_sub(indent_width + prefix_width))
+            .max(1)
+        };
+        let rest_prefix = string_of_strings(" ", prefix_width);
+        for (i, line) in wrap(text, width, alignment).iter().enumerate() {
+            self.write_line(&format!(
+                "{}{}",
+                if i == 0 { first_prefix } else { &rest_prefix },
+                line
+            ))?;
+        }
+        Ok(())
+    }
+
+    fn write_table(&self, table: TableText) -> crate::error::Result<()> {
+        let column_count = table
+            .rows
+            .iter()
+            .map(|row| row.len())
+            .chain(std::iter::once(table.columns.len()))
+            .max()
+            .unwrap_or_default();
+        let mut widths = vec![0; column_count];
+        for (i, (text, _)) in table.columns.iter().enumerate() {
+            widths[i] = display_width(text);
+        }
+        for row in &table.rows {
+            for (i, cell) in row.iter().enumerate() {
+                widths[i] = widths[i].max(display_width(cell));
+            }
+        }
+        let alignment = |i: usize| {
+            table
+                .columns
+                .get(i)
+                .map(|(_, alignment)| alignment.clone())
+                .unwrap_or(Alignment::Left)
+        };
+        let boxed = self.decoration != Decoration::None;
+        let (top, head, bottom, bar) = if boxed {
+            (
+                ["\u{250c}", "\u{2500}", "\u{252c}", "\u{2510}"],
+                ["\u{255e}", "\u{2550}", "\u{256a}", "\u{2561}"],
+                ["\u{2514}", "\u{2500}", "\u{2534}", "\u{2518}"],
+                "\u{2502}",
+            )
+        } else {
+            (
+                ["+", "-", "+", "+"],
+                ["+", "=", "+", "+"],
+                ["+", "-", "+", "+"],
+                "|",
+            )
+        };
+        let border = |[left, fill, middle, right]: [&str; 4]| {
+            format!(
+                "{}{}{}",
+                left,
+                widths
+                    .iter()
+                    .map(|width| string_of_strings(fill, width + 2))
+                    .collect::<Vec<String>>()
+                    .join(middle),
+                right
+            )
+        };
+        let row_line = |cells: Vec<String>| {
+            format!(
+                "{}{}{}",
+                bar,
+                widths
+                    .iter()
+                    .enumerate()
+                    .map(|(i, width)| format!(
+                        " {} ",
+                        pad(
+                            cells.get(i).map(String::as_str).unwrap_or_default(),
+                            *width,
+                            &alignment(i)
+                        )
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(bar),
+                bar
+            )
+        };
+        self.write_line(&border(top))?;
+        if !table.columns.is_empty() {
+            self.write_line(&row_line(
+                table
+                    .columns
+                    .iter()
+                    .map(|(text, _)| self.styled("1", text))
+                    .collect(),
+            ))?;
+            if boxed && table.rows.is_empty() {
+                self.write_line(&border(bottom))?;
+            } else {
+                self.write_line(&border(head))?;
+            }
+        }
+        if !table.rows.is_empty() {
+            for row in &table.rows {
+                self.write_line(&row_line(row.clone()))?;
+            }
+            self.write_line(&border(bottom))?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, W: Write> DocumentVisitor for PlainTextWriter<'a, W> {
+    fn metadata(&self, meta_datum: &Metadata) -> crate::error::Result<()> {
+        match meta_datum {
+            Metadata::Title(title) => {
+                let rule = string_of_strings(
+                    if self.decoration == Decoration::None {
+                        "="
+                    } else {
+                        "\u{2550}"
+                    },
+                    display_width(title),
+                );
+                self.write_line(&rule)?;
+                self.write_line(&self.styled("1", title))?;
+                self.write_line(&rule)?;
+            }
+            _ => {
+                let key = meta_datum.key();
+                let mut chars = key.chars();
+                self.write_line(&format!(
+                    "{}{}: {}",
+                    chars
+                        .next()
+                        .map(|c| c.to_uppercase().to_string())
+                        .unwrap_or_default(),
+                    chars.as_str(),
+                    meta_datum.value_string()
+                ))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn block_visitor(&self) -> Option<&dyn BlockVisitor> {
+        let _ = self
+            .blank_pending
+            .replace(self.block_written.replace(false));
+        Some(self)
+    }
+
+    fn end_document(&self) -> crate::error::Result<()> {
+        let links = self.links.borrow();
+        if !links.is_empty() {
+            for (i, link) in links.iter().enumerate() {
+                self.write_line(&format!("[{}] {}", i + 1, link))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a, W: Write> BlockVisitor for PlainTextWriter<'a, W> {
+    fn start_abstract(&self) -> crate::error::Result<()> {
+        self.start_text();
+        Ok(())
+    }
+
+    fn end_abstract(&self) -> crate::error::Result<()> {
+        let text = self.end_text();
+        self.indent_stack
+            .borrow_mut()
+            .push(BLOCK_INDENT.to_string());
+        self.write_wrapped(&text, "", &Alignment::Left)?;
+        let _ = self.indent_stack.borrow_mut().pop();
+        Ok(())
+    }
+
+    fn comment(&self, _: &str) -> crate::error::Result<()> {
+        Ok(())
+    }
+
+    fn front_matter(&self, _: &FrontMatter) -> crate::error::Result<()> {
+        Ok(())
+    }
+
+    fn start_heading(&self, level: &HeadingLevel, _: &Option<Label>) -> crate::error::Result<()> {
+        if self.is_ansi() {
+            // spans within the heading restore its color when they end.
+            self.styles
+                .borrow_mut()
+                .push(heading_codes(level).to_string());
+        }
+        self.start_text();
+        Ok(())
+    }
+
+    fn end_heading(&self, level: &HeadingLevel, _: &Option<Label>) -> crate::error::Result<()> {
+        let text = self.end_text();
+        if self.is_ansi() {
+            let _ = self.styles.borrow_mut().pop();
+            self.write_line(&self.styled(heading_codes(level), &text))
+        } else {
+            self.write_line(&text)?;
+            self.write_line(&string_of_strings(
+                if *level == HeadingLevel::Section {
+                    "="
+                } else {
+                    "-"
+                },
+                display_width(&text),
+            ))
+        }
+    }
+
+    fn image(
+        &self,
+        value: &Image,
+        caption: &Option<Caption>,
+        _: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_caption(caption)?;
+        self.write_line(&image_text(value))
+    }
+
+    fn math(
+        &self,
+        value: &Math,
+        caption: &Option<Caption>,
+        _: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_caption(caption)?;
+        self.write_verbatim(value.inner())
+    }
+
+    fn start_list(&self, kind: &ListKind, _: &Option<Label>) -> crate::error::Result<()> {
+        let mut list_stack = self.list_stack.borrow_mut();
+        if let Some((parent, count)) = list_stack.last() {
+            // nested lists are indented to the parent item's text.
+            self.indent_stack
+                .borrow_mut()
+                .push(string_of_strings(" ", list_marker(parent, *count).len()));
+        }
+        list_stack.push((kind.clone(), 0));
+        Ok(())
+    }
+
+    fn end_list(&self, _: &ListKind, _: &Option<Label>) -> crate::error::Result<()> {
+        let mut list_stack = self.list_stack.borrow_mut();
+        let _ = list_stack.pop();
+        if !list_stack.is_empty() {
+            let _ = self.indent_stack.borrow_mut().pop();
+        }
+        Ok(())
+    }
+
+    fn start_list_item(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.start_text();
+        Ok(())
+    }
+
+    fn end_list_item(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        let text = self.end_text();
+        let marker = match self.list_stack.borrow_mut().last_mut() {
+            Some((kind, count)) => {
+                *count += 1;
+                list_marker(kind, *count)
+            }
+            None => String::new(),
+        };
+        self.write_wrapped(&text, &marker, &Alignment::Left)
+    }
+
+    fn start_definition(&self, term: &str, _: &Option<Label>) -> crate::error::Result<()> {
+        self.write_line(term)
+    }
+
+    fn start_definition_list_text(&self) -> crate::error::Result<()> {
+        self.start_text();
+        Ok(())
+    }
+
+    fn end_definition_list_text(&self) -> crate::error::Result<()> {
+        let text = self.end_text();
+        self.indent_stack
+            .borrow_mut()
+            .push(BLOCK_INDENT.to_string());
+        self.write_wrapped(&text, "", &Alignment::Left)?;
+        let _ = self.indent_stack.borrow_mut().pop();
+        Ok(())
+    }
+
+    fn formatted(&self, value: &str, _: &Option<Label>) -> crate::error::Result<()> {
+        self.write_verbatim(value)
+    }
+
+    fn code_block(
+        &self,
+        code: &str,
+        _: &Option<String>,
+        caption: &Option<Caption>,
+        _: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_caption(caption)?;
+        self.write_verbatim(code)
+    }
+
+    fn start_paragraph(&self, _: &Alignment, _: &Option<Label>) -> crate::error::Result<()> {
+        self.start_text();
+        Ok(())
+    }
+
+    fn end_paragraph(&self, alignment: &Alignment, _: &Option<Label>) -> crate::error::Result<()> {
+        let text = self.end_text();
+        self.write_wrapped(&text, "", alignment)
+    }
+
+    fn start_quote(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.indent_stack
+            .borrow_mut()
+            .push(BLOCK_INDENT.to_string());
+        Ok(())
+    }
+
+    fn end_quote(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_line()?;
+        let _ = self.indent_stack.borrow_mut().pop();
+        Ok(())
+    }
+
+    fn thematic_break(&self) -> crate::error::Result<()> {
+        self.write_line(&string_of_strings(
+            if self.decoration == Decoration::None {
+                "-"
+            } else {
+                "\u{2500}"
+            },
+            if self.config.width == 0 {
+                DEFAULT_WIDTH
+            } else {
+                self.config.width
+            },
+        ))
+    }
+
+    fn end_block(&self) -> crate::error::Result<()> {
+        if self.block_written.replace(false) {
+            self.end_line()?;
+            let _ = self.blank_pending.replace(true);
+        }
+        Ok(())
+    }
+
+    fn table_visitor(&self) -> Option<&dyn TableVisitor> {
+        Some(self)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> TableVisitor for PlainTextWriter<'a, W> {
+    fn start_table(
+        &self,
+        caption: &Option<Caption>,
+        _: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.write_caption(caption)?;
+        let _ = self.table.replace(Some(TableText::default()));
+        Ok(())
+    }
+
+    fn table_header_cell(
+        &self,
+        column_cell: &Column,
+        _column_idx: usize,
+    ) -> crate::error::Result<()> {
+        if let Some(table) = self.table.borrow_mut().as_mut() {
+            table.columns.push((
+                column_cell.text().to_string(),
+                column_cell.alignment().clone(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn start_table_row(&self, _: usize) -> crate::error::Result<()> {
+        if let Some(table) = self.table.borrow_mut().as_mut() {
+            table.rows.push(Vec::default());
+        }
+        Ok(())
+    }
+
+    fn start_table_cell(&self, _: usize, _: &Option<Label>) -> crate::error::Result<()> {
+        self.start_text();
+        Ok(())
+    }
+
+    fn end_table_cell(&self, _: usize, _: &Option<Label>) -> crate::error::Result<()> {
+        let text = self.end_text().replace('\n', " ");
+        if let Some(table) = self.table.borrow_mut().as_mut() {
+            if let Some(row) = table.rows.last_mut() {
+                row.push(text);
+            }
+        }
+        Ok(())
+    }
+
+    fn end_table(&self, _: &Option<Caption>, _: &Option<Label>) -> crate::error::Result<()> {
+        let table = self.table.replace(None);
+        match table {
+            Some(table) => self.write_table(table),
+            None => Ok(()),
+        }
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> InlineVisitor for PlainTextWriter<'a, W> {
+    fn link(&self, value: &HyperLink) -> crate::error::Result<()> {
+        match (value.target(), value.caption()) {
+            (HyperLinkTarget::External(target), caption) if self.is_ansi() => {
+                self.write_inline(&format!(
+                    "\u{1b}]8;;{}\u{1b}\\{}\u{1b}]8;;\u{1b}\\",
+                    target,
+                    match caption {
+                        Some(caption) => caption.inner(),
+                        None => target,
+                    }
+                ))
+            }
+            (HyperLinkTarget::External(target), Some(caption)) => {
+                let mut links = self.links.borrow_mut();
+                let number = match links.iter().position(|link| link == target) {
+                    Some(index) => index + 1,
+                    None => {
+                        links.push(target.clone());
+                        links.len()
+                    }
+                };
+                self.write_inline(&format!("{}[{}]", caption.inner(), number))
+            }
+            (HyperLinkTarget::External(target), None) => self.write_inline(target),
+            (HyperLinkTarget::Internal(_), Some(caption)) => self.write_inline(caption.inner()),
+            (HyperLinkTarget::Internal(target), None) => self.write_inline(&target.to_string()),
+        }
+    }
+
+    fn image(&self, value: &Image) -> crate::error::Result<()> {
+        self.write_inline(&image_text(value))
+    }
+
+    fn text(&self, value: &Text) -> crate::error::Result<()> {
+        self.write_inline(value.inner())
+    }
+
+    fn math(&self, value: &Math) -> crate::error::Result<()> {
+        self.write_inline(value.inner())
+    }
+
+    fn character(&self, value: &Character) -> crate::error::Result<()> {
+        self.write_inline(&match value {
+            Character::Space => " ".to_string(),
+            Character::NonBreakSpace => "\u{a0}".to_string(),
+            Character::Hyphen => "-".to_string(),
+            Character::EmDash => "\u{2014}".to_string(),
+            Character::EnDash => "\u{2013}".to_string(),
+            Character::Emoji(name) => format!(":{}:", name.inner()),
+            Character::Other(c) => c.to_string(),
+        })
+    }
+
+    fn line_break(&self) -> crate::error::Result<()> {
+        self.write_inline("\n")
+    }
+
+    fn start_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        if self.is_ansi() {
+            let mut codes = span_codes(styles);
+            let mut stack = self.styles.borrow_mut();
+            if codes.starts_with('0') && stack.iter().all(|c| c.is_empty()) {
+                // a plain span has nothing to reset unless it is nested in a styled one.
+                codes = codes
+                    .trim_start_matches('0')
+                    .trim_start_matches(';')
+                    .to_string();
+            }
+            stack.push(codes.clone());
+            drop(stack);
+            if !codes.is_empty() {
+                self.write_inline(&sgr(&codes))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn end_span(&self, _: &[SpanStyle]) -> crate::error::Result<()> {
+        if self.is_ansi() {
+            let codes = self.styles.borrow_mut().pop().unwrap_or_default();
+            if !codes.is_empty() {
+                // SGR has no "pop", so reset and re-apply any enclosing styles.
+                let mut restore = sgr("0");
+                for codes in self.styles.borrow().iter().filter(|c| !c.is_empty()) {
+                    restore.push_str(&sgr(codes));
+                }
+                self.write_inline(&restore)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn list_marker(kind: &ListKind, count: usize) -> String {
+    match kind {
+        ListKind::Ordered => format!("{}. ", count),
+        ListKind::Unordered => "* ".to_string(),
+    }
+}
+
+fn image_text(value: &Image) -> String {
+    format!(
+        "[image: {}]",
+        value.alt_text().as_deref().unwrap_or_else(|| value.inner())
+    )
+}
+
+fn sgr(codes: &str) -> String {
+    format!("\u{1b}[{}m", codes)
+}
+
+fn span_codes(styles: &[SpanStyle]) -> String {
+    let mut codes: Vec<&str> = Vec::new();
+    for style in styles {
+        match style {
+            SpanStyle::Plain => {
+                codes.clear();
+                codes.push("0");
+            }
+            SpanStyle::Bold => codes.push("1"),
+            SpanStyle::Italic => codes.push("3"),
+            SpanStyle::Underline => codes.push("4"),
+            SpanStyle::Strikethrough => codes.push("9"),
+            SpanStyle::Code => codes.push("36"),
+            _ => {}
+        }
+    }
+    codes.join(";")
+}
+
+fn heading_codes(level: &HeadingLevel) -> &'static str {
+    match level {
+        HeadingLevel::Section => "1;4;35",
+        HeadingLevel::SubSection => "1;34",
+        HeadingLevel::SubSubSection => "1;36",
+        HeadingLevel::SubSubSubSection => "1;32",
+        _ => "1",
+    }
+}
+
+///
+/// The number of columns `text` occupies on screen, ignoring SGR (`ESC [`) and OSC (`ESC ]`)
+/// escape sequences.
+///
+fn display_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            match chars.next() {
+                Some('[') => {
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\u{7}' || (c == '\u{1b}' && chars.next() == Some('\\')) {
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+fn wrap(text: &str, width: usize, alignment: &Alignment) -> Vec<String> {
+    let mut lines = Vec::new();
+    for hard_line in text.split('\n') {
+        let words: Vec<&str> = hard_line.split(' ').filter(|w| !w.is_empty()).collect();
+        if width == 0 {
+            lines.push(words.join(" "));
+            continue;
+        }
+        let mut wrapped: Vec<Vec<&str>> = vec![Vec::new()];
+        let mut line_width = 0;
+        for word in words {
+            let word_width = display_width(word);
+            let current = wrapped.last_mut().unwrap();
+            if !current.is_empty() && line_width + 1 + word_width > width {
+                wrapped.push(vec![word]);
+                line_width = word_width;
+            } else {
+                line_width += if current.is_empty() { 0 } else { 1 } + word_width;
+                current.push(word);
+            }
+        }
+        let last = wrapped.len() - 1;
+        for (i, words) in wrapped.into_iter().enumerate() {
+            lines.push(match alignment {
+                Alignment::Justified if i < last => justify(&words, width),
+                Alignment::Right | Alignment::Centered => pad(&words.join(" "), width, alignment)
+                    .trim_end()
+                    .to_string(),
+                _ => words.join(" "),
+            });
+        }
+    }
+    lines
+}
+
+fn justify(words: &[&str], width: usize) -> String {
+    if words.len() < 2 {
+        return words.join(" ");
+    }
+    let gaps = words.len() - 1;
+    let spaces = width - words.iter().map(|w| display_width(w)).sum::<usize>();
+    let mut line = String::new();
+    for (i, word) in words.iter().enumerate() {
+        line.push_str(word);
+        if i < gaps {
+            let gap = spaces / gaps + if i < spaces % gaps { 1 } else { 0 };
+            line.push_str(&string_of_strings(" ", gap));
+        }
+    }
+    line
+}
+
+fn pad(text: &str, width: usize, alignment: &Alignment) -> String {
+    let padding = width.saturating_sub(display_width(text));
+    match alignment {
+        Alignment::Right => format!("{}{}", string_of_strings(" ", padding), text),
+        Alignment::Centered => format!(
+            "{}{}{}",
+            string_of_strings(" ", padding / 2),
+            text,
+            string_of_strings(" ", padding - padding / 2)
+        ),
+        _ => format!("{}{}", text, string_of_strings(" ", padding)),
+    }
+}
@@ -0,0 +1,777 @@
+/*!
+Write a document in the [Typst](https://typst.app/docs/) markup language.
+
+Captioned images, tables, code, and math blocks are wrapped in a `#figure`, labels are written as
+Typst `<label>` anchors following the labeled element. Typst only loads images from local files,
+so images given by URL are written as links.
+
+# Example
+
+```rust
+# use somedoc::model::Document;
+use somedoc::write::typst::writer;
+
+# fn make_some_document() -> Document { Document::default() }
+let doc = make_some_document();
+
+writer(&doc, &mut std::io::stdout()).unwrap();
+```
+*/
+
+use crate::model::block::{
+    Alignment, Caption, Column, FrontMatter, HasAlignment, HasCaption, HeadingLevel, Label,
+    ListKind,
+};
+use crate::model::document::Metadata;
+use crate::model::inline::text::Size;
+use crate::model::inline::{Character, HyperLink, HyperLinkTarget, Image, Math, SpanStyle, Text};
+use crate::model::visitor::{
+    walk_document, BlockVisitor, DocumentVisitor, InlineVisitor, TableVisitor,
+};
+use crate::model::Document;
+use crate::write::utils::string_of_strings;
+use crate::write::Writer;
+use std::cell::RefCell;
+use std::io::Write;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the Typst writer structure, usually this is accessed via the `writer`
+/// function, but may be used directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use somedoc::model::Document;
+/// use somedoc::write::typst::TypstWriter;
+/// use somedoc::write::Writer;
+///
+/// # fn make_some_document() -> Document { Document::default() }
+/// let doc = make_some_document();
+/// let mut out = std::io::stdout();
+/// let writer = TypstWriter::new(&mut out);
+/// assert!(writer.write_document(&doc).is_ok());
+/// ```
+///
+#[derive(Debug)]
+pub struct TypstWriter<'a, W: Write> {
+    metadata: RefCell<Vec<Metadata>>,
+    list_stack: RefCell<Vec<ListKind>>,
+    indent_stack: RefCell<Vec<String>>,
+    table_columns: RefCell<Vec<(String, Alignment)>>,
+    table_cell_count: RefCell<usize>,
+    raw_depth: RefCell<usize>,
+    line_open: RefCell<bool>,
+    block_written: RefCell<bool>,
+    blank_pending: RefCell<bool>,
+    w: RefCell<&'a mut W>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implementation of the writer function for Typst.
+///
+/// While this can be called directly it is most often used  by calling either
+/// [`model::write_document`](../fn.write_document.html) or
+/// [`model::write_document_to_string`](../fn.write_document_to_string.html).
+///
+#[inline]
+pub fn writer<W: Write>(doc: &Document, w: &mut W) -> crate::error::Result<()> {
+    let writer = TypstWriter::new(w);
+    writer.write_document(doc)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> Writer<'a, W> for TypstWriter<'a, W> {
+    fn new(w: &'a mut W) -> Self {
+        Self {
+            metadata: RefCell::from(Vec::default()),
+            list_stack: RefCell::from(Vec::default()),
+            indent_stack: RefCell::from(Vec::default()),
+            table_columns: RefCell::from(Vec::default()),
+            table_cell_count: RefCell::from(0),
+            raw_depth: RefCell::from(0),
+            line_open: RefCell::from(false),
+            block_written: RefCell::from(false),
+            blank_pending: RefCell::from(false),
+            w: RefCell::from(w),
+        }
+    }
+
+    fn write_document(&self, doc: &Document) -> crate::error::Result<()> {
+        walk_document(doc, self)?;
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, W: Write> TypstWriter<'a, W> {
+    fn write(&self, text: &str) -> crate::error::Result<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        let mut w = self.w.borrow_mut();
+        if self.blank_pending.replace(false) {
+            writeln!(w)?;
+        }
+        let prefix = self.indent_stack.borrow().join("");
+        for (i, segment) in text.split('\n').enumerate() {
+            if i > 0 {
+                writeln!(w)?;
+                let _ = self.line_open.replace(false);
+            }
+            if !segment.is_empty() {
+                if !self.line_open.replace(true) {
+                    write!(w, "{}", prefix)?;
+                }
+                write!(w, "{}", segment)?;
+            }
+        }
+        let _ = self.block_written.replace(true);
+        Ok(())
+    }
+
+    ///
+    /// Write document text, escaping any markup characters unless within raw text; markers that
+    /// are only significant at the start of a line are escaped there.
+    ///
+    fn write_text(&self, text: &str) -> crate::error::Result<()> {
+        if *self.raw_depth.borrow() > 0 {
+            self.write(text)
+        } else if *self.line_open.borrow() {
+            self.write(&escape(text))
+        } else {
+            self.write(&escape_line_start(&escape(text)))
+        }
+    }
+
+    fn end_line(&self) -> crate::error::Result<()> {
+        if self.line_open.replace(false) {
+            writeln!(&mut self.w.borrow_mut())?;
+        }
+        Ok(())
+    }
+
+    fn write_line(&self, text: &str) -> crate::error::Result<()> {
+        self.write(text)?;
+        self.end_line()
+    }
+
+    fn write_label(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        if let Some(label) = label {
+            self.write(&format!(" <{}>", label))?;
+        }
+        Ok(())
+    }
+
+    fn start_figure(&self, caption: &Option<Caption>) -> crate::error::Result<()> {
+        if let Some(caption) = caption {
+            self.write_line(&format!("#figure(caption: [{}])[", escape(caption.inner())))?;
+        }
+        Ok(())
+    }
+
+    fn end_figure(
+        &self,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        if caption.is_some() {
+            self.end_line()?;
+            self.write("]")?;
+        }
+        self.write_label(label)
+    }
+
+    fn write_raw_block(&self, text: &str, language: &Option<String>) -> crate::error::Result<()> {
+        let fence = string_of_strings("`", longest_run(text, '`').max(2) + 1);
+        self.write_line(&format!(
+            "{}{}",
+            fence,
+            language.as_deref().unwrap_or_default()
+        ))?;
+        self.write_line(text)?;
+        self.write(&fence)
+    }
+
+    fn write_header(&self) -> crate::error::Result<()> {
+        let metadata = self.metadata.borrow();
+        let title = metadata.iter().find_map(|datum| match datum {
+            Metadata::Title(title) => Some(title.as_str()),
+            _ => None,
+        });
+        let authors: Vec<String> = metadata
+            .iter()
+            .filter_map(|datum| match datum {
+                Metadata::Author(author) => Some(author.name.clone()),
+                _ => None,
+            })
+            .collect();
+        let keywords: Vec<String> = metadata
+            .iter()
+            .filter_map(|datum| match datum {
+                Metadata::Keywords(keywords) => Some(keywords.clone()),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        let mut arguments = Vec::new();
+        if let Some(title) = title {
+            arguments.push(format!("title: {}", string_literal(title)));
+        }
+        if !authors.is_empty() {
+            arguments.push(format!("author: {}", string_array(&authors)));
+        }
+        if !keywords.is_empty() {
+            arguments.push(format!("keywords: {}", string_array(&keywords)));
+        }
+        for datum in metadata.iter() {
+            match datum {
+                Metadata::Author(_) | Metadata::Title(_) | Metadata::Keywords(_) => {}
+                _ => self.write_line(&format!(
+                    "// {}: {}",
+                    datum.key(),
+                    datum.value_string().replace('\n', " ")
+                ))?,
+            }
+        }
+        if !arguments.is_empty() {
+            self.write_line(&format!("#set document({})", arguments.join(", ")))?;
+        }
+        if title.is_some() || !authors.is_empty() {
+            // the document settings are not displayed, so add a simple title block.
+            let mut lines = Vec::new();
+            if let Some(title) = title {
+                lines.push(format!(
+                    "  #text(size: 1.7em, weight: \"bold\")[{}]",
+                    escape(title)
+                ));
+            }
+            if !authors.is_empty() {
+                lines.push(format!(
+                    "  {}",
+                    authors
+                        .iter()
+                        .map(|author| escape(author))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ));
+            }
+            self.write_line(&format!("#align(center)[\n{}\n]", lines.join("\n\n")))?;
+        }
+        let _ = self
+            .blank_pending
+            .replace(self.block_written.replace(false));
+        Ok(())
+    }
+}
+
+impl<'a, W: Write> DocumentVisitor for TypstWriter<'a, W> {
+    fn metadata(&self, meta_datum: &Metadata) -> crate::error::Result<()> {
+        self.metadata.borrow_mut().push(meta_datum.clone());
+        Ok(())
+    }
+
+    fn block_visitor(&self) -> Option<&dyn BlockVisitor> {
+        if !self.metadata.borrow().is_empty() {
+            let _ = self.write_header();
+        }
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> BlockVisitor for TypstWriter<'a, W> {
+    fn start_abstract(&self) -> crate::error::Result<()> {
+        self.write_line("#align(center)[#strong[Abstract]]")?;
+        self.write("#pad(x: 2em)[")
+    }
+
+    fn end_abstract(&self) -> crate::error::Result<()> {
+        self.write("]")
+    }
+
+    fn comment(&self, value: &str) -> crate::error::Result<()> {
+        self.write(
+            &value
+                .split('\n')
+                .map(|line| format!("// {}", line))
+                .collect::<Vec<String>>()
+                .join("\n"),
+        )
+    }
+
+    fn front_matter(&self, value: &FrontMatter) -> crate::error::Result<()> {
+        match value {
+            FrontMatter::TableOfContents => self.write("#outline()"),
+            FrontMatter::TableOfEquations => {
+                self.write("#outline(title: [Equations], target: math.equation.where(block: true))")
+            }
+            FrontMatter::TableOfFigures => {
+                self.write("#outline(title: [Figures], target: figure.where(kind: image))")
+            }
+            FrontMatter::TableOfListings => {
+                self.write("#outline(title: [Listings], target: figure.where(kind: raw))")
+            }
+            FrontMatter::TableOfTables => {
+                self.write("#outline(title: [Tables], target: figure.where(kind: table))")
+            }
+            FrontMatter::Glossary => Ok(()),
+        }
+    }
+
+    fn start_heading(&self, level: &HeadingLevel, _: &Option<Label>) -> crate::error::Result<()> {
+        self.write(&format!(
+            "{} ",
+            string_of_strings("=", level.clone() as usize)
+        ))
+    }
+
+    fn end_heading(&self, _: &HeadingLevel, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_label(label)
+    }
+
+    fn image(
+        &self,
+        value: &Image,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.start_figure(caption)?;
+        self.write(&format!("#{}", image_call(value)))?;
+        self.end_figure(caption, label)
+    }
+
+    fn math(
+        &self,
+        value: &Math,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.start_figure(caption)?;
+        self.write(&format!("$ {} $", value.inner()))?;
+        self.end_figure(caption, label)
+    }
+
+    fn start_list(&self, kind: &ListKind, _: &Option<Label>) -> crate::error::Result<()> {
+        let mut list_stack = self.list_stack.borrow_mut();
+        if !list_stack.is_empty() {
+            // nested lists are indented to the parent item's text.
+            self.indent_stack.borrow_mut().push("  ".to_string());
+        }
+        list_stack.push(kind.clone());
+        Ok(())
+    }
+
+    fn end_list(&self, _: &ListKind, _: &Option<Label>) -> crate::error::Result<()> {
+        let mut list_stack = self.list_stack.borrow_mut();
+        let _ = list_stack.pop();
+        if !list_stack.is_empty() {
+            let _ = self.indent_stack.borrow_mut().pop();
+        }
+        Ok(())
+    }
+
+    fn start_list_item(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        match self.list_stack.borrow().last() {
+            Some(ListKind::Ordered) => self.write("+ "),
+            Some(ListKind::Unordered) => self.write("- "),
+            None => Ok(()),
+        }
+    }
+
+    fn end_list_item(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_line()
+    }
+
+    fn start_definition(&self, term: &str, _: &Option<Label>) -> crate::error::Result<()> {
+        self.write(&format!("/ {}:", escape(term)))
+    }
+
+    fn start_definition_list_text(&self) -> crate::error::Result<()> {
+        self.write(" ")
+    }
+
+    fn end_definition(&self, _: &str, _: &Option<Label>) -> crate::error::Result<()> {
+        self.end_line()
+    }
+
+    fn formatted(&self, value: &str, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write_raw_block(value, &None)?;
+        self.write_label(label)
+    }
+
+    fn code_block(
+        &self,
+        code: &str,
+        language: &Option<String>,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.start_figure(caption)?;
+        self.write_raw_block(code, language)?;
+        self.end_figure(caption, label)
+    }
+
+    fn start_paragraph(
+        &self,
+        alignment: &Alignment,
+        _: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        match alignment {
+            Alignment::Right => self.write("#align(right)["),
+            Alignment::Centered => self.write("#align(center)["),
+            Alignment::Justified => self.write("#par(justify: true)["),
+            _ => Ok(()),
+        }
+    }
+
+    fn end_paragraph(
+        &self,
+        alignment: &Alignment,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        if matches!(
+            alignment,
+            Alignment::Right | Alignment::Centered | Alignment::Justified
+        ) {
+            self.write("]")?;
+        }
+        self.write_label(label)
+    }
+
+    fn start_quote(&self, _: &Option<Label>) -> crate::error::Result<()> {
+        self.write_line("#quote(block: true)[")
+    }
+
+    fn end_quote(&self, label: &Option<Label>) -> crate::error::Result<()> {
+        let _ = self.blank_pending.replace(false);
+        self.end_line()?;
+        self.write("]")?;
+        self.write_label(label)
+    }
+
+    fn thematic_break(&self) -> crate::error::Result<()> {
+        self.write("#line(length: 100%)")
+    }
+
+    fn end_block(&self) -> crate::error::Result<()> {
+        if self.block_written.replace(false) {
+            self.end_line()?;
+            let _ = self.blank_pending.replace(true);
+        }
+        Ok(())
+    }
+
+    fn table_visitor(&self) -> Option<&dyn TableVisitor> {
+        Some(self)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> TableVisitor for TypstWriter<'a, W> {
+    fn start_table(
+        &self,
+        caption: &Option<Caption>,
+        _: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        self.start_figure(caption)?;
+        self.table_columns.borrow_mut().clear();
+        let _ = self.table_cell_count.replace(0);
+        self.write_line("#table(")?;
+        self.indent_stack.borrow_mut().push("  ".to_string());
+        Ok(())
+    }
+
+    fn table_header_cell(
+        &self,
+        column_cell: &Column,
+        _column_idx: usize,
+    ) -> crate::error::Result<()> {
+        self.table_columns
+            .borrow_mut()
+            .push((escape(column_cell.text()), column_cell.alignment().clone()));
+        Ok(())
+    }
+
+    fn end_table_header_row(&self) -> crate::error::Result<()> {
+        let columns = self.table_columns.borrow();
+        self.write_line(&format!("columns: {},", columns.len()))?;
+        self.write_line(&format!(
+            "align: ({}{}),",
+            columns
+                .iter()
+                .map(|(_, alignment)| match alignment {
+                    Alignment::Right => "right",
+                    Alignment::Centered => "center",
+                    _ => "left",
+                })
+                .collect::<Vec<&str>>()
+                .join(", "),
+            if columns.len() == 1 { "," } else { "" }
+        ))?;
+        self.write_line(&format!(
+            "table.header({}),",
+            columns
+                .iter()
+                .map(|(text, _)| format!("[{}]", text))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ))
+    }
+
+    fn start_table_cell(&self, cell_idx: usize, _: &Option<Label>) -> crate::error::Result<()> {
+        let mut cell_count = self.table_cell_count.borrow_mut();
+        *cell_count = (*cell_count).max(cell_idx + 1);
+        if cell_idx > 0 {
+            self.write(" ")?;
+        }
+        self.write("[")
+    }
+
+    fn end_table_cell(&self, _: usize, label: &Option<Label>) -> crate::error::Result<()> {
+        self.write("]")?;
+        self.write_label(label)?;
+        self.write(",")
+    }
+
+    fn end_table_row(&self, _: usize) -> crate::error::Result<()> {
+        self.end_line()
+    }
+
+    fn end_table(
+        &self,
+        caption: &Option<Caption>,
+        label: &Option<Label>,
+    ) -> crate::error::Result<()> {
+        if self.table_columns.borrow().is_empty() {
+            // named arguments may follow the cells, so the column count is now known.
+            self.write_line(&format!("columns: {},", self.table_cell_count.borrow()))?;
+        }
+        let _ = self.indent_stack.borrow_mut().pop();
+        self.write(")")?;
+        self.end_figure(caption, label)
+    }
+
+    fn inline_visitor(&self) -> Option<&dyn InlineVisitor> {
+        Some(self)
+    }
+}
+
+impl<'a, W: Write> InlineVisitor for TypstWriter<'a, W> {
+    fn link(&self, value: &HyperLink) -> crate::error::Result<()> {
+        match (value.target(), value.caption()) {
+            (HyperLinkTarget::External(target), Some(caption)) => self.write(&format!(
+                "#link({})[{}]",
+                string_literal(target),
+                escape(caption.inner())
+            )),
+            (HyperLinkTarget::External(target), None) => {
+                self.write(&format!("#link({})", string_literal(target)))
+            }
+            (HyperLinkTarget::Internal(target), Some(caption)) => {
+                self.write(&format!("#link(<{}>)[{}]", target, escape(caption.inner())))
+            }
+            (HyperLinkTarget::Internal(target), None) => self.write(&format!(
+                "#link(<{}>)[{}]",
+                target,
+                escape(&target.to_string())
+            )),
+        }
+    }
+
+    fn image(&self, value: &Image) -> crate::error::Result<()> {
+        if is_local(value.inner()) {
+            self.write(&format!("#box({})", image_call(value)))
+        } else {
+            self.write(&format!("#{}", image_call(value)))
+        }
+    }
+
+    fn text(&self, value: &Text) -> crate::error::Result<()> {
+        self.write_text(value.inner())
+    }
+
+    fn math(&self, value: &Math) -> crate::error::Result<()> {
+        self.write(&format!("${}$", value.inner()))
+    }
+
+    fn character(&self, value: &Character) -> crate::error::Result<()> {
+        match value {
+            Character::Space => self.write(" "),
+            Character::NonBreakSpace => self.write("~"),
+            Character::Hyphen => self.write("-"),
+            Character::EmDash => self.write("---"),
+            Character::EnDash => self.write("--"),
+            Character::Emoji(name) => self.write_text(&format!(":{}:", name.inner())),
+            Character::Other(c) => self.write_text(&c.to_string()),
+        }
+    }
+
+    fn line_break(&self) -> crate::error::Result<()> {
+        self.write(" \\\n")
+    }
+
+    fn start_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        let style_stack = make_style_stack(styles);
+        if style_stack.iter().any(|(start, _)| *start == "`") {
+            *self.raw_depth.borrow_mut() += 1;
+        }
+        self.write(
+            &style_stack
+                .into_iter()
+                .map(|(start, _)| start)
+                .collect::<Vec<String>>()
+                .join(""),
+        )
+    }
+
+    fn end_span(&self, styles: &[SpanStyle]) -> crate::error::Result<()> {
+        let style_stack = make_style_stack(styles);
+        if style_stack.iter().any(|(start, _)| *start == "`") {
+            *self.raw_depth.borrow_mut() -= 1;
+        }
+        self.write(
+            &style_stack
+                .into_iter()
+                .rev()
+                .map(|(_, end)| end)
+                .collect::<Vec<&str>>()
+                .join(""),
+        )
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn make_style_stack(styles: &[SpanStyle]) -> Vec<(String, &'static str)> {
+    let mut style_stack = Vec::new();
+    for style in styles {
+        match style {
+            SpanStyle::Plain => style_stack.clear(),
+            SpanStyle::Italic => style_stack.push(("#emph[".to_string(), "]")),
+            SpanStyle::Bold => style_stack.push(("#strong[".to_string(), "]")),
+            SpanStyle::Mono | SpanStyle::Code => style_stack.push(("`".to_string(), "`")),
+            SpanStyle::Strikethrough => style_stack.push(("#strike[".to_string(), "]")),
+            SpanStyle::Underline => style_stack.push(("#underline[".to_string(), "]")),
+            SpanStyle::SmallCaps => style_stack.push(("#smallcaps[".to_string(), "]")),
+            SpanStyle::Superscript => style_stack.push(("#super[".to_string(), "]")),
+            SpanStyle::Subscript => style_stack.push(("#sub[".to_string(), "]")),
+            SpanStyle::Sized(size) => style_stack.push((
+                format!(
+                    "#text(size: {})[",
+                    match size {
+                        Size::Largest => "1.7em",
+                        Size::Larger => "1.4em",
+                        Size::Large => "1.2em",
+                        Size::Normal => "1em",
+                        Size::Small => "0.9em",
+                        Size::Smaller => "0.8em",
+                        Size::Smallest => "0.7em",
+                    }
+                ),
+                "]",
+            )),
+        }
+    }
+    style_stack
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut previous = None;
+    while let Some(c) = chars.next() {
+        let comment =
+            c == '/' && matches!(chars.peek(), Some('/') | Some('*')) && previous != Some(':');
+        if comment || "\\*_`$#<>@[]~".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+        previous = Some(c);
+    }
+    escaped
+}
+
+fn escape_line_start(text: &str) -> String {
+    let digits = text.chars().take_while(|c| c.is_ascii_digit()).count();
+    if text.starts_with(['=', '-', '+', '/']) {
+        format!("\\{}", text)
+    } else if digits > 0 && text[digits..].starts_with('.') {
+        format!("{}\\{}", &text[..digits], &text[digits..])
+    } else {
+        text.to_string()
+    }
+}
+
+fn string_literal(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn string_array(values: &[String]) -> String {
+    if values.len() == 1 {
+        string_literal(&values[0])
+    } else {
+        format!(
+            "({})",
+            values
+                .iter()
+                .map(|value| string_literal(value))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+}
+
+///
+/// Typst can only load images from local files, so images with a URL are written as a link
+/// instead, with the alternate text, if any, as its body.
+///
+fn image_call(value: &Image) -> String {
+    match (is_local(value.inner()), value.alt_text()) {
+        (true, Some(alt_text)) => format!(
+            "image({}, alt: {})",
+            string_literal(value.inner()),
+            string_literal(alt_text)
+        ),
+        (true, None) => format!("image({})", string_literal(value.inner())),
+        (false, Some(alt_text)) => format!(
+            "link({})[{}]",
+            string_literal(value.inner()),
+            escape(alt_text)
+        ),
+        (false, None) => format!("link({})", string_literal(value.inner())),
+    }
+}
+
+fn is_local(path: &str) -> bool {
+    !(path.contains("://") || path.starts_with("data:"))
+}
+
+fn longest_run(text: &str, c: char) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for ch in text.chars() {
+        if ch == c {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    longest
+}
@@ -0,0 +1,406 @@
+use somedoc::model::block::{Cell, Column, HasBlockContent, Label, Paragraph, Row, Table};
+use somedoc::model::inline::{HasInlineContent, HyperLink};
+use somedoc::model::Document;
+use somedoc::write::OutputFormat;
+use std::str::FromStr;
+
+pub mod common;
+
+#[inline]
+fn assert_asciidoc_eq(part_fn: impl Fn() -> Document, expected: &str) {
+    common::assert_serialized_eq(&part_fn(), OutputFormat::AsciiDoc, expected)
+}
+
+#[test]
+fn test_skos() {
+    assert_asciidoc_eq(
+        common::skos::document,
+        r###"= Scheme: Clothing shapes, patterns, and details
+Simon
+
+== Scheme: Clothing shapes, patterns, and details
+
+////
+TODO:
+- more nested lists
+- tables
+////
+
+__Terms commonly used to describe fashion items. It includes terms for outline, fit, elements, detailing, and patterns.__
+
+link:http://amazon.com/vocabulary/fashion-design#DesignScheme[]
+
+[[Labels]]
+=== Labels
+
+____
+**skos:prefLabel**
+
+**skos:altLabel**
+____
+
+.Other labels
+[cols="<,<",options="header"]
+|===
+|Label text |Language
+|Clothing shapes, patterns, and details |**en**
+|===
+
+[[Other_Properties]]
+=== Other Properties
+
+'''
+
+Jump to: <<Concepts_Hierarchy,Concepts Hierarchy>> &#124; <<Concepts,Concepts>> &#124; <<Collections,Collections>> &#124; <<Appendix_-_RDF,Appendix - RDF>>
+
+'''
+
+[[Concept_Hierarchy]]
+=== Concept Hierarchy
+
+* **First item**
+* Second item
+** __Third item__
+* First item
+
+[[Appendix_-_RDF]]
+=== Appendix - RDF
+
+[source,turtle]
+----
+@prefix foo: <...>
+foo:bar foo:baz 12.
+----
+
+....
+@prefix foo: <...>
+foo:bar foo:baz 12.
+....
+"###,
+    );
+}
+
+#[test]
+fn test_empty_document() {
+    assert_asciidoc_eq(common::parts::empty_document, r###""###);
+}
+
+#[test]
+fn test_document_with_title() {
+    assert_asciidoc_eq(
+        common::parts::document_with_title,
+        r###"= Test Document
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_heading() {
+    assert_asciidoc_eq(
+        common::parts::document_with_heading,
+        r###"== Test Document
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_labeled_heading() {
+    assert_asciidoc_eq(
+        common::parts::document_with_labeled_heading,
+        r###"[[Test_Document]]
+== Test Document
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_headings() {
+    assert_asciidoc_eq(
+        common::parts::document_with_headings,
+        r###"== Section
+
+=== Sub-section
+
+==== Sub-sub-section
+
+===== Sub-sub-sub-section
+
+====== Sub-sub-sub-sub-section
+
+====== Sub-sub-sub-sub-sub-section
+
+====== Sub-sub-sub-sub-sub-sub-section
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_front_matter() {
+    assert_asciidoc_eq(
+        common::parts::document_with_front_matter,
+        r###":toc: macro
+
+toc::[]
+
+== Section One
+
+== Section Two
+"###,
+    );
+}
+
+#[test]
+fn test_paragraph_alignment() {
+    assert_asciidoc_eq(
+        common::parts::paragraph_alignment,
+        r###"left-aligned
+
+[.text-right]
+right-aligned
+
+[.text-center]
+center-aligned
+
+[.text-justify]
+both-aligned
+"###,
+    );
+}
+
+#[test]
+fn test_ordered_list() {
+    assert_asciidoc_eq(
+        common::parts::ordered_list,
+        r###". one
+. two
+. three
+"###,
+    );
+}
+
+#[test]
+fn test_labeled_ordered_list() {
+    assert_asciidoc_eq(
+        common::parts::labeled_ordered_list,
+        r###"[[lst1]]
+. [[lst1-itm1]]one
+. [[lst1-itm2]]two
+. [[lst1-itm3]]three
+"###,
+    );
+}
+
+#[test]
+fn test_unordered_list() {
+    assert_asciidoc_eq(
+        common::parts::unordered_list,
+        r###"* one
+* two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_ordered_list() {
+    assert_asciidoc_eq(
+        common::parts::nested_ordered_list,
+        r###". one
+. two
+.. inner one
+.. inner two
+. three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_unordered_list() {
+    assert_asciidoc_eq(
+        common::parts::nested_unordered_list,
+        r###"* one
+* two
+** inner one
+** inner two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_mixed_lists() {
+    assert_asciidoc_eq(
+        common::parts::nested_mixed_lists,
+        r###"* one
+* two
+.. inner one
+*** inner inner one
+.. inner two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_definition_list() {
+    assert_asciidoc_eq(
+        common::parts::definition_list,
+        r###"Universe:: Big, really big
+"###,
+    );
+}
+
+#[test]
+fn test_image_block() {
+    assert_asciidoc_eq(
+        common::parts::image_block,
+        r###"image::https://example.org/example.png[]
+"###,
+    );
+}
+
+#[test]
+fn test_image_block_with_label_and_caption() {
+    assert_asciidoc_eq(
+        common::parts::image_block_with_label_and_caption,
+        r###"[[img:example]]
+.An Example Image
+image::https://example.org/example.png[]
+"###,
+    );
+}
+
+#[test]
+fn test_math_block() {
+    assert_asciidoc_eq(
+        common::parts::math_block,
+        r###"[latexmath]
+++++
+x=2+2^2
+++++
+"###,
+    );
+}
+
+#[test]
+fn test_math_block_with_label_and_caption() {
+    assert_asciidoc_eq(
+        common::parts::math_block_with_label_and_caption,
+        r###"[[math:example]]
+.Example Math
+[latexmath]
+++++
+x=2+2^2
+++++
+"###,
+    );
+}
+
+#[test]
+fn test_block_quote() {
+    assert_asciidoc_eq(
+        common::parts::block_quote,
+        r###"____
+a block quote
+____
+"###,
+    );
+}
+
+#[test]
+fn test_nested_block_quotes() {
+    assert_asciidoc_eq(
+        common::parts::nested_block_quotes,
+        r###"____
+a block quote
+
+_____
+another block quote
+_____
+____
+"###,
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_asciidoc_eq(
+        common::parts::text_styles,
+        r###"Here is some plain **bold** __italic__ ``mono`` ``code`` plain [line-through]##strikethrough## [underline]##underline## [small-caps]##small caps## ^superscript^ ~subscript~ text.
+"###,
+    );
+}
+
+#[test]
+fn test_nested_text_styles() {
+    assert_asciidoc_eq(
+        common::parts::nested_text_styles,
+        r###"Here is some **__bold italic__** text.
+
+Here is some bold italic plain text.
+
+Here is some __bold plain italic__ text.
+"###,
+    );
+}
+
+#[test]
+fn test_hyper_links() {
+    assert_asciidoc_eq(
+        common::parts::hyper_links,
+        r###"link:https://example.org/[]
+
+link:https://example.org/[example]
+
+<<section-2>>
+
+<<section-2,example>>
+"###,
+    );
+}
+
+#[test]
+fn test_complex_paragraph() {
+    assert_asciidoc_eq(
+        common::parts::complex_paragraph,
+        r###"This paragraph has link:https://example.org/[a link], some math:{nbsp}latexmath:[x=2+2^2], a line break, +
+an image:{nbsp}image:https://example.org/favicon.png[logo]{nbsp}—{nbsp} all together!
+"###,
+    );
+}
+
+#[test]
+fn test_escaped_text_and_cells() {
+    assert_asciidoc_eq(
+        || {
+            let mut table = Table::new(&[Column::from("a | b"), Column::from("c")]);
+            table.add_row(Row::new(&[
+                Cell::plain_str("x | y"),
+                Cell::plain_str("*z*"),
+            ]));
+            Document::default()
+                .add_paragraph(Paragraph::plain_str(".not a title"))
+                .add_paragraph(Paragraph::plain_str(
+                    "*not bold* _not italic_ `not mono` #not marked# +not passed+",
+                ))
+                .add_paragraph(Paragraph::link(HyperLink::internal_with_caption_str(
+                    Label::from_str("sec").unwrap(),
+                    "*not bold*",
+                )))
+                .add_table(table)
+                .clone()
+        },
+        r###"&#46;not a title
+
+&#42;not bold&#42; &#95;not italic&#95; &#96;not mono&#96; &#35;not marked&#35; &#43;not passed&#43;
+
+<<sec,&#42;not bold&#42;>>
+
+[cols="<,<",options="header"]
+|===
+|a &#124; b |c
+|x &#124; y |&#42;z&#42;
+|===
+"###,
+    );
+}
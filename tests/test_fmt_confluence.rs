@@ -0,0 +1,450 @@
+use somedoc::model::block::{
+    Caption, CodeBlock, HasBlockContent, HasCaption, ImageBlock, Paragraph,
+};
+use somedoc::model::inline::{HasInlineContent, Image};
+use somedoc::model::Document;
+use somedoc::write::OutputFormat;
+
+pub mod common;
+
+#[inline]
+fn assert_confluence_eq(part_fn: impl Fn() -> Document, expected: &str) {
+    common::assert_serialized_eq(&part_fn(), OutputFormat::Confluence, expected)
+}
+
+#[test]
+fn test_skos() {
+    assert_confluence_eq(
+        common::skos::document,
+        r###"<h1>Scheme: Clothing shapes, patterns, and details</h1>
+<!-- TODO:
+- more nested lists
+- tables -->
+<p><em>Terms commonly used to describe fashion items. It includes terms for outline, fit, elements, detailing, and patterns.</em></p>
+<p><a href="http://amazon.com/vocabulary/fashion-design#DesignScheme">http://amazon.com/vocabulary/fashion-design#DesignScheme</a></p>
+<h2><ac:structured-macro ac:name="anchor"><ac:parameter ac:name="">Labels</ac:parameter></ac:structured-macro>Labels</h2>
+<blockquote>
+  <p><strong>skos:prefLabel</strong></p>
+  <p><strong>skos:altLabel</strong></p>
+</blockquote>
+<table>
+  <tbody>
+    <tr>
+      <th>Label text</th>
+      <th>Language</th>
+    </tr>
+    <tr>
+      <td>Clothing shapes, patterns, and details</td>
+      <td><strong>en</strong></td>
+    </tr>
+  </tbody>
+</table>
+<p style="text-align: center;"><em>Other labels</em></p>
+<h2><ac:structured-macro ac:name="anchor"><ac:parameter ac:name="">Other_Properties</ac:parameter></ac:structured-macro>Other Properties</h2>
+<hr/>
+<p>Jump to: <ac:link ac:anchor="Concepts_Hierarchy"><ac:plain-text-link-body><![CDATA[Concepts Hierarchy]]></ac:plain-text-link-body></ac:link> | <ac:link ac:anchor="Concepts"><ac:plain-text-link-body><![CDATA[Concepts]]></ac:plain-text-link-body></ac:link> | <ac:link ac:anchor="Collections"><ac:plain-text-link-body><![CDATA[Collections]]></ac:plain-text-link-body></ac:link> | <ac:link ac:anchor="Appendix_-_RDF"><ac:plain-text-link-body><![CDATA[Appendix - RDF]]></ac:plain-text-link-body></ac:link></p>
+<hr/>
+<h2><ac:structured-macro ac:name="anchor"><ac:parameter ac:name="">Concept_Hierarchy</ac:parameter></ac:structured-macro>Concept Hierarchy</h2>
+<ul>
+  <li>
+    <p><strong>First item</strong></p>
+  </li>
+  <li>
+    <p>Second item</p>
+    <ul>
+      <li>
+        <p><em>Third item</em></p>
+      </li>
+    </ul>
+  </li>
+  <li>
+    <p>First item</p>
+  </li>
+</ul>
+<h2><ac:structured-macro ac:name="anchor"><ac:parameter ac:name="">Appendix_-_RDF</ac:parameter></ac:structured-macro>Appendix - RDF</h2>
+<ac:structured-macro ac:name="code">
+  <ac:parameter ac:name="language">turtle</ac:parameter>
+  <ac:plain-text-body><![CDATA[@prefix foo: <...>
+foo:bar foo:baz 12.]]></ac:plain-text-body>
+</ac:structured-macro>
+<pre>@prefix foo: &lt;...&gt;
+foo:bar foo:baz 12.</pre>
+"###,
+    );
+}
+
+#[test]
+fn test_empty_document() {
+    assert_confluence_eq(common::parts::empty_document, r###""###);
+}
+
+#[test]
+fn test_document_with_title() {
+    assert_confluence_eq(common::parts::document_with_title, r###""###);
+}
+
+#[test]
+fn test_document_with_heading() {
+    assert_confluence_eq(
+        common::parts::document_with_heading,
+        r###"<h1>Test Document</h1>
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_labeled_heading() {
+    assert_confluence_eq(
+        common::parts::document_with_labeled_heading,
+        r###"<h1><ac:structured-macro ac:name="anchor"><ac:parameter ac:name="">Test_Document</ac:parameter></ac:structured-macro>Test Document</h1>
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_headings() {
+    assert_confluence_eq(
+        common::parts::document_with_headings,
+        r###"<h1>Section</h1>
+<h2>Sub-section</h2>
+<h3>Sub-sub-section</h3>
+<h4>Sub-sub-sub-section</h4>
+<h5>Sub-sub-sub-sub-section</h5>
+<h6>Sub-sub-sub-sub-sub-section</h6>
+<h6>Sub-sub-sub-sub-sub-sub-section</h6>
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_front_matter() {
+    assert_confluence_eq(
+        common::parts::document_with_front_matter,
+        r###"<ac:structured-macro ac:name="toc"/>
+<h1>Section One</h1>
+<h1>Section Two</h1>
+"###,
+    );
+}
+
+#[test]
+fn test_paragraph_alignment() {
+    assert_confluence_eq(
+        common::parts::paragraph_alignment,
+        r###"<p>left-aligned</p>
+<p style="text-align: right;">right-aligned</p>
+<p style="text-align: center;">center-aligned</p>
+<p style="text-align: justify;">both-aligned</p>
+"###,
+    );
+}
+
+#[test]
+fn test_ordered_list() {
+    assert_confluence_eq(
+        common::parts::ordered_list,
+        r###"<ol>
+  <li>
+    <p>one</p>
+  </li>
+  <li>
+    <p>two</p>
+  </li>
+  <li>
+    <p>three</p>
+  </li>
+</ol>
+"###,
+    );
+}
+
+#[test]
+fn test_labeled_ordered_list() {
+    assert_confluence_eq(
+        common::parts::labeled_ordered_list,
+        r###"<ac:structured-macro ac:name="anchor"><ac:parameter ac:name="">lst1</ac:parameter></ac:structured-macro>
+<ol>
+  <li>
+    <p><ac:structured-macro ac:name="anchor"><ac:parameter ac:name="">lst1-itm1</ac:parameter></ac:structured-macro>one</p>
+  </li>
+  <li>
+    <p><ac:structured-macro ac:name="anchor"><ac:parameter ac:name="">lst1-itm2</ac:parameter></ac:structured-macro>two</p>
+  </li>
+  <li>
+    <p><ac:structured-macro ac:name="anchor"><ac:parameter ac:name="">lst1-itm3</ac:parameter></ac:structured-macro>three</p>
+  </li>
+</ol>
+"###,
+    );
+}
+
+#[test]
+fn test_unordered_list() {
+    assert_confluence_eq(
+        common::parts::unordered_list,
+        r###"<ul>
+  <li>
+    <p>one</p>
+  </li>
+  <li>
+    <p>two</p>
+  </li>
+  <li>
+    <p>three</p>
+  </li>
+</ul>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_ordered_list() {
+    assert_confluence_eq(
+        common::parts::nested_ordered_list,
+        r###"<ol>
+  <li>
+    <p>one</p>
+  </li>
+  <li>
+    <p>two</p>
+    <ol>
+      <li>
+        <p>inner one</p>
+      </li>
+      <li>
+        <p>inner two</p>
+      </li>
+    </ol>
+  </li>
+  <li>
+    <p>three</p>
+  </li>
+</ol>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_unordered_list() {
+    assert_confluence_eq(
+        common::parts::nested_unordered_list,
+        r###"<ul>
+  <li>
+    <p>one</p>
+  </li>
+  <li>
+    <p>two</p>
+    <ul>
+      <li>
+        <p>inner one</p>
+      </li>
+      <li>
+        <p>inner two</p>
+      </li>
+    </ul>
+  </li>
+  <li>
+    <p>three</p>
+  </li>
+</ul>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_mixed_lists() {
+    assert_confluence_eq(
+        common::parts::nested_mixed_lists,
+        r###"<ul>
+  <li>
+    <p>one</p>
+  </li>
+  <li>
+    <p>two</p>
+    <ol>
+      <li>
+        <p>inner one</p>
+        <ul>
+          <li>
+            <p>inner inner one</p>
+          </li>
+        </ul>
+      </li>
+      <li>
+        <p>inner two</p>
+      </li>
+    </ol>
+  </li>
+  <li>
+    <p>three</p>
+  </li>
+</ul>
+"###,
+    );
+}
+
+#[test]
+fn test_definition_list() {
+    assert_confluence_eq(
+        common::parts::definition_list,
+        r###"<dl>
+  <dt>Universe</dt>
+  <dd>Big, really big</dd>
+</dl>
+"###,
+    );
+}
+
+#[test]
+fn test_image_block() {
+    assert_confluence_eq(
+        common::parts::image_block,
+        r###"<p style="text-align: center;"><ac:image><ri:url ri:value="https://example.org/example.png"/></ac:image></p>
+"###,
+    );
+}
+
+#[test]
+fn test_image_block_with_label_and_caption() {
+    assert_confluence_eq(
+        common::parts::image_block_with_label_and_caption,
+        r###"<p style="text-align: center;"><ac:structured-macro ac:name="anchor"><ac:parameter ac:name="">img:example</ac:parameter></ac:structured-macro><ac:image><ri:url ri:value="https://example.org/example.png"/></ac:image></p>
+<p style="text-align: center;"><em>An Example Image</em></p>
+"###,
+    );
+}
+
+#[test]
+fn test_math_block() {
+    assert_confluence_eq(
+        common::parts::math_block,
+        r###"<p style="text-align: center;"><code>x=2+2^2</code></p>
+"###,
+    );
+}
+
+#[test]
+fn test_math_block_with_label_and_caption() {
+    assert_confluence_eq(
+        common::parts::math_block_with_label_and_caption,
+        r###"<p style="text-align: center;"><ac:structured-macro ac:name="anchor"><ac:parameter ac:name="">math:example</ac:parameter></ac:structured-macro><code>x=2+2^2</code></p>
+<p style="text-align: center;"><em>Example Math</em></p>
+"###,
+    );
+}
+
+#[test]
+fn test_block_quote() {
+    assert_confluence_eq(
+        common::parts::block_quote,
+        r###"<blockquote>
+  <p>a block quote</p>
+</blockquote>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_block_quotes() {
+    assert_confluence_eq(
+        common::parts::nested_block_quotes,
+        r###"<blockquote>
+  <p>a block quote</p>
+  <blockquote>
+    <p>another block quote</p>
+  </blockquote>
+</blockquote>
+"###,
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_confluence_eq(
+        common::parts::text_styles,
+        r###"<p>Here is some plain <strong>bold</strong> <em>italic</em> <code>mono</code> <code>code</code> plain <span style="text-decoration: line-through;">strikethrough</span> <u>underline</u> <span style="font-variant: small-caps;">small caps</span> <sup>superscript</sup> <sub>subscript</sub> text.</p>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_text_styles() {
+    assert_confluence_eq(
+        common::parts::nested_text_styles,
+        r###"<p>Here is some <strong><em>bold italic</em></strong> text.</p>
+<p>Here is some bold italic plain text.</p>
+<p>Here is some <em>bold plain italic</em> text.</p>
+"###,
+    );
+}
+
+#[test]
+fn test_hyper_links() {
+    assert_confluence_eq(
+        common::parts::hyper_links,
+        r###"<p><a href="https://example.org/">https://example.org/</a></p>
+<p><a href="https://example.org/">example</a></p>
+<p><ac:link ac:anchor="section-2"><ac:plain-text-link-body><![CDATA[section-2]]></ac:plain-text-link-body></ac:link></p>
+<p><ac:link ac:anchor="section-2"><ac:plain-text-link-body><![CDATA[example]]></ac:plain-text-link-body></ac:link></p>
+"###,
+    );
+}
+
+#[test]
+fn test_complex_paragraph() {
+    assert_confluence_eq(
+        common::parts::complex_paragraph,
+        r###"<p>This paragraph has <a href="https://example.org/">a link</a>, some math:&#160;<code>x=2+2^2</code>, a line break,<br/>an image:&#160;<ac:image ac:alt="logo"><ri:url ri:value="https://example.org/favicon.png"/></ac:image>&#160;&#8212;&#160; all together!</p>
+"###,
+    );
+}
+
+#[test]
+fn test_code_block_with_caption() {
+    assert_confluence_eq(
+        || {
+            let mut code = CodeBlock::with_language("let s = \"]]>\";", "rust");
+            let _ = code.set_caption(Caption::from("Example & Test"));
+            Document::default().add_code_block(code).clone()
+        },
+        r###"<ac:structured-macro ac:name="code">
+  <ac:parameter ac:name="language">rust</ac:parameter>
+  <ac:parameter ac:name="title">Example &amp; Test</ac:parameter>
+  <ac:plain-text-body><![CDATA[let s = "]]]]><![CDATA[>";]]></ac:plain-text-body>
+</ac:structured-macro>
+"###,
+    );
+}
+
+#[test]
+fn test_abstract_excerpt() {
+    assert_confluence_eq(
+        || {
+            Document::default()
+                .add_abstract(Paragraph::plain_str("In short."))
+                .clone()
+        },
+        r###"<ac:structured-macro ac:name="excerpt">
+  <ac:rich-text-body>
+    <p>In short.</p>
+  </ac:rich-text-body>
+</ac:structured-macro>
+"###,
+    );
+}
+
+#[test]
+fn test_local_image_attachment() {
+    assert_confluence_eq(
+        || {
+            Document::default()
+                .add_image(ImageBlock::from(Image::with_alt_text(
+                    "images/diagram.png",
+                    "A diagram",
+                )))
+                .clone()
+        },
+        r###"<p style="text-align: center;"><ac:image ac:alt="A diagram"><ri:attachment ri:filename="diagram.png"/></ac:image></p>
+"###,
+    );
+}
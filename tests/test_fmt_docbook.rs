@@ -0,0 +1,575 @@
+use somedoc::model::block::{HasBlockContent, Paragraph};
+use somedoc::model::Document;
+use somedoc::write::OutputFormat;
+
+pub mod common;
+
+#[inline]
+fn assert_docbook_eq(part_fn: impl Fn() -> Document, expected: &str) {
+    common::assert_serialized_eq(&part_fn(), OutputFormat::DocBook, expected)
+}
+
+#[test]
+fn test_skos() {
+    assert_docbook_eq(
+        common::skos::document,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <info>
+    <title>Scheme: Clothing shapes, patterns, and details</title>
+    <author>
+      <personname>Simon</personname>
+    </author>
+  </info>
+  <section>
+    <title>Scheme: Clothing shapes, patterns, and details</title>
+    <!-- TODO:
+- more nested lists
+- tables -->
+    <para><emphasis>Terms commonly used to describe fashion items. It includes terms for outline, fit, elements, detailing, and patterns.</emphasis></para>
+    <para><link xlink:href="http://amazon.com/vocabulary/fashion-design#DesignScheme"/></para>
+    <section xml:id="Labels">
+      <title>Labels</title>
+      <blockquote>
+        <para><emphasis role="bold">skos:prefLabel</emphasis></para>
+        <para><emphasis role="bold">skos:altLabel</emphasis></para>
+      </blockquote>
+      <table>
+        <title>Other labels</title>
+        <tgroup cols="2">
+          <colspec colname="c1" align="left"/>
+          <colspec colname="c2" align="left"/>
+          <thead>
+            <row>
+              <entry>Label text</entry>
+              <entry>Language</entry>
+            </row>
+          </thead>
+          <tbody>
+            <row>
+              <entry>Clothing shapes, patterns, and details</entry>
+              <entry><emphasis role="bold">en</emphasis></entry>
+            </row>
+          </tbody>
+        </tgroup>
+      </table>
+    </section>
+    <section xml:id="Other_Properties">
+      <title>Other Properties</title>
+      <para role="separator"/>
+      <para>Jump to: <link linkend="Concepts_Hierarchy">Concepts Hierarchy</link> | <link linkend="Concepts">Concepts</link> | <link linkend="Collections">Collections</link> | <link linkend="Appendix_-_RDF">Appendix - RDF</link></para>
+      <para role="separator"/>
+    </section>
+    <section xml:id="Concept_Hierarchy">
+      <title>Concept Hierarchy</title>
+      <itemizedlist>
+        <listitem>
+          <para><emphasis role="bold">First item</emphasis></para>
+        </listitem>
+        <listitem>
+          <para>Second item</para>
+          <itemizedlist>
+            <listitem>
+              <para><emphasis>Third item</emphasis></para>
+            </listitem>
+          </itemizedlist>
+        </listitem>
+        <listitem>
+          <para>First item</para>
+        </listitem>
+      </itemizedlist>
+    </section>
+    <section xml:id="Appendix_-_RDF">
+      <title>Appendix - RDF</title>
+      <programlisting language="turtle">@prefix foo: &lt;...&gt;
+foo:bar foo:baz 12.</programlisting>
+      <literallayout class="monospaced">@prefix foo: &lt;...&gt;
+foo:bar foo:baz 12.</literallayout>
+    </section>
+  </section>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_empty_document() {
+    assert_docbook_eq(
+        common::parts::empty_document,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_title() {
+    assert_docbook_eq(
+        common::parts::document_with_title,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <info>
+    <title>Test Document</title>
+  </info>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_heading() {
+    assert_docbook_eq(
+        common::parts::document_with_heading,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <section>
+    <title>Test Document</title>
+  </section>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_labeled_heading() {
+    assert_docbook_eq(
+        common::parts::document_with_labeled_heading,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <section xml:id="Test_Document">
+    <title>Test Document</title>
+  </section>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_headings() {
+    assert_docbook_eq(
+        common::parts::document_with_headings,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <section>
+    <title>Section</title>
+    <section>
+      <title>Sub-section</title>
+      <section>
+        <title>Sub-sub-section</title>
+        <section>
+          <title>Sub-sub-sub-section</title>
+          <section>
+            <title>Sub-sub-sub-sub-section</title>
+            <section>
+              <title>Sub-sub-sub-sub-sub-section</title>
+              <section>
+                <title>Sub-sub-sub-sub-sub-sub-section</title>
+              </section>
+            </section>
+          </section>
+        </section>
+      </section>
+    </section>
+  </section>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_front_matter() {
+    assert_docbook_eq(
+        common::parts::document_with_front_matter,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <toc/>
+  <section>
+    <title>Section One</title>
+  </section>
+  <section>
+    <title>Section Two</title>
+  </section>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_paragraph_alignment() {
+    assert_docbook_eq(
+        common::parts::paragraph_alignment,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <para>left-aligned</para>
+  <para role="right">right-aligned</para>
+  <para role="center">center-aligned</para>
+  <para role="justify">both-aligned</para>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_ordered_list() {
+    assert_docbook_eq(
+        common::parts::ordered_list,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <orderedlist>
+    <listitem>
+      <para>one</para>
+    </listitem>
+    <listitem>
+      <para>two</para>
+    </listitem>
+    <listitem>
+      <para>three</para>
+    </listitem>
+  </orderedlist>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_labeled_ordered_list() {
+    assert_docbook_eq(
+        common::parts::labeled_ordered_list,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <orderedlist xml:id="lst1">
+    <listitem xml:id="lst1-itm1">
+      <para>one</para>
+    </listitem>
+    <listitem xml:id="lst1-itm2">
+      <para>two</para>
+    </listitem>
+    <listitem xml:id="lst1-itm3">
+      <para>three</para>
+    </listitem>
+  </orderedlist>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_unordered_list() {
+    assert_docbook_eq(
+        common::parts::unordered_list,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <itemizedlist>
+    <listitem>
+      <para>one</para>
+    </listitem>
+    <listitem>
+      <para>two</para>
+    </listitem>
+    <listitem>
+      <para>three</para>
+    </listitem>
+  </itemizedlist>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_ordered_list() {
+    assert_docbook_eq(
+        common::parts::nested_ordered_list,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <orderedlist>
+    <listitem>
+      <para>one</para>
+    </listitem>
+    <listitem>
+      <para>two</para>
+      <orderedlist>
+        <listitem>
+          <para>inner one</para>
+        </listitem>
+        <listitem>
+          <para>inner two</para>
+        </listitem>
+      </orderedlist>
+    </listitem>
+    <listitem>
+      <para>three</para>
+    </listitem>
+  </orderedlist>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_unordered_list() {
+    assert_docbook_eq(
+        common::parts::nested_unordered_list,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <itemizedlist>
+    <listitem>
+      <para>one</para>
+    </listitem>
+    <listitem>
+      <para>two</para>
+      <itemizedlist>
+        <listitem>
+          <para>inner one</para>
+        </listitem>
+        <listitem>
+          <para>inner two</para>
+        </listitem>
+      </itemizedlist>
+    </listitem>
+    <listitem>
+      <para>three</para>
+    </listitem>
+  </itemizedlist>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_mixed_lists() {
+    assert_docbook_eq(
+        common::parts::nested_mixed_lists,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <itemizedlist>
+    <listitem>
+      <para>one</para>
+    </listitem>
+    <listitem>
+      <para>two</para>
+      <orderedlist>
+        <listitem>
+          <para>inner one</para>
+          <itemizedlist>
+            <listitem>
+              <para>inner inner one</para>
+            </listitem>
+          </itemizedlist>
+        </listitem>
+        <listitem>
+          <para>inner two</para>
+        </listitem>
+      </orderedlist>
+    </listitem>
+    <listitem>
+      <para>three</para>
+    </listitem>
+  </itemizedlist>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_definition_list() {
+    assert_docbook_eq(
+        common::parts::definition_list,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <variablelist>
+    <varlistentry>
+      <term>Universe</term>
+      <listitem>
+        <para>Big, really big</para>
+      </listitem>
+    </varlistentry>
+  </variablelist>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_image_block() {
+    assert_docbook_eq(
+        common::parts::image_block,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <mediaobject><imageobject><imagedata fileref="https://example.org/example.png"/></imageobject></mediaobject>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_image_block_with_label_and_caption() {
+    assert_docbook_eq(
+        common::parts::image_block_with_label_and_caption,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <figure xml:id="img_example">
+    <title>An Example Image</title>
+    <mediaobject><imageobject><imagedata fileref="https://example.org/example.png"/></imageobject></mediaobject>
+  </figure>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_math_block() {
+    assert_docbook_eq(
+        common::parts::math_block,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <informalequation>
+    <mathphrase>x=2+2^2</mathphrase>
+  </informalequation>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_math_block_with_label_and_caption() {
+    assert_docbook_eq(
+        common::parts::math_block_with_label_and_caption,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <equation xml:id="math_example">
+    <title>Example Math</title>
+    <mathphrase>x=2+2^2</mathphrase>
+  </equation>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_block_quote() {
+    assert_docbook_eq(
+        common::parts::block_quote,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <blockquote>
+    <para>a block quote</para>
+  </blockquote>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_block_quotes() {
+    assert_docbook_eq(
+        common::parts::nested_block_quotes,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <blockquote>
+    <para>a block quote</para>
+    <blockquote>
+      <para>another block quote</para>
+    </blockquote>
+  </blockquote>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_docbook_eq(
+        common::parts::text_styles,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <para>Here is some plain <emphasis role="bold">bold</emphasis> <emphasis>italic</emphasis> <literal>mono</literal> <code>code</code> plain <emphasis role="strikethrough">strikethrough</emphasis> <emphasis role="underline">underline</emphasis> <phrase role="smallcaps">small caps</phrase> <superscript>superscript</superscript> <subscript>subscript</subscript> text.</para>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_text_styles() {
+    assert_docbook_eq(
+        common::parts::nested_text_styles,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <para>Here is some <emphasis role="bold"><emphasis>bold italic</emphasis></emphasis> text.</para>
+  <para>Here is some bold italic plain text.</para>
+  <para>Here is some <emphasis>bold plain italic</emphasis> text.</para>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_hyper_links() {
+    assert_docbook_eq(
+        common::parts::hyper_links,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <para><link xlink:href="https://example.org/"/></para>
+  <para><link xlink:href="https://example.org/">example</link></para>
+  <para><xref linkend="section-2"/></para>
+  <para><link linkend="section-2">example</link></para>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_complex_paragraph() {
+    assert_docbook_eq(
+        common::parts::complex_paragraph,
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <para>This paragraph has <link xlink:href="https://example.org/">a link</link>, some math:&#160;<inlineequation><mathphrase>x=2+2^2</mathphrase></inlineequation>, a line break,<?linebreak?>an image:&#160;<inlinemediaobject><imageobject><imagedata fileref="https://example.org/favicon.png"/></imageobject><textobject><phrase>logo</phrase></textobject></inlinemediaobject>&#160;&#8212;&#160; all together!</para>
+</article>
+"###,
+    );
+}
+
+#[test]
+fn test_info_and_escapes() {
+    assert_docbook_eq(
+        || {
+            Document::default()
+                .set_title("Fish & Chips")
+                .add_author_str("Simon", Some("simon@example.org"), Some("<Chips> Inc."))
+                .add_copyright_str(2020, Some("Chips \"R\" Us"), None)
+                .add_keywords_str(&["fish", "chips"])
+                .add_metadata_property_str("status", "draft")
+                .add_abstract(Paragraph::from("a < b && b > c"))
+                .add_paragraph(Paragraph::from("<para> & \"quoted\""))
+                .clone()
+        },
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<article xmlns="http://docbook.org/ns/docbook" xmlns:xlink="http://www.w3.org/1999/xlink" version="5.0">
+  <info>
+    <title>Fish &amp; Chips</title>
+    <author>
+      <personname>Simon</personname>
+      <email>simon@example.org</email>
+      <affiliation>
+        <orgname>&lt;Chips&gt; Inc.</orgname>
+      </affiliation>
+    </author>
+    <copyright>
+      <year>2020</year>
+      <holder>Chips "R" Us</holder>
+    </copyright>
+    <keywordset>
+      <keyword>fish</keyword>
+      <keyword>chips</keyword>
+    </keywordset>
+    <bibliomisc role="status">draft</bibliomisc>
+    <abstract>
+      <para>a &lt; b &amp;&amp; b &gt; c</para>
+    </abstract>
+  </info>
+  <para>&lt;para&gt; &amp; "quoted"</para>
+</article>
+"###,
+    );
+}
@@ -0,0 +1,462 @@
+use pretty_assertions::assert_eq;
+use somedoc::model::block::{HasBlockContent, Heading, ImageBlock, List, Paragraph};
+use somedoc::model::inline::{HasInlineContent, Image};
+use somedoc::model::Document;
+use somedoc::write::{write_document, write_document_to_string, OutputFormat};
+use std::io::{Cursor, Read};
+use zip::ZipArchive;
+
+pub mod common;
+
+fn write_docx(doc: &Document) -> ZipArchive<Cursor<Vec<u8>>> {
+    let mut buffer = Cursor::new(Vec::new());
+    write_document(doc, OutputFormat::Docx, &mut buffer).unwrap();
+    ZipArchive::new(Cursor::new(buffer.into_inner())).unwrap()
+}
+
+fn read_file(archive: &mut ZipArchive<Cursor<Vec<u8>>>, name: &str) -> String {
+    let mut content = String::new();
+    let _ = archive
+        .by_name(name)
+        .unwrap()
+        .read_to_string(&mut content)
+        .unwrap();
+    content
+}
+
+#[inline]
+fn assert_docx_body_eq(part_fn: impl Fn() -> Document, expected: &str) {
+    let document = read_file(&mut write_docx(&part_fn()), "word/document.xml");
+    let body = &document
+        [document.find("  <w:body>\n").unwrap() + 11..document.find("    <w:sectPr>").unwrap()];
+    println!("{}", body);
+    assert_eq!(body, expected);
+}
+
+#[test]
+fn test_skos() {
+    assert_docx_body_eq(
+        common::skos::document,
+        r###"    <w:p><w:pPr><w:pStyle w:val="Title"/></w:pPr><w:r><w:t xml:space="preserve">Scheme: Clothing shapes, patterns, and details</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t xml:space="preserve">Scheme: Clothing shapes, patterns, and details</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Normal"/></w:pPr><w:r><w:rPr><w:i/></w:rPr><w:t xml:space="preserve">Terms commonly used to describe fashion items. It includes terms for outline, fit, elements, detailing, and patterns.</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Normal"/></w:pPr><w:hyperlink r:id="rId3" w:history="1"><w:r><w:rPr><w:rStyle w:val="Hyperlink"/></w:rPr><w:t xml:space="preserve">http://amazon.com/vocabulary/fashion-design#DesignScheme</w:t></w:r></w:hyperlink></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Heading2"/></w:pPr><w:bookmarkStart w:id="0" w:name="Labels"/><w:bookmarkEnd w:id="0"/><w:r><w:t xml:space="preserve">Labels</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Quote"/></w:pPr><w:r><w:rPr><w:b/></w:rPr><w:t xml:space="preserve">skos:prefLabel</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Quote"/></w:pPr><w:r><w:rPr><w:b/></w:rPr><w:t xml:space="preserve">skos:altLabel</w:t></w:r></w:p>
+    <w:tbl>
+      <w:tblPr><w:tblStyle w:val="TableGrid"/><w:tblW w:w="0" w:type="auto"/></w:tblPr>
+      <w:tblGrid><w:gridCol w:w="4680"/><w:gridCol w:w="4680"/></w:tblGrid>
+      <w:tr>
+        <w:trPr><w:tblHeader/></w:trPr>
+        <w:tc>
+          <w:p><w:pPr><w:pStyle w:val="TableHeading"/></w:pPr><w:r><w:t xml:space="preserve">Label text</w:t></w:r></w:p>
+        </w:tc>
+        <w:tc>
+          <w:p><w:pPr><w:pStyle w:val="TableHeading"/></w:pPr><w:r><w:t xml:space="preserve">Language</w:t></w:r></w:p>
+        </w:tc>
+      </w:tr>
+      <w:tr>
+        <w:tc>
+          <w:p><w:pPr><w:pStyle w:val="TableContents"/></w:pPr><w:r><w:t xml:space="preserve">Clothing shapes, patterns, and details</w:t></w:r></w:p>
+        </w:tc>
+        <w:tc>
+          <w:p><w:pPr><w:pStyle w:val="TableContents"/></w:pPr><w:r><w:rPr><w:b/></w:rPr><w:t xml:space="preserve">en</w:t></w:r></w:p>
+        </w:tc>
+      </w:tr>
+    </w:tbl>
+    <w:p><w:pPr><w:pStyle w:val="Caption"/></w:pPr><w:r><w:t xml:space="preserve">Other labels</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Heading2"/></w:pPr><w:bookmarkStart w:id="1" w:name="Other_Properties"/><w:bookmarkEnd w:id="1"/><w:r><w:t xml:space="preserve">Other Properties</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Normal"/><w:pBdr><w:bottom w:val="single" w:sz="6" w:space="1" w:color="auto"/></w:pBdr></w:pPr></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Normal"/></w:pPr><w:r><w:t xml:space="preserve">Jump to: </w:t></w:r><w:hyperlink w:anchor="Concepts_Hierarchy" w:history="1"><w:r><w:rPr><w:rStyle w:val="Hyperlink"/></w:rPr><w:t xml:space="preserve">Concepts Hierarchy</w:t></w:r></w:hyperlink><w:r><w:t xml:space="preserve"> | </w:t></w:r><w:hyperlink w:anchor="Concepts" w:history="1"><w:r><w:rPr><w:rStyle w:val="Hyperlink"/></w:rPr><w:t xml:space="preserve">Concepts</w:t></w:r></w:hyperlink><w:r><w:t xml:space="preserve"> | </w:t></w:r><w:hyperlink w:anchor="Collections" w:history="1"><w:r><w:rPr><w:rStyle w:val="Hyperlink"/></w:rPr><w:t xml:space="preserve">Collections</w:t></w:r></w:hyperlink><w:r><w:t xml:space="preserve"> | </w:t></w:r><w:hyperlink w:anchor="Appendix___RDF" w:history="1"><w:r><w:rPr><w:rStyle w:val="Hyperlink"/></w:rPr><w:t xml:space="preserve">Appendix - RDF</w:t></w:r></w:hyperlink></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Normal"/><w:pBdr><w:bottom w:val="single" w:sz="6" w:space="1" w:color="auto"/></w:pBdr></w:pPr></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Heading2"/></w:pPr><w:bookmarkStart w:id="2" w:name="Concept_Hierarchy"/><w:bookmarkEnd w:id="2"/><w:r><w:t xml:space="preserve">Concept Hierarchy</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:rPr><w:b/></w:rPr><w:t xml:space="preserve">First item</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">Second item</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="1"/><w:numId w:val="2"/></w:numPr></w:pPr><w:r><w:rPr><w:i/></w:rPr><w:t xml:space="preserve">Third item</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">First item</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Heading2"/></w:pPr><w:bookmarkStart w:id="3" w:name="Appendix___RDF"/><w:bookmarkEnd w:id="3"/><w:r><w:t xml:space="preserve">Appendix - RDF</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="SourceCode"/></w:pPr><w:r><w:t xml:space="preserve">@prefix foo: &lt;...&gt;</w:t></w:r><w:r><w:br/></w:r><w:r><w:t xml:space="preserve">foo:bar foo:baz 12.</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="SourceCode"/></w:pPr><w:r><w:t xml:space="preserve">@prefix foo: &lt;...&gt;</w:t></w:r><w:r><w:br/></w:r><w:r><w:t xml:space="preserve">foo:bar foo:baz 12.</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_empty_document() {
+    assert_docx_body_eq(common::parts::empty_document, r###""###);
+}
+
+#[test]
+fn test_document_with_title() {
+    assert_docx_body_eq(
+        common::parts::document_with_title,
+        r###"    <w:p><w:pPr><w:pStyle w:val="Title"/></w:pPr><w:r><w:t xml:space="preserve">Test Document</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_heading() {
+    assert_docx_body_eq(
+        common::parts::document_with_heading,
+        r###"    <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t xml:space="preserve">Test Document</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_labeled_heading() {
+    assert_docx_body_eq(
+        common::parts::document_with_labeled_heading,
+        r###"    <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:bookmarkStart w:id="0" w:name="Test_Document"/><w:bookmarkEnd w:id="0"/><w:r><w:t xml:space="preserve">Test Document</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_headings() {
+    assert_docx_body_eq(
+        common::parts::document_with_headings,
+        r###"    <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t xml:space="preserve">Section</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Heading2"/></w:pPr><w:r><w:t xml:space="preserve">Sub-section</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Heading3"/></w:pPr><w:r><w:t xml:space="preserve">Sub-sub-section</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Heading4"/></w:pPr><w:r><w:t xml:space="preserve">Sub-sub-sub-section</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Heading5"/></w:pPr><w:r><w:t xml:space="preserve">Sub-sub-sub-sub-section</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Heading6"/></w:pPr><w:r><w:t xml:space="preserve">Sub-sub-sub-sub-sub-section</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Heading7"/></w:pPr><w:r><w:t xml:space="preserve">Sub-sub-sub-sub-sub-sub-section</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_front_matter() {
+    assert_docx_body_eq(
+        common::parts::document_with_front_matter,
+        r###"    <w:p><w:pPr><w:pStyle w:val="TOCHeading"/></w:pPr><w:r><w:t xml:space="preserve">Contents</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Normal"/></w:pPr><w:r><w:fldChar w:fldCharType="begin" w:dirty="true"/></w:r><w:r><w:instrText xml:space="preserve"> TOC \o "1-3" \h \z \u </w:instrText></w:r><w:r><w:fldChar w:fldCharType="separate"/></w:r><w:r><w:t>Update this field to show the table of contents.</w:t></w:r><w:r><w:fldChar w:fldCharType="end"/></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t xml:space="preserve">Section One</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t xml:space="preserve">Section Two</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_paragraph_alignment() {
+    assert_docx_body_eq(
+        common::parts::paragraph_alignment,
+        r###"    <w:p><w:pPr><w:pStyle w:val="Normal"/></w:pPr><w:r><w:t xml:space="preserve">left-aligned</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Normal"/><w:jc w:val="right"/></w:pPr><w:r><w:t xml:space="preserve">right-aligned</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Normal"/><w:jc w:val="center"/></w:pPr><w:r><w:t xml:space="preserve">center-aligned</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Normal"/><w:jc w:val="both"/></w:pPr><w:r><w:t xml:space="preserve">both-aligned</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_ordered_list() {
+    assert_docx_body_eq(
+        common::parts::ordered_list,
+        r###"    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">one</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">two</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">three</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_labeled_ordered_list() {
+    assert_docx_body_eq(
+        common::parts::labeled_ordered_list,
+        r###"    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:bookmarkStart w:id="0" w:name="lst1"/><w:bookmarkEnd w:id="0"/><w:bookmarkStart w:id="1" w:name="lst1_itm1"/><w:bookmarkEnd w:id="1"/><w:r><w:t xml:space="preserve">one</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:bookmarkStart w:id="2" w:name="lst1_itm2"/><w:bookmarkEnd w:id="2"/><w:r><w:t xml:space="preserve">two</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:bookmarkStart w:id="3" w:name="lst1_itm3"/><w:bookmarkEnd w:id="3"/><w:r><w:t xml:space="preserve">three</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_unordered_list() {
+    assert_docx_body_eq(
+        common::parts::unordered_list,
+        r###"    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">one</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">two</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">three</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_ordered_list() {
+    assert_docx_body_eq(
+        common::parts::nested_ordered_list,
+        r###"    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">one</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">two</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="1"/><w:numId w:val="2"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">inner one</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="1"/><w:numId w:val="2"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">inner two</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">three</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_unordered_list() {
+    assert_docx_body_eq(
+        common::parts::nested_unordered_list,
+        r###"    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">one</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">two</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="1"/><w:numId w:val="2"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">inner one</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="1"/><w:numId w:val="2"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">inner two</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">three</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_mixed_lists() {
+    assert_docx_body_eq(
+        common::parts::nested_mixed_lists,
+        r###"    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">one</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">two</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="1"/><w:numId w:val="2"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">inner one</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="2"/><w:numId w:val="3"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">inner inner one</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="1"/><w:numId w:val="2"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">inner two</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">three</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_definition_list() {
+    assert_docx_body_eq(
+        common::parts::definition_list,
+        r###"    <w:p><w:pPr><w:pStyle w:val="DefinitionTerm"/></w:pPr><w:r><w:t xml:space="preserve">Universe</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Definition"/></w:pPr><w:r><w:t xml:space="preserve">Big, really big</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_image_block() {
+    assert_docx_body_eq(
+        common::parts::image_block,
+        r###"    <w:p><w:pPr><w:pStyle w:val="Normal"/></w:pPr><w:r><w:drawing><wp:inline distT="0" distB="0" distL="0" distR="0"><wp:extent cx="5943600" cy="4457700"/><wp:docPr id="1" name="Picture 1" descr=""/><wp:cNvGraphicFramePr><a:graphicFrameLocks noChangeAspect="1"/></wp:cNvGraphicFramePr><a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/picture"><pic:pic><pic:nvPicPr><pic:cNvPr id="1" name="Picture 1"/><pic:cNvPicPr/></pic:nvPicPr><pic:blipFill><a:blip r:link="rId3"/><a:stretch><a:fillRect/></a:stretch></pic:blipFill><pic:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="5943600" cy="4457700"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom></pic:spPr></pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_image_block_with_label_and_caption() {
+    assert_docx_body_eq(
+        common::parts::image_block_with_label_and_caption,
+        r###"    <w:p><w:pPr><w:pStyle w:val="Normal"/></w:pPr><w:r><w:drawing><wp:inline distT="0" distB="0" distL="0" distR="0"><wp:extent cx="5943600" cy="4457700"/><wp:docPr id="1" name="Picture 1" descr=""/><wp:cNvGraphicFramePr><a:graphicFrameLocks noChangeAspect="1"/></wp:cNvGraphicFramePr><a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/picture"><pic:pic><pic:nvPicPr><pic:cNvPr id="1" name="Picture 1"/><pic:cNvPicPr/></pic:nvPicPr><pic:blipFill><a:blip r:link="rId3"/><a:stretch><a:fillRect/></a:stretch></pic:blipFill><pic:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="5943600" cy="4457700"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom></pic:spPr></pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Caption"/></w:pPr><w:bookmarkStart w:id="0" w:name="img_example"/><w:bookmarkEnd w:id="0"/><w:r><w:t xml:space="preserve">An Example Image</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_math_block() {
+    assert_docx_body_eq(
+        common::parts::math_block,
+        r###"    <w:p><w:pPr><w:pStyle w:val="SourceCode"/><w:jc w:val="center"/></w:pPr><w:r><w:t xml:space="preserve">x=2+2^2</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_math_block_with_label_and_caption() {
+    assert_docx_body_eq(
+        common::parts::math_block_with_label_and_caption,
+        r###"    <w:p><w:pPr><w:pStyle w:val="SourceCode"/><w:jc w:val="center"/></w:pPr><w:r><w:t xml:space="preserve">x=2+2^2</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Caption"/></w:pPr><w:bookmarkStart w:id="0" w:name="math_example"/><w:bookmarkEnd w:id="0"/><w:r><w:t xml:space="preserve">Example Math</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_block_quote() {
+    assert_docx_body_eq(
+        common::parts::block_quote,
+        r###"    <w:p><w:pPr><w:pStyle w:val="Quote"/></w:pPr><w:r><w:t xml:space="preserve">a block quote</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_block_quotes() {
+    assert_docx_body_eq(
+        common::parts::nested_block_quotes,
+        r###"    <w:p><w:pPr><w:pStyle w:val="Quote"/></w:pPr><w:r><w:t xml:space="preserve">a block quote</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Quote"/></w:pPr><w:r><w:t xml:space="preserve">another block quote</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_docx_body_eq(
+        common::parts::text_styles,
+        r###"    <w:p><w:pPr><w:pStyle w:val="Normal"/></w:pPr><w:r><w:t xml:space="preserve">Here is some plain </w:t></w:r><w:r><w:rPr><w:b/></w:rPr><w:t xml:space="preserve">bold</w:t></w:r><w:r><w:t xml:space="preserve"> </w:t></w:r><w:r><w:rPr><w:i/></w:rPr><w:t xml:space="preserve">italic</w:t></w:r><w:r><w:t xml:space="preserve"> </w:t></w:r><w:r><w:rPr><w:rStyle w:val="VerbatimChar"/></w:rPr><w:t xml:space="preserve">mono</w:t></w:r><w:r><w:t xml:space="preserve"> </w:t></w:r><w:r><w:rPr><w:rStyle w:val="VerbatimChar"/></w:rPr><w:t xml:space="preserve">code</w:t></w:r><w:r><w:t xml:space="preserve"> plain </w:t></w:r><w:r><w:rPr><w:strike/></w:rPr><w:t xml:space="preserve">strikethrough</w:t></w:r><w:r><w:t xml:space="preserve"> </w:t></w:r><w:r><w:rPr><w:u w:val="single"/></w:rPr><w:t xml:space="preserve">underline</w:t></w:r><w:r><w:t xml:space="preserve"> </w:t></w:r><w:r><w:rPr><w:smallCaps/></w:rPr><w:t xml:space="preserve">small caps</w:t></w:r><w:r><w:t xml:space="preserve"> </w:t></w:r><w:r><w:rPr><w:vertAlign w:val="superscript"/></w:rPr><w:t xml:space="preserve">superscript</w:t></w:r><w:r><w:t xml:space="preserve"> </w:t></w:r><w:r><w:rPr><w:vertAlign w:val="subscript"/></w:rPr><w:t xml:space="preserve">subscript</w:t></w:r><w:r><w:t xml:space="preserve"> text.</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_text_styles() {
+    assert_docx_body_eq(
+        common::parts::nested_text_styles,
+        r###"    <w:p><w:pPr><w:pStyle w:val="Normal"/></w:pPr><w:r><w:t xml:space="preserve">Here is some </w:t></w:r><w:r><w:rPr><w:b/><w:i/></w:rPr><w:t xml:space="preserve">bold italic</w:t></w:r><w:r><w:t xml:space="preserve"> text.</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Normal"/></w:pPr><w:r><w:t xml:space="preserve">Here is some bold italic plain text.</w:t></w:r></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Normal"/></w:pPr><w:r><w:t xml:space="preserve">Here is some </w:t></w:r><w:r><w:rPr><w:i/></w:rPr><w:t xml:space="preserve">bold plain italic</w:t></w:r><w:r><w:t xml:space="preserve"> text.</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_hyper_links() {
+    assert_docx_body_eq(
+        common::parts::hyper_links,
+        r###"    <w:p><w:pPr><w:pStyle w:val="Normal"/></w:pPr><w:hyperlink r:id="rId3" w:history="1"><w:r><w:rPr><w:rStyle w:val="Hyperlink"/></w:rPr><w:t xml:space="preserve">https://example.org/</w:t></w:r></w:hyperlink></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Normal"/></w:pPr><w:hyperlink r:id="rId3" w:history="1"><w:r><w:rPr><w:rStyle w:val="Hyperlink"/></w:rPr><w:t xml:space="preserve">example</w:t></w:r></w:hyperlink></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Normal"/></w:pPr><w:hyperlink w:anchor="section_2" w:history="1"><w:r><w:rPr><w:rStyle w:val="Hyperlink"/></w:rPr><w:t xml:space="preserve">section-2</w:t></w:r></w:hyperlink></w:p>
+    <w:p><w:pPr><w:pStyle w:val="Normal"/></w:pPr><w:hyperlink w:anchor="section_2" w:history="1"><w:r><w:rPr><w:rStyle w:val="Hyperlink"/></w:rPr><w:t xml:space="preserve">example</w:t></w:r></w:hyperlink></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_complex_paragraph() {
+    assert_docx_body_eq(
+        common::parts::complex_paragraph,
+        r###"    <w:p><w:pPr><w:pStyle w:val="Normal"/></w:pPr><w:r><w:t xml:space="preserve">This paragraph has </w:t></w:r><w:hyperlink r:id="rId3" w:history="1"><w:r><w:rPr><w:rStyle w:val="Hyperlink"/></w:rPr><w:t xml:space="preserve">a link</w:t></w:r></w:hyperlink><w:r><w:t xml:space="preserve">, some math: </w:t></w:r><w:r><w:rPr><w:rStyle w:val="VerbatimChar"/></w:rPr><w:t xml:space="preserve">x=2+2^2</w:t></w:r><w:r><w:t xml:space="preserve">, a line break,</w:t></w:r><w:r><w:br/></w:r><w:r><w:t xml:space="preserve">an image: </w:t></w:r><w:r><w:drawing><wp:inline distT="0" distB="0" distL="0" distR="0"><wp:extent cx="5943600" cy="4457700"/><wp:docPr id="1" name="Picture 1" descr="logo"/><wp:cNvGraphicFramePr><a:graphicFrameLocks noChangeAspect="1"/></wp:cNvGraphicFramePr><a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/picture"><pic:pic><pic:nvPicPr><pic:cNvPr id="1" name="Picture 1"/><pic:cNvPicPr/></pic:nvPicPr><pic:blipFill><a:blip r:link="rId4"/><a:stretch><a:fillRect/></a:stretch></pic:blipFill><pic:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="5943600" cy="4457700"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom></pic:spPr></pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r><w:r><w:t xml:space="preserve"> —  all together!</w:t></w:r></w:p>
+"###,
+    );
+}
+
+#[test]
+fn test_package_parts() {
+    let mut archive = write_docx(&common::parts::hyper_links());
+    let content_types = read_file(&mut archive, "[Content_Types].xml");
+    assert!(content_types.contains(r#"<Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>"#));
+    assert!(content_types.contains(r#"<Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>"#));
+    assert!(read_file(&mut archive, "_rels/.rels").contains(r#"Target="word/document.xml""#));
+    assert_eq!(
+        read_file(&mut archive, "word/_rels/document.xml.rels"),
+        r###"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>
+  <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering" Target="numbering.xml"/>
+  <Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://example.org/" TargetMode="External"/>
+</Relationships>
+"###
+    );
+    let styles = read_file(&mut archive, "word/styles.xml");
+    assert!(styles.contains(r#"<w:style w:type="paragraph" w:styleId="Heading3">"#));
+    assert!(styles.contains(r#"<w:outlineLvl w:val="2"/>"#));
+}
+
+#[test]
+fn test_core_and_custom_properties() {
+    let mut doc = Document::default();
+    let _ = doc
+        .set_title("Fish & Chips")
+        .add_author_str("Simon", None, None)
+        .add_author_str("Alex", None, None)
+        .set_date("2021-02-03")
+        .add_keywords_str(&["fish", "chips"])
+        .add_copyright_str(2021, Some("Chippy"), None)
+        .add_metadata_property_str("language", "en-GB")
+        .add_metadata_property_str("department", "Food")
+        .add_abstract(Paragraph::plain_str("All about chips."));
+    let mut archive = write_docx(&doc);
+
+    assert_eq!(
+        read_file(&mut archive, "docProps/core.xml"),
+        r###"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+  <dc:title>Fish &amp; Chips</dc:title>
+  <dcterms:created xsi:type="dcterms:W3CDTF">2021-02-03</dcterms:created>
+  <cp:keywords>fish, chips</cp:keywords>
+  <dc:language>en-GB</dc:language>
+  <dc:creator>Simon; Alex</dc:creator>
+  <dc:description>All about chips.</dc:description>
+</cp:coreProperties>
+"###
+    );
+    let custom = read_file(&mut archive, "docProps/custom.xml");
+    assert!(custom.contains(r#"pid="2" name="copyright"><vt:lpwstr>2021 Chippy.</vt:lpwstr>"#));
+    assert!(custom.contains(r#"pid="3" name="department"><vt:lpwstr>Food</vt:lpwstr>"#));
+    assert!(read_file(&mut archive, "_rels/.rels").contains(r#"Target="docProps/custom.xml""#));
+}
+
+#[test]
+fn test_ordered_lists_restart_numbering() {
+    let mut doc = Document::default();
+    let mut list = List::ordered();
+    let _ = list.add_item_str("one").add_item_str("two");
+    let _ = doc.add_list(list.clone()).add_list(list);
+    let mut archive = write_docx(&doc);
+
+    let numbering = read_file(&mut archive, "word/numbering.xml");
+    assert!(numbering.contains(r#"<w:lvl w:ilvl="1"><w:start w:val="1"/><w:numFmt w:val="decimal"/><w:lvlText w:val="%2."/>"#));
+    assert!(numbering.contains(r#"<w:num w:numId="1"><w:abstractNumId w:val="1"/><w:lvlOverride w:ilvl="0"><w:startOverride w:val="1"/></w:lvlOverride></w:num>"#));
+    assert!(numbering.contains(r#"<w:num w:numId="2"><w:abstractNumId w:val="1"/><w:lvlOverride w:ilvl="0"><w:startOverride w:val="1"/></w:lvlOverride></w:num>"#));
+    assert!(read_file(&mut archive, "word/document.xml")
+        .contains(r#"<w:numPr><w:ilvl w:val="0"/><w:numId w:val="2"/></w:numPr>"#));
+}
+
+#[test]
+fn test_local_images_are_embedded() {
+    let image_path = std::env::temp_dir().join("somedoc-test-docx-image.png");
+    let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    png.extend_from_slice(&200u32.to_be_bytes());
+    png.extend_from_slice(&100u32.to_be_bytes());
+    std::fs::write(&image_path, &png).unwrap();
+    let image_path = image_path.to_string_lossy().to_string();
+
+    let mut doc = Document::default();
+    let _ = doc
+        .add_heading(Heading::section("Images"))
+        .add_image(ImageBlock::from(Image::with_alt_text(&image_path, "local")))
+        .add_paragraph(Paragraph::image(Image::new(&image_path)));
+    let mut archive = write_docx(&doc);
+
+    let mut embedded = Vec::new();
+    let _ = archive
+        .by_name("word/media/image1.png")
+        .unwrap()
+        .read_to_end(&mut embedded)
+        .unwrap();
+    assert_eq!(embedded, png);
+    assert!(read_file(&mut archive, "[Content_Types].xml")
+        .contains(r#"<Default Extension="png" ContentType="image/png"/>"#));
+    assert!(read_file(&mut archive, "word/_rels/document.xml.rels").contains(r#"<Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="media/image1.png"/>"#));
+    let document = read_file(&mut archive, "word/document.xml");
+    assert!(document.contains(
+        r#"<wp:extent cx="1905000" cy="952500"/><wp:docPr id="1" name="Picture 1" descr="local"/>"#
+    ));
+    assert_eq!(document.matches(r#"<a:blip r:embed="rId3"/>"#).count(), 2);
+    assert!(archive.by_name("word/media/image2.png").is_err());
+}
+
+#[test]
+fn test_missing_local_image_names_the_file() {
+    let mut doc = Document::default();
+    let _ = doc.add_image(ImageBlock::from(Image::new("does/not/exist.png")));
+    let mut buffer = Cursor::new(Vec::new());
+    let result = write_document(&doc, OutputFormat::Docx, &mut buffer);
+
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("'does/not/exist.png'"));
+}
+
+#[test]
+fn test_write_to_string_is_an_error() {
+    assert!(
+        write_document_to_string(&common::parts::document_with_title(), OutputFormat::Docx)
+            .is_err()
+    );
+}
@@ -0,0 +1,227 @@
+use pretty_assertions::assert_eq;
+use somedoc::model::block::{
+    FrontMatter, HasBlockContent, HasLabel, Heading, ImageBlock, Label, Paragraph,
+};
+use somedoc::model::inline::{HasInlineContent, HyperLink, Image};
+use somedoc::model::Document;
+use somedoc::write::{write_document, write_document_to_string, OutputFormat};
+use std::io::{Cursor, Read};
+use std::str::FromStr;
+use zip::{CompressionMethod, ZipArchive};
+
+pub mod common;
+
+fn write_epub(doc: &Document) -> ZipArchive<Cursor<Vec<u8>>> {
+    let mut buffer = Cursor::new(Vec::new());
+    write_document(doc, OutputFormat::Epub, &mut buffer).unwrap();
+    ZipArchive::new(Cursor::new(buffer.into_inner())).unwrap()
+}
+
+fn read_file(archive: &mut ZipArchive<Cursor<Vec<u8>>>, name: &str) -> String {
+    let mut content = String::new();
+    let _ = archive
+        .by_name(name)
+        .unwrap()
+        .read_to_string(&mut content)
+        .unwrap();
+    content
+}
+
+fn labeled_section(text: &str, label: &str) -> Heading {
+    let mut heading = Heading::section(text);
+    let _ = heading.set_label(Label::from_str(label).unwrap());
+    heading
+}
+
+#[test]
+fn test_mimetype_is_first_and_stored() {
+    let mut archive = write_epub(&common::parts::document_with_title());
+    let mimetype = archive.by_index(0).unwrap();
+    assert_eq!(mimetype.name(), "mimetype");
+    assert_eq!(mimetype.compression(), CompressionMethod::Stored);
+    assert!(mimetype.extra_data().is_empty());
+    drop(mimetype);
+    assert_eq!(read_file(&mut archive, "mimetype"), "application/epub+zip");
+    assert!(read_file(&mut archive, "META-INF/container.xml")
+        .contains(r#"<rootfile full-path="OEBPS/content.opf""#));
+}
+
+#[test]
+fn test_chapters_and_navigation() {
+    let mut doc = Document::default();
+    let _ = doc
+        .set_title("Manual")
+        .add_paragraph(Paragraph::plain_str("Preface"))
+        .add_heading(labeled_section("One", "one"))
+        .add_paragraph(Paragraph::link(HyperLink::internal_with_caption_str(
+            Label::from_str("two").unwrap(),
+            "see two",
+        )))
+        .add_heading(Heading::sub_section("One & A"))
+        .add_heading(labeled_section("Two", "two"));
+    let mut archive = write_epub(&doc);
+
+    assert_eq!(
+        read_file(&mut archive, "OEBPS/nav.xhtml"),
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops">
+  <head>
+    <meta charset="UTF-8"/>
+    <title>Manual</title>
+  </head>
+  <body>
+    <nav epub:type="toc" id="toc">
+      <h1>Contents</h1>
+      <ol>
+        <li><a href="chapter-1.xhtml">Manual</a></li>
+        <li><a href="chapter-2.xhtml">One</a>
+        <ol>
+          <li><a href="chapter-2.xhtml#chapter-2-heading-3">One &amp; A</a></li>
+        </ol></li>
+        <li><a href="chapter-3.xhtml">Two</a></li>
+      </ol>
+    </nav>
+  </body>
+</html>
+"###
+    );
+    assert_eq!(
+        read_file(&mut archive, "OEBPS/chapter-2.xhtml"),
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops">
+  <head>
+    <meta charset="UTF-8"/>
+    <title>One</title>
+  </head>
+  <body>
+    <h1 id="one">One</h1>
+    <p><a href="chapter-3.xhtml#two">see two</a></p>
+    <h2 id="chapter-2-heading-3">One &amp; A</h2>
+  </body>
+</html>"###
+    );
+}
+
+#[test]
+fn test_unrendered_blocks_do_not_make_a_chapter() {
+    let mut doc = Document::default();
+    let _ = doc
+        .set_title("Manual")
+        .add_comment_str("generated")
+        .add_front_matter(FrontMatter::TableOfContents)
+        .add_heading(labeled_section("One", "one"))
+        .add_paragraph(Paragraph::plain_str("Text"));
+    let mut archive = write_epub(&doc);
+
+    assert!(archive.by_name("OEBPS/chapter-2.xhtml").is_err());
+    let content = read_file(&mut archive, "OEBPS/chapter-1.xhtml");
+    assert!(content.contains("<!-- generated -->"));
+    assert!(content.contains("One</h1>"));
+    let nav = read_file(&mut archive, "OEBPS/nav.xhtml");
+    assert!(nav.contains(r#"<li><a href="chapter-1.xhtml">One</a></li>"#));
+    assert!(!nav.contains(r#">Manual</a>"#));
+}
+
+#[test]
+fn test_package_metadata() {
+    let mut doc = Document::default();
+    let _ = doc
+        .set_title("Fish & Chips")
+        .add_author_str("Simon", None, None)
+        .set_date("2021-02-03")
+        .add_keywords_str(&["fish", "chips"])
+        .add_metadata_property_str("identifier", "urn:isbn:0000000000")
+        .add_metadata_property_str("language", "en-GB")
+        .add_heading(Heading::section("Only"));
+    let mut archive = write_epub(&doc);
+
+    assert_eq!(
+        read_file(&mut archive, "OEBPS/content.opf"),
+        r###"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="pub-id">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <dc:identifier id="pub-id">urn:isbn:0000000000</dc:identifier>
+    <dc:title>Fish &amp; Chips</dc:title>
+    <dc:language>en-GB</dc:language>
+    <dc:creator>Simon</dc:creator>
+    <dc:date>2021-02-03</dc:date>
+    <dc:subject>fish</dc:subject>
+    <dc:subject>chips</dc:subject>
+    <meta property="dcterms:modified">2021-02-03T00:00:00Z</meta>
+  </metadata>
+  <manifest>
+    <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+    <item id="chapter-1" href="chapter-1.xhtml" media-type="application/xhtml+xml"/>
+  </manifest>
+  <spine>
+    <itemref idref="chapter-1"/>
+  </spine>
+</package>
+"###
+    );
+}
+
+#[test]
+fn test_package_language() {
+    let mut doc = Document::default();
+    let _ = doc.add_heading(Heading::section("Only"));
+    let package = read_file(&mut write_epub(&doc), "OEBPS/content.opf");
+    assert!(package.contains("<dc:language>und</dc:language>"));
+
+    let _ = doc.add_metadata_property_str("lang", "fr");
+    let package = read_file(&mut write_epub(&doc), "OEBPS/content.opf");
+    assert!(package.contains("<dc:language>fr</dc:language>"));
+}
+
+#[test]
+fn test_local_images_are_packaged() {
+    let image_path = std::env::temp_dir().join("somedoc-test-epub-image.png");
+    std::fs::write(&image_path, b"not really a png").unwrap();
+    let image_path = image_path.to_string_lossy().to_string();
+
+    let mut doc = Document::default();
+    let _ = doc
+        .add_heading(Heading::section("Images"))
+        .add_image(ImageBlock::from(Image::with_alt_text(&image_path, "local")))
+        .add_image(ImageBlock::from(Image::new(
+            "https://example.org/remote.png",
+        )));
+    let mut archive = write_epub(&doc);
+
+    assert_eq!(
+        read_file(&mut archive, "OEBPS/images/image-1.png"),
+        "not really a png"
+    );
+    let content = read_file(&mut archive, "OEBPS/chapter-1.xhtml");
+    assert!(content.contains(r#"<img src="images/image-1.png" alt="local"/>"#));
+    assert!(content.contains(r#"<img src="https://example.org/remote.png" alt=""/>"#));
+    let package = read_file(&mut archive, "OEBPS/content.opf");
+    assert!(package
+        .contains(r#"<item id="image-1" href="images/image-1.png" media-type="image/png"/>"#));
+    assert!(package.contains(r#"properties="remote-resources""#));
+}
+
+#[test]
+fn test_missing_local_image_names_the_file() {
+    let mut doc = Document::default();
+    let _ = doc
+        .add_heading(Heading::section("Images"))
+        .add_image(ImageBlock::from(Image::new("does/not/exist.png")));
+    let mut buffer = Cursor::new(Vec::new());
+    let result = write_document(&doc, OutputFormat::Epub, &mut buffer);
+
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("'does/not/exist.png'"));
+}
+
+#[test]
+fn test_write_to_string_is_an_error() {
+    assert!(
+        write_document_to_string(&common::parts::document_with_title(), OutputFormat::Epub)
+            .is_err()
+    );
+}
@@ -0,0 +1,423 @@
+use somedoc::model::block::{
+    Alignment, Caption, Cell, Column, HasBlockContent, HasCaption, Item, List, Paragraph, Quote,
+    Row, Table,
+};
+use somedoc::model::inline::{HasInlineContent, HyperLink};
+use somedoc::model::Document;
+use somedoc::write::OutputFormat;
+
+pub mod common;
+
+#[inline]
+fn assert_gemini_eq(part_fn: impl Fn() -> Document, expected: &str) {
+    common::assert_serialized_eq(&part_fn(), OutputFormat::Gemini, expected)
+}
+
+#[test]
+fn test_skos() {
+    assert_gemini_eq(
+        common::skos::document,
+        r###"# Scheme: Clothing shapes, patterns, and details
+
+# Scheme: Clothing shapes, patterns, and details
+
+Terms commonly used to describe fashion items. It includes terms for outline, fit, elements, detailing, and patterns.
+
+http://amazon.com/vocabulary/fashion-design#DesignScheme
+=> http://amazon.com/vocabulary/fashion-design#DesignScheme
+
+## Labels
+
+> skos:prefLabel
+>
+> skos:altLabel
+
+```Other labels
++----------------------------------------+----------+
+| Label text                             | Language |
++----------------------------------------+----------+
+| Clothing shapes, patterns, and details | en       |
++----------------------------------------+----------+
+```
+
+## Other Properties
+
+---
+
+Jump to: Concepts Hierarchy | Concepts | Collections | Appendix - RDF
+
+---
+
+## Concept Hierarchy
+
+* First item
+* Second item
+* Third item
+* First item
+
+## Appendix - RDF
+
+```turtle
+@prefix foo: <...>
+foo:bar foo:baz 12.
+```
+
+```
+@prefix foo: <...>
+foo:bar foo:baz 12.
+```
+"###,
+    );
+}
+
+#[test]
+fn test_empty_document() {
+    assert_gemini_eq(common::parts::empty_document, r###""###);
+}
+
+#[test]
+fn test_document_with_title() {
+    assert_gemini_eq(
+        common::parts::document_with_title,
+        r###"# Test Document
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_heading() {
+    assert_gemini_eq(
+        common::parts::document_with_heading,
+        r###"# Test Document
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_labeled_heading() {
+    assert_gemini_eq(
+        common::parts::document_with_labeled_heading,
+        r###"# Test Document
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_headings() {
+    assert_gemini_eq(
+        common::parts::document_with_headings,
+        r###"# Section
+
+## Sub-section
+
+### Sub-sub-section
+
+### Sub-sub-sub-section
+
+### Sub-sub-sub-sub-section
+
+### Sub-sub-sub-sub-sub-section
+
+### Sub-sub-sub-sub-sub-sub-section
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_front_matter() {
+    assert_gemini_eq(
+        common::parts::document_with_front_matter,
+        r###"# Section One
+
+# Section Two
+"###,
+    );
+}
+
+#[test]
+fn test_paragraph_alignment() {
+    assert_gemini_eq(
+        common::parts::paragraph_alignment,
+        r###"left-aligned
+
+right-aligned
+
+center-aligned
+
+both-aligned
+"###,
+    );
+}
+
+#[test]
+fn test_ordered_list() {
+    assert_gemini_eq(
+        common::parts::ordered_list,
+        r###"* one
+* two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_labeled_ordered_list() {
+    assert_gemini_eq(
+        common::parts::labeled_ordered_list,
+        r###"* one
+* two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_unordered_list() {
+    assert_gemini_eq(
+        common::parts::unordered_list,
+        r###"* one
+* two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_ordered_list() {
+    assert_gemini_eq(
+        common::parts::nested_ordered_list,
+        r###"* one
+* two
+* inner one
+* inner two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_unordered_list() {
+    assert_gemini_eq(
+        common::parts::nested_unordered_list,
+        r###"* one
+* two
+* inner one
+* inner two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_mixed_lists() {
+    assert_gemini_eq(
+        common::parts::nested_mixed_lists,
+        r###"* one
+* two
+* inner one
+* inner inner one
+* inner two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_definition_list() {
+    assert_gemini_eq(
+        common::parts::definition_list,
+        r###"* Universe: Big, really big
+"###,
+    );
+}
+
+#[test]
+fn test_image_block() {
+    assert_gemini_eq(
+        common::parts::image_block,
+        r###"=> https://example.org/example.png
+"###,
+    );
+}
+
+#[test]
+fn test_image_block_with_label_and_caption() {
+    assert_gemini_eq(
+        common::parts::image_block_with_label_and_caption,
+        r###"=> https://example.org/example.png An Example Image
+"###,
+    );
+}
+
+#[test]
+fn test_math_block() {
+    assert_gemini_eq(
+        common::parts::math_block,
+        r###"```
+x=2+2^2
+```
+"###,
+    );
+}
+
+#[test]
+fn test_math_block_with_label_and_caption() {
+    assert_gemini_eq(
+        common::parts::math_block_with_label_and_caption,
+        r###"```Example Math
+x=2+2^2
+```
+"###,
+    );
+}
+
+#[test]
+fn test_block_quote() {
+    assert_gemini_eq(
+        common::parts::block_quote,
+        r###"> a block quote
+"###,
+    );
+}
+
+#[test]
+fn test_nested_block_quotes() {
+    assert_gemini_eq(
+        common::parts::nested_block_quotes,
+        r###"> a block quote
+>
+> another block quote
+"###,
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_gemini_eq(
+        common::parts::text_styles,
+        r###"Here is some plain bold italic mono code plain strikethrough underline small caps superscript subscript text.
+"###,
+    );
+}
+
+#[test]
+fn test_nested_text_styles() {
+    assert_gemini_eq(
+        common::parts::nested_text_styles,
+        r###"Here is some bold italic text.
+
+Here is some bold italic plain text.
+
+Here is some bold plain italic text.
+"###,
+    );
+}
+
+#[test]
+fn test_hyper_links() {
+    assert_gemini_eq(
+        common::parts::hyper_links,
+        r###"https://example.org/
+=> https://example.org/
+
+example
+=> https://example.org/ example
+
+section-2
+
+example
+"###,
+    );
+}
+
+#[test]
+fn test_complex_paragraph() {
+    assert_gemini_eq(
+        common::parts::complex_paragraph,
+        r###"This paragraph has a link, some math: x=2+2^2, a line break,
+an image: logo —  all together!
+=> https://example.org/ a link
+=> https://example.org/favicon.png logo
+"###,
+    );
+}
+
+#[test]
+fn test_links_in_lists_and_quotes() {
+    assert_gemini_eq(
+        || {
+            let mut list = List::default();
+            let mut item = Item::default();
+            let _ = item
+                .add_text_str("see ")
+                .add_link(HyperLink::external_with_caption_str(
+                    "gemini://example.org/",
+                    "the capsule",
+                ));
+            let _ = list.add_item(item);
+            let mut quote = Quote::default();
+            let mut paragraph = Paragraph::plain_str("quoting ");
+            let _ = paragraph.add_link(HyperLink::external("https://example.org/"));
+            let _ = quote.add_paragraph(paragraph);
+            let mut doc = Document::default();
+            let _ = doc.add_list(list).add_block_quote(quote);
+            doc
+        },
+        r###"* see the capsule
+=> gemini://example.org/ the capsule
+
+> quoting https://example.org/
+=> https://example.org/
+"###,
+    );
+}
+
+#[test]
+fn test_line_types_in_text_are_escaped() {
+    assert_gemini_eq(
+        || {
+            let mut doc = Document::default();
+            let _ = doc
+                .add_paragraph(Paragraph::plain_str("# not a heading"))
+                .add_paragraph(Paragraph::plain_str("=> not a link"))
+                .add_paragraph(Paragraph::plain_str("*emphasis* is fine"));
+            doc
+        },
+        r###" # not a heading
+
+ => not a link
+
+*emphasis* is fine
+"###,
+    );
+}
+
+#[test]
+fn test_table_alignment() {
+    assert_gemini_eq(
+        || {
+            let mut table = Table::new(&[
+                Column::from("Name"),
+                Column::from(("Size", Alignment::Right)),
+                Column::from(("Kind", Alignment::Centered)),
+            ]);
+            table.add_row(Row::new(&[
+                Cell::plain_str("index.gmi"),
+                Cell::plain_str("2k"),
+                Cell::plain_str("page"),
+            ]));
+            table.set_caption(Caption::from("Files"));
+            let mut doc = Document::default();
+            let _ = doc.add_table(table);
+            doc
+        },
+        r###"```Files
++-----------+------+------+
+| Name      | Size | Kind |
++-----------+------+------+
+| index.gmi |   2k | page |
++-----------+------+------+
+```
+"###,
+    );
+}
@@ -1,3 +1,5 @@
+use somedoc::model::block::{HasBlockContent, Paragraph};
+use somedoc::model::inline::HasInlineContent;
 use somedoc::model::Document;
 use somedoc::write::OutputFormat;
 
@@ -59,7 +61,7 @@ fn test_skos() {
       <thead>
         <tr><th>Label text</th><th>Language</th></tr>
       </thead>
-      </tbody>
+      <tbody>
         <tr><td>Clothing shapes, patterns, and details</td><td><strong>en</strong></td></tr>
       </tbody>
     </table>
@@ -459,3 +461,21 @@ an image:&nbsp;<img src="https://example.org/favicon.png"/>&nbsp;&mdash;&nbsp; a
         false,
     );
 }
+
+#[test]
+fn test_abstract() {
+    assert_html_eq(
+        || {
+            Document::default()
+                .add_abstract(Paragraph::plain_str("In short."))
+                .add_paragraph(Paragraph::plain_str("At length."))
+                .clone()
+        },
+        r###"<body>
+    <p class="abstract">In short.</p>
+    <p>At length.</p>
+  </body>
+</html>"###,
+        false,
+    );
+}
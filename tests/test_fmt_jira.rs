@@ -0,0 +1,396 @@
+use somedoc::model::block::{Caption, CodeBlock, HasBlockContent, HasCaption, Paragraph};
+use somedoc::model::inline::{HasInlineContent, Image};
+use somedoc::model::Document;
+use somedoc::write::OutputFormat;
+
+pub mod common;
+
+#[inline]
+fn assert_jira_eq(part_fn: impl Fn() -> Document, expected: &str) {
+    common::assert_serialized_eq(&part_fn(), OutputFormat::Jira, expected)
+}
+
+#[test]
+fn test_skos() {
+    assert_jira_eq(
+        common::skos::document,
+        r###"h1. Scheme: Clothing shapes, patterns, and details
+
+_Terms commonly used to describe fashion items. It includes terms for outline, fit, elements, detailing, and patterns._
+
+[http://amazon.com/vocabulary/fashion-design#DesignScheme]
+
+h2. {anchor:Labels}Labels
+
+{quote}
+*skos:prefLabel*
+
+*skos:altLabel*
+{quote}
+
+||Label text||Language||
+|Clothing shapes, patterns, and details|*en*|
+_Other labels_
+
+h2. {anchor:Other_Properties}Other Properties
+
+----
+
+Jump to: [Concepts Hierarchy|#Concepts_Hierarchy] \| [Concepts|#Concepts] \| [Collections|#Collections] \| [Appendix \- RDF|#Appendix_-_RDF]
+
+----
+
+h2. {anchor:Concept_Hierarchy}Concept Hierarchy
+
+* *First item*
+* Second item
+** _Third item_
+* First item
+
+h2. {anchor:Appendix_-_RDF}Appendix \- RDF
+
+{code:turtle}
+@prefix foo: <...>
+foo:bar foo:baz 12.
+{code}
+
+{noformat}
+@prefix foo: <...>
+foo:bar foo:baz 12.
+{noformat}
+"###,
+    );
+}
+
+#[test]
+fn test_empty_document() {
+    assert_jira_eq(common::parts::empty_document, r###""###);
+}
+
+#[test]
+fn test_document_with_title() {
+    assert_jira_eq(common::parts::document_with_title, r###""###);
+}
+
+#[test]
+fn test_document_with_heading() {
+    assert_jira_eq(
+        common::parts::document_with_heading,
+        r###"h1. Test Document
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_labeled_heading() {
+    assert_jira_eq(
+        common::parts::document_with_labeled_heading,
+        r###"h1. {anchor:Test_Document}Test Document
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_headings() {
+    assert_jira_eq(
+        common::parts::document_with_headings,
+        r###"h1. Section
+
+h2. Sub\-section
+
+h3. Sub\-sub\-section
+
+h4. Sub\-sub\-sub\-section
+
+h5. Sub\-sub\-sub\-sub\-section
+
+h6. Sub\-sub\-sub\-sub\-sub\-section
+
+h6. Sub\-sub\-sub\-sub\-sub\-sub\-section
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_front_matter() {
+    assert_jira_eq(
+        common::parts::document_with_front_matter,
+        r###"h1. Section One
+
+h1. Section Two
+"###,
+    );
+}
+
+#[test]
+fn test_paragraph_alignment() {
+    assert_jira_eq(
+        common::parts::paragraph_alignment,
+        r###"left\-aligned
+
+right\-aligned
+
+center\-aligned
+
+both\-aligned
+"###,
+    );
+}
+
+#[test]
+fn test_ordered_list() {
+    assert_jira_eq(
+        common::parts::ordered_list,
+        r###"# one
+# two
+# three
+"###,
+    );
+}
+
+#[test]
+fn test_labeled_ordered_list() {
+    assert_jira_eq(
+        common::parts::labeled_ordered_list,
+        r###"{anchor:lst1}
+# {anchor:lst1-itm1}one
+# {anchor:lst1-itm2}two
+# {anchor:lst1-itm3}three
+"###,
+    );
+}
+
+#[test]
+fn test_unordered_list() {
+    assert_jira_eq(
+        common::parts::unordered_list,
+        r###"* one
+* two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_ordered_list() {
+    assert_jira_eq(
+        common::parts::nested_ordered_list,
+        r###"# one
+# two
+## inner one
+## inner two
+# three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_unordered_list() {
+    assert_jira_eq(
+        common::parts::nested_unordered_list,
+        r###"* one
+* two
+** inner one
+** inner two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_mixed_lists() {
+    assert_jira_eq(
+        common::parts::nested_mixed_lists,
+        r###"* one
+* two
+*# inner one
+*#* inner inner one
+*# inner two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_definition_list() {
+    assert_jira_eq(
+        common::parts::definition_list,
+        r###"*Universe*
+bq. Big, really big
+"###,
+    );
+}
+
+#[test]
+fn test_image_block() {
+    assert_jira_eq(
+        common::parts::image_block,
+        r###"!https://example.org/example.png!
+"###,
+    );
+}
+
+#[test]
+fn test_image_block_with_label_and_caption() {
+    assert_jira_eq(
+        common::parts::image_block_with_label_and_caption,
+        r###"{anchor:img:example}
+!https://example.org/example.png!
+_An Example Image_
+"###,
+    );
+}
+
+#[test]
+fn test_math_block() {
+    assert_jira_eq(
+        common::parts::math_block,
+        r###"{noformat}
+x=2+2^2
+{noformat}
+"###,
+    );
+}
+
+#[test]
+fn test_math_block_with_label_and_caption() {
+    assert_jira_eq(
+        common::parts::math_block_with_label_and_caption,
+        r###"{anchor:math:example}
+{noformat}
+x=2+2^2
+{noformat}
+_Example Math_
+"###,
+    );
+}
+
+#[test]
+fn test_block_quote() {
+    assert_jira_eq(
+        common::parts::block_quote,
+        r###"{quote}
+a block quote
+{quote}
+"###,
+    );
+}
+
+#[test]
+fn test_nested_block_quotes() {
+    assert_jira_eq(
+        common::parts::nested_block_quotes,
+        r###"{quote}
+a block quote
+
+another block quote
+{quote}
+"###,
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_jira_eq(
+        common::parts::text_styles,
+        r###"Here is some plain *bold* _italic_ {{mono}} {{code}} plain -strikethrough- +underline+ small caps ^superscript^ ~subscript~ text.
+"###,
+    );
+}
+
+#[test]
+fn test_nested_text_styles() {
+    assert_jira_eq(
+        common::parts::nested_text_styles,
+        r###"Here is some *_bold italic_* text.
+
+Here is some bold italic plain text.
+
+Here is some _bold plain italic_ text.
+"###,
+    );
+}
+
+#[test]
+fn test_hyper_links() {
+    assert_jira_eq(
+        common::parts::hyper_links,
+        r###"[https://example.org/]
+
+[example|https://example.org/]
+
+[#section-2]
+
+[example|#section-2]
+"###,
+    );
+}
+
+#[test]
+fn test_complex_paragraph() {
+    assert_jira_eq(
+        common::parts::complex_paragraph,
+        r###"This paragraph has [a link|https://example.org/], some math:&nbsp;{{x=2\+2\^2}}, a line break,\\
+an image:&nbsp;!https://example.org/favicon.png|alt=logo!&nbsp;---&nbsp; all together\!
+"###,
+    );
+}
+
+#[test]
+fn test_code_block_with_title() {
+    assert_jira_eq(
+        || {
+            let mut code = CodeBlock::with_language("fn main() {}", "rust");
+            let _ = code.set_caption(Caption::from("Example|a}b"));
+            Document::default().add_code_block(code).clone()
+        },
+        r###"{code:title=Exampleab|language=rust}
+fn main() {}
+{code}
+"###,
+    );
+}
+
+#[test]
+fn test_escapes_and_attachments() {
+    assert_jira_eq(
+        || {
+            Document::default()
+                .add_paragraph(Paragraph::plain_str(
+                    "*not bold* _not italic_ -not struck- +not underlined+ {not a macro} [not a link] C:\\path!",
+                ))
+                .add_paragraph(Paragraph::plain_str("h1. not a heading"))
+                .add_paragraph(Paragraph::plain_str("bq. not a quote"))
+                .add_paragraph(Paragraph::plain_str("# not a list, nor h2. or # here"))
+                .add_paragraph(Paragraph::image(Image::with_alt_text(
+                    "/tmp/report/chart.png",
+                    "Chart",
+                )))
+                .clone()
+        },
+        r###"\*not bold\* \_not italic\_ \-not struck\- \+not underlined\+ \{not a macro\} \[not a link\] C:&#92;path\!
+
+h1&#46; not a heading
+
+bq&#46; not a quote
+
+\# not a list, nor h2. or # here
+
+!chart.png|alt=Chart!
+"###,
+    );
+}
+
+#[test]
+fn test_no_blank_line_after_trailing_comment() {
+    assert_jira_eq(
+        || {
+            Document::default()
+                .add_paragraph(Paragraph::plain_str("Text."))
+                .add_comment_str("not written")
+                .clone()
+        },
+        r###"Text.
+"###,
+    );
+}
@@ -556,3 +556,18 @@ fn test_hyper_links() {
         None,
     );
 }
+
+#[test]
+fn test_complex_paragraph() {
+    assert_latex_eq(
+        common::parts::complex_paragraph,
+        r###"\begin{document}
+
+  \maketitle
+
+  This paragraph has \href{https://example.org/}{a link}, some math:~\(x=2+2^2\), a line break,\newline an image:~\includegraphics{https://example.org/favicon.png}~---~ all together!
+
+\end{document}"###,
+        None,
+    );
+}
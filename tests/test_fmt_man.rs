@@ -0,0 +1,455 @@
+use somedoc::model::block::{
+    Cell, Column, Formatted, HasBlockContent, Paragraph, Quote, Row, Table,
+};
+use somedoc::model::inline::HasInlineContent;
+use somedoc::model::Document;
+use somedoc::write::OutputFormat;
+
+pub mod common;
+
+#[inline]
+fn assert_man_eq(part_fn: impl Fn() -> Document, expected: &str) {
+    common::assert_serialized_eq(&part_fn(), OutputFormat::Man, expected)
+}
+
+#[test]
+fn test_skos() {
+    assert_man_eq(
+        common::skos::document,
+        r###"'\" t
+.\" author: Simon
+.TH "Scheme: Clothing shapes, patterns, and details" "1" "" ""
+.SH Scheme: Clothing shapes, patterns, and details
+.\" TODO:
+.\" - more nested lists
+.\" - tables
+.PP
+\fITerms commonly used to describe fashion items. It includes terms for outline, fit, elements, detailing, and patterns.\fR
+.PP
+http://amazon.com/vocabulary/fashion-design#DesignScheme
+.SS Labels
+.RS
+.PP
+\fBskos:prefLabel\fR
+.PP
+\fBskos:altLabel\fR
+.RE
+.PP
+\fIOther labels\fR
+.PP
+.TS
+tab(|);
+lB lB
+l l.
+Label text|Language
+_
+Clothing shapes, patterns, and details|\fBen\fR
+.TE
+.SS Other Properties
+.PP
+.ce
+* * *
+.PP
+Jump to: Concepts Hierarchy | Concepts | Collections | Appendix - RDF
+.PP
+.ce
+* * *
+.SS Concept Hierarchy
+.IP \(bu 2
+\fBFirst item\fR
+.IP \(bu 2
+Second item
+.RS
+.IP \(bu 2
+\fIThird item\fR
+.RE
+.IP \(bu 2
+First item
+.SS Appendix - RDF
+.RS 4
+.nf
+@prefix foo: <...>
+foo:bar foo:baz 12.
+.fi
+.RE
+.RS 4
+.nf
+@prefix foo: <...>
+foo:bar foo:baz 12.
+.fi
+.RE
+"###,
+    );
+}
+
+#[test]
+fn test_empty_document() {
+    assert_man_eq(common::parts::empty_document, r###""###);
+}
+
+#[test]
+fn test_document_with_title() {
+    assert_man_eq(
+        common::parts::document_with_title,
+        r###".TH "Test Document" "1" "" ""
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_heading() {
+    assert_man_eq(
+        common::parts::document_with_heading,
+        r###".SH Test Document
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_labeled_heading() {
+    assert_man_eq(
+        common::parts::document_with_labeled_heading,
+        r###".SH Test Document
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_headings() {
+    assert_man_eq(
+        common::parts::document_with_headings,
+        r###".SH Section
+.SS Sub-section
+.PP
+\fBSub-sub-section\fR
+.PP
+\fBSub-sub-sub-section\fR
+.PP
+\fBSub-sub-sub-sub-section\fR
+.PP
+\fBSub-sub-sub-sub-sub-section\fR
+.PP
+\fBSub-sub-sub-sub-sub-sub-section\fR
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_front_matter() {
+    assert_man_eq(
+        common::parts::document_with_front_matter,
+        r###".SH Section One
+.SH Section Two
+"###,
+    );
+}
+
+#[test]
+fn test_paragraph_alignment() {
+    assert_man_eq(
+        common::parts::paragraph_alignment,
+        r###".PP
+left-aligned
+.PP
+right-aligned
+.PP
+center-aligned
+.PP
+both-aligned
+"###,
+    );
+}
+
+#[test]
+fn test_ordered_list() {
+    assert_man_eq(
+        common::parts::ordered_list,
+        r###".IP "1." 4
+one
+.IP "2." 4
+two
+.IP "3." 4
+three
+"###,
+    );
+}
+
+#[test]
+fn test_labeled_ordered_list() {
+    assert_man_eq(
+        common::parts::labeled_ordered_list,
+        r###".IP "1." 4
+one
+.IP "2." 4
+two
+.IP "3." 4
+three
+"###,
+    );
+}
+
+#[test]
+fn test_unordered_list() {
+    assert_man_eq(
+        common::parts::unordered_list,
+        r###".IP \(bu 2
+one
+.IP \(bu 2
+two
+.IP \(bu 2
+three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_ordered_list() {
+    assert_man_eq(
+        common::parts::nested_ordered_list,
+        r###".IP "1." 4
+one
+.IP "2." 4
+two
+.RS
+.IP "1." 4
+inner one
+.IP "2." 4
+inner two
+.RE
+.IP "3." 4
+three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_unordered_list() {
+    assert_man_eq(
+        common::parts::nested_unordered_list,
+        r###".IP \(bu 2
+one
+.IP \(bu 2
+two
+.RS
+.IP \(bu 2
+inner one
+.IP \(bu 2
+inner two
+.RE
+.IP \(bu 2
+three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_mixed_lists() {
+    assert_man_eq(
+        common::parts::nested_mixed_lists,
+        r###".IP \(bu 2
+one
+.IP \(bu 2
+two
+.RS
+.IP "1." 4
+inner one
+.RS
+.IP \(bu 2
+inner inner one
+.RE
+.IP "2." 4
+inner two
+.RE
+.IP \(bu 2
+three
+"###,
+    );
+}
+
+#[test]
+fn test_definition_list() {
+    assert_man_eq(
+        common::parts::definition_list,
+        r###".TP
+\fBUniverse\fR
+Big, really big
+"###,
+    );
+}
+
+#[test]
+fn test_image_block() {
+    assert_man_eq(
+        common::parts::image_block,
+        r###".PP
+[image: https://example.org/example.png]
+"###,
+    );
+}
+
+#[test]
+fn test_image_block_with_label_and_caption() {
+    assert_man_eq(
+        common::parts::image_block_with_label_and_caption,
+        r###".PP
+\fIAn Example Image\fR
+.PP
+[image: https://example.org/example.png]
+"###,
+    );
+}
+
+#[test]
+fn test_math_block() {
+    assert_man_eq(
+        common::parts::math_block,
+        r###".RS 4
+.nf
+x=2+2^2
+.fi
+.RE
+"###,
+    );
+}
+
+#[test]
+fn test_math_block_with_label_and_caption() {
+    assert_man_eq(
+        common::parts::math_block_with_label_and_caption,
+        r###".PP
+\fIExample Math\fR
+.RS 4
+.nf
+x=2+2^2
+.fi
+.RE
+"###,
+    );
+}
+
+#[test]
+fn test_block_quote() {
+    assert_man_eq(
+        common::parts::block_quote,
+        r###".RS
+.PP
+a block quote
+.RE
+"###,
+    );
+}
+
+#[test]
+fn test_nested_block_quotes() {
+    assert_man_eq(
+        common::parts::nested_block_quotes,
+        r###".RS
+.PP
+a block quote
+.RS
+.PP
+another block quote
+.RE
+.RE
+"###,
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_man_eq(
+        common::parts::text_styles,
+        r###".PP
+Here is some plain \fBbold\fR \fIitalic\fR \fBmono\fR \fBcode\fR plain strikethrough \fIunderline\fR small caps superscript subscript text.
+"###,
+    );
+}
+
+#[test]
+fn test_nested_text_styles() {
+    assert_man_eq(
+        common::parts::nested_text_styles,
+        r###".PP
+Here is some \f(BIbold italic\fR text.
+.PP
+Here is some bold italic plain text.
+.PP
+Here is some \fIbold plain italic\fR text.
+"###,
+    );
+}
+
+#[test]
+fn test_hyper_links() {
+    assert_man_eq(
+        common::parts::hyper_links,
+        r###".PP
+https://example.org/
+.PP
+example <https://example.org/>
+.PP
+section-2
+.PP
+example
+"###,
+    );
+}
+
+#[test]
+fn test_complex_paragraph() {
+    assert_man_eq(
+        common::parts::complex_paragraph,
+        r###".PP
+This paragraph has a link <https://example.org/>, some math:\ x=2+2^2, a line break,
+.br
+an image:\ [image: logo]\ \(em\  all together!
+"###,
+    );
+}
+
+#[test]
+fn test_control_line_escapes() {
+    assert_man_eq(
+        || {
+            Document::default()
+                .add_paragraph(Paragraph::from(".hidden \\fB not bold"))
+                .add_formatted(Formatted::from("'quoted\n.also"))
+                .clone()
+        },
+        r###".PP
+\&.hidden \efB not bold
+.RS 4
+.nf
+\&'quoted
+\&.also
+.fi
+.RE
+"###,
+    );
+}
+
+#[test]
+fn test_table_requests_tbl_preprocessor() {
+    assert_man_eq(
+        || {
+            let mut table = Table::new(&[Column::from("Name")]);
+            table.add_row(Row::new(&[Cell::plain_str("a")]));
+            let mut quote = Quote::default();
+            let _ = quote.add_table(table);
+            Document::default().add_block_quote(quote).clone()
+        },
+        r###"'\" t
+.RS
+.PP
+.TS
+tab(|);
+lB
+l.
+Name
+_
+a
+.TE
+.RE
+"###,
+    );
+}
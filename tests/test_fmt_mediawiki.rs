@@ -0,0 +1,461 @@
+use somedoc::model::block::{
+    Alignment, Caption, Cell, CodeBlock, Column, HasBlockContent, HasCaption, HasLabel, ImageBlock,
+    Label, Paragraph, Row, Table,
+};
+use somedoc::model::inline::{HasInlineContent, Image};
+use somedoc::model::Document;
+use somedoc::write::OutputFormat;
+use std::str::FromStr;
+
+pub mod common;
+
+#[inline]
+fn assert_mediawiki_eq(part_fn: impl Fn() -> Document, expected: &str) {
+    common::assert_serialized_eq(&part_fn(), OutputFormat::MediaWiki, expected)
+}
+
+#[test]
+fn test_skos() {
+    assert_mediawiki_eq(
+        common::skos::document,
+        r###"== Scheme: Clothing shapes, patterns, and details ==
+
+<!-- TODO:
+- more nested lists
+- tables -->
+
+''Terms commonly used to describe fashion items. It includes terms for outline, fit, elements, detailing, and patterns.''
+
+http://amazon.com/vocabulary/fashion-design#DesignScheme
+
+=== <span id="Labels"></span>Labels ===
+
+<blockquote>
+'''skos:prefLabel'''
+
+'''skos:altLabel'''
+</blockquote>
+
+{| class="wikitable"
+|+ Other labels
+|-
+! Label text
+! Language
+|-
+| Clothing shapes, patterns, and details
+| '''en'''
+|}
+
+=== <span id="Other_Properties"></span>Other Properties ===
+
+----
+
+Jump to: [[#Concepts_Hierarchy|Concepts Hierarchy]] | [[#Concepts|Concepts]] | [[#Collections|Collections]] | [[#Appendix_-_RDF|Appendix - RDF]]
+
+----
+
+=== <span id="Concept_Hierarchy"></span>Concept Hierarchy ===
+
+* '''First item'''
+* Second item
+** ''Third item''
+* First item
+
+=== <span id="Appendix_-_RDF"></span>Appendix - RDF ===
+
+<syntaxhighlight lang="turtle">
+@prefix foo: <...>
+foo:bar foo:baz 12.
+</syntaxhighlight>
+
+<pre>@prefix foo: &lt;...&gt;
+foo:bar foo:baz 12.</pre>
+"###,
+    );
+}
+
+#[test]
+fn test_empty_document() {
+    assert_mediawiki_eq(common::parts::empty_document, r###""###);
+}
+
+#[test]
+fn test_document_with_title() {
+    assert_mediawiki_eq(common::parts::document_with_title, r###""###);
+}
+
+#[test]
+fn test_document_with_heading() {
+    assert_mediawiki_eq(
+        common::parts::document_with_heading,
+        r###"== Test Document ==
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_labeled_heading() {
+    assert_mediawiki_eq(
+        common::parts::document_with_labeled_heading,
+        r###"== <span id="Test_Document"></span>Test Document ==
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_headings() {
+    assert_mediawiki_eq(
+        common::parts::document_with_headings,
+        r###"== Section ==
+
+=== Sub-section ===
+
+==== Sub-sub-section ====
+
+===== Sub-sub-sub-section =====
+
+====== Sub-sub-sub-sub-section ======
+
+====== Sub-sub-sub-sub-sub-section ======
+
+====== Sub-sub-sub-sub-sub-sub-section ======
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_front_matter() {
+    assert_mediawiki_eq(
+        common::parts::document_with_front_matter,
+        r###"__TOC__
+
+== Section One ==
+
+== Section Two ==
+"###,
+    );
+}
+
+#[test]
+fn test_paragraph_alignment() {
+    assert_mediawiki_eq(
+        common::parts::paragraph_alignment,
+        r###"left-aligned
+
+<div style="text-align: right;">right-aligned</div>
+
+<div style="text-align: center;">center-aligned</div>
+
+<div style="text-align: justify;">both-aligned</div>
+"###,
+    );
+}
+
+#[test]
+fn test_ordered_list() {
+    assert_mediawiki_eq(
+        common::parts::ordered_list,
+        r###"# one
+# two
+# three
+"###,
+    );
+}
+
+#[test]
+fn test_labeled_ordered_list() {
+    assert_mediawiki_eq(
+        common::parts::labeled_ordered_list,
+        r###"<div id="lst1"></div>
+# <span id="lst1-itm1"></span>one
+# <span id="lst1-itm2"></span>two
+# <span id="lst1-itm3"></span>three
+"###,
+    );
+}
+
+#[test]
+fn test_unordered_list() {
+    assert_mediawiki_eq(
+        common::parts::unordered_list,
+        r###"* one
+* two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_ordered_list() {
+    assert_mediawiki_eq(
+        common::parts::nested_ordered_list,
+        r###"# one
+# two
+## inner one
+## inner two
+# three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_unordered_list() {
+    assert_mediawiki_eq(
+        common::parts::nested_unordered_list,
+        r###"* one
+* two
+** inner one
+** inner two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_mixed_lists() {
+    assert_mediawiki_eq(
+        common::parts::nested_mixed_lists,
+        r###"* one
+* two
+*# inner one
+*#* inner inner one
+*# inner two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_definition_list() {
+    assert_mediawiki_eq(
+        common::parts::definition_list,
+        r###"; Universe
+: Big, really big
+"###,
+    );
+}
+
+#[test]
+fn test_image_block() {
+    assert_mediawiki_eq(
+        common::parts::image_block,
+        r###"https://example.org/example.png
+"###,
+    );
+}
+
+#[test]
+fn test_image_block_with_label_and_caption() {
+    assert_mediawiki_eq(
+        common::parts::image_block_with_label_and_caption,
+        r###"<div id="img:example"></div>
+https://example.org/example.png
+<div style="text-align: center;">''An Example Image''</div>
+"###,
+    );
+}
+
+#[test]
+fn test_math_block() {
+    assert_mediawiki_eq(
+        common::parts::math_block,
+        r###"<math display="block">x=2+2^2</math>
+"###,
+    );
+}
+
+#[test]
+fn test_math_block_with_label_and_caption() {
+    assert_mediawiki_eq(
+        common::parts::math_block_with_label_and_caption,
+        r###"<div id="math:example"></div>
+<math display="block">x=2+2^2</math>
+<div style="text-align: center;">''Example Math''</div>
+"###,
+    );
+}
+
+#[test]
+fn test_block_quote() {
+    assert_mediawiki_eq(
+        common::parts::block_quote,
+        r###"<blockquote>
+a block quote
+</blockquote>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_block_quotes() {
+    assert_mediawiki_eq(
+        common::parts::nested_block_quotes,
+        r###"<blockquote>
+a block quote
+
+<blockquote>
+another block quote
+</blockquote>
+</blockquote>
+"###,
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_mediawiki_eq(
+        common::parts::text_styles,
+        r###"Here is some plain '''bold''' ''italic'' <code>mono</code> <code>code</code> plain <s>strikethrough</s> <u>underline</u> <span style="font-variant: small-caps;">small caps</span> <sup>superscript</sup> <sub>subscript</sub> text.
+"###,
+    );
+}
+
+#[test]
+fn test_nested_text_styles() {
+    assert_mediawiki_eq(
+        common::parts::nested_text_styles,
+        r###"Here is some '''''bold italic''''' text.
+
+Here is some bold italic plain text.
+
+Here is some ''bold plain italic'' text.
+"###,
+    );
+}
+
+#[test]
+fn test_hyper_links() {
+    assert_mediawiki_eq(
+        common::parts::hyper_links,
+        r###"https://example.org/
+
+[https://example.org/ example]
+
+[[#section-2]]
+
+[[#section-2|example]]
+"###,
+    );
+}
+
+#[test]
+fn test_complex_paragraph() {
+    assert_mediawiki_eq(
+        common::parts::complex_paragraph,
+        r###"This paragraph has [https://example.org/ a link], some math:&nbsp;<math>x=2+2^2</math>, a line break,<br />an image:&nbsp;https://example.org/favicon.png&nbsp;&mdash;&nbsp; all together!
+"###,
+    );
+}
+
+#[test]
+fn test_table_with_caption_label_and_alignment() {
+    assert_mediawiki_eq(
+        || {
+            let mut table = Table::new(&[
+                Column::from("Name"),
+                Column::from(("Count", Alignment::Right)),
+            ]);
+            table.add_row(Row::new(&[Cell::plain_str("a | b"), Cell::plain_str("2")]));
+            let _ = table
+                .set_caption(Caption::from("Totals"))
+                .set_label(Label::from_str("totals").unwrap());
+            Document::default().add_table(table).clone()
+        },
+        r###"{| class="wikitable" id="totals"
+|+ Totals
+|-
+! Name
+! style="text-align: right;" | Count
+|-
+| a &#124; b
+| style="text-align: right;" | 2
+|}
+"###,
+    );
+}
+
+#[test]
+fn test_code_block_and_escapes() {
+    assert_mediawiki_eq(
+        || {
+            let mut code = CodeBlock::from("x < 1");
+            let _ = code.set_caption(Caption::from("No language"));
+            Document::default()
+                .add_code_block(code)
+                .add_paragraph(Paragraph::plain_str(
+                    "''not italic'' [[not a link]] {{not a template}} & <b>",
+                ))
+                .clone()
+        },
+        r###"<syntaxhighlight lang="text">
+x < 1
+</syntaxhighlight>
+<div style="text-align: center;">''No language''</div>
+
+'&#39;not italic'&#39; &#91;&#91;not a link&#93;&#93; &#123;&#123;not a template&#125;&#125; &amp; &lt;b&gt;
+"###,
+    );
+}
+
+#[test]
+fn test_local_images_link_to_files() {
+    assert_mediawiki_eq(
+        || {
+            let mut image =
+                ImageBlock::from(Image::with_alt_text("images/diagram.png", "A diagram"));
+            let _ = image.set_caption(Caption::from("The diagram"));
+            Document::default()
+                .add_image(image)
+                .add_paragraph(Paragraph::image(Image::new("logo.png")))
+                .clone()
+        },
+        r###"[[File:diagram.png|thumb|alt=A diagram|The diagram]]
+
+[[File:logo.png]]
+"###,
+    );
+}
+
+#[test]
+fn test_external_images_are_bare_urls() {
+    assert_mediawiki_eq(
+        || {
+            Document::default()
+                .add_image(ImageBlock::from(Image::with_alt_text(
+                    "https://example.org/diagram.png",
+                    "A diagram",
+                )))
+                .clone()
+        },
+        r###"https://example.org/diagram.png
+"###,
+    );
+}
+
+#[test]
+fn test_escaped_line_starts() {
+    assert_mediawiki_eq(
+        || {
+            Document::default()
+                .add_paragraph(Paragraph::plain_str("* not a list"))
+                .add_paragraph(Paragraph::plain_str("# not a list"))
+                .add_paragraph(Paragraph::plain_str("; not a term"))
+                .add_paragraph(Paragraph::plain_str(": not indented"))
+                .add_paragraph(Paragraph::plain_str("= not a heading ="))
+                .add_paragraph(Paragraph::plain_str(" not preformatted, * or #"))
+                .clone()
+        },
+        r###"&#42; not a list
+
+&#35; not a list
+
+&#59; not a term
+
+&#58; not indented
+
+&#61; not a heading =
+
+&#32;not preformatted, * or #
+"###,
+    );
+}
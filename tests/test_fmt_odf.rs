@@ -0,0 +1,644 @@
+use pretty_assertions::assert_eq;
+use somedoc::model::block::{HasBlockContent, Paragraph};
+use somedoc::model::inline::HasInlineContent;
+use somedoc::model::Document;
+use somedoc::write::odf::OdfPackage;
+use somedoc::write::{write_document, write_document_to_string, OutputFormat};
+use std::io::{Cursor, Read};
+use zip::{CompressionMethod, ZipArchive};
+
+pub mod common;
+
+fn write_fodt(doc: &Document) -> String {
+    write_document_to_string(doc, OutputFormat::OpenDocument(OdfPackage::Flat)).unwrap()
+}
+
+#[inline]
+fn assert_odf_body_eq(part_fn: impl Fn() -> Document, expected: &str) {
+    let result = write_fodt(&part_fn());
+    let body = &result[result.find("  <office:body>").unwrap()..];
+    println!("{}", body);
+    assert_eq!(body, expected);
+}
+
+#[test]
+fn test_skos() {
+    assert_odf_body_eq(
+        common::skos::document,
+        r###"  <office:body>
+    <office:text>
+      <text:p text:style-name="Title">Scheme: Clothing shapes, patterns, and details</text:p>
+      <text:h text:style-name="Heading_20_1" text:outline-level="1">Scheme: Clothing shapes, patterns, and details</text:h>
+      <!-- TODO:
+- more nested lists
+- tables -->
+      <text:p text:style-name="Text_20_body"><text:span text:style-name="T1">Terms commonly used to describe fashion items. It includes terms for outline, fit, elements, detailing, and patterns.</text:span></text:p>
+      <text:p text:style-name="Text_20_body"><text:a xlink:type="simple" xlink:href="http://amazon.com/vocabulary/fashion-design#DesignScheme">http://amazon.com/vocabulary/fashion-design#DesignScheme</text:a></text:p>
+      <text:h text:style-name="Heading_20_2" text:outline-level="2"><text:bookmark text:name="Labels"/>Labels</text:h>
+      <text:p text:style-name="Quotations"><text:span text:style-name="T2">skos:prefLabel</text:span></text:p>
+      <text:p text:style-name="Quotations"><text:span text:style-name="T2">skos:altLabel</text:span></text:p>
+      <table:table table:name="Table1">
+        <table:table-column table:number-columns-repeated="2"/>
+        <table:table-header-rows>
+          <table:table-row>
+            <table:table-cell office:value-type="string">
+              <text:p text:style-name="Table_20_Heading">Label text</text:p>
+            </table:table-cell>
+            <table:table-cell office:value-type="string">
+              <text:p text:style-name="Table_20_Heading">Language</text:p>
+            </table:table-cell>
+          </table:table-row>
+        </table:table-header-rows>
+        <table:table-row>
+          <table:table-cell office:value-type="string">
+            <text:p text:style-name="Table_20_Contents">Clothing shapes, patterns, and details</text:p>
+          </table:table-cell>
+          <table:table-cell office:value-type="string">
+            <text:p text:style-name="Table_20_Contents"><text:span text:style-name="T2">en</text:span></text:p>
+          </table:table-cell>
+        </table:table-row>
+      </table:table>
+      <text:p text:style-name="Caption">Other labels</text:p>
+      <text:h text:style-name="Heading_20_2" text:outline-level="2"><text:bookmark text:name="Other_Properties"/>Other Properties</text:h>
+      <text:p text:style-name="Horizontal_20_Line"/>
+      <text:p text:style-name="Text_20_body">Jump to: <text:a xlink:type="simple" xlink:href="#Concepts_Hierarchy">Concepts Hierarchy</text:a> | <text:a xlink:type="simple" xlink:href="#Concepts">Concepts</text:a> | <text:a xlink:type="simple" xlink:href="#Collections">Collections</text:a> | <text:a xlink:type="simple" xlink:href="#Appendix_-_RDF">Appendix - RDF</text:a></text:p>
+      <text:p text:style-name="Horizontal_20_Line"/>
+      <text:h text:style-name="Heading_20_2" text:outline-level="2"><text:bookmark text:name="Concept_Hierarchy"/>Concept Hierarchy</text:h>
+      <text:list text:style-name="L1">
+        <text:list-item>
+          <text:p text:style-name="List"><text:span text:style-name="T2">First item</text:span></text:p>
+        </text:list-item>
+        <text:list-item>
+          <text:p text:style-name="List">Second item</text:p>
+          <text:list text:style-name="L1">
+            <text:list-item>
+              <text:p text:style-name="List"><text:span text:style-name="T1">Third item</text:span></text:p>
+            </text:list-item>
+          </text:list>
+        </text:list-item>
+        <text:list-item>
+          <text:p text:style-name="List">First item</text:p>
+        </text:list-item>
+      </text:list>
+      <text:h text:style-name="Heading_20_2" text:outline-level="2"><text:bookmark text:name="Appendix_-_RDF"/>Appendix - RDF</text:h>
+      <text:p text:style-name="Preformatted_20_Text">@prefix foo: &lt;...&gt;</text:p>
+      <text:p text:style-name="Preformatted_20_Text">foo:bar foo:baz 12.</text:p>
+      <text:p text:style-name="Preformatted_20_Text">@prefix foo: &lt;...&gt;</text:p>
+      <text:p text:style-name="Preformatted_20_Text">foo:bar foo:baz 12.</text:p>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_empty_document() {
+    assert_odf_body_eq(
+        common::parts::empty_document,
+        r###"  <office:body>
+    <office:text>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_title() {
+    assert_odf_body_eq(
+        common::parts::document_with_title,
+        r###"  <office:body>
+    <office:text>
+      <text:p text:style-name="Title">Test Document</text:p>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_heading() {
+    assert_odf_body_eq(
+        common::parts::document_with_heading,
+        r###"  <office:body>
+    <office:text>
+      <text:h text:style-name="Heading_20_1" text:outline-level="1">Test Document</text:h>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_labeled_heading() {
+    assert_odf_body_eq(
+        common::parts::document_with_labeled_heading,
+        r###"  <office:body>
+    <office:text>
+      <text:h text:style-name="Heading_20_1" text:outline-level="1"><text:bookmark text:name="Test_Document"/>Test Document</text:h>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_headings() {
+    assert_odf_body_eq(
+        common::parts::document_with_headings,
+        r###"  <office:body>
+    <office:text>
+      <text:h text:style-name="Heading_20_1" text:outline-level="1">Section</text:h>
+      <text:h text:style-name="Heading_20_2" text:outline-level="2">Sub-section</text:h>
+      <text:h text:style-name="Heading_20_3" text:outline-level="3">Sub-sub-section</text:h>
+      <text:h text:style-name="Heading_20_4" text:outline-level="4">Sub-sub-sub-section</text:h>
+      <text:h text:style-name="Heading_20_5" text:outline-level="5">Sub-sub-sub-sub-section</text:h>
+      <text:h text:style-name="Heading_20_6" text:outline-level="6">Sub-sub-sub-sub-sub-section</text:h>
+      <text:h text:style-name="Heading_20_7" text:outline-level="7">Sub-sub-sub-sub-sub-sub-section</text:h>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_front_matter() {
+    assert_odf_body_eq(
+        common::parts::document_with_front_matter,
+        r###"  <office:body>
+    <office:text>
+      <text:table-of-content text:name="Table of Contents">
+        <text:table-of-content-source text:outline-level="10"/>
+        <text:index-body/>
+      </text:table-of-content>
+      <text:h text:style-name="Heading_20_1" text:outline-level="1">Section One</text:h>
+      <text:h text:style-name="Heading_20_1" text:outline-level="1">Section Two</text:h>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_paragraph_alignment() {
+    assert_odf_body_eq(
+        common::parts::paragraph_alignment,
+        r###"  <office:body>
+    <office:text>
+      <text:p text:style-name="Text_20_body">left-aligned</text:p>
+      <text:p text:style-name="P1">right-aligned</text:p>
+      <text:p text:style-name="P2">center-aligned</text:p>
+      <text:p text:style-name="P3">both-aligned</text:p>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_ordered_list() {
+    assert_odf_body_eq(
+        common::parts::ordered_list,
+        r###"  <office:body>
+    <office:text>
+      <text:list text:style-name="L2">
+        <text:list-item>
+          <text:p text:style-name="List">one</text:p>
+        </text:list-item>
+        <text:list-item>
+          <text:p text:style-name="List">two</text:p>
+        </text:list-item>
+        <text:list-item>
+          <text:p text:style-name="List">three</text:p>
+        </text:list-item>
+      </text:list>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_labeled_ordered_list() {
+    assert_odf_body_eq(
+        common::parts::labeled_ordered_list,
+        r###"  <office:body>
+    <office:text>
+      <text:list text:style-name="L2">
+        <text:list-item>
+          <text:p text:style-name="List"><text:bookmark text:name="lst1"/><text:bookmark text:name="lst1-itm1"/>one</text:p>
+        </text:list-item>
+        <text:list-item>
+          <text:p text:style-name="List"><text:bookmark text:name="lst1-itm2"/>two</text:p>
+        </text:list-item>
+        <text:list-item>
+          <text:p text:style-name="List"><text:bookmark text:name="lst1-itm3"/>three</text:p>
+        </text:list-item>
+      </text:list>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_unordered_list() {
+    assert_odf_body_eq(
+        common::parts::unordered_list,
+        r###"  <office:body>
+    <office:text>
+      <text:list text:style-name="L1">
+        <text:list-item>
+          <text:p text:style-name="List">one</text:p>
+        </text:list-item>
+        <text:list-item>
+          <text:p text:style-name="List">two</text:p>
+        </text:list-item>
+        <text:list-item>
+          <text:p text:style-name="List">three</text:p>
+        </text:list-item>
+      </text:list>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_ordered_list() {
+    assert_odf_body_eq(
+        common::parts::nested_ordered_list,
+        r###"  <office:body>
+    <office:text>
+      <text:list text:style-name="L2">
+        <text:list-item>
+          <text:p text:style-name="List">one</text:p>
+        </text:list-item>
+        <text:list-item>
+          <text:p text:style-name="List">two</text:p>
+          <text:list text:style-name="L2">
+            <text:list-item>
+              <text:p text:style-name="List">inner one</text:p>
+            </text:list-item>
+            <text:list-item>
+              <text:p text:style-name="List">inner two</text:p>
+            </text:list-item>
+          </text:list>
+        </text:list-item>
+        <text:list-item>
+          <text:p text:style-name="List">three</text:p>
+        </text:list-item>
+      </text:list>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_unordered_list() {
+    assert_odf_body_eq(
+        common::parts::nested_unordered_list,
+        r###"  <office:body>
+    <office:text>
+      <text:list text:style-name="L1">
+        <text:list-item>
+          <text:p text:style-name="List">one</text:p>
+        </text:list-item>
+        <text:list-item>
+          <text:p text:style-name="List">two</text:p>
+          <text:list text:style-name="L1">
+            <text:list-item>
+              <text:p text:style-name="List">inner one</text:p>
+            </text:list-item>
+            <text:list-item>
+              <text:p text:style-name="List">inner two</text:p>
+            </text:list-item>
+          </text:list>
+        </text:list-item>
+        <text:list-item>
+          <text:p text:style-name="List">three</text:p>
+        </text:list-item>
+      </text:list>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_mixed_lists() {
+    assert_odf_body_eq(
+        common::parts::nested_mixed_lists,
+        r###"  <office:body>
+    <office:text>
+      <text:list text:style-name="L1">
+        <text:list-item>
+          <text:p text:style-name="List">one</text:p>
+        </text:list-item>
+        <text:list-item>
+          <text:p text:style-name="List">two</text:p>
+          <text:list text:style-name="L2">
+            <text:list-item>
+              <text:p text:style-name="List">inner one</text:p>
+              <text:list text:style-name="L1">
+                <text:list-item>
+                  <text:p text:style-name="List">inner inner one</text:p>
+                </text:list-item>
+              </text:list>
+            </text:list-item>
+            <text:list-item>
+              <text:p text:style-name="List">inner two</text:p>
+            </text:list-item>
+          </text:list>
+        </text:list-item>
+        <text:list-item>
+          <text:p text:style-name="List">three</text:p>
+        </text:list-item>
+      </text:list>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_definition_list() {
+    assert_odf_body_eq(
+        common::parts::definition_list,
+        r###"  <office:body>
+    <office:text>
+      <text:p text:style-name="List_20_Heading">Universe</text:p>
+      <text:p text:style-name="List_20_Contents">Big, really big</text:p>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_image_block() {
+    assert_odf_body_eq(
+        common::parts::image_block,
+        r###"  <office:body>
+    <office:text>
+      <text:p text:style-name="Standard"><draw:frame draw:name="Image1" text:anchor-type="as-char"><draw:image xlink:type="simple" xlink:href="https://example.org/example.png" xlink:show="embed" xlink:actuate="onLoad"/></draw:frame></text:p>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_image_block_with_label_and_caption() {
+    assert_odf_body_eq(
+        common::parts::image_block_with_label_and_caption,
+        r###"  <office:body>
+    <office:text>
+      <text:p text:style-name="Standard"><draw:frame draw:name="Image1" text:anchor-type="as-char"><draw:image xlink:type="simple" xlink:href="https://example.org/example.png" xlink:show="embed" xlink:actuate="onLoad"/></draw:frame></text:p>
+      <text:p text:style-name="Caption"><text:bookmark text:name="img:example"/>An Example Image</text:p>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_math_block() {
+    assert_odf_body_eq(
+        common::parts::math_block,
+        r###"  <office:body>
+    <office:text>
+      <text:p text:style-name="P1">x=2+2^2</text:p>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_math_block_with_label_and_caption() {
+    assert_odf_body_eq(
+        common::parts::math_block_with_label_and_caption,
+        r###"  <office:body>
+    <office:text>
+      <text:p text:style-name="P1">x=2+2^2</text:p>
+      <text:p text:style-name="Caption"><text:bookmark text:name="math:example"/>Example Math</text:p>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_block_quote() {
+    assert_odf_body_eq(
+        common::parts::block_quote,
+        r###"  <office:body>
+    <office:text>
+      <text:p text:style-name="Quotations">a block quote</text:p>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_block_quotes() {
+    assert_odf_body_eq(
+        common::parts::nested_block_quotes,
+        r###"  <office:body>
+    <office:text>
+      <text:p text:style-name="Quotations">a block quote</text:p>
+      <text:p text:style-name="Quotations">another block quote</text:p>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_odf_body_eq(
+        common::parts::text_styles,
+        r###"  <office:body>
+    <office:text>
+      <text:p text:style-name="Text_20_body">Here is some plain <text:span text:style-name="T1">bold</text:span> <text:span text:style-name="T2">italic</text:span> <text:span text:style-name="T3">mono</text:span> <text:span text:style-name="T3">code</text:span> plain <text:span text:style-name="T4">strikethrough</text:span> <text:span text:style-name="T5">underline</text:span> <text:span text:style-name="T6">small caps</text:span> <text:span text:style-name="T7">superscript</text:span> <text:span text:style-name="T8">subscript</text:span> text.</text:p>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_nested_text_styles() {
+    assert_odf_body_eq(
+        common::parts::nested_text_styles,
+        r###"  <office:body>
+    <office:text>
+      <text:p text:style-name="Text_20_body">Here is some <text:span text:style-name="T1">bold italic</text:span> text.</text:p>
+      <text:p text:style-name="Text_20_body">Here is some bold italic plain text.</text:p>
+      <text:p text:style-name="Text_20_body">Here is some <text:span text:style-name="T2">bold plain italic</text:span> text.</text:p>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_hyper_links() {
+    assert_odf_body_eq(
+        common::parts::hyper_links,
+        r###"  <office:body>
+    <office:text>
+      <text:p text:style-name="Text_20_body"><text:a xlink:type="simple" xlink:href="https://example.org/">https://example.org/</text:a></text:p>
+      <text:p text:style-name="Text_20_body"><text:a xlink:type="simple" xlink:href="https://example.org/">example</text:a></text:p>
+      <text:p text:style-name="Text_20_body"><text:a xlink:type="simple" xlink:href="#section-2">section-2</text:a></text:p>
+      <text:p text:style-name="Text_20_body"><text:a xlink:type="simple" xlink:href="#section-2">example</text:a></text:p>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_complex_paragraph() {
+    assert_odf_body_eq(
+        common::parts::complex_paragraph,
+        r###"  <office:body>
+    <office:text>
+      <text:p text:style-name="Text_20_body">This paragraph has <text:a xlink:type="simple" xlink:href="https://example.org/">a link</text:a>, some math:&#160;<text:span text:style-name="Source_20_Text">x=2+2^2</text:span>, a line break,<text:line-break/>an image:&#160;<draw:frame draw:name="Image1" text:anchor-type="as-char"><draw:image xlink:type="simple" xlink:href="https://example.org/favicon.png" xlink:show="embed" xlink:actuate="onLoad"/><svg:title>logo</svg:title></draw:frame>&#160;&#8212;&#160; all together!</text:p>
+    </office:text>
+  </office:body>
+</office:document>
+"###,
+    );
+}
+
+#[test]
+fn test_metadata_and_styles() {
+    let mut doc = Document::default();
+    let _ = doc
+        .set_title("Fish & Chips")
+        .add_author_str("Simon", None, None)
+        .add_keywords_str(&["fish", "chips"])
+        .add_metadata_property_str("status", "draft")
+        .add_abstract(Paragraph::plain_str("All about <chips>."))
+        .add_paragraph(Paragraph::plain_str("Some text."));
+    let result = write_fodt(&doc);
+
+    assert!(result.starts_with(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0""#
+    ));
+    assert!(result.contains(
+        r#"  <office:meta>
+    <dc:description>All about &lt;chips&gt;.</dc:description>
+    <dc:title>Fish &amp; Chips</dc:title>
+    <meta:initial-creator>Simon</meta:initial-creator>
+    <meta:keyword>fish</meta:keyword>
+    <meta:keyword>chips</meta:keyword>
+    <meta:user-defined meta:name="status">draft</meta:user-defined>
+  </office:meta>
+"#
+    ));
+    assert!(
+        result.contains(r#"<style:style style:name="Heading_20_1" style:display-name="Heading 1""#)
+    );
+    assert!(result.contains(
+        r#"    <office:text>
+      <text:p text:style-name="Title">Fish &amp; Chips</text:p>
+      <text:p text:style-name="Abstract">All about &lt;chips&gt;.</text:p>
+      <text:p text:style-name="Text_20_body">Some text.</text:p>
+    </office:text>
+"#
+    ));
+}
+
+#[test]
+fn test_automatic_text_styles() {
+    let result = write_fodt(&common::parts::nested_text_styles());
+    assert!(result.contains(
+        r#"  <office:automatic-styles>
+    <style:style style:name="T1" style:family="text">
+      <style:text-properties fo:font-weight="bold" fo:font-style="italic"/>
+    </style:style>
+    <style:style style:name="T2" style:family="text">
+      <style:text-properties fo:font-style="italic"/>
+    </style:style>
+  </office:automatic-styles>
+"#
+    ));
+}
+
+#[test]
+fn test_zipped_package() {
+    let mut buffer = Cursor::new(Vec::new());
+    write_document(
+        &common::parts::paragraph_alignment(),
+        OutputFormat::OpenDocument(OdfPackage::Zipped),
+        &mut buffer,
+    )
+    .unwrap();
+    let mut archive = ZipArchive::new(Cursor::new(buffer.into_inner())).unwrap();
+
+    let mimetype = archive.by_index(0).unwrap();
+    assert_eq!(mimetype.name(), "mimetype");
+    assert_eq!(mimetype.compression(), CompressionMethod::Stored);
+    drop(mimetype);
+
+    let mut read_file = |name: &str| {
+        let mut content = String::new();
+        let _ = archive
+            .by_name(name)
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        content
+    };
+    assert_eq!(
+        read_file("mimetype"),
+        "application/vnd.oasis.opendocument.text"
+    );
+    assert!(read_file("META-INF/manifest.xml").contains(
+        r#"<manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/>"#
+    ));
+    assert!(read_file("styles.xml").contains("<office:document-styles "));
+    assert!(read_file("meta.xml").contains("<office:document-meta "));
+    let content = read_file("content.xml");
+    assert!(content.contains("<office:document-content "));
+    assert!(content.contains(r#"<text:p text:style-name="P2">center-aligned</text:p>"#));
+}
+
+#[test]
+fn test_write_zipped_to_string_is_an_error() {
+    assert!(write_document_to_string(
+        &common::parts::document_with_title(),
+        OutputFormat::OpenDocument(OdfPackage::Zipped)
+    )
+    .is_err());
+}
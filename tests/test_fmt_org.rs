@@ -0,0 +1,415 @@
+use somedoc::model::block::{Cell, Column, HasBlockContent, Paragraph, Row, Table};
+use somedoc::model::inline::{HasInlineContent, Span, SpanStyle};
+use somedoc::model::Document;
+use somedoc::write::OutputFormat;
+
+pub mod common;
+
+#[inline]
+fn assert_org_eq(part_fn: impl Fn() -> Document, expected: &str) {
+    common::assert_serialized_eq(&part_fn(), OutputFormat::Org, expected)
+}
+
+#[test]
+fn test_skos() {
+    assert_org_eq(
+        common::skos::document,
+        r###"#+TITLE: Scheme: Clothing shapes, patterns, and details
+#+AUTHOR: Simon
+
+* Scheme: Clothing shapes, patterns, and details
+
+# TODO:
+# - more nested lists
+# - tables
+
+/Terms commonly used to describe fashion items. It includes terms for outline, fit, elements, detailing, and patterns./
+
+[[http://amazon.com/vocabulary/fashion-design#DesignScheme]]
+
+** Labels
+:PROPERTIES:
+:CUSTOM_ID: Labels
+:END:
+
+#+BEGIN_QUOTE
+*skos:prefLabel*
+
+*skos:altLabel*
+#+END_QUOTE
+
+#+CAPTION: Other labels
+| Label text | Language |
+|---+---|
+| Clothing shapes, patterns, and details | *en* |
+
+** Other Properties
+:PROPERTIES:
+:CUSTOM_ID: Other_Properties
+:END:
+
+-----
+
+Jump to: [[Concepts_Hierarchy][Concepts Hierarchy]] | [[Concepts][Concepts]] | [[Collections][Collections]] | [[#Appendix_-_RDF][Appendix - RDF]]
+
+-----
+
+** Concept Hierarchy
+:PROPERTIES:
+:CUSTOM_ID: Concept_Hierarchy
+:END:
+
+- *First item*
+- Second item
+  - /Third item/
+- First item
+
+** Appendix - RDF
+:PROPERTIES:
+:CUSTOM_ID: Appendix_-_RDF
+:END:
+
+#+BEGIN_SRC turtle
+@prefix foo: <...>
+foo:bar foo:baz 12.
+#+END_SRC
+
+#+BEGIN_EXAMPLE
+@prefix foo: <...>
+foo:bar foo:baz 12.
+#+END_EXAMPLE
+"###,
+    );
+}
+
+#[test]
+fn test_empty_document() {
+    assert_org_eq(common::parts::empty_document, r###""###);
+}
+
+#[test]
+fn test_document_with_title() {
+    assert_org_eq(
+        common::parts::document_with_title,
+        r###"#+TITLE: Test Document
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_heading() {
+    assert_org_eq(
+        common::parts::document_with_heading,
+        r###"* Test Document
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_labeled_heading() {
+    assert_org_eq(
+        common::parts::document_with_labeled_heading,
+        r###"* Test Document
+:PROPERTIES:
+:CUSTOM_ID: Test_Document
+:END:
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_headings() {
+    assert_org_eq(
+        common::parts::document_with_headings,
+        r###"* Section
+
+** Sub-section
+
+*** Sub-sub-section
+
+**** Sub-sub-sub-section
+
+***** Sub-sub-sub-sub-section
+
+****** Sub-sub-sub-sub-sub-section
+
+******* Sub-sub-sub-sub-sub-sub-section
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_front_matter() {
+    assert_org_eq(
+        common::parts::document_with_front_matter,
+        r###"#+TOC: headlines
+
+#+TOC: tables
+
+#+TOC: listings
+
+* Section One
+
+* Section Two
+"###,
+    );
+}
+
+#[test]
+fn test_paragraph_alignment() {
+    assert_org_eq(
+        common::parts::paragraph_alignment,
+        r###"left-aligned
+
+right-aligned
+
+#+BEGIN_CENTER
+center-aligned
+#+END_CENTER
+
+both-aligned
+"###,
+    );
+}
+
+#[test]
+fn test_ordered_list() {
+    assert_org_eq(
+        common::parts::ordered_list,
+        r###"1. one
+1. two
+1. three
+"###,
+    );
+}
+
+#[test]
+fn test_labeled_ordered_list() {
+    assert_org_eq(
+        common::parts::labeled_ordered_list,
+        r###"#+NAME: lst1
+1. <<lst1-itm1>>one
+1. <<lst1-itm2>>two
+1. <<lst1-itm3>>three
+"###,
+    );
+}
+
+#[test]
+fn test_unordered_list() {
+    assert_org_eq(
+        common::parts::unordered_list,
+        r###"- one
+- two
+- three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_ordered_list() {
+    assert_org_eq(
+        common::parts::nested_ordered_list,
+        r###"1. one
+1. two
+   1. inner one
+   1. inner two
+1. three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_unordered_list() {
+    assert_org_eq(
+        common::parts::nested_unordered_list,
+        r###"- one
+- two
+  - inner one
+  - inner two
+- three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_mixed_lists() {
+    assert_org_eq(
+        common::parts::nested_mixed_lists,
+        r###"- one
+- two
+  1. inner one
+     - inner inner one
+  1. inner two
+- three
+"###,
+    );
+}
+
+#[test]
+fn test_definition_list() {
+    assert_org_eq(
+        common::parts::definition_list,
+        r###"- Universe :: Big, really big
+"###,
+    );
+}
+
+#[test]
+fn test_image_block() {
+    assert_org_eq(
+        common::parts::image_block,
+        r###"[[https://example.org/example.png]]
+"###,
+    );
+}
+
+#[test]
+fn test_image_block_with_label_and_caption() {
+    assert_org_eq(
+        common::parts::image_block_with_label_and_caption,
+        r###"#+CAPTION: An Example Image
+#+NAME: img:example
+[[https://example.org/example.png]]
+"###,
+    );
+}
+
+#[test]
+fn test_math_block() {
+    assert_org_eq(
+        common::parts::math_block,
+        r###"\begin{equation}
+x=2+2^2
+\end{equation}
+"###,
+    );
+}
+
+#[test]
+fn test_math_block_with_label_and_caption() {
+    assert_org_eq(
+        common::parts::math_block_with_label_and_caption,
+        r###"#+CAPTION: Example Math
+#+NAME: math:example
+\begin{equation}
+x=2+2^2
+\end{equation}
+"###,
+    );
+}
+
+#[test]
+fn test_block_quote() {
+    assert_org_eq(
+        common::parts::block_quote,
+        r###"#+BEGIN_QUOTE
+a block quote
+#+END_QUOTE
+"###,
+    );
+}
+
+#[test]
+fn test_nested_block_quotes() {
+    assert_org_eq(
+        common::parts::nested_block_quotes,
+        r###"#+BEGIN_QUOTE
+a block quote
+
+#+BEGIN_QUOTE
+another block quote
+#+END_QUOTE
+#+END_QUOTE
+"###,
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_org_eq(
+        common::parts::text_styles,
+        r###"Here is some plain *bold* /italic/ =mono= ~code~ plain +strikethrough+ _underline_ small caps ^{superscript} _{subscript} text.
+"###,
+    );
+}
+
+#[test]
+fn test_nested_text_styles() {
+    assert_org_eq(
+        common::parts::nested_text_styles,
+        r###"Here is some */bold italic/* text.
+
+Here is some bold italic plain text.
+
+Here is some /bold plain italic/ text.
+"###,
+    );
+}
+
+#[test]
+fn test_hyper_links() {
+    assert_org_eq(
+        common::parts::hyper_links,
+        r###"[[https://example.org/]]
+
+[[https://example.org/][example]]
+
+[[section-2]]
+
+[[section-2][example]]
+"###,
+    );
+}
+
+#[test]
+fn test_complex_paragraph() {
+    assert_org_eq(
+        common::parts::complex_paragraph,
+        r###"This paragraph has [[https://example.org/][a link]], some math:\nbsp{}\(x=2+2^2\), a line break,\\
+an image:\nbsp{}[[https://example.org/favicon.png]]\nbsp{}---\nbsp{} all together!
+"###,
+    );
+}
+
+#[test]
+fn test_escaped_text_and_cells() {
+    assert_org_eq(
+        || {
+            let mut table = Table::new(&[Column::from("a | b"), Column::from("c")]);
+            table.add_row(Row::new(&[
+                Cell::plain_str("x | y"),
+                Cell::plain_str("*z*"),
+            ]));
+            Document::default()
+                .add_paragraph(Paragraph::plain_str("* not a heading"))
+                .add_paragraph(Paragraph::plain_str("#+not_a_keyword"))
+                .add_paragraph(Paragraph::plain_str(
+                    "*not bold* /not italic/ =not mono= and/or a*b",
+                ))
+                .add_paragraph(
+                    Paragraph::default()
+                        .add_span(Span::with_style("bold", SpanStyle::Bold))
+                        .add_span(Span::with_style("italic", SpanStyle::Italic))
+                        .add_text_str("s, and ")
+                        .add_span(Span::with_style("mono", SpanStyle::Mono))
+                        .add_text_str(".")
+                        .clone(),
+                )
+                .add_table(table)
+                .clone()
+        },
+        "\u{200B}* not a heading
+
+\u{200B}#+not_a_keyword
+
+\u{200B}*not bold* \u{200B}/not italic/ \u{200B}=not mono= and/or a*b
+
+*bold*\u{200B}/italic/\u{200B}s, and =mono=.
+
+| a \\vert{} b | c |
+|---+---|
+| x \\vert{} y | \u{200B}*z* |
+",
+    );
+}
@@ -0,0 +1,135 @@
+use somedoc::model::Document;
+use somedoc::write::OutputFormat;
+
+pub mod common;
+
+#[inline]
+fn assert_pandoc_eq(part_fn: impl Fn() -> Document, meta: &str, blocks: &str) {
+    let expected = format!(
+        "{{\"blocks\":[{}],\"meta\":{{{}}},\"pandoc-api-version\":[1,23,1]}}",
+        blocks, meta
+    );
+    common::assert_serialized_eq(&part_fn(), OutputFormat::PandocJson, &expected)
+}
+
+#[test]
+fn test_empty_document() {
+    assert_pandoc_eq(common::parts::empty_document, "", "");
+}
+
+#[test]
+fn test_document_with_title() {
+    assert_pandoc_eq(
+        common::parts::document_with_title,
+        r###""title":{"c":[{"c":"Test","t":"Str"},{"t":"Space"},{"c":"Document","t":"Str"}],"t":"MetaInlines"}"###,
+        "",
+    );
+}
+
+#[test]
+fn test_document_with_heading() {
+    assert_pandoc_eq(
+        common::parts::document_with_heading,
+        "",
+        r###"{"c":[1,["",[],[]],[{"c":"Test","t":"Str"},{"t":"Space"},{"c":"Document","t":"Str"}]],"t":"Header"}"###,
+    );
+}
+
+#[test]
+fn test_document_with_labeled_heading() {
+    assert_pandoc_eq(
+        common::parts::document_with_labeled_heading,
+        "",
+        r###"{"c":[1,["Test_Document",[],[]],[{"c":"Test","t":"Str"},{"t":"Space"},{"c":"Document","t":"Str"}]],"t":"Header"}"###,
+    );
+}
+
+#[test]
+fn test_document_with_front_matter() {
+    assert_pandoc_eq(
+        common::parts::document_with_front_matter,
+        "",
+        r###"{"c":["latex","\\tableofcontents"],"t":"RawBlock"},{"c":["latex","\\listoffigures"],"t":"RawBlock"},{"c":["latex","\\listoftables"],"t":"RawBlock"},{"c":["latex","\\listofequfloats"],"t":"RawBlock"},{"c":["latex","\\lstlistoflistings"],"t":"RawBlock"},{"c":[1,["",[],[]],[{"c":"Section","t":"Str"},{"t":"Space"},{"c":"One","t":"Str"}]],"t":"Header"},{"c":[1,["",[],[]],[{"c":"Section","t":"Str"},{"t":"Space"},{"c":"Two","t":"Str"}]],"t":"Header"}"###,
+    );
+}
+
+#[test]
+fn test_nested_mixed_lists() {
+    assert_pandoc_eq(
+        common::parts::nested_mixed_lists,
+        "",
+        r###"{"c":[[{"c":[{"c":"one","t":"Str"}],"t":"Plain"}],[{"c":[{"c":"two","t":"Str"}],"t":"Plain"},{"c":[[1,{"t":"Decimal"},{"t":"Period"}],[[{"c":[{"c":"inner","t":"Str"},{"t":"Space"},{"c":"one","t":"Str"}],"t":"Plain"},{"c":[[{"c":[{"c":"inner","t":"Str"},{"t":"Space"},{"c":"inner","t":"Str"},{"t":"Space"},{"c":"one","t":"Str"}],"t":"Plain"}]],"t":"BulletList"}],[{"c":[{"c":"inner","t":"Str"},{"t":"Space"},{"c":"two","t":"Str"}],"t":"Plain"}]]],"t":"OrderedList"}],[{"c":[{"c":"three","t":"Str"}],"t":"Plain"}]],"t":"BulletList"}"###,
+    );
+}
+
+#[test]
+fn test_labeled_ordered_list() {
+    assert_pandoc_eq(
+        common::parts::labeled_ordered_list,
+        "",
+        r###"{"c":[["lst1",[],[]],[{"c":[[1,{"t":"Decimal"},{"t":"Period"}],[[{"c":[{"c":[["lst1-itm1",[],[]],[{"c":"one","t":"Str"}]],"t":"Span"}],"t":"Plain"}],[{"c":[{"c":[["lst1-itm2",[],[]],[{"c":"two","t":"Str"}]],"t":"Span"}],"t":"Plain"}],[{"c":[{"c":[["lst1-itm3",[],[]],[{"c":"three","t":"Str"}]],"t":"Span"}],"t":"Plain"}]]],"t":"OrderedList"}]],"t":"Div"}"###,
+    );
+}
+
+#[test]
+fn test_definition_list() {
+    assert_pandoc_eq(
+        common::parts::definition_list,
+        "",
+        r###"{"c":[[[{"c":"Universe","t":"Str"}],[[{"c":[{"c":"Big,","t":"Str"},{"t":"Space"},{"c":"really","t":"Str"},{"t":"Space"},{"c":"big","t":"Str"}],"t":"Plain"}]]]],"t":"DefinitionList"}"###,
+    );
+}
+
+#[test]
+fn test_image_block_with_label_and_caption() {
+    assert_pandoc_eq(
+        common::parts::image_block_with_label_and_caption,
+        "",
+        r###"{"c":[["img:example",[],[]],[null,[{"c":[{"c":"An","t":"Str"},{"t":"Space"},{"c":"Example","t":"Str"},{"t":"Space"},{"c":"Image","t":"Str"}],"t":"Plain"}]],[{"c":[{"c":[["",[],[]],[],["https://example.org/example.png",""]],"t":"Image"}],"t":"Plain"}]],"t":"Figure"}"###,
+    );
+}
+
+#[test]
+fn test_math_block() {
+    assert_pandoc_eq(
+        common::parts::math_block,
+        "",
+        r###"{"c":[{"c":[{"t":"DisplayMath"},"x=2+2^2"],"t":"Math"}],"t":"Para"}"###,
+    );
+}
+
+#[test]
+fn test_math_block_with_label_and_caption() {
+    assert_pandoc_eq(
+        common::parts::math_block_with_label_and_caption,
+        "",
+        r###"{"c":[["math:example",[],[]],[null,[{"c":[{"c":"Example","t":"Str"},{"t":"Space"},{"c":"Math","t":"Str"}],"t":"Plain"}]],[{"c":[{"c":[{"t":"DisplayMath"},"x=2+2^2"],"t":"Math"}],"t":"Para"}]],"t":"Figure"}"###,
+    );
+}
+
+#[test]
+fn test_nested_block_quotes() {
+    assert_pandoc_eq(
+        common::parts::nested_block_quotes,
+        "",
+        r###"{"c":[{"c":[{"c":"a","t":"Str"},{"t":"Space"},{"c":"block","t":"Str"},{"t":"Space"},{"c":"quote","t":"Str"}],"t":"Para"},{"c":[{"c":[{"c":"another","t":"Str"},{"t":"Space"},{"c":"block","t":"Str"},{"t":"Space"},{"c":"quote","t":"Str"}],"t":"Para"}],"t":"BlockQuote"}],"t":"BlockQuote"}"###,
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_pandoc_eq(
+        common::parts::text_styles,
+        "",
+        r###"{"c":[{"c":"Here","t":"Str"},{"t":"Space"},{"c":"is","t":"Str"},{"t":"Space"},{"c":"some","t":"Str"},{"t":"Space"},{"c":"plain","t":"Str"},{"t":"Space"},{"c":[{"c":"bold","t":"Str"}],"t":"Strong"},{"t":"Space"},{"c":[{"c":"italic","t":"Str"}],"t":"Emph"},{"t":"Space"},{"c":[["",[],[]],"mono"],"t":"Code"},{"t":"Space"},{"c":[["",[],[]],"code"],"t":"Code"},{"t":"Space"},{"c":"plain","t":"Str"},{"t":"Space"},{"c":[{"c":"strikethrough","t":"Str"}],"t":"Strikeout"},{"t":"Space"},{"c":[{"c":"underline","t":"Str"}],"t":"Underline"},{"t":"Space"},{"c":[{"c":"small","t":"Str"},{"t":"Space"},{"c":"caps","t":"Str"}],"t":"SmallCaps"},{"t":"Space"},{"c":[{"c":"superscript","t":"Str"}],"t":"Superscript"},{"t":"Space"},{"c":[{"c":"subscript","t":"Str"}],"t":"Subscript"},{"t":"Space"},{"c":"text.","t":"Str"}],"t":"Para"}"###,
+    );
+}
+
+#[test]
+fn test_hyper_links() {
+    assert_pandoc_eq(
+        common::parts::hyper_links,
+        "",
+        r###"{"c":[{"c":[["",["uri"],[]],[{"c":"https://example.org/","t":"Str"}],["https://example.org/",""]],"t":"Link"}],"t":"Para"},{"c":[{"c":[["",[],[]],[{"c":"example","t":"Str"}],["https://example.org/",""]],"t":"Link"}],"t":"Para"},{"c":[{"c":[["",[],[]],[{"c":"section-2","t":"Str"}],["#section-2",""]],"t":"Link"}],"t":"Para"},{"c":[{"c":[["",[],[]],[{"c":"example","t":"Str"}],["#section-2",""]],"t":"Link"}],"t":"Para"}"###,
+    );
+}
@@ -0,0 +1,437 @@
+use somedoc::model::block::{HasBlockContent, Paragraph};
+use somedoc::model::inline::{HasInlineContent, Math, Span, SpanStyle};
+use somedoc::model::Document;
+use somedoc::write::OutputFormat;
+use std::str::FromStr;
+
+pub mod common;
+
+#[inline]
+fn assert_rst_eq(part_fn: impl Fn() -> Document, expected: &str) {
+    common::assert_serialized_eq(&part_fn(), OutputFormat::ReStructuredText, expected)
+}
+
+#[test]
+fn test_skos() {
+    assert_rst_eq(
+        common::skos::document,
+        r###"##############################################
+Scheme: Clothing shapes, patterns, and details
+##############################################
+
+:Author: Simon
+
+Scheme: Clothing shapes, patterns, and details
+==============================================
+
+.. TODO:
+   - more nested lists
+   - tables
+
+*Terms commonly used to describe fashion items. It includes terms for outline, fit, elements, detailing, and patterns.*
+
+http://amazon.com/vocabulary/fashion-design#DesignScheme
+
+.. _Labels:
+
+Labels
+------
+
+   **skos:prefLabel**
+
+   **skos:altLabel**
+
+.. list-table:: Other labels
+   :header-rows: 1
+
+   * - Label text
+     - Language
+   * - Clothing shapes, patterns, and details
+     - **en**
+
+.. _Other_Properties:
+
+Other Properties
+----------------
+
+----
+
+Jump to: :ref:`Concepts Hierarchy <Concepts_Hierarchy>` \| :ref:`Concepts <Concepts>` \| :ref:`Collections <Collections>` \| :ref:`Appendix - RDF <Appendix_-_RDF>`
+
+----
+
+.. _Concept_Hierarchy:
+
+Concept Hierarchy
+-----------------
+
+* **First item**
+* Second item
+
+  * *Third item*
+
+* First item
+
+.. _Appendix_-_RDF:
+
+Appendix - RDF
+--------------
+
+.. code-block:: turtle
+
+   @prefix foo: <...>
+   foo:bar foo:baz 12.
+
+::
+
+   @prefix foo: <...>
+   foo:bar foo:baz 12.
+"###,
+    );
+}
+
+#[test]
+fn test_empty_document() {
+    assert_rst_eq(common::parts::empty_document, r###""###);
+}
+
+#[test]
+fn test_document_with_title() {
+    assert_rst_eq(
+        common::parts::document_with_title,
+        r###"#############
+Test Document
+#############
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_heading() {
+    assert_rst_eq(
+        common::parts::document_with_heading,
+        r###"Test Document
+=============
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_labeled_heading() {
+    assert_rst_eq(
+        common::parts::document_with_labeled_heading,
+        r###".. _Test_Document:
+
+Test Document
+=============
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_headings() {
+    assert_rst_eq(
+        common::parts::document_with_headings,
+        r###"Section
+=======
+
+Sub-section
+-----------
+
+Sub-sub-section
+^^^^^^^^^^^^^^^
+
+Sub-sub-sub-section
+"""""""""""""""""""
+
+Sub-sub-sub-sub-section
+'''''''''''''''''''''''
+
+Sub-sub-sub-sub-sub-section
+~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+Sub-sub-sub-sub-sub-sub-section
++++++++++++++++++++++++++++++++
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_front_matter() {
+    assert_rst_eq(
+        common::parts::document_with_front_matter,
+        r###".. contents::
+
+Section One
+===========
+
+Section Two
+===========
+"###,
+    );
+}
+
+#[test]
+fn test_paragraph_alignment() {
+    assert_rst_eq(
+        common::parts::paragraph_alignment,
+        r###"left-aligned
+
+.. class:: align-right
+
+right-aligned
+
+.. class:: align-center
+
+center-aligned
+
+both-aligned
+"###,
+    );
+}
+
+#[test]
+fn test_ordered_list() {
+    assert_rst_eq(
+        common::parts::ordered_list,
+        r###"#. one
+#. two
+#. three
+"###,
+    );
+}
+
+#[test]
+fn test_labeled_ordered_list() {
+    assert_rst_eq(
+        common::parts::labeled_ordered_list,
+        r###".. _lst1:
+
+#. one
+#. two
+#. three
+"###,
+    );
+}
+
+#[test]
+fn test_unordered_list() {
+    assert_rst_eq(
+        common::parts::unordered_list,
+        r###"* one
+* two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_ordered_list() {
+    assert_rst_eq(
+        common::parts::nested_ordered_list,
+        r###"#. one
+#. two
+
+   #. inner one
+   #. inner two
+
+#. three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_unordered_list() {
+    assert_rst_eq(
+        common::parts::nested_unordered_list,
+        r###"* one
+* two
+
+  * inner one
+  * inner two
+
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_mixed_lists() {
+    assert_rst_eq(
+        common::parts::nested_mixed_lists,
+        r###"* one
+* two
+
+  #. inner one
+
+     * inner inner one
+
+  #. inner two
+
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_definition_list() {
+    assert_rst_eq(
+        common::parts::definition_list,
+        r###"Universe
+   Big, really big
+"###,
+    );
+}
+
+#[test]
+fn test_image_block() {
+    assert_rst_eq(
+        common::parts::image_block,
+        r###".. image:: https://example.org/example.png
+"###,
+    );
+}
+
+#[test]
+fn test_image_block_with_label_and_caption() {
+    assert_rst_eq(
+        common::parts::image_block_with_label_and_caption,
+        r###".. _`img:example`:
+
+.. figure:: https://example.org/example.png
+
+   An Example Image
+"###,
+    );
+}
+
+#[test]
+fn test_math_block() {
+    assert_rst_eq(
+        common::parts::math_block,
+        r###".. math::
+
+   x=2+2^2
+"###,
+    );
+}
+
+#[test]
+fn test_math_block_with_label_and_caption() {
+    assert_rst_eq(
+        common::parts::math_block_with_label_and_caption,
+        r###".. _`math:example`:
+
+.. math::
+
+   x=2+2^2
+"###,
+    );
+}
+
+#[test]
+fn test_block_quote() {
+    assert_rst_eq(
+        common::parts::block_quote,
+        r###"   a block quote
+"###,
+    );
+}
+
+#[test]
+fn test_nested_block_quotes() {
+    assert_rst_eq(
+        common::parts::nested_block_quotes,
+        r###"   a block quote
+
+      another block quote
+"###,
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_rst_eq(
+        common::parts::text_styles,
+        r###"Here is some plain **bold** *italic* ``mono`` ``code`` plain strikethrough underline small caps :sup:`superscript` :sub:`subscript` text.
+"###,
+    );
+}
+
+#[test]
+fn test_nested_text_styles() {
+    assert_rst_eq(
+        common::parts::nested_text_styles,
+        r###"Here is some **bold italic** text.
+
+Here is some bold italic plain text.
+
+Here is some *bold plain italic* text.
+"###,
+    );
+}
+
+#[test]
+fn test_hyper_links() {
+    assert_rst_eq(
+        common::parts::hyper_links,
+        r###"https://example.org/
+
+`example <https://example.org/>`__
+
+:ref:`section-2`
+
+:ref:`example <section-2>`
+"###,
+    );
+}
+
+#[test]
+fn test_complex_paragraph() {
+    assert_rst_eq(
+        common::parts::complex_paragraph,
+        r###"This paragraph has `a link <https://example.org/>`__, some math: :math:`x=2+2^2`, a line break,
+an image: |logo| —  all together!
+
+.. |logo| image:: https://example.org/favicon.png
+"###,
+    );
+}
+
+#[test]
+fn test_adjacent_inline_markup() {
+    assert_rst_eq(
+        || {
+            Document::default()
+                .add_paragraph(
+                    Paragraph::default()
+                        .add_span(Span::with_style("a", SpanStyle::Bold))
+                        .add_math(Math::from_str("x").unwrap())
+                        .add_text_str("and")
+                        .add_span(Span::with_style("b", SpanStyle::Italic))
+                        .add_text_str("s, (")
+                        .add_span(Span::with_style("c", SpanStyle::Mono))
+                        .add_text_str(").")
+                        .clone(),
+                )
+                .clone()
+        },
+        r###"**a**\ :math:`x`\ and\ *b*\ s, (``c``).
+"###,
+    );
+}
+
+#[test]
+fn test_escaped_text() {
+    assert_rst_eq(
+        || {
+            Document::default()
+                .add_paragraph(Paragraph::plain_str(
+                    "*not emphasis* `not code` |not a substitution| not_a_reference_ C:\\temp",
+                ))
+                .clone()
+        },
+        r###"\*not emphasis\* \`not code\` \|not a substitution\| not\_a\_reference\_ C:\\temp
+"###,
+    );
+}
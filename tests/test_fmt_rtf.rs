@@ -0,0 +1,371 @@
+use pretty_assertions::assert_eq;
+use somedoc::model::block::{HasBlockContent, Heading, Paragraph};
+use somedoc::model::inline::HasInlineContent;
+use somedoc::model::Document;
+use somedoc::write::{write_document_to_string, OutputFormat};
+
+pub mod common;
+
+fn assert_rtf_body_eq(part_fn: impl Fn() -> Document, expected: &str) {
+    let result = write_document_to_string(&part_fn(), OutputFormat::Rtf).unwrap();
+    let start = result.find("\\widowctrl\n").unwrap() + 11;
+    assert_eq!(&result[start..], expected);
+}
+
+#[test]
+fn test_skos() {
+    common::assert_serialized_eq(
+        &common::skos::document(),
+        OutputFormat::Rtf,
+        r###"{\rtf1\ansi\ansicpg1252\deff0\uc1
+{\fonttbl{\f0\froman\fcharset0 Times New Roman;}{\f1\fswiss\fcharset0 Arial;}{\f2\fmodern\fcharset0 Courier New;}}
+{\colortbl;\red0\green0\blue0;\red5\green99\blue193;}
+{\info{\title Scheme: Clothing shapes, patterns, and details}{\author Simon}}
+\paperw12240\paperh15840\margl1440\margr1440\margt1440\margb1440\widowctrl
+\pard\plain\sa180\f0\fs24\qc\sa360\b\fs48 Scheme: Clothing shapes, patterns, and details\par
+\pard\plain\sa180\f0\fs24\sb240\sa120\keepn\outlinelevel0\b\fs36 Scheme: Clothing shapes, patterns, and details\par
+\pard\plain\sa180\f0\fs24\ql {\i Terms commonly used to describe fashion items. It includes terms for outline, fit, elements, detailing, and patterns.}\par
+\pard\plain\sa180\f0\fs24\ql {\field{\*\fldinst{HYPERLINK "http://amazon.com/vocabulary/fashion-design#DesignScheme"}}{\fldrslt{\ul\cf2 http://amazon.com/vocabulary/fashion-design#DesignScheme}}}\par
+\pard\plain\sa180\f0\fs24\sb240\sa120\keepn\outlinelevel1\b\fs32 {\*\bkmkstart Labels}{\*\bkmkend Labels}Labels\par
+\pard\plain\sa180\f0\fs24\li720\ri720\ql {\b skos:prefLabel}\par
+\pard\plain\sa180\f0\fs24\li720\ri720\ql {\b skos:altLabel}\par
+\trowd\trgaph108\trleft0\trhdr
+\clbrdrt\brdrs\brdrw10\clbrdrl\brdrs\brdrw10\clbrdrb\brdrs\brdrw10\clbrdrr\brdrs\brdrw10\cellx4680
+\clbrdrt\brdrs\brdrw10\clbrdrl\brdrs\brdrw10\clbrdrb\brdrs\brdrw10\clbrdrr\brdrs\brdrw10\cellx9360
+\pard\plain\intbl\f0\fs24\ql {\b Label text}\cell
+\pard\plain\intbl\f0\fs24\ql {\b Language}\cell
+\row
+\trowd\trgaph108\trleft0
+\clbrdrt\brdrs\brdrw10\clbrdrl\brdrs\brdrw10\clbrdrb\brdrs\brdrw10\clbrdrr\brdrs\brdrw10\cellx4680
+\clbrdrt\brdrs\brdrw10\clbrdrl\brdrs\brdrw10\clbrdrb\brdrs\brdrw10\clbrdrr\brdrs\brdrw10\cellx9360
+\pard\plain\intbl\f0\fs24\ql Clothing shapes, patterns, and details\cell
+\pard\plain\intbl\f0\fs24\ql {\b en}\cell
+\row
+\pard\plain\sa180\f0\fs24\qc\i\fs20 Other labels\par
+\pard\plain\sa180\f0\fs24\sb240\sa120\keepn\outlinelevel1\b\fs32 {\*\bkmkstart Other_Properties}{\*\bkmkend Other_Properties}Other Properties\par
+\pard\plain\sa180\f0\fs24\brdrb\brdrs\brdrw10\brsp20 \par
+\pard\plain\sa180\f0\fs24\ql Jump to: {\field{\*\fldinst{HYPERLINK \\l "Concepts_Hierarchy"}}{\fldrslt{\ul\cf2 Concepts Hierarchy}}} | {\field{\*\fldinst{HYPERLINK \\l "Concepts"}}{\fldrslt{\ul\cf2 Concepts}}} | {\field{\*\fldinst{HYPERLINK \\l "Collections"}}{\fldrslt{\ul\cf2 Collections}}} | {\field{\*\fldinst{HYPERLINK \\l "Appendix___RDF"}}{\fldrslt{\ul\cf2 Appendix - RDF}}}\par
+\pard\plain\sa180\f0\fs24\brdrb\brdrs\brdrw10\brsp20 \par
+\pard\plain\sa180\f0\fs24\sb240\sa120\keepn\outlinelevel1\b\fs32 {\*\bkmkstart Concept_Hierarchy}{\*\bkmkend Concept_Hierarchy}Concept Hierarchy\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 \bullet\tab {{\b First item}}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 \bullet\tab Second item\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li1440\tx1440 \bullet\tab {{\i Third item}}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 \bullet\tab First item\par
+\pard\plain\sa180\f0\fs24\sb240\sa120\keepn\outlinelevel1\b\fs32 {\*\bkmkstart Appendix___RDF}{\*\bkmkend Appendix___RDF}Appendix - RDF\par
+\pard\plain\sa180\f0\fs24\f2\fs20 @prefix foo: <...>\line
+foo:bar foo:baz 12.\par
+\pard\plain\sa180\f0\fs24\f2\fs20 @prefix foo: <...>\line
+foo:bar foo:baz 12.\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_empty_document() {
+    assert_rtf_body_eq(
+        common::parts::empty_document,
+        r###"}
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_title() {
+    assert_rtf_body_eq(
+        common::parts::document_with_title,
+        r###"\pard\plain\sa180\f0\fs24\qc\sa360\b\fs48 Test Document\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_heading() {
+    assert_rtf_body_eq(
+        common::parts::document_with_heading,
+        r###"\pard\plain\sa180\f0\fs24\sb240\sa120\keepn\outlinelevel0\b\fs36 Test Document\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_labeled_heading() {
+    assert_rtf_body_eq(
+        common::parts::document_with_labeled_heading,
+        r###"\pard\plain\sa180\f0\fs24\sb240\sa120\keepn\outlinelevel0\b\fs36 {\*\bkmkstart Test_Document}{\*\bkmkend Test_Document}Test Document\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_headings() {
+    assert_rtf_body_eq(
+        common::parts::document_with_headings,
+        r###"\pard\plain\sa180\f0\fs24\sb240\sa120\keepn\outlinelevel0\b\fs36 Section\par
+\pard\plain\sa180\f0\fs24\sb240\sa120\keepn\outlinelevel1\b\fs32 Sub-section\par
+\pard\plain\sa180\f0\fs24\sb240\sa120\keepn\outlinelevel2\b\fs28 Sub-sub-section\par
+\pard\plain\sa180\f0\fs24\sb240\sa120\keepn\outlinelevel3\b\fs26 Sub-sub-sub-section\par
+\pard\plain\sa180\f0\fs24\sb240\sa120\keepn\outlinelevel4\b\fs24 Sub-sub-sub-sub-section\par
+\pard\plain\sa180\f0\fs24\sb240\sa120\keepn\outlinelevel5\b\fs24 Sub-sub-sub-sub-sub-section\par
+\pard\plain\sa180\f0\fs24\sb240\sa120\keepn\outlinelevel6\b\fs24 Sub-sub-sub-sub-sub-sub-section\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_front_matter() {
+    assert_rtf_body_eq(
+        common::parts::document_with_front_matter,
+        r###"\pard\plain\sa180\f0\fs24 {\field{\*\fldinst{TOC \\o "1-3" \\h}}{\fldrslt{Update this field to show the table of contents.}}}\par
+\pard\plain\sa180\f0\fs24\sb240\sa120\keepn\outlinelevel0\b\fs36 Section One\par
+\pard\plain\sa180\f0\fs24\sb240\sa120\keepn\outlinelevel0\b\fs36 Section Two\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_paragraph_alignment() {
+    assert_rtf_body_eq(
+        common::parts::paragraph_alignment,
+        r###"\pard\plain\sa180\f0\fs24\ql left-aligned\par
+\pard\plain\sa180\f0\fs24\qr right-aligned\par
+\pard\plain\sa180\f0\fs24\qc center-aligned\par
+\pard\plain\sa180\f0\fs24\qj both-aligned\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_ordered_list() {
+    assert_rtf_body_eq(
+        common::parts::ordered_list,
+        r###"\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 1.\tab {\plain\f0\fs24 one}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 2.\tab {\plain\f0\fs24 two}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 3.\tab {\plain\f0\fs24 three}\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_labeled_ordered_list() {
+    assert_rtf_body_eq(
+        common::parts::labeled_ordered_list,
+        r###"\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 {\*\bkmkstart lst1}{\*\bkmkend lst1}{\*\bkmkstart lst1_itm1}{\*\bkmkend lst1_itm1}1.\tab {\plain\f0\fs24 one}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 {\*\bkmkstart lst1_itm2}{\*\bkmkend lst1_itm2}2.\tab {\plain\f0\fs24 two}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 {\*\bkmkstart lst1_itm3}{\*\bkmkend lst1_itm3}3.\tab {\plain\f0\fs24 three}\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_unordered_list() {
+    assert_rtf_body_eq(
+        common::parts::unordered_list,
+        r###"\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 \bullet\tab {\plain\f0\fs24 one}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 \bullet\tab {\plain\f0\fs24 two}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 \bullet\tab {\plain\f0\fs24 three}\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_nested_ordered_list() {
+    assert_rtf_body_eq(
+        common::parts::nested_ordered_list,
+        r###"\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 1.\tab {\plain\f0\fs24 one}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 2.\tab {\plain\f0\fs24 two}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li1440\tx1440 1.\tab {\plain\f0\fs24 inner one}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li1440\tx1440 2.\tab {\plain\f0\fs24 inner two}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 3.\tab {\plain\f0\fs24 three}\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_nested_unordered_list() {
+    assert_rtf_body_eq(
+        common::parts::nested_unordered_list,
+        r###"\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 \bullet\tab {\plain\f0\fs24 one}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 \bullet\tab {\plain\f0\fs24 two}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li1440\tx1440 \bullet\tab {\plain\f0\fs24 inner one}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li1440\tx1440 \bullet\tab {\plain\f0\fs24 inner two}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 \bullet\tab {\plain\f0\fs24 three}\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_nested_mixed_lists() {
+    assert_rtf_body_eq(
+        common::parts::nested_mixed_lists,
+        r###"\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 \bullet\tab {\plain\f0\fs24 one}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 \bullet\tab {\plain\f0\fs24 two}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li1440\tx1440 1.\tab {\plain\f0\fs24 inner one}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li2160\tx2160 \bullet\tab {\plain\f0\fs24 inner inner one}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li1440\tx1440 2.\tab {\plain\f0\fs24 inner two}\par
+\pard\plain\sa180\f0\fs24\sa60\fi-360\li720\tx720 \bullet\tab {\plain\f0\fs24 three}\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_definition_list() {
+    assert_rtf_body_eq(
+        common::parts::definition_list,
+        r###"\pard\plain\sa180\f0\fs24\sa0\keepn\b Universe\par
+\pard\plain\sa180\f0\fs24\li720 Big, really big\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_image_block() {
+    assert_rtf_body_eq(
+        common::parts::image_block,
+        r###"\pard\plain\sa180\f0\fs24\qc {\field{\*\fldinst{HYPERLINK "https://example.org/example.png"}}{\fldrslt{\ul\cf2 https://example.org/example.png}}}\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_image_block_with_label_and_caption() {
+    assert_rtf_body_eq(
+        common::parts::image_block_with_label_and_caption,
+        r###"\pard\plain\sa180\f0\fs24\qc {\field{\*\fldinst{HYPERLINK "https://example.org/example.png"}}{\fldrslt{\ul\cf2 https://example.org/example.png}}}\par
+\pard\plain\sa180\f0\fs24\qc\i\fs20 {\*\bkmkstart img_example}{\*\bkmkend img_example}An Example Image\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_math_block() {
+    assert_rtf_body_eq(
+        common::parts::math_block,
+        r###"\pard\plain\sa180\f0\fs24\qc\f2\fs20 x=2+2^2\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_math_block_with_label_and_caption() {
+    assert_rtf_body_eq(
+        common::parts::math_block_with_label_and_caption,
+        r###"\pard\plain\sa180\f0\fs24\qc\f2\fs20 x=2+2^2\par
+\pard\plain\sa180\f0\fs24\qc\i\fs20 {\*\bkmkstart math_example}{\*\bkmkend math_example}Example Math\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_block_quote() {
+    assert_rtf_body_eq(
+        common::parts::block_quote,
+        r###"\pard\plain\sa180\f0\fs24\li720\ri720\ql {\plain\f0\fs24 a block quote}\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_nested_block_quotes() {
+    assert_rtf_body_eq(
+        common::parts::nested_block_quotes,
+        r###"\pard\plain\sa180\f0\fs24\li720\ri720\ql {\plain\f0\fs24 a block quote}\par
+\pard\plain\sa180\f0\fs24\li1440\ri720\ql {\plain\f0\fs24 another block quote}\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_rtf_body_eq(
+        common::parts::text_styles,
+        r###"\pard\plain\sa180\f0\fs24\ql Here is some {{\plain\f0\fs24 plain}} {{\b bold}} {{\i italic}} {{\f2 mono}} {{\f2 code}} {{\plain\f0\fs24 plain}} {{\strike strikethrough}} {{\ul underline}} {{\scaps small caps}} {{\super superscript}} {{\sub subscript}}{{\plain\f0\fs24  text.}}\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_nested_text_styles() {
+    assert_rtf_body_eq(
+        common::parts::nested_text_styles,
+        r###"\pard\plain\sa180\f0\fs24\ql Here is some {\b\i bold italic}{{\plain\f0\fs24  text.}}\par
+\pard\plain\sa180\f0\fs24\ql Here is some {\b\i\plain\f0\fs24 bold italic plain}{{\plain\f0\fs24  text.}}\par
+\pard\plain\sa180\f0\fs24\ql Here is some {\b\plain\f0\fs24\i bold plain italic}{{\plain\f0\fs24  text.}}\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_hyper_links() {
+    assert_rtf_body_eq(
+        common::parts::hyper_links,
+        r###"\pard\plain\sa180\f0\fs24\ql {\field{\*\fldinst{HYPERLINK "https://example.org/"}}{\fldrslt{\ul\cf2 https://example.org/}}}\par
+\pard\plain\sa180\f0\fs24\ql {\field{\*\fldinst{HYPERLINK "https://example.org/"}}{\fldrslt{\ul\cf2 example}}}\par
+\pard\plain\sa180\f0\fs24\ql {\field{\*\fldinst{HYPERLINK \\l "section_2"}}{\fldrslt{\ul\cf2 section-2}}}\par
+\pard\plain\sa180\f0\fs24\ql {\field{\*\fldinst{HYPERLINK \\l "section_2"}}{\fldrslt{\ul\cf2 example}}}\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_complex_paragraph() {
+    assert_rtf_body_eq(
+        common::parts::complex_paragraph,
+        r###"\pard\plain\sa180\f0\fs24\ql This paragraph has {\field{\*\fldinst{HYPERLINK "https://example.org/"}}{\fldrslt{\ul\cf2 a link}}}, some math:\~{\f2 x=2+2^2}, a line break,\line an image:\~{\field{\*\fldinst{HYPERLINK "https://example.org/favicon.png"}}{\fldrslt{\ul\cf2 logo}}}\~\emdash \~ all together!\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_unicode_and_special_characters() {
+    assert_rtf_body_eq(
+        || {
+            Document::default()
+                .add_paragraph(Paragraph::from("{braces} \\ café €5 😀"))
+                .clone()
+        },
+        r###"\pard\plain\sa180\f0\fs24\ql \{braces\} \\ caf\u233? \u8364?5 \u-10179?\u-8704?\par
+}
+"###,
+    );
+}
+
+#[test]
+fn test_info_group() {
+    let mut doc = Document::default();
+    let _ = doc
+        .set_title("Fish & Chips")
+        .add_author_str("Simon", None, None)
+        .add_author_str("Zoë", None, None)
+        .set_date("2021-02-03")
+        .add_keywords_str(&["fish", "chips"])
+        .add_abstract(Paragraph::plain_str("A {short} abstract."))
+        .add_heading(Heading::section("Only"));
+    let result = write_document_to_string(&doc, OutputFormat::Rtf).unwrap();
+    assert!(result.contains(
+        r###"{\info{\title Fish & Chips}{\creatim\yr2021\mo2\dy3}{\author Simon; Zo\u235?}{\keywords fish, chips}{\doccomm A \{short\} abstract.}}"###
+    ));
+}
@@ -0,0 +1,499 @@
+use somedoc::model::block::{
+    Alignment, Caption, Cell, CodeBlock, Column, HasBlockContent, HasCaption, Heading, ImageBlock,
+    Paragraph, Row, Table,
+};
+use somedoc::model::inline::{HasInlineContent, Image};
+use somedoc::model::Document;
+use somedoc::write::slack::SlackFormat;
+use somedoc::write::OutputFormat;
+
+pub mod common;
+
+#[inline]
+fn assert_mrkdwn_eq(part_fn: impl Fn() -> Document, expected: &str) {
+    common::assert_serialized_eq(
+        &part_fn(),
+        OutputFormat::Slack(SlackFormat::Mrkdwn),
+        expected,
+    )
+}
+
+#[inline]
+fn assert_block_kit_eq(part_fn: impl Fn() -> Document, blocks: &str) {
+    let expected = format!("{{\"blocks\":[{}]}}", blocks);
+    common::assert_serialized_eq(
+        &part_fn(),
+        OutputFormat::Slack(SlackFormat::BlockKit),
+        &expected,
+    )
+}
+
+#[test]
+fn test_skos() {
+    assert_mrkdwn_eq(
+        common::skos::document,
+        r###"*Scheme: Clothing shapes, patterns, and details*
+
+*Scheme: Clothing shapes, patterns, and details*
+
+_Terms commonly used to describe fashion items. It includes terms for outline, fit, elements, detailing, and patterns._
+
+<http://amazon.com/vocabulary/fashion-design#DesignScheme>
+
+*Labels*
+
+> *skos:prefLabel*
+>
+> *skos:altLabel*
+
+```
+| Label text                             | Language |
+|----------------------------------------|----------|
+| Clothing shapes, patterns, and details | en       |
+```
+_Other labels_
+
+*Other Properties*
+
+---
+
+Jump to: Concepts Hierarchy | Concepts | Collections | Appendix - RDF
+
+---
+
+*Concept Hierarchy*
+
+• *First item*
+• Second item
+    • _Third item_
+• First item
+
+*Appendix - RDF*
+
+```
+@prefix foo: &lt;...&gt;
+foo:bar foo:baz 12.
+```
+
+```
+@prefix foo: &lt;...&gt;
+foo:bar foo:baz 12.
+```
+"###,
+    );
+}
+
+#[test]
+fn test_empty_document() {
+    assert_mrkdwn_eq(common::parts::empty_document, r###""###);
+}
+
+#[test]
+fn test_document_with_title() {
+    assert_mrkdwn_eq(
+        common::parts::document_with_title,
+        r###"*Test Document*
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_heading() {
+    assert_mrkdwn_eq(
+        common::parts::document_with_heading,
+        r###"*Test Document*
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_labeled_heading() {
+    assert_mrkdwn_eq(
+        common::parts::document_with_labeled_heading,
+        r###"*Test Document*
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_headings() {
+    assert_mrkdwn_eq(
+        common::parts::document_with_headings,
+        r###"*Section*
+
+*Sub-section*
+
+*Sub-sub-section*
+
+*Sub-sub-sub-section*
+
+*Sub-sub-sub-sub-section*
+
+*Sub-sub-sub-sub-sub-section*
+
+*Sub-sub-sub-sub-sub-sub-section*
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_front_matter() {
+    assert_mrkdwn_eq(
+        common::parts::document_with_front_matter,
+        r###"*Section One*
+
+*Section Two*
+"###,
+    );
+}
+
+#[test]
+fn test_paragraph_alignment() {
+    assert_mrkdwn_eq(
+        common::parts::paragraph_alignment,
+        r###"left-aligned
+
+right-aligned
+
+center-aligned
+
+both-aligned
+"###,
+    );
+}
+
+#[test]
+fn test_ordered_list() {
+    assert_mrkdwn_eq(
+        common::parts::ordered_list,
+        r###"1. one
+2. two
+3. three
+"###,
+    );
+}
+
+#[test]
+fn test_labeled_ordered_list() {
+    assert_mrkdwn_eq(
+        common::parts::labeled_ordered_list,
+        r###"1. one
+2. two
+3. three
+"###,
+    );
+}
+
+#[test]
+fn test_unordered_list() {
+    assert_mrkdwn_eq(
+        common::parts::unordered_list,
+        r###"• one
+• two
+• three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_ordered_list() {
+    assert_mrkdwn_eq(
+        common::parts::nested_ordered_list,
+        r###"1. one
+2. two
+    1. inner one
+    2. inner two
+3. three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_unordered_list() {
+    assert_mrkdwn_eq(
+        common::parts::nested_unordered_list,
+        r###"• one
+• two
+    • inner one
+    • inner two
+• three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_mixed_lists() {
+    assert_mrkdwn_eq(
+        common::parts::nested_mixed_lists,
+        r###"• one
+• two
+    1. inner one
+        • inner inner one
+    2. inner two
+• three
+"###,
+    );
+}
+
+#[test]
+fn test_definition_list() {
+    assert_mrkdwn_eq(
+        common::parts::definition_list,
+        r###"*Universe*: Big, really big
+"###,
+    );
+}
+
+#[test]
+fn test_image_block() {
+    assert_mrkdwn_eq(
+        common::parts::image_block,
+        r###"<https://example.org/example.png>
+"###,
+    );
+}
+
+#[test]
+fn test_image_block_with_label_and_caption() {
+    assert_mrkdwn_eq(
+        common::parts::image_block_with_label_and_caption,
+        r###"<https://example.org/example.png>
+_An Example Image_
+"###,
+    );
+}
+
+#[test]
+fn test_math_block() {
+    assert_mrkdwn_eq(
+        common::parts::math_block,
+        r###"```
+x=2+2^2
+```
+"###,
+    );
+}
+
+#[test]
+fn test_math_block_with_label_and_caption() {
+    assert_mrkdwn_eq(
+        common::parts::math_block_with_label_and_caption,
+        r###"```
+x=2+2^2
+```
+_Example Math_
+"###,
+    );
+}
+
+#[test]
+fn test_block_quote() {
+    assert_mrkdwn_eq(
+        common::parts::block_quote,
+        r###"> a block quote
+"###,
+    );
+}
+
+#[test]
+fn test_nested_block_quotes() {
+    assert_mrkdwn_eq(
+        common::parts::nested_block_quotes,
+        r###"> a block quote
+>
+> another block quote
+"###,
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_mrkdwn_eq(
+        common::parts::text_styles,
+        r###"Here is some plain *bold* _italic_ `mono` `code` plain ~strikethrough~ underline small caps superscript subscript text.
+"###,
+    );
+}
+
+#[test]
+fn test_nested_text_styles() {
+    assert_mrkdwn_eq(
+        common::parts::nested_text_styles,
+        r###"Here is some *_bold italic_* text.
+
+Here is some *_bold italic plain_* text.
+
+Here is some *_bold plain italic_* text.
+"###,
+    );
+}
+
+#[test]
+fn test_hyper_links() {
+    assert_mrkdwn_eq(
+        common::parts::hyper_links,
+        r###"<https://example.org/>
+
+<https://example.org/|example>
+
+section-2
+
+example
+"###,
+    );
+}
+
+#[test]
+fn test_complex_paragraph() {
+    assert_mrkdwn_eq(
+        common::parts::complex_paragraph,
+        r###"This paragraph has <https://example.org/|a link>, some math: `x=2+2^2`, a line break,
+an image: <https://example.org/favicon.png|logo> —  all together!
+"###,
+    );
+}
+
+#[test]
+fn test_block_kit_empty_document() {
+    assert_block_kit_eq(common::parts::empty_document, r###""###);
+}
+
+#[test]
+fn test_block_kit_document_with_title() {
+    assert_block_kit_eq(
+        common::parts::document_with_title,
+        r###"{"text":{"emoji":true,"text":"Test Document","type":"plain_text"},"type":"header"}"###,
+    );
+}
+
+#[test]
+fn test_block_kit_document_with_labeled_heading() {
+    assert_block_kit_eq(
+        common::parts::document_with_labeled_heading,
+        r###"{"block_id":"Test_Document","text":{"emoji":true,"text":"Test Document","type":"plain_text"},"type":"header"}"###,
+    );
+}
+
+#[test]
+fn test_block_kit_ordered_list() {
+    assert_block_kit_eq(
+        common::parts::ordered_list,
+        r###"{"text":{"text":"1. one\n2. two\n3. three","type":"mrkdwn"},"type":"section"}"###,
+    );
+}
+
+#[test]
+fn test_block_kit_definition_list() {
+    assert_block_kit_eq(
+        common::parts::definition_list,
+        r###"{"text":{"text":"*Universe*: Big, really big","type":"mrkdwn"},"type":"section"}"###,
+    );
+}
+
+#[test]
+fn test_block_kit_image_block_with_label_and_caption() {
+    assert_block_kit_eq(
+        common::parts::image_block_with_label_and_caption,
+        r###"{"alt_text":"An Example Image","block_id":"img:example","image_url":"https://example.org/example.png","title":{"emoji":true,"text":"An Example Image","type":"plain_text"},"type":"image"}"###,
+    );
+}
+
+#[test]
+fn test_block_kit_math_block_with_label_and_caption() {
+    assert_block_kit_eq(
+        common::parts::math_block_with_label_and_caption,
+        r###"{"block_id":"math:example","elements":[{"elements":[{"text":"x=2+2^2","type":"text"}],"type":"rich_text_preformatted"}],"type":"rich_text"},{"elements":[{"text":"_Example Math_","type":"mrkdwn"}],"type":"context"}"###,
+    );
+}
+
+#[test]
+fn test_block_kit_nested_block_quotes() {
+    assert_block_kit_eq(
+        common::parts::nested_block_quotes,
+        r###"{"text":{"text":"> a block quote\n>\n> another block quote","type":"mrkdwn"},"type":"section"}"###,
+    );
+}
+
+#[test]
+fn test_block_kit_text_styles() {
+    assert_block_kit_eq(
+        common::parts::text_styles,
+        r###"{"text":{"text":"Here is some plain *bold* _italic_ `mono` `code` plain ~strikethrough~ underline small caps superscript subscript text.","type":"mrkdwn"},"type":"section"}"###,
+    );
+}
+
+#[test]
+fn test_block_kit_complex_paragraph() {
+    assert_block_kit_eq(
+        common::parts::complex_paragraph,
+        r###"{"text":{"text":"This paragraph has <https://example.org/|a link>, some math: `x=2+2^2`, a line break,\nan image: <https://example.org/favicon.png|logo> —  all together!","type":"mrkdwn"},"type":"section"}"###,
+    );
+}
+
+#[test]
+fn test_table_as_monospaced_text() {
+    let table_document = || {
+        let mut table = Table::new(&[
+            Column::from("Service"),
+            Column::from(("Latency", Alignment::Right)),
+        ]);
+        table.add_row(Row::new(&[Cell::plain_str("api"), Cell::plain_str("12ms")]));
+        table.add_row(Row::new(&[
+            Cell::plain_str("a<b>&c"),
+            Cell::plain_str("1,024ms"),
+        ]));
+        table.set_caption(Caption::from("Latencies"));
+        let mut doc = Document::default();
+        let _ = doc.add_table(table);
+        doc
+    };
+    assert_mrkdwn_eq(
+        table_document,
+        r###"```
+| Service | Latency |
+|---------|---------|
+| api     |    12ms |
+| a&lt;b&gt;&amp;c  | 1,024ms |
+```
+_Latencies_
+"###,
+    );
+    assert_block_kit_eq(
+        table_document,
+        r###"{"elements":[{"elements":[{"text":"| Service | Latency |\n|---------|---------|\n| api     |    12ms |\n| a<b>&c  | 1,024ms |","type":"text"}],"type":"rich_text_preformatted"}],"type":"rich_text"},{"elements":[{"text":"_Latencies_","type":"mrkdwn"}],"type":"context"}"###,
+    );
+}
+
+#[test]
+fn test_block_kit_code_block_with_caption() {
+    assert_block_kit_eq(
+        || {
+            let mut code = CodeBlock::with_language("fn main() {}\n", "rust");
+            code.set_caption(Caption::from("Entry point"));
+            let mut doc = Document::default();
+            let _ = doc.add_code_block(code);
+            doc
+        },
+        r###"{"elements":[{"elements":[{"text":"fn main() {}","type":"text"}],"type":"rich_text_preformatted"}],"type":"rich_text"},{"elements":[{"text":"_Entry point_","type":"mrkdwn"}],"type":"context"}"###,
+    );
+}
+
+#[test]
+fn test_block_kit_local_image_and_long_heading() {
+    assert_block_kit_eq(
+        || {
+            let mut doc = Document::default();
+            let _ = doc.add_heading(Heading::section(&"x".repeat(160)));
+            let _ = doc.add_image(ImageBlock::from(Image::with_alt_text(
+                "images/graph.png",
+                "Error rate",
+            )));
+            let _ = doc.add_paragraph(Paragraph::image(Image::new("https://example.org/a.png")));
+            doc
+        },
+        &format!(
+            r###"{{"text":{{"emoji":true,"text":"{}","type":"plain_text"}},"type":"header"}},{{"text":{{"text":"Error rate","type":"mrkdwn"}},"type":"section"}},{{"text":{{"text":"<https://example.org/a.png>","type":"mrkdwn"}},"type":"section"}}"###,
+            "x".repeat(150)
+        ),
+    );
+}
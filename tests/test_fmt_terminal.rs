@@ -0,0 +1,404 @@
+use somedoc::model::Document;
+use somedoc::write::terminal::TerminalConfig;
+use somedoc::write::OutputFormat;
+
+pub mod common;
+
+#[inline]
+fn assert_terminal_eq(part_fn: impl Fn() -> Document, expected: &str) {
+    common::assert_serialized_eq(
+        &part_fn(),
+        OutputFormat::Terminal(Default::default()),
+        expected,
+    )
+}
+
+#[inline]
+fn assert_no_color_eq(part_fn: impl Fn() -> Document, expected: &str) {
+    common::assert_serialized_eq(&part_fn(), TerminalConfig::new(80, false).into(), expected)
+}
+
+#[test]
+fn test_skos() {
+    assert_no_color_eq(
+        common::skos::document,
+        r###"══════════════════════════════════════════════
+Scheme: Clothing shapes, patterns, and details
+══════════════════════════════════════════════
+Author: Simon
+
+Scheme: Clothing shapes, patterns, and details
+==============================================
+
+Terms commonly used to describe fashion items. It includes terms for outline,
+fit, elements, detailing, and patterns.
+
+http://amazon.com/vocabulary/fashion-design#DesignScheme
+
+Labels
+------
+
+    skos:prefLabel
+
+    skos:altLabel
+
+Other labels
+┌────────────────────────────────────────┬──────────┐
+│ Label text                             │ Language │
+╞════════════════════════════════════════╪══════════╡
+│ Clothing shapes, patterns, and details │ en       │
+└────────────────────────────────────────┴──────────┘
+
+Other Properties
+----------------
+
+────────────────────────────────────────────────────────────────────────────────
+
+Jump to: Concepts Hierarchy | Concepts | Collections | Appendix - RDF
+
+────────────────────────────────────────────────────────────────────────────────
+
+Concept Hierarchy
+-----------------
+
+* First item
+* Second item
+  * Third item
+* First item
+
+Appendix - RDF
+--------------
+
+    @prefix foo: <...>
+    foo:bar foo:baz 12.
+
+    @prefix foo: <...>
+    foo:bar foo:baz 12.
+"###,
+    );
+}
+
+#[test]
+fn test_empty_document() {
+    assert_no_color_eq(common::parts::empty_document, r###""###);
+}
+
+#[test]
+fn test_document_with_title() {
+    assert_no_color_eq(
+        common::parts::document_with_title,
+        r###"═════════════
+Test Document
+═════════════
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_heading() {
+    assert_no_color_eq(
+        common::parts::document_with_heading,
+        r###"Test Document
+=============
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_labeled_heading() {
+    assert_no_color_eq(
+        common::parts::document_with_labeled_heading,
+        r###"Test Document
+=============
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_headings() {
+    assert_no_color_eq(
+        common::parts::document_with_headings,
+        r###"Section
+=======
+
+Sub-section
+-----------
+
+Sub-sub-section
+---------------
+
+Sub-sub-sub-section
+-------------------
+
+Sub-sub-sub-sub-section
+-----------------------
+
+Sub-sub-sub-sub-sub-section
+---------------------------
+
+Sub-sub-sub-sub-sub-sub-section
+-------------------------------
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_front_matter() {
+    assert_no_color_eq(
+        common::parts::document_with_front_matter,
+        r###"Section One
+===========
+
+Section Two
+===========
+"###,
+    );
+}
+
+#[test]
+fn test_paragraph_alignment() {
+    assert_no_color_eq(
+        common::parts::paragraph_alignment,
+        r###"left-aligned
+
+                                                                   right-aligned
+
+                                 center-aligned
+
+both-aligned
+"###,
+    );
+}
+
+#[test]
+fn test_ordered_list() {
+    assert_no_color_eq(
+        common::parts::ordered_list,
+        r###"1. one
+2. two
+3. three
+"###,
+    );
+}
+
+#[test]
+fn test_labeled_ordered_list() {
+    assert_no_color_eq(
+        common::parts::labeled_ordered_list,
+        r###"1. one
+2. two
+3. three
+"###,
+    );
+}
+
+#[test]
+fn test_unordered_list() {
+    assert_no_color_eq(
+        common::parts::unordered_list,
+        r###"* one
+* two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_ordered_list() {
+    assert_no_color_eq(
+        common::parts::nested_ordered_list,
+        r###"1. one
+2. two
+   1. inner one
+   2. inner two
+3. three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_unordered_list() {
+    assert_no_color_eq(
+        common::parts::nested_unordered_list,
+        r###"* one
+* two
+  * inner one
+  * inner two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_mixed_lists() {
+    assert_no_color_eq(
+        common::parts::nested_mixed_lists,
+        r###"* one
+* two
+  1. inner one
+     * inner inner one
+  2. inner two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_definition_list() {
+    assert_no_color_eq(
+        common::parts::definition_list,
+        r###"Universe
+    Big, really big
+"###,
+    );
+}
+
+#[test]
+fn test_image_block() {
+    assert_no_color_eq(
+        common::parts::image_block,
+        r###"[image: https://example.org/example.png]
+"###,
+    );
+}
+
+#[test]
+fn test_image_block_with_label_and_caption() {
+    assert_no_color_eq(
+        common::parts::image_block_with_label_and_caption,
+        r###"An Example Image
+[image: https://example.org/example.png]
+"###,
+    );
+}
+
+#[test]
+fn test_math_block() {
+    assert_no_color_eq(
+        common::parts::math_block,
+        r###"    x=2+2^2
+"###,
+    );
+}
+
+#[test]
+fn test_math_block_with_label_and_caption() {
+    assert_no_color_eq(
+        common::parts::math_block_with_label_and_caption,
+        r###"Example Math
+    x=2+2^2
+"###,
+    );
+}
+
+#[test]
+fn test_block_quote() {
+    assert_no_color_eq(
+        common::parts::block_quote,
+        r###"    a block quote
+"###,
+    );
+}
+
+#[test]
+fn test_nested_block_quotes() {
+    assert_no_color_eq(
+        common::parts::nested_block_quotes,
+        r###"    a block quote
+
+        another block quote
+"###,
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_no_color_eq(
+        common::parts::text_styles,
+        r###"Here is some plain bold italic mono code plain strikethrough underline small
+caps superscript subscript text.
+"###,
+    );
+}
+
+#[test]
+fn test_nested_text_styles() {
+    assert_no_color_eq(
+        common::parts::nested_text_styles,
+        r###"Here is some bold italic text.
+
+Here is some bold italic plain text.
+
+Here is some bold plain italic text.
+"###,
+    );
+}
+
+#[test]
+fn test_hyper_links() {
+    assert_no_color_eq(
+        common::parts::hyper_links,
+        r###"https://example.org/
+
+example[1]
+
+section-2
+
+example
+
+[1] https://example.org/
+"###,
+    );
+}
+
+#[test]
+fn test_complex_paragraph() {
+    assert_no_color_eq(
+        common::parts::complex_paragraph,
+        r###"This paragraph has a link[1], some math: x=2+2^2, a line break,
+an image: [image: logo] —  all together!
+
+[1] https://example.org/
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_headings_color() {
+    assert_terminal_eq(
+        common::parts::document_with_headings,
+        "\u{1b}[1;4;35mSection\u{1b}[0m\n\n\u{1b}[1;34mSub-section\u{1b}[0m\n\n\u{1b}[1;36mSub-sub-section\u{1b}[0m\n\n\u{1b}[1;32mSub-sub-sub-section\u{1b}[0m\n\n\u{1b}[1mSub-sub-sub-sub-section\u{1b}[0m\n\n\u{1b}[1mSub-sub-sub-sub-sub-section\u{1b}[0m\n\n\u{1b}[1mSub-sub-sub-sub-sub-sub-section\u{1b}[0m\n",
+    );
+}
+
+#[test]
+fn test_text_styles_color() {
+    assert_terminal_eq(
+        common::parts::text_styles,
+        "Here is some plain \u{1b}[1mbold\u{1b}[0m \u{1b}[3mitalic\u{1b}[0m mono \u{1b}[36mcode\u{1b}[0m plain \u{1b}[9mstrikethrough\u{1b}[0m \u{1b}[4munderline\u{1b}[0m small\ncaps superscript subscript text.\n",
+    );
+}
+
+#[test]
+fn test_nested_text_styles_color() {
+    assert_terminal_eq(
+        common::parts::nested_text_styles,
+        "Here is some \u{1b}[1;3mbold italic\u{1b}[0m text.\n\nHere is some bold italic plain text.\n\nHere is some \u{1b}[3mbold plain italic\u{1b}[0m text.\n",
+    );
+}
+
+#[test]
+fn test_hyper_links_color() {
+    assert_terminal_eq(
+        common::parts::hyper_links,
+        "\u{1b}]8;;https://example.org/\u{1b}\\https://example.org/\u{1b}]8;;\u{1b}\\\n\n\u{1b}]8;;https://example.org/\u{1b}\\example\u{1b}]8;;\u{1b}\\\n\nsection-2\n\nexample\n",
+    );
+}
+
+#[test]
+fn test_complex_paragraph_color() {
+    assert_terminal_eq(
+        common::parts::complex_paragraph,
+        "This paragraph has \u{1b}]8;;https://example.org/\u{1b}\\a link\u{1b}]8;;\u{1b}\\, some math:\u{a0}x=2+2^2, a line break,\nan image:\u{a0}[image: logo]\u{a0}—\u{a0} all together!\n",
+    );
+}
@@ -0,0 +1,415 @@
+use somedoc::model::Document;
+use somedoc::write::text::PlainTextConfig;
+use somedoc::write::OutputFormat;
+
+pub mod common;
+
+#[inline]
+fn assert_text_eq(part_fn: impl Fn() -> Document, expected: &str) {
+    common::assert_serialized_eq(
+        &part_fn(),
+        OutputFormat::PlainText(Default::default()),
+        expected,
+    )
+}
+
+#[inline]
+fn assert_narrow_text_eq(part_fn: impl Fn() -> Document, expected: &str) {
+    common::assert_serialized_eq(&part_fn(), PlainTextConfig::with_width(40).into(), expected)
+}
+
+#[test]
+fn test_skos() {
+    assert_text_eq(
+        common::skos::document,
+        r###"==============================================
+Scheme: Clothing shapes, patterns, and details
+==============================================
+Author: Simon
+
+Scheme: Clothing shapes, patterns, and details
+==============================================
+
+Terms commonly used to describe fashion items. It includes terms for outline,
+fit, elements, detailing, and patterns.
+
+http://amazon.com/vocabulary/fashion-design#DesignScheme
+
+Labels
+------
+
+    skos:prefLabel
+
+    skos:altLabel
+
+Other labels
++----------------------------------------+----------+
+| Label text                             | Language |
++========================================+==========+
+| Clothing shapes, patterns, and details | en       |
++----------------------------------------+----------+
+
+Other Properties
+----------------
+
+--------------------------------------------------------------------------------
+
+Jump to: Concepts Hierarchy | Concepts | Collections | Appendix - RDF
+
+--------------------------------------------------------------------------------
+
+Concept Hierarchy
+-----------------
+
+* First item
+* Second item
+  * Third item
+* First item
+
+Appendix - RDF
+--------------
+
+    @prefix foo: <...>
+    foo:bar foo:baz 12.
+
+    @prefix foo: <...>
+    foo:bar foo:baz 12.
+"###,
+    );
+}
+
+#[test]
+fn test_empty_document() {
+    assert_text_eq(common::parts::empty_document, r###""###);
+}
+
+#[test]
+fn test_document_with_title() {
+    assert_text_eq(
+        common::parts::document_with_title,
+        r###"=============
+Test Document
+=============
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_heading() {
+    assert_text_eq(
+        common::parts::document_with_heading,
+        r###"Test Document
+=============
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_labeled_heading() {
+    assert_text_eq(
+        common::parts::document_with_labeled_heading,
+        r###"Test Document
+=============
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_headings() {
+    assert_text_eq(
+        common::parts::document_with_headings,
+        r###"Section
+=======
+
+Sub-section
+-----------
+
+Sub-sub-section
+---------------
+
+Sub-sub-sub-section
+-------------------
+
+Sub-sub-sub-sub-section
+-----------------------
+
+Sub-sub-sub-sub-sub-section
+---------------------------
+
+Sub-sub-sub-sub-sub-sub-section
+-------------------------------
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_front_matter() {
+    assert_text_eq(
+        common::parts::document_with_front_matter,
+        r###"Section One
+===========
+
+Section Two
+===========
+"###,
+    );
+}
+
+#[test]
+fn test_paragraph_alignment() {
+    assert_text_eq(
+        common::parts::paragraph_alignment,
+        r###"left-aligned
+
+                                                                   right-aligned
+
+                                 center-aligned
+
+both-aligned
+"###,
+    );
+}
+
+#[test]
+fn test_ordered_list() {
+    assert_text_eq(
+        common::parts::ordered_list,
+        r###"1. one
+2. two
+3. three
+"###,
+    );
+}
+
+#[test]
+fn test_labeled_ordered_list() {
+    assert_text_eq(
+        common::parts::labeled_ordered_list,
+        r###"1. one
+2. two
+3. three
+"###,
+    );
+}
+
+#[test]
+fn test_unordered_list() {
+    assert_text_eq(
+        common::parts::unordered_list,
+        r###"* one
+* two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_ordered_list() {
+    assert_text_eq(
+        common::parts::nested_ordered_list,
+        r###"1. one
+2. two
+   1. inner one
+   2. inner two
+3. three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_unordered_list() {
+    assert_text_eq(
+        common::parts::nested_unordered_list,
+        r###"* one
+* two
+  * inner one
+  * inner two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_mixed_lists() {
+    assert_text_eq(
+        common::parts::nested_mixed_lists,
+        r###"* one
+* two
+  1. inner one
+     * inner inner one
+  2. inner two
+* three
+"###,
+    );
+}
+
+#[test]
+fn test_definition_list() {
+    assert_text_eq(
+        common::parts::definition_list,
+        r###"Universe
+    Big, really big
+"###,
+    );
+}
+
+#[test]
+fn test_image_block() {
+    assert_text_eq(
+        common::parts::image_block,
+        r###"[image: https://example.org/example.png]
+"###,
+    );
+}
+
+#[test]
+fn test_image_block_with_label_and_caption() {
+    assert_text_eq(
+        common::parts::image_block_with_label_and_caption,
+        r###"An Example Image
+[image: https://example.org/example.png]
+"###,
+    );
+}
+
+#[test]
+fn test_math_block() {
+    assert_text_eq(
+        common::parts::math_block,
+        r###"    x=2+2^2
+"###,
+    );
+}
+
+#[test]
+fn test_math_block_with_label_and_caption() {
+    assert_text_eq(
+        common::parts::math_block_with_label_and_caption,
+        r###"Example Math
+    x=2+2^2
+"###,
+    );
+}
+
+#[test]
+fn test_block_quote() {
+    assert_text_eq(
+        common::parts::block_quote,
+        r###"    a block quote
+"###,
+    );
+}
+
+#[test]
+fn test_nested_block_quotes() {
+    assert_text_eq(
+        common::parts::nested_block_quotes,
+        r###"    a block quote
+
+        another block quote
+"###,
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_text_eq(
+        common::parts::text_styles,
+        r###"Here is some plain bold italic mono code plain strikethrough underline small
+caps superscript subscript text.
+"###,
+    );
+}
+
+#[test]
+fn test_nested_text_styles() {
+    assert_text_eq(
+        common::parts::nested_text_styles,
+        r###"Here is some bold italic text.
+
+Here is some bold italic plain text.
+
+Here is some bold plain italic text.
+"###,
+    );
+}
+
+#[test]
+fn test_hyper_links() {
+    assert_text_eq(
+        common::parts::hyper_links,
+        r###"https://example.org/
+
+example[1]
+
+section-2
+
+example
+
+[1] https://example.org/
+"###,
+    );
+}
+
+#[test]
+fn test_complex_paragraph() {
+    assert_text_eq(
+        common::parts::complex_paragraph,
+        r###"This paragraph has a link[1], some math: x=2+2^2, a line break,
+an image: [image: logo] —  all together!
+
+[1] https://example.org/
+"###,
+    );
+}
+
+#[test]
+fn test_text_styles_narrow() {
+    assert_narrow_text_eq(
+        common::parts::text_styles,
+        r###"Here is some plain bold italic mono code
+plain strikethrough underline small caps
+superscript subscript text.
+"###,
+    );
+}
+
+#[test]
+fn test_paragraph_alignment_narrow() {
+    assert_narrow_text_eq(
+        common::parts::paragraph_alignment,
+        r###"left-aligned
+
+                           right-aligned
+
+             center-aligned
+
+both-aligned
+"###,
+    );
+}
+
+#[test]
+fn test_complex_paragraph_narrow() {
+    assert_narrow_text_eq(
+        common::parts::complex_paragraph,
+        r###"This paragraph has a link[1], some
+math: x=2+2^2, a line break,
+an image: [image: logo] —  all together!
+
+[1] https://example.org/
+"###,
+    );
+}
+
+#[test]
+fn test_title_rule_ignores_escape_sequences() {
+    assert_text_eq(
+        || {
+            Document::default()
+                .set_title("\u{1b}[1mBold\u{1b}[0m")
+                .clone()
+        },
+        "====\n\u{1b}[1mBold\u{1b}[0m\n====\n",
+    );
+}
@@ -0,0 +1,403 @@
+use somedoc::model::block::{HasBlockContent, ImageBlock, Paragraph};
+use somedoc::model::inline::{HasInlineContent, Image};
+use somedoc::model::Document;
+use somedoc::write::OutputFormat;
+
+pub mod common;
+
+#[inline]
+fn assert_typst_eq(part_fn: impl Fn() -> Document, expected: &str) {
+    common::assert_serialized_eq(&part_fn(), OutputFormat::Typst, expected)
+}
+
+#[test]
+fn test_skos() {
+    assert_typst_eq(
+        common::skos::document,
+        r###"#set document(title: "Scheme: Clothing shapes, patterns, and details", author: "Simon")
+#align(center)[
+  #text(size: 1.7em, weight: "bold")[Scheme: Clothing shapes, patterns, and details]
+
+  Simon
+]
+
+= Scheme: Clothing shapes, patterns, and details
+
+// TODO:
+// - more nested lists
+// - tables
+
+#emph[Terms commonly used to describe fashion items. It includes terms for outline, fit, elements, detailing, and patterns.]
+
+#link("http://amazon.com/vocabulary/fashion-design#DesignScheme")
+
+== Labels <Labels>
+
+#quote(block: true)[
+#strong[skos:prefLabel]
+
+#strong[skos:altLabel]
+]
+
+#figure(caption: [Other labels])[
+#table(
+  columns: 2,
+  align: (left, left),
+  table.header([Label text], [Language]),
+  [Clothing shapes, patterns, and details], [#strong[en]],
+)
+]
+
+== Other Properties <Other_Properties>
+
+#line(length: 100%)
+
+Jump to: #link(<Concepts_Hierarchy>)[Concepts Hierarchy] | #link(<Concepts>)[Concepts] | #link(<Collections>)[Collections] | #link(<Appendix_-_RDF>)[Appendix - RDF]
+
+#line(length: 100%)
+
+== Concept Hierarchy <Concept_Hierarchy>
+
+- #strong[First item]
+- Second item
+  - #emph[Third item]
+- First item
+
+== Appendix - RDF <Appendix_-_RDF>
+
+```turtle
+@prefix foo: <...>
+foo:bar foo:baz 12.
+```
+
+```
+@prefix foo: <...>
+foo:bar foo:baz 12.
+```
+"###,
+    );
+}
+
+#[test]
+fn test_empty_document() {
+    assert_typst_eq(common::parts::empty_document, r###""###);
+}
+
+#[test]
+fn test_document_with_title() {
+    assert_typst_eq(
+        common::parts::document_with_title,
+        r###"#set document(title: "Test Document")
+#align(center)[
+  #text(size: 1.7em, weight: "bold")[Test Document]
+]
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_heading() {
+    assert_typst_eq(
+        common::parts::document_with_heading,
+        r###"= Test Document
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_labeled_heading() {
+    assert_typst_eq(
+        common::parts::document_with_labeled_heading,
+        r###"= Test Document <Test_Document>
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_headings() {
+    assert_typst_eq(
+        common::parts::document_with_headings,
+        r###"= Section
+
+== Sub-section
+
+=== Sub-sub-section
+
+==== Sub-sub-sub-section
+
+===== Sub-sub-sub-sub-section
+
+====== Sub-sub-sub-sub-sub-section
+
+======= Sub-sub-sub-sub-sub-sub-section
+"###,
+    );
+}
+
+#[test]
+fn test_document_with_front_matter() {
+    assert_typst_eq(
+        common::parts::document_with_front_matter,
+        r###"#outline()
+
+#outline(title: [Figures], target: figure.where(kind: image))
+
+#outline(title: [Tables], target: figure.where(kind: table))
+
+#outline(title: [Equations], target: math.equation.where(block: true))
+
+#outline(title: [Listings], target: figure.where(kind: raw))
+
+= Section One
+
+= Section Two
+"###,
+    );
+}
+
+#[test]
+fn test_paragraph_alignment() {
+    assert_typst_eq(
+        common::parts::paragraph_alignment,
+        r###"left-aligned
+
+#align(right)[right-aligned]
+
+#align(center)[center-aligned]
+
+#par(justify: true)[both-aligned]
+"###,
+    );
+}
+
+#[test]
+fn test_ordered_list() {
+    assert_typst_eq(
+        common::parts::ordered_list,
+        r###"+ one
++ two
++ three
+"###,
+    );
+}
+
+#[test]
+fn test_labeled_ordered_list() {
+    assert_typst_eq(
+        common::parts::labeled_ordered_list,
+        r###"+ one
++ two
++ three
+"###,
+    );
+}
+
+#[test]
+fn test_unordered_list() {
+    assert_typst_eq(
+        common::parts::unordered_list,
+        r###"- one
+- two
+- three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_ordered_list() {
+    assert_typst_eq(
+        common::parts::nested_ordered_list,
+        r###"+ one
++ two
+  + inner one
+  + inner two
++ three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_unordered_list() {
+    assert_typst_eq(
+        common::parts::nested_unordered_list,
+        r###"- one
+- two
+  - inner one
+  - inner two
+- three
+"###,
+    );
+}
+
+#[test]
+fn test_nested_mixed_lists() {
+    assert_typst_eq(
+        common::parts::nested_mixed_lists,
+        r###"- one
+- two
+  + inner one
+    - inner inner one
+  + inner two
+- three
+"###,
+    );
+}
+
+#[test]
+fn test_definition_list() {
+    assert_typst_eq(
+        common::parts::definition_list,
+        r###"/ Universe: Big, really big
+"###,
+    );
+}
+
+#[test]
+fn test_image_block() {
+    assert_typst_eq(
+        common::parts::image_block,
+        r###"#link("https://example.org/example.png")
+"###,
+    );
+}
+
+#[test]
+fn test_image_block_with_label_and_caption() {
+    assert_typst_eq(
+        common::parts::image_block_with_label_and_caption,
+        r###"#figure(caption: [An Example Image])[
+#link("https://example.org/example.png")
+] <img:example>
+"###,
+    );
+}
+
+#[test]
+fn test_math_block() {
+    assert_typst_eq(
+        common::parts::math_block,
+        r###"$ x=2+2^2 $
+"###,
+    );
+}
+
+#[test]
+fn test_math_block_with_label_and_caption() {
+    assert_typst_eq(
+        common::parts::math_block_with_label_and_caption,
+        r###"#figure(caption: [Example Math])[
+$ x=2+2^2 $
+] <math:example>
+"###,
+    );
+}
+
+#[test]
+fn test_block_quote() {
+    assert_typst_eq(
+        common::parts::block_quote,
+        r###"#quote(block: true)[
+a block quote
+]
+"###,
+    );
+}
+
+#[test]
+fn test_nested_block_quotes() {
+    assert_typst_eq(
+        common::parts::nested_block_quotes,
+        r###"#quote(block: true)[
+a block quote
+
+#quote(block: true)[
+another block quote
+]
+]
+"###,
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_typst_eq(
+        common::parts::text_styles,
+        r###"Here is some plain #strong[bold] #emph[italic] `mono` `code` plain #strike[strikethrough] #underline[underline] #smallcaps[small caps] #super[superscript] #sub[subscript] text.
+"###,
+    );
+}
+
+#[test]
+fn test_nested_text_styles() {
+    assert_typst_eq(
+        common::parts::nested_text_styles,
+        r###"Here is some #strong[#emph[bold italic]] text.
+
+Here is some bold italic plain text.
+
+Here is some #emph[bold plain italic] text.
+"###,
+    );
+}
+
+#[test]
+fn test_hyper_links() {
+    assert_typst_eq(
+        common::parts::hyper_links,
+        r###"#link("https://example.org/")
+
+#link("https://example.org/")[example]
+
+#link(<section-2>)[section-2]
+
+#link(<section-2>)[example]
+"###,
+    );
+}
+
+#[test]
+fn test_complex_paragraph() {
+    assert_typst_eq(
+        common::parts::complex_paragraph,
+        r###"This paragraph has #link("https://example.org/")[a link], some math:~$x=2+2^2$, a line break, \
+an image:~#link("https://example.org/favicon.png")[logo]~---~ all together!
+"###,
+    );
+}
+
+#[test]
+fn test_markup_escapes() {
+    assert_typst_eq(
+        || {
+            Document::default()
+                .add_paragraph(Paragraph::from(
+                    "= not a heading, *not* bold $5 // at https://example.org",
+                ))
+                .add_paragraph(Paragraph::from("1. not a list <tag> #hash"))
+                .clone()
+        },
+        r###"\= not a heading, \*not\* bold \$5 \// at https://example.org
+
+1\. not a list \<tag\> \#hash
+"###,
+    );
+}
+
+#[test]
+fn test_local_images() {
+    assert_typst_eq(
+        || {
+            Document::default()
+                .add_image(ImageBlock::from(Image::with_alt_text(
+                    "images/diagram.png",
+                    "A diagram",
+                )))
+                .add_paragraph(Paragraph::image(Image::new("logo.png")))
+                .clone()
+        },
+        r###"#image("images/diagram.png", alt: "A diagram")
+
+#box(image("logo.png"))
+"###,
+    );
+}
@@ -1,4 +1,8 @@
 use somedoc::write::markdown::MarkdownFlavor;
+use somedoc::write::odf::OdfPackage;
+use somedoc::write::slack::SlackFormat;
+use somedoc::write::terminal::TerminalConfig;
+use somedoc::write::text::PlainTextConfig;
 use somedoc::write::OutputFormat;
 use std::str::FromStr;
 
@@ -7,6 +11,53 @@ fn test_to_string() {
     assert_eq!(OutputFormat::Html.to_string(), "html".to_string());
     assert_eq!(OutputFormat::Json.to_string(), "json".to_string());
     assert_eq!(OutputFormat::Latex.to_string(), "latex".to_string());
+    assert_eq!(OutputFormat::PandocJson.to_string(), "pandoc".to_string());
+    assert_eq!(OutputFormat::AsciiDoc.to_string(), "asciidoc".to_string());
+    assert_eq!(
+        OutputFormat::ReStructuredText.to_string(),
+        "rst".to_string()
+    );
+    assert_eq!(OutputFormat::Man.to_string(), "man".to_string());
+    assert_eq!(OutputFormat::Typst.to_string(), "typst".to_string());
+    assert_eq!(OutputFormat::DocBook.to_string(), "docbook".to_string());
+    assert_eq!(OutputFormat::Epub.to_string(), "epub".to_string());
+    assert_eq!(
+        OutputFormat::OpenDocument(OdfPackage::Flat).to_string(),
+        "fodt".to_string()
+    );
+    assert_eq!(
+        OutputFormat::OpenDocument(OdfPackage::Zipped).to_string(),
+        "odt".to_string()
+    );
+    assert_eq!(OutputFormat::Docx.to_string(), "docx".to_string());
+    assert_eq!(OutputFormat::Rtf.to_string(), "rtf".to_string());
+    assert_eq!(
+        OutputFormat::Confluence.to_string(),
+        "confluence".to_string()
+    );
+    assert_eq!(OutputFormat::MediaWiki.to_string(), "mediawiki".to_string());
+    assert_eq!(OutputFormat::Jira.to_string(), "jira".to_string());
+    assert_eq!(
+        OutputFormat::Slack(SlackFormat::Mrkdwn).to_string(),
+        "slack".to_string()
+    );
+    assert_eq!(
+        OutputFormat::Slack(SlackFormat::BlockKit).to_string(),
+        "slack+blockkit".to_string()
+    );
+    assert_eq!(OutputFormat::Gemini.to_string(), "gemini".to_string());
+    assert_eq!(
+        OutputFormat::Terminal(Default::default()).to_string(),
+        "terminal".to_string()
+    );
+    assert_eq!(
+        OutputFormat::Terminal(TerminalConfig::new(80, false)).to_string(),
+        "terminal+nocolor".to_string()
+    );
+    assert_eq!(
+        OutputFormat::Terminal(TerminalConfig::new(100, false)).to_string(),
+        "terminal+100,nocolor".to_string()
+    );
     assert_eq!(
         OutputFormat::Markdown(Default::default()).to_string(),
         "markdown+commonmark".to_string()
@@ -23,6 +74,10 @@ fn test_to_string() {
         OutputFormat::Markdown(MarkdownFlavor::GitHub).to_string(),
         "markdown+gfm".to_string()
     );
+    assert_eq!(
+        OutputFormat::Markdown(MarkdownFlavor::Pandoc).to_string(),
+        "markdown+pandoc".to_string()
+    );
     assert_eq!(
         OutputFormat::Markdown(MarkdownFlavor::Multi).to_string(),
         "markdown+multi".to_string()
@@ -45,6 +100,123 @@ fn test_from_str() {
         OutputFormat::from_str("latex").unwrap(),
         OutputFormat::Latex
     );
+    assert_eq!(
+        OutputFormat::from_str("pandoc").unwrap(),
+        OutputFormat::PandocJson
+    );
+    assert_eq!(
+        OutputFormat::from_str("pandoc-json").unwrap(),
+        OutputFormat::PandocJson
+    );
+    assert_eq!(
+        OutputFormat::from_str("asciidoc").unwrap(),
+        OutputFormat::AsciiDoc
+    );
+    assert_eq!(
+        OutputFormat::from_str("adoc").unwrap(),
+        OutputFormat::AsciiDoc
+    );
+    assert_eq!(
+        OutputFormat::from_str("rst").unwrap(),
+        OutputFormat::ReStructuredText
+    );
+    assert_eq!(OutputFormat::from_str("org").unwrap(), OutputFormat::Org);
+    assert_eq!(
+        OutputFormat::from_str("text").unwrap(),
+        OutputFormat::PlainText(Default::default())
+    );
+    assert_eq!(
+        OutputFormat::from_str("text+72").unwrap(),
+        OutputFormat::PlainText(PlainTextConfig::with_width(72))
+    );
+    assert!(OutputFormat::from_str("text+wide").is_err());
+    assert_eq!(
+        OutputFormat::from_str("terminal").unwrap(),
+        OutputFormat::Terminal(Default::default())
+    );
+    assert_eq!(
+        OutputFormat::from_str("ansi+100").unwrap(),
+        OutputFormat::Terminal(TerminalConfig::new(100, true))
+    );
+    assert_eq!(
+        OutputFormat::from_str("term+100,nocolor").unwrap(),
+        OutputFormat::Terminal(TerminalConfig::new(100, false))
+    );
+    assert!(OutputFormat::from_str("terminal+mono").is_err());
+    assert_eq!(OutputFormat::from_str("man").unwrap(), OutputFormat::Man);
+    assert_eq!(OutputFormat::from_str("roff").unwrap(), OutputFormat::Man);
+    assert_eq!(
+        OutputFormat::from_str("typst").unwrap(),
+        OutputFormat::Typst
+    );
+    assert_eq!(OutputFormat::from_str("typ").unwrap(), OutputFormat::Typst);
+    assert_eq!(
+        OutputFormat::from_str("docbook").unwrap(),
+        OutputFormat::DocBook
+    );
+    assert_eq!(
+        OutputFormat::from_str("docbook5").unwrap(),
+        OutputFormat::DocBook
+    );
+    assert_eq!(
+        OutputFormat::from_str("dbk").unwrap(),
+        OutputFormat::DocBook
+    );
+    assert_eq!(
+        OutputFormat::from_str("fodt").unwrap(),
+        OutputFormat::OpenDocument(OdfPackage::Flat)
+    );
+    assert_eq!(
+        OutputFormat::from_str("odt").unwrap(),
+        OutputFormat::OpenDocument(OdfPackage::Zipped)
+    );
+    assert_eq!(
+        OutputFormat::from_str("odf").unwrap(),
+        OutputFormat::OpenDocument(OdfPackage::Flat)
+    );
+    assert_eq!(
+        OutputFormat::from_str("odf+odt").unwrap(),
+        OutputFormat::OpenDocument(OdfPackage::Zipped)
+    );
+    assert!(OutputFormat::from_str("odf+docx").is_err());
+    assert_eq!(OutputFormat::from_str("docx").unwrap(), OutputFormat::Docx);
+    assert_eq!(OutputFormat::from_str("word").unwrap(), OutputFormat::Docx);
+    assert_eq!(OutputFormat::from_str("rtf").unwrap(), OutputFormat::Rtf);
+    assert_eq!(
+        OutputFormat::from_str("confluence").unwrap(),
+        OutputFormat::Confluence
+    );
+    assert_eq!(
+        OutputFormat::from_str("mediawiki").unwrap(),
+        OutputFormat::MediaWiki
+    );
+    assert_eq!(
+        OutputFormat::from_str("wikitext").unwrap(),
+        OutputFormat::MediaWiki
+    );
+    assert_eq!(OutputFormat::from_str("jira").unwrap(), OutputFormat::Jira);
+    assert_eq!(
+        OutputFormat::from_str("slack").unwrap(),
+        OutputFormat::Slack(SlackFormat::Mrkdwn)
+    );
+    assert_eq!(
+        OutputFormat::from_str("slack+blocks").unwrap(),
+        OutputFormat::Slack(SlackFormat::BlockKit)
+    );
+    assert_eq!(
+        OutputFormat::from_str("mrkdwn").unwrap(),
+        OutputFormat::Slack(SlackFormat::Mrkdwn)
+    );
+    assert_eq!(
+        OutputFormat::from_str("blockkit").unwrap(),
+        OutputFormat::Slack(SlackFormat::BlockKit)
+    );
+    assert!(OutputFormat::from_str("slack+html").is_err());
+    assert_eq!(
+        OutputFormat::from_str("gemini").unwrap(),
+        OutputFormat::Gemini
+    );
+    assert_eq!(OutputFormat::from_str("gmi").unwrap(), OutputFormat::Gemini);
     assert_eq!(
         OutputFormat::from_str("markdown").unwrap(),
         OutputFormat::Markdown(Default::default())
@@ -61,6 +233,10 @@ fn test_from_str() {
         OutputFormat::from_str("markdown+gfm").unwrap(),
         OutputFormat::Markdown(MarkdownFlavor::GitHub)
     );
+    assert_eq!(
+        OutputFormat::from_str("markdown+pandoc").unwrap(),
+        OutputFormat::Markdown(MarkdownFlavor::Pandoc)
+    );
     assert_eq!(
         OutputFormat::from_str("markdown+multi").unwrap(),
         OutputFormat::Markdown(MarkdownFlavor::Multi)
@@ -0,0 +1,272 @@
+use somedoc::model::block::{BlockContent, HasCaption, HasLabel, HeadingLevel, ListItem, ListKind};
+use somedoc::model::document::Metadata;
+use somedoc::model::inline::{HasInlineContent, HyperLinkTarget, InlineContent, SpanStyle};
+use somedoc::model::{Document, HasInnerContent, HasStyles};
+use somedoc::read::html::from_str;
+use somedoc::write::{write_document_to_string, OutputFormat};
+
+pub mod common;
+
+#[inline]
+fn assert_html_reads(html: &str, is_valid: impl Fn(Document) -> bool) {
+    let result = from_str(html);
+    assert!(result.is_ok());
+    let doc = result.unwrap();
+    println!("{:#?}", doc);
+    assert!(is_valid(doc));
+}
+
+#[inline]
+fn round_trip(part_fn: impl Fn() -> Document) -> Document {
+    let html = write_document_to_string(&part_fn(), OutputFormat::Html).unwrap();
+    let doc = from_str(&html).unwrap();
+    println!("{:#?}", doc);
+    doc
+}
+
+#[test]
+fn test_empty_document() {
+    assert_html_reads("", |doc| !doc.has_metadata() && !doc.has_inner());
+}
+
+#[test]
+fn test_unbalanced_author_meta() {
+    for (content, name) in [("Bob <x", "Bob <x"), ("Bob <x> y", "Bob <x> y")] {
+        assert_html_reads(
+            &format!(
+                "<html><head><meta name=\"author\" content=\"{}\"></head><body></body></html>",
+                content
+            ),
+            |doc| {
+                doc.metadata()
+                    == &vec![Metadata::Author(somedoc::model::document::Author {
+                        name: name.to_string(),
+                        email: None,
+                        organization: None,
+                    })]
+            },
+        );
+    }
+}
+
+#[test]
+fn test_headings_and_labels() {
+    assert_html_reads(
+        "<h1 id=\"top\">One</h1><h3>Three</h3><h6 id=\"not valid\">Six</h6>",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::Heading(one), BlockContent::Heading(three), BlockContent::Heading(six)] => {
+                one.level() == &HeadingLevel::Section
+                    && one.label().as_ref().unwrap().to_string() == "top"
+                    && three.level() == &HeadingLevel::SubSubSection
+                    && six.level() == &HeadingLevel::SubSubSubSubSubSection
+                    && six.label().is_none()
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_unknown_tags_flattened() {
+    assert_html_reads(
+        "<section><p>Some <span class=\"x\"><mark>marked</mark></span> text.</p><custom>Loose text</custom></section>",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::Paragraph(first), BlockContent::Paragraph(second)] => {
+                first.unformatted_string() == "Some marked text."
+                    && second.unformatted_string() == "Loose text"
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_html_reads(
+        "<p><em>i</em> <strong>b</strong> <del>d</del> <ins>u</ins> <sup>p</sup> <sub>s</sub> <code>c</code></p>",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::Paragraph(paragraph)] => {
+                let styles: Vec<Vec<SpanStyle>> = paragraph
+                    .inner()
+                    .iter()
+                    .filter_map(|inline| match inline {
+                        InlineContent::Span(span) => Some(span.styles().clone()),
+                        _ => None,
+                    })
+                    .collect();
+                styles
+                    == vec![
+                        vec![SpanStyle::Italic],
+                        vec![SpanStyle::Bold],
+                        vec![SpanStyle::Strikethrough],
+                        vec![SpanStyle::Underline],
+                        vec![SpanStyle::Superscript],
+                        vec![SpanStyle::Subscript],
+                        vec![SpanStyle::Code],
+                    ]
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_implied_end_tags() {
+    assert_html_reads(
+        "<ul><li>one<li>two</ul><p>first<p>second",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::List(list), BlockContent::Paragraph(_), BlockContent::Paragraph(_)] => {
+                list.inner().len() == 2
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_code_block() {
+    assert_html_reads(
+        "<pre><code class=\"language-rust\">fn main() {\n    println!(\"&lt;hi&gt;\");\n}\n</code></pre>",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::CodeBlock(code)] => {
+                code.language() == &Some("rust".to_string())
+                    && code.code() == "fn main() {\n    println!(\"<hi>\");\n}"
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_round_trip_skos() {
+    let doc = round_trip(common::skos::document);
+    assert_eq!(
+        doc.metadata(),
+        &vec![
+            Metadata::Title("Scheme: Clothing shapes, patterns, and details".to_string()),
+            Metadata::Author(somedoc::model::document::Author {
+                name: "Simon".to_string(),
+                email: None,
+                organization: None
+            }),
+        ]
+    );
+    assert!(matches!(
+        &doc.inner()[1],
+        BlockContent::Comment(comment) if comment == "TODO:\n- more nested lists\n- tables"
+    ));
+    assert!(matches!(
+        &doc.inner()[4],
+        BlockContent::Heading(heading) if heading.label().as_ref().unwrap().to_string() == "Labels"
+    ));
+    let table = doc
+        .inner()
+        .iter()
+        .find_map(|block| match block {
+            BlockContent::Table(table) => Some(table),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(table.caption().as_ref().unwrap().inner(), "Other labels");
+    assert_eq!(table.columns().len(), 2);
+    assert_eq!(table.rows().len(), 1);
+    let code = doc
+        .inner()
+        .iter()
+        .find_map(|block| match block {
+            BlockContent::CodeBlock(code) => Some(code),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(code.language(), &Some("turtle".to_string()));
+    assert_eq!(code.code(), "@prefix foo: <...>\nfoo:bar foo:baz 12.");
+    assert!(matches!(
+        doc.inner().last().unwrap(),
+        BlockContent::Formatted(formatted) if formatted.inner() == "@prefix foo: <...>\nfoo:bar foo:baz 12."
+    ));
+}
+
+#[test]
+fn test_round_trip_nested_mixed_lists() {
+    let doc = round_trip(common::parts::nested_mixed_lists);
+    match doc.inner().as_slice() {
+        [BlockContent::List(list)] => {
+            assert_eq!(list.inner().len(), 4);
+            assert!(matches!(
+                &list.inner()[2],
+                ListItem::List(inner) if *inner.kind() == ListKind::Ordered
+            ));
+        }
+        _ => panic!("expected a single list"),
+    }
+}
+
+#[test]
+fn test_round_trip_definition_list() {
+    let doc = round_trip(common::parts::definition_list);
+    assert!(matches!(
+        doc.inner().as_slice(),
+        [BlockContent::DefinitionList(list)] if list.inner().len() == 1
+            && list.inner()[0].term() == "Universe"
+            && list.inner()[0].text().unformatted_string() == "Big, really big"
+    ));
+}
+
+#[test]
+fn test_round_trip_image_and_math_blocks() {
+    let doc = round_trip(common::parts::image_block_with_label_and_caption);
+    assert!(matches!(
+        doc.inner().as_slice(),
+        [BlockContent::ImageBlock(image)] if image.label().as_ref().unwrap().to_string() == "img:example"
+            && image.inner().inner() == "https://example.org/example.png"
+    ));
+    let doc = round_trip(common::parts::math_block_with_label_and_caption);
+    assert!(matches!(
+        doc.inner().as_slice(),
+        [BlockContent::MathBlock(math)] if math.label().as_ref().unwrap().to_string() == "math:example"
+            && math.inner().inner() == "x=2+2^2"
+    ));
+}
+
+#[test]
+fn test_round_trip_hyper_links() {
+    let doc = round_trip(common::parts::hyper_links);
+    let links: Vec<(HyperLinkTarget, bool)> = doc
+        .inner()
+        .iter()
+        .filter_map(|block| match block {
+            BlockContent::Paragraph(paragraph) => match paragraph.inner().as_slice() {
+                [InlineContent::HyperLink(link)] => {
+                    Some((link.target().clone(), link.caption().is_some()))
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(links.len(), 4);
+    assert!(matches!(&links[0], (HyperLinkTarget::External(_), false)));
+    assert!(matches!(&links[1], (HyperLinkTarget::External(_), true)));
+    assert!(matches!(&links[2], (HyperLinkTarget::Internal(_), false)));
+    assert!(matches!(&links[3], (HyperLinkTarget::Internal(_), true)));
+}
+
+#[test]
+fn test_round_trip_complex_paragraph() {
+    let doc = round_trip(common::parts::complex_paragraph);
+    match doc.inner().as_slice() {
+        [BlockContent::Paragraph(paragraph)] => {
+            let inner = paragraph.inner();
+            assert!(inner.iter().any(
+                |inline| matches!(inline, InlineContent::Math(math) if math.inner() == "x=2+2^2")
+            ));
+            assert!(inner
+                .iter()
+                .any(|inline| matches!(inline, InlineContent::LineBreak)));
+            assert!(inner
+                .iter()
+                .any(|inline| matches!(inline, InlineContent::Image(_))));
+        }
+        _ => panic!("expected a single paragraph"),
+    }
+}
@@ -1,5 +1,9 @@
+use somedoc::error::ErrorKind;
+use somedoc::model::block::BlockContent;
+use somedoc::model::inline::InlineContent;
 use somedoc::model::{Document, HasInnerContent};
-use somedoc::read::from_str;
+use somedoc::read::migration::Migrations;
+use somedoc::read::{from_str, from_str_with};
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -98,3 +102,64 @@ fn test_definition_list() {
         |_| true,
     );
 }
+
+#[test]
+fn test_missing_version() {
+    let result = from_str(r###"{"content":[]}"###);
+    assert!(matches!(
+        result.unwrap_err().kind(),
+        ErrorKind::IncompatibleVersion(version) if version.is_empty()
+    ));
+}
+
+#[test]
+fn test_incompatible_versions() {
+    for version in &["0.1.9", "1.0.0", "0.2.999", "not.a.version"] {
+        let result = from_str(&format!("{{\"version\":\"{}\",\"content\":[]}}", version));
+        assert!(matches!(
+            result.unwrap_err().kind(),
+            ErrorKind::IncompatibleVersion(found) if found == version
+        ));
+    }
+}
+
+#[test]
+fn test_migrate_hyper_link_images() {
+    let result = from_str(
+        r###"{"version":"0.2.5","content":[{"ImageBlock":{"image":{"target":{"External":"https://example.org/example.png"},"caption":"alt"},"label":"img"}},{"Paragraph":{"inner":[{"Image":{"target":{"Internal":"img"}}}],"alignment":"Left"}}]}"###,
+    );
+    let doc = result.unwrap();
+    match doc.inner().as_slice() {
+        [BlockContent::ImageBlock(block), BlockContent::Paragraph(paragraph)] => {
+            assert_eq!(block.inner().inner(), "https://example.org/example.png");
+            assert_eq!(block.inner().alt_text(), &Some("alt".to_string()));
+            assert!(matches!(
+                paragraph.inner().as_slice(),
+                [InlineContent::Image(image)] if image.inner() == "img"
+            ));
+        }
+        _ => panic!("expected an image block and paragraph"),
+    }
+}
+
+#[test]
+fn test_registered_migration() {
+    let mut migrations = Migrations::empty();
+    let _ = migrations
+        .add(VERSION, |mut json| {
+            json["content"] = serde_json::json!([{"Comment": "migrated"}]);
+            Ok(json)
+        })
+        .unwrap();
+    let doc = from_str_with(r###"{"version":"0.2.3","content":[]}"###, &migrations).unwrap();
+    assert!(matches!(
+        doc.inner().as_slice(),
+        [BlockContent::Comment(comment)] if comment == "migrated"
+    ));
+    assert!(from_str_with(
+        r###"{"version":"0.2.3","content":[]}"###,
+        &Migrations::empty()
+    )
+    .is_ok());
+    assert!(migrations.add("0.2", Ok).is_err());
+}
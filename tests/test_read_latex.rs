@@ -0,0 +1,375 @@
+use somedoc::model::block::{
+    Alignment, BlockContent, FrontMatter, HasAlignment, HasCaption, HasLabel, HeadingLevel,
+    ListItem, ListKind,
+};
+use somedoc::model::document::{Author, Metadata};
+use somedoc::model::inline::{
+    Character, HasInlineContent, HyperLinkTarget, InlineContent, SpanStyle,
+};
+use somedoc::model::{Document, HasInnerContent, HasStyles};
+use somedoc::read::latex::from_str;
+use somedoc::write::{write_document_to_string, OutputFormat};
+
+pub mod common;
+
+#[inline]
+fn assert_latex_reads(latex: &str, is_valid: impl Fn(Document) -> bool) {
+    let result = from_str(latex);
+    assert!(result.is_ok());
+    let doc = result.unwrap();
+    println!("{:#?}", doc);
+    assert!(is_valid(doc));
+}
+
+#[inline]
+fn round_trip(part_fn: impl Fn() -> Document) -> Document {
+    let latex = write_document_to_string(&part_fn(), OutputFormat::Latex).unwrap();
+    let doc = from_str(&latex).unwrap();
+    println!("{:#?}", doc);
+    doc
+}
+
+#[test]
+fn test_empty_document() {
+    assert_latex_reads("", |doc| !doc.has_metadata() && !doc.has_inner());
+}
+
+#[test]
+fn test_preamble_metadata() {
+    assert_latex_reads(
+        "\\documentclass{article}\n\\usepackage[utf8]{inputenc}\n\\title{A Title}\n\\author{Simon\\\\simon@example.org\\\\Example Org \\and Someone}\n\\date{2020}\n\\begin{document}\n\\maketitle\nText.\n\\end{document}\n",
+        |doc| {
+            doc.metadata()
+                == &vec![
+                    Metadata::Title("A Title".to_string()),
+                    Metadata::Author(Author {
+                        name: "Simon".to_string(),
+                        email: Some("simon@example.org".to_string()),
+                        organization: Some("Example Org".to_string()),
+                    }),
+                    Metadata::Author(Author {
+                        name: "Someone".to_string(),
+                        email: None,
+                        organization: None,
+                    }),
+                    Metadata::Date("2020".to_string()),
+                ]
+                && matches!(doc.inner().as_slice(), [BlockContent::Paragraph(_)])
+        },
+    );
+}
+
+#[test]
+fn test_headings_and_labels() {
+    assert_latex_reads(
+        "\\section{One}\n\\label{one}\n\n\\label{three}\\subsubsection{Three}\n\n\\subsubsubsubsubsection*{Six}\n",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::Heading(one), BlockContent::Heading(three), BlockContent::Heading(six)] => {
+                one.level() == &HeadingLevel::Section
+                    && one.label().as_ref().unwrap().to_string() == "one"
+                    && three.level() == &HeadingLevel::SubSubSection
+                    && three.label().as_ref().unwrap().to_string() == "three"
+                    && six.level() == &HeadingLevel::SubSubSubSubSubSection
+                    && six.label().is_none()
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_latex_reads(
+        "\\textit{i} \\textbf{b} \\sout{d} \\underline{u} \\textsuperscript{p} \\textsubscript{s} \\texttt{c}",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::Paragraph(paragraph)] => {
+                let styles: Vec<Vec<SpanStyle>> = paragraph
+                    .inner()
+                    .iter()
+                    .filter_map(|inline| match inline {
+                        InlineContent::Span(span) => Some(span.styles().clone()),
+                        _ => None,
+                    })
+                    .collect();
+                styles
+                    == vec![
+                        vec![SpanStyle::Italic],
+                        vec![SpanStyle::Bold],
+                        vec![SpanStyle::Strikethrough],
+                        vec![SpanStyle::Underline],
+                        vec![SpanStyle::Superscript],
+                        vec![SpanStyle::Subscript],
+                        vec![SpanStyle::Mono],
+                    ]
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_characters_and_escapes() {
+    assert_latex_reads(
+        "50\\% of A\\&B~--- or---not\\\\ {grouped} text",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::Paragraph(paragraph)] => {
+                matches!(
+                    paragraph.inner().as_slice(),
+                    [InlineContent::Text(first), InlineContent::Character(Character::NonBreakSpace), InlineContent::Character(Character::EmDash), _, InlineContent::Character(Character::EmDash), _, InlineContent::LineBreak, InlineContent::Text(last)]
+                        if first.inner() == "50% of A&B" && last.inner() == "grouped text"
+                )
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_comments() {
+    assert_latex_reads(
+        "% a comment\n%% another\nSome text. % trailing\n\nMore text.\n",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::Comment(first), BlockContent::Paragraph(_), BlockContent::Comment(second), BlockContent::Paragraph(_)] => {
+                first == "a comment\nanother" && second == "trailing"
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_unknown_environment_retained() {
+    assert_latex_reads(
+        "\\begin{tikzpicture}\n\\draw (0,0) -- (1,1);\n\\end{tikzpicture}\n\nSome \\unknown{text}.",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::Comment(comment), BlockContent::Paragraph(paragraph)] => {
+                comment.starts_with("\\begin{tikzpicture}")
+                    && comment.ends_with("\\end{tikzpicture}")
+                    && paragraph.unformatted_string() == "Some \\unknown{text}."
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_code_block_options() {
+    assert_latex_reads(
+        "\\begin{lstlisting}[language=rust, caption={Main, function}, label=lst:main]\nfn main() {}\n\\end{lstlisting}\n",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::CodeBlock(code)] => {
+                code.code() == "fn main() {}"
+                    && code.language() == &Some("rust".to_string())
+                    && code.caption().as_ref().unwrap().inner() == "Main, function"
+                    && code.label().as_ref().unwrap().to_string() == "lst:main"
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_table_alignment() {
+    assert_latex_reads(
+        "\\begin{tabular}{|l|r|c|p{2cm}|}\n\\hline\nOne & Two & Three & Four \\\\\n\\hline\na & b & c & d \\\\\ne & f & g & h \\\\\n\\hline\n\\end{tabular}\n",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::Table(table)] => {
+                let alignments: Vec<Alignment> = table
+                    .columns()
+                    .iter()
+                    .map(|column| column.alignment().clone())
+                    .collect();
+                alignments
+                    == vec![
+                        Alignment::Left,
+                        Alignment::Right,
+                        Alignment::Centered,
+                        Alignment::Justified,
+                    ]
+                    && table.columns()[2].text() == "Three"
+                    && table.rows().len() == 2
+                    && table.rows()[1].cells()[3].unformatted_string() == "h"
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_round_trip_skos() {
+    let doc = round_trip(common::skos::document);
+    assert_eq!(
+        doc.metadata(),
+        &vec![
+            Metadata::Title("Scheme: Clothing shapes, patterns, and details".to_string()),
+            Metadata::Author(Author {
+                name: "Simon".to_string(),
+                email: None,
+                organization: None
+            }),
+        ]
+    );
+    assert!(matches!(
+        &doc.inner()[1],
+        BlockContent::Comment(comment) if comment == "TODO:\n- more nested lists\n- tables"
+    ));
+    assert!(matches!(
+        &doc.inner()[4],
+        BlockContent::Heading(heading) if heading.label().as_ref().unwrap().to_string() == "Labels"
+    ));
+    assert!(matches!(&doc.inner()[5], BlockContent::Quote(quote) if quote.inner().len() == 2));
+    let table = doc
+        .inner()
+        .iter()
+        .find_map(|block| match block {
+            BlockContent::Table(table) => Some(table),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(table.caption().as_ref().unwrap().inner(), "Other labels");
+    assert_eq!(table.columns().len(), 2);
+    assert_eq!(table.rows().len(), 1);
+    assert_eq!(
+        doc.inner()
+            .iter()
+            .filter(|block| matches!(block, BlockContent::ThematicBreak))
+            .count(),
+        2
+    );
+    let code = doc
+        .inner()
+        .iter()
+        .find_map(|block| match block {
+            BlockContent::CodeBlock(code) => Some(code),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(code.language(), &Some("turtle".to_string()));
+    assert_eq!(code.code(), "@prefix foo: <...>\nfoo:bar foo:baz 12.");
+    assert!(matches!(
+        doc.inner().last().unwrap(),
+        BlockContent::Formatted(formatted) if formatted.inner() == "@prefix foo: <...>\nfoo:bar foo:baz 12."
+    ));
+}
+
+#[test]
+fn test_round_trip_front_matter() {
+    let doc = round_trip(common::parts::document_with_front_matter);
+    assert_eq!(
+        doc.inner()
+            .iter()
+            .filter(|block| matches!(block, BlockContent::FrontMatter(_)))
+            .count(),
+        5
+    );
+    assert!(matches!(
+        doc.inner().first(),
+        Some(BlockContent::FrontMatter(FrontMatter::TableOfContents))
+    ));
+}
+
+#[test]
+fn test_round_trip_nested_mixed_lists() {
+    let doc = round_trip(common::parts::nested_mixed_lists);
+    match doc.inner().as_slice() {
+        [BlockContent::List(list)] => {
+            assert_eq!(list.inner().len(), 4);
+            assert!(matches!(
+                &list.inner()[2],
+                ListItem::List(inner) if *inner.kind() == ListKind::Ordered && inner.inner().len() == 3
+            ));
+        }
+        _ => panic!("expected a single list"),
+    }
+}
+
+#[test]
+fn test_round_trip_labeled_list() {
+    let doc = round_trip(common::parts::labeled_ordered_list);
+    match doc.inner().as_slice() {
+        [BlockContent::List(list)] => {
+            assert_eq!(list.label().as_ref().unwrap().to_string(), "lst1");
+            assert!(matches!(
+                &list.inner()[1],
+                ListItem::Item(item) if item.label().as_ref().unwrap().to_string() == "lst1-itm2"
+                    && item.unformatted_string() == "two"
+            ));
+        }
+        _ => panic!("expected a single list"),
+    }
+}
+
+#[test]
+fn test_round_trip_definition_list() {
+    let doc = round_trip(common::parts::definition_list);
+    assert!(matches!(
+        doc.inner().as_slice(),
+        [BlockContent::DefinitionList(list)] if list.inner().len() == 1
+            && list.inner()[0].term() == "Universe"
+            && list.inner()[0].text().unformatted_string() == "Big, really big"
+    ));
+}
+
+#[test]
+fn test_round_trip_image_and_math_blocks() {
+    let doc = round_trip(common::parts::image_block_with_label_and_caption);
+    assert!(matches!(
+        doc.inner().as_slice(),
+        [BlockContent::ImageBlock(image)] if image.label().as_ref().unwrap().to_string() == "img:example"
+            && image.caption().as_ref().unwrap().inner() == "An Example Image"
+            && image.inner().inner() == "https://example.org/example.png"
+    ));
+    let doc = round_trip(common::parts::math_block_with_label_and_caption);
+    assert!(matches!(
+        doc.inner().as_slice(),
+        [BlockContent::MathBlock(math)] if math.label().as_ref().unwrap().to_string() == "math:example"
+            && math.caption().as_ref().unwrap().inner() == "Example Math"
+            && math.inner().inner() == "x=2+2^2"
+    ));
+}
+
+#[test]
+fn test_round_trip_hyper_links() {
+    let doc = round_trip(common::parts::hyper_links);
+    let links: Vec<(HyperLinkTarget, bool)> = doc
+        .inner()
+        .iter()
+        .filter_map(|block| match block {
+            BlockContent::Paragraph(paragraph) => match paragraph.inner().as_slice() {
+                [InlineContent::HyperLink(link)] => {
+                    Some((link.target().clone(), link.caption().is_some()))
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(links.len(), 4);
+    assert!(matches!(&links[0], (HyperLinkTarget::External(_), false)));
+    assert!(matches!(&links[1], (HyperLinkTarget::External(_), true)));
+    assert!(matches!(&links[2], (HyperLinkTarget::Internal(_), false)));
+    assert!(matches!(&links[3], (HyperLinkTarget::Internal(_), true)));
+}
+
+#[test]
+fn test_round_trip_complex_paragraph() {
+    let doc = round_trip(common::parts::complex_paragraph);
+    match doc.inner().as_slice() {
+        [BlockContent::Paragraph(paragraph)] => {
+            let inner = paragraph.inner();
+            assert!(inner.iter().any(
+                |inline| matches!(inline, InlineContent::Math(math) if math.inner() == "x=2+2^2")
+            ));
+            assert!(inner
+                .iter()
+                .any(|inline| matches!(inline, InlineContent::LineBreak)));
+            assert!(inner
+                .iter()
+                .any(|inline| matches!(inline, InlineContent::Image(_))));
+            assert!(inner
+                .iter()
+                .any(|inline| matches!(inline, InlineContent::Character(Character::EmDash))));
+        }
+        _ => panic!("expected a single paragraph"),
+    }
+}
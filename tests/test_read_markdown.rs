@@ -0,0 +1,346 @@
+use somedoc::model::block::{
+    Alignment, BlockContent, HasAlignment, HasCaption, HeadingLevel, ListItem, ListKind,
+};
+use somedoc::model::document::Metadata;
+use somedoc::model::inline::{HasInlineContent, HyperLinkTarget, InlineContent, SpanStyle};
+use somedoc::model::{Document, HasInnerContent, HasStyles};
+use somedoc::read::markdown::{from_str, from_str_with};
+use somedoc::write::markdown::MarkdownFlavor;
+use somedoc::write::write_document_to_string;
+
+pub mod common;
+
+#[inline]
+fn assert_markdown_reads(markdown: &str, is_valid: impl Fn(Document) -> bool) {
+    let result = from_str(markdown);
+    assert!(result.is_ok());
+    let doc = result.unwrap();
+    println!("{:#?}", doc);
+    assert!(is_valid(doc));
+}
+
+#[inline]
+fn assert_gfm_reads(markdown: &str, is_valid: impl Fn(Document) -> bool) {
+    let result = from_str_with(markdown, MarkdownFlavor::GitHub);
+    assert!(result.is_ok());
+    let doc = result.unwrap();
+    println!("{:#?}", doc);
+    assert!(is_valid(doc));
+}
+
+#[inline]
+fn round_trip(part_fn: impl Fn() -> Document) -> Document {
+    round_trip_with(part_fn, MarkdownFlavor::CommonMark)
+}
+
+#[inline]
+fn round_trip_with(part_fn: impl Fn() -> Document, flavor: MarkdownFlavor) -> Document {
+    let markdown = write_document_to_string(&part_fn(), flavor.clone().into()).unwrap();
+    from_str_with(&markdown, flavor).unwrap()
+}
+
+#[test]
+fn test_empty_document() {
+    assert_markdown_reads("", |doc| !doc.has_metadata() && !doc.has_inner());
+}
+
+#[test]
+fn test_headings() {
+    assert_markdown_reads("# One\n\n## Two\n\nThree\n-----\n\n###### Six\n", |doc| {
+        let levels: Vec<HeadingLevel> = doc
+            .inner()
+            .iter()
+            .filter_map(|block| match block {
+                BlockContent::Heading(heading) => Some(heading.level().clone()),
+                _ => None,
+            })
+            .collect();
+        levels
+            == vec![
+                HeadingLevel::Section,
+                HeadingLevel::SubSection,
+                HeadingLevel::SubSection,
+                HeadingLevel::SubSubSubSubSubSection,
+            ]
+    });
+}
+
+#[test]
+fn test_code_blocks() {
+    assert_markdown_reads(
+        "```rust\nfn main() {}\n```\n\n```\nplain\n```\n\n    indented\n",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::CodeBlock(first), BlockContent::CodeBlock(second), BlockContent::Formatted(third)] => {
+                first.code() == "fn main() {}"
+                    && first.language() == &Some("rust".to_string())
+                    && second.code() == "plain"
+                    && second.language().is_none()
+                    && third.inner() == "indented"
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_nested_block_quotes() {
+    assert_markdown_reads("> outer\n>\n> > inner\n", |doc| {
+        match doc.inner().as_slice() {
+            [BlockContent::Quote(outer)] => match outer.inner().as_slice() {
+                [BlockContent::Paragraph(_), BlockContent::Quote(inner)] => inner.has_inner(),
+                _ => false,
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_nested_lists() {
+    assert_markdown_reads(
+        "* one\n* two\n  1. inner one\n  1. inner two\n* three\n",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::List(list)] => {
+                *list.kind() == ListKind::Unordered
+                    && list.inner().len() == 4
+                    && match &list.inner()[2] {
+                        ListItem::List(inner) => {
+                            *inner.kind() == ListKind::Ordered && inner.inner().len() == 2
+                        }
+                        _ => false,
+                    }
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_markdown_reads("Some *italic*, **bold**, and `code` text.", |doc| match doc
+        .inner()
+        .as_slice()
+    {
+        [BlockContent::Paragraph(paragraph)] => {
+            let styles: Vec<Vec<SpanStyle>> = paragraph
+                .inner()
+                .iter()
+                .filter_map(|inline| match inline {
+                    InlineContent::Span(span) => Some(span.styles().clone()),
+                    _ => None,
+                })
+                .collect();
+            styles
+                == vec![
+                    vec![SpanStyle::Italic],
+                    vec![SpanStyle::Bold],
+                    vec![SpanStyle::Code],
+                ]
+                && paragraph.unformatted_string() == "Some italic, bold, and code text."
+        }
+        _ => false,
+    });
+}
+
+#[test]
+fn test_hyper_links() {
+    assert_markdown_reads(
+        "[example](https://example.org/) and [section](#section-2)",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::Paragraph(paragraph)] => match paragraph.inner().as_slice() {
+                [InlineContent::HyperLink(external), InlineContent::Text(_), InlineContent::HyperLink(internal)] => {
+                    external.target()
+                        == &HyperLinkTarget::External("https://example.org/".to_string())
+                        && external.caption().as_ref().unwrap().inner() == "example"
+                        && internal.is_internal()
+                }
+                _ => false,
+            },
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_image_block() {
+    assert_markdown_reads("![logo](https://example.org/example.png)", |doc| match doc
+        .inner()
+        .as_slice()
+    {
+        [BlockContent::ImageBlock(image)] => {
+            image.inner().inner() == "https://example.org/example.png"
+                && image.inner().alt_text() == &Some("logo".to_string())
+        }
+        _ => false,
+    });
+}
+
+#[test]
+fn test_round_trip_skos() {
+    let doc = round_trip(common::skos::document);
+    assert_eq!(doc.metadata().len(), 2);
+    assert_eq!(
+        doc.metadata()[0],
+        Metadata::Title("Scheme: Clothing shapes, patterns, and details".to_string())
+    );
+    assert!(matches!(&doc.metadata()[1], Metadata::Author(author) if author.name == "Simon"));
+    assert!(matches!(
+        &doc.inner()[1],
+        BlockContent::Comment(comment) if comment == "TODO:\n- more nested lists\n- tables"
+    ));
+}
+
+#[test]
+fn test_unbalanced_author_metadata() {
+    assert_markdown_reads("[_metadata_:author]:- \"Alice (foo\"\n\nText.\n", |doc| {
+        doc.metadata()
+            == &vec![Metadata::Author(somedoc::model::document::Author {
+                name: "Alice (foo".to_string(),
+                email: None,
+                organization: None,
+            })]
+    });
+}
+
+#[test]
+fn test_comment_and_metadata_in_code_blocks() {
+    assert_markdown_reads(
+        "```\n[//]: # \"not a comment\"\n[_metadata_:title]:- \"not a title\"\n```\n\n    [//]: # \"indented\"\n",
+        |doc| {
+            doc.metadata().is_empty()
+                && matches!(
+                    doc.inner().as_slice(),
+                    [BlockContent::CodeBlock(_), BlockContent::Formatted(_)]
+                )
+        },
+    );
+}
+
+#[test]
+fn test_round_trip_nested_mixed_lists() {
+    let doc = round_trip(common::parts::nested_mixed_lists);
+    assert!(
+        matches!(doc.inner().as_slice(), [BlockContent::List(list)] if list.inner().len() == 4)
+    );
+}
+
+#[test]
+fn test_round_trip_headings() {
+    let doc = round_trip(common::parts::document_with_headings);
+    assert_eq!(
+        doc.inner()
+            .iter()
+            .filter(|block| matches!(block, BlockContent::Heading(_)))
+            .count(),
+        6
+    );
+}
+
+#[test]
+fn test_unsupported_flavor() {
+    assert!(from_str_with("# Heading", MarkdownFlavor::PhpExtra).is_err());
+}
+
+#[test]
+fn test_gfm_table_alignment() {
+    assert_gfm_reads(
+        "|One|Two|Three|Four|\n|:----|----:|:---:|-----|\n|a|b|c|d|\n|e|f|g|h|\n",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::Table(table)] => {
+                let alignments: Vec<Alignment> = table
+                    .columns()
+                    .iter()
+                    .map(|column| column.alignment().clone())
+                    .collect();
+                alignments
+                    == vec![
+                        Alignment::Left,
+                        Alignment::Right,
+                        Alignment::Centered,
+                        Alignment::Justified,
+                    ]
+                    && table.columns()[2].text() == "Three"
+                    && table.rows().len() == 2
+                    && table.rows()[1].cells()[3].unformatted_string() == "h"
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_gfm_strikethrough() {
+    assert_gfm_reads("Some ~~old~~ text.", |doc| match doc.inner().as_slice() {
+        [BlockContent::Paragraph(paragraph)] => matches!(
+            &paragraph.inner()[1],
+            InlineContent::Span(span) if span.styles() == &vec![SpanStyle::Strikethrough]
+        ),
+        _ => false,
+    });
+}
+
+#[test]
+fn test_gfm_yaml_front_matter() {
+    assert_gfm_reads(
+        "---\ntitle: \"A Title\"\nkeywords: [one, two]\nauthor:\n- name: Simon\n- email: simon@example.org\n---\n\n# Heading\n",
+        |doc| {
+            doc.metadata()
+                == &vec![
+                    Metadata::Title("A Title".to_string()),
+                    Metadata::Keywords(vec!["one".to_string(), "two".to_string()]),
+                    Metadata::Author(somedoc::model::document::Author {
+                        name: "Simon".to_string(),
+                        email: Some("simon@example.org".to_string()),
+                        organization: None,
+                    }),
+                ]
+                && matches!(doc.inner().as_slice(), [BlockContent::Heading(_)])
+        },
+    );
+}
+
+#[test]
+fn test_gfm_front_matter_unbalanced_author() {
+    assert_gfm_reads("---\nauthor: Alice (ACME\n---\n\nText.\n", |doc| {
+        doc.metadata()
+            == &vec![Metadata::Author(somedoc::model::document::Author {
+                name: "Alice (ACME".to_string(),
+                email: None,
+                organization: None,
+            })]
+    });
+}
+
+#[test]
+fn test_round_trip_gfm_skos() {
+    let doc = round_trip_with(common::skos::document, MarkdownFlavor::GitHub);
+    assert_eq!(doc.metadata().len(), 2);
+    assert!(matches!(&doc.inner()[0], BlockContent::Heading(_)));
+    let table = doc
+        .inner()
+        .iter()
+        .find_map(|block| match block {
+            BlockContent::Table(table) => Some(table),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(table.columns()[0].text(), "Label text");
+    assert_eq!(table.columns()[1].text(), "Language");
+    assert_eq!(table.rows().len(), 1);
+    assert!(matches!(
+        table.rows()[0].cells()[1].inner().as_slice(),
+        [InlineContent::Span(span)] if span.styles() == &vec![SpanStyle::Bold]
+    ));
+}
+
+#[test]
+fn test_round_trip_gfm_text_styles() {
+    let doc = round_trip_with(common::parts::text_styles, MarkdownFlavor::GitHub);
+    assert!(matches!(
+        doc.inner().as_slice(),
+        [BlockContent::Paragraph(paragraph)] if paragraph.inner().iter().any(|inline| matches!(
+            inline,
+            InlineContent::Span(span) if span.styles() == &vec![SpanStyle::Strikethrough]
+        ))
+    ));
+}
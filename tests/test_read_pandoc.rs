@@ -0,0 +1,308 @@
+use somedoc::error::ErrorKind;
+use somedoc::model::block::{
+    Alignment, BlockContent, FrontMatter, HasAlignment, HasCaption, HasLabel, HeadingLevel,
+    ListItem, ListKind,
+};
+use somedoc::model::document::{Author, Metadata};
+use somedoc::model::inline::{
+    Character, HasInlineContent, HyperLinkTarget, InlineContent, SpanStyle,
+};
+use somedoc::model::{Document, HasInnerContent, HasStyles};
+use somedoc::read::pandoc::from_str;
+use somedoc::write::{write_document_to_string, OutputFormat};
+
+pub mod common;
+
+#[inline]
+fn assert_pandoc_reads(meta: &str, blocks: &str, is_valid: impl Fn(Document) -> bool) {
+    let json = format!(
+        "{{\"pandoc-api-version\":[1,23,1],\"meta\":{{{}}},\"blocks\":[{}]}}",
+        meta, blocks
+    );
+    let result = from_str(&json);
+    assert!(result.is_ok());
+    let doc = result.unwrap();
+    println!("{:#?}", doc);
+    assert!(is_valid(doc));
+}
+
+#[inline]
+fn round_trip(part_fn: impl Fn() -> Document) -> Document {
+    let json = write_document_to_string(&part_fn(), OutputFormat::PandocJson).unwrap();
+    let doc = from_str(&json).unwrap();
+    println!("{:#?}", doc);
+    doc
+}
+
+#[test]
+fn test_empty_document() {
+    assert_pandoc_reads("", "", |doc| !doc.has_metadata() && !doc.has_inner());
+}
+
+#[test]
+fn test_unsupported_version() {
+    let result = from_str(r#"{"pandoc-api-version":[2,0],"meta":{},"blocks":[]}"#);
+    assert!(matches!(
+        result.unwrap_err().kind(),
+        ErrorKind::IncompatibleVersion(version) if version == "2.0"
+    ));
+}
+
+#[test]
+fn test_not_pandoc_json() {
+    let result = from_str(r#"{"version":"0.2.10","content":[]}"#);
+    assert!(matches!(
+        result.unwrap_err().kind(),
+        ErrorKind::UnknownFormat
+    ));
+}
+
+#[test]
+fn test_metadata() {
+    assert_pandoc_reads(
+        r###""title":{"t":"MetaInlines","c":[{"t":"Str","c":"A"},{"t":"Space"},{"t":"Str","c":"Title"}]},
+            "author":{"t":"MetaList","c":[
+                {"t":"MetaInlines","c":[{"t":"Str","c":"Simon"}]},
+                {"t":"MetaMap","c":{"name":{"t":"MetaInlines","c":[{"t":"Str","c":"Someone"}]},"affiliation":{"t":"MetaInlines","c":[{"t":"Str","c":"Example"}]}}}]},
+            "keywords":{"t":"MetaList","c":[{"t":"MetaInlines","c":[{"t":"Str","c":"one"}]},{"t":"MetaInlines","c":[{"t":"Str","c":"two"}]}]},
+            "abstract":{"t":"MetaBlocks","c":[{"t":"Para","c":[{"t":"Str","c":"Summary."}]}]}"###,
+        "",
+        |doc| {
+            doc.metadata()
+                == &vec![
+                    Metadata::Author(Author {
+                        name: "Simon".to_string(),
+                        email: None,
+                        organization: None,
+                    }),
+                    Metadata::Author(Author {
+                        name: "Someone".to_string(),
+                        email: None,
+                        organization: Some("Example".to_string()),
+                    }),
+                    Metadata::Keywords(vec!["one".to_string(), "two".to_string()]),
+                    Metadata::Title("A Title".to_string()),
+                ]
+                && doc.abstract_block().as_ref().unwrap().unformatted_string() == "Summary."
+        },
+    );
+}
+
+#[test]
+fn test_headers_and_paragraphs() {
+    assert_pandoc_reads(
+        "",
+        r###"{"t":"Header","c":[2,["intro",["unnumbered"],[]],[{"t":"Str","c":"Introduction"}]]},
+            {"t":"Para","c":[{"t":"Str","c":"Some"},{"t":"Space"},{"t":"Emph","c":[{"t":"Str","c":"styled"}]},{"t":"SoftBreak"},{"t":"Strong","c":[{"t":"Str","c":"text"}]},{"t":"Str","c":"—done."}]},
+            {"t":"HorizontalRule"}"###,
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::Heading(heading), BlockContent::Paragraph(para), BlockContent::ThematicBreak] => {
+                heading.level() == &HeadingLevel::SubSection
+                    && heading.label().as_ref().unwrap().to_string() == "intro"
+                    && para.unformatted_string() == "Some styled text---done."
+                    && matches!(&para.inner()[1], InlineContent::Span(span) if span.styles() == &vec![SpanStyle::Italic])
+                    && matches!(&para.inner()[3], InlineContent::Span(span) if span.styles() == &vec![SpanStyle::Bold])
+                    && matches!(
+                        &para.inner()[4],
+                        InlineContent::Character(Character::EmDash)
+                    )
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_code_and_raw_blocks() {
+    assert_pandoc_reads(
+        "",
+        r###"{"t":"CodeBlock","c":[["",["rust","numberLines"],[]],"fn main() {}"]},
+            {"t":"CodeBlock","c":[["",[],[]],"plain"]},
+            {"t":"LineBlock","c":[[{"t":"Str","c":"one"}],[{"t":"Str","c":"two"}]]},
+            {"t":"RawBlock","c":["html","<!-- a comment -->"]},
+            {"t":"RawBlock","c":["latex","\\tableofcontents"]},
+            {"t":"RawBlock","c":["latex","\\newpage"]}"###,
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::CodeBlock(rust), BlockContent::CodeBlock(plain), BlockContent::Formatted(formatted), BlockContent::Comment(comment), BlockContent::FrontMatter(FrontMatter::TableOfContents), BlockContent::Comment(raw)] => {
+                rust.language() == &Some("rust".to_string())
+                    && plain.language().is_none()
+                    && formatted.inner() == "one\ntwo"
+                    && comment == "a comment"
+                    && raw == "\\newpage"
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_lists() {
+    assert_pandoc_reads(
+        "",
+        r###"{"t":"BulletList","c":[
+                [{"t":"Plain","c":[{"t":"Str","c":"one"}]}],
+                [{"t":"Para","c":[{"t":"Str","c":"two"}]},{"t":"OrderedList","c":[[1,{"t":"Decimal"},{"t":"Period"}],[[{"t":"Plain","c":[{"t":"Str","c":"inner"}]}]]]}]]},
+            {"t":"DefinitionList","c":[[[{"t":"Str","c":"Term"}],[[{"t":"Plain","c":[{"t":"Str","c":"Definition"}]}]]]]}"###,
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::List(list), BlockContent::DefinitionList(definitions)] => {
+                list.kind() == &ListKind::Unordered
+                    && matches!(list.inner().as_slice(), [ListItem::Item(one), ListItem::Item(two), ListItem::List(inner)]
+                        if one.unformatted_string() == "one"
+                            && two.unformatted_string() == "two"
+                            && inner.kind() == &ListKind::Ordered)
+                    && definitions.inner()[0].term() == "Term"
+                    && definitions.inner()[0].text().unformatted_string() == "Definition"
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_table() {
+    assert_pandoc_reads(
+        "",
+        r###"{"t":"Table","c":[["tbl",[],[]],[null,[{"t":"Plain","c":[{"t":"Str","c":"Caption"}]}]],
+            [[{"t":"AlignLeft"},{"t":"ColWidthDefault"}],[{"t":"AlignRight"},{"t":"ColWidthDefault"}]],
+            [["",[],[]],[[["",[],[]],[[["",[],[]],{"t":"AlignDefault"},1,1,[{"t":"Plain","c":[{"t":"Str","c":"Name"}]}]],[["",[],[]],{"t":"AlignDefault"},1,1,[{"t":"Plain","c":[{"t":"Str","c":"Value"}]}]]]]]],
+            [[["",[],[]],0,[],[[["",[],[]],[[["",[],[]],{"t":"AlignDefault"},1,1,[{"t":"Plain","c":[{"t":"Str","c":"a"}]}]],[["",[],[]],{"t":"AlignDefault"},1,1,[{"t":"Plain","c":[{"t":"Str","c":"1"}]}]]]]]]],
+            [["",[],[]],[]]]}"###,
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::Table(table)] => {
+                table.label().as_ref().unwrap().to_string() == "tbl"
+                    && table.caption().as_ref().unwrap().to_string() == "Caption"
+                    && table.columns().len() == 2
+                    && table.columns()[0].text() == "Name"
+                    && table.columns()[1].alignment() == &Alignment::Right
+                    && table.rows().len() == 1
+                    && table.rows()[0].cells()[1].unformatted_string() == "1"
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_links_math_and_images() {
+    assert_pandoc_reads(
+        "",
+        r###"{"t":"Para","c":[
+                {"t":"Link","c":[["",[],[]],[{"t":"Str","c":"example"}],["https://example.org/",""]]},
+                {"t":"Link","c":[["",[],[]],[{"t":"Str","c":"there"}],["#intro",""]]},
+                {"t":"Math","c":[{"t":"InlineMath"},"x^2"]},
+                {"t":"Image","c":[["",[],[]],[{"t":"Str","c":"logo"}],["logo.png",""]]}]},
+            {"t":"Para","c":[{"t":"Math","c":[{"t":"DisplayMath"},"y=x^2"]}]},
+            {"t":"Figure","c":[["fig",[],[]],[null,[{"t":"Plain","c":[{"t":"Str","c":"A"},{"t":"Space"},{"t":"Str","c":"figure"}]}]],
+                [{"t":"Plain","c":[{"t":"Image","c":[["",[],[]],[],["figure.png",""]]}]}]]}"###,
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::Paragraph(para), BlockContent::MathBlock(math), BlockContent::ImageBlock(image)] => {
+                matches!(&para.inner()[0], InlineContent::HyperLink(link)
+                    if link.target() == &HyperLinkTarget::External("https://example.org/".to_string()))
+                    && matches!(&para.inner()[1], InlineContent::HyperLink(link) if link.is_internal())
+                    && matches!(&para.inner()[2], InlineContent::Math(math) if math.to_string() == "x^2")
+                    && matches!(&para.inner()[3], InlineContent::Image(image) if image.inner() == "logo.png")
+                    && math.inner().to_string() == "y=x^2"
+                    && image.label().as_ref().unwrap().to_string() == "fig"
+                    && image.caption().as_ref().unwrap().to_string() == "A figure"
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_div_label() {
+    assert_pandoc_reads(
+        "",
+        r###"{"t":"Div","c":[["note",["warning"],[]],[{"t":"Para","c":[{"t":"Str","c":"Careful."}]}]]}"###,
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::Paragraph(para)] => {
+                para.label().as_ref().unwrap().to_string() == "note"
+                    && para.alignment() == &Alignment::Left
+            }
+            _ => false,
+        },
+    );
+}
+
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn test_round_trip_skos() {
+    let doc = round_trip(common::skos::document);
+    assert!(common::skos::document()
+        .metadata()
+        .iter()
+        .all(|datum| doc.metadata().contains(datum)));
+    assert_eq!(doc.inner().len(), common::skos::document().inner().len());
+    assert!(
+        matches!(&doc.inner()[1], BlockContent::Comment(comment) if comment.starts_with("TODO:"))
+    );
+    assert!(matches!(&doc.inner()[5], BlockContent::Quote(quote) if quote.inner().len() == 2));
+    assert!(matches!(&doc.inner()[6], BlockContent::Table(table)
+        if table.has_caption() && table.columns().len() == 2 && table.rows().len() == 1));
+}
+
+#[test]
+fn test_round_trip_nested_mixed_lists() {
+    let doc = round_trip(common::parts::nested_mixed_lists);
+    assert!(matches!(doc.inner().as_slice(), [BlockContent::List(list)]
+        if matches!(list.inner().as_slice(), [ListItem::Item(_), ListItem::Item(_), ListItem::List(inner), ListItem::Item(_)]
+            if inner.kind() == &ListKind::Ordered
+                && matches!(inner.inner().as_slice(), [ListItem::Item(_), ListItem::List(_), ListItem::Item(_)]))));
+}
+
+#[test]
+fn test_round_trip_labeled_ordered_list() {
+    let doc = round_trip(common::parts::labeled_ordered_list);
+    assert!(matches!(doc.inner().as_slice(), [BlockContent::List(list)]
+        if list.label().as_ref().unwrap().to_string() == "lst1"
+            && matches!(&list.inner()[1], ListItem::Item(item)
+                if item.label().as_ref().unwrap().to_string() == "lst1-itm2")));
+}
+
+#[test]
+fn test_round_trip_captioned_blocks() {
+    for part_fn in &[
+        common::parts::image_block_with_label_and_caption,
+        common::parts::math_block_with_label_and_caption,
+    ] {
+        let doc = round_trip(part_fn);
+        assert!(match doc.inner().as_slice() {
+            [BlockContent::ImageBlock(block)] => block.has_label() && block.has_caption(),
+            [BlockContent::MathBlock(block)] => block.has_label() && block.has_caption(),
+            _ => false,
+        });
+    }
+}
+
+#[test]
+fn test_round_trip_text_styles() {
+    let original = common::parts::text_styles();
+    let doc = round_trip(common::parts::text_styles);
+    assert!(
+        matches!((doc.inner().as_slice(), original.inner().as_slice()),
+        ([BlockContent::Paragraph(para)], [BlockContent::Paragraph(expected)])
+            if para.unformatted_string() == expected.unformatted_string())
+    );
+}
+
+#[test]
+fn test_round_trip_hyper_links() {
+    let doc = round_trip(common::parts::hyper_links);
+    let links: Vec<(bool, bool)> = doc
+        .inner()
+        .iter()
+        .filter_map(|block| match block {
+            BlockContent::Paragraph(para) => match para.inner().as_slice() {
+                [InlineContent::HyperLink(link)] => Some((link.is_internal(), link.has_caption())),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        links,
+        vec![(false, false), (false, true), (true, false), (true, true)]
+    );
+}
@@ -0,0 +1,342 @@
+use somedoc::model::block::{
+    BlockContent, FrontMatter, HasBlockContent, HasCaption, HasLabel, HeadingLevel, ListItem,
+    ListKind, Paragraph,
+};
+use somedoc::model::document::{Author, Metadata};
+use somedoc::model::inline::{
+    Character, HasInlineContent, HyperLinkTarget, InlineContent, SpanStyle,
+};
+use somedoc::model::{Document, HasInnerContent, HasStyles};
+use somedoc::read::markdown::from_str_with;
+use somedoc::read::xwiki::from_str;
+use somedoc::write::markdown::MarkdownFlavor;
+use somedoc::write::write_document_to_string;
+
+pub mod common;
+
+#[inline]
+fn assert_xwiki_reads(xwiki: &str, is_valid: impl Fn(Document) -> bool) {
+    let result = from_str(xwiki);
+    assert!(result.is_ok());
+    let doc = result.unwrap();
+    println!("{:#?}", doc);
+    assert!(is_valid(doc));
+}
+
+#[inline]
+fn round_trip(part_fn: impl Fn() -> Document) -> Document {
+    let xwiki = write_document_to_string(&part_fn(), MarkdownFlavor::XWiki.into()).unwrap();
+    let doc = from_str_with(&xwiki, MarkdownFlavor::XWiki).unwrap();
+    println!("{:#?}", doc);
+    doc
+}
+
+#[test]
+fn test_empty_document() {
+    assert_xwiki_reads("", |doc| !doc.has_metadata() && !doc.has_inner());
+}
+
+#[test]
+fn test_headings_and_labels() {
+    assert_xwiki_reads(
+        "= One =\n\n{{id name=\"three\" /}}\n=== Three ===\n\n====== {{id name=\"six\"/}}Six ======\n",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::Heading(one), BlockContent::Heading(three), BlockContent::Heading(six)] => {
+                one.level() == &HeadingLevel::Section
+                    && one.label().is_none()
+                    && three.level() == &HeadingLevel::SubSubSection
+                    && three.label().as_ref().unwrap().to_string() == "three"
+                    && six.level() == &HeadingLevel::SubSubSubSubSubSection
+                    && six.label().as_ref().unwrap().to_string() == "six"
+                    && six.unformatted_string() == "Six"
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_text_styles() {
+    assert_xwiki_reads(
+        "//i// **b** --d-- __u__ ^^p^^ ,,s,, ##m## http://example.org/ a -- b",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::Paragraph(paragraph)] => {
+                let styles: Vec<Vec<SpanStyle>> = paragraph
+                    .inner()
+                    .iter()
+                    .filter_map(|inline| match inline {
+                        InlineContent::Span(span) => Some(span.styles().clone()),
+                        _ => None,
+                    })
+                    .collect();
+                styles
+                    == vec![
+                        vec![SpanStyle::Italic],
+                        vec![SpanStyle::Bold],
+                        vec![SpanStyle::Strikethrough],
+                        vec![SpanStyle::Underline],
+                        vec![SpanStyle::Superscript],
+                        vec![SpanStyle::Subscript],
+                        vec![SpanStyle::Mono],
+                    ]
+                    && paragraph.inner().iter().any(|inline| {
+                        matches!(inline, InlineContent::Text(text) if text.inner().contains("http://example.org/"))
+                    })
+                    && paragraph
+                        .inner()
+                        .iter()
+                        .any(|inline| matches!(inline, InlineContent::Character(Character::EnDash)))
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_escapes_and_line_breaks() {
+    assert_xwiki_reads("~**not bold~** and\\\\broken\nline", |doc| {
+        match doc.inner().as_slice() {
+            [BlockContent::Paragraph(paragraph)] => matches!(
+                paragraph.inner().as_slice(),
+                [InlineContent::Text(first), InlineContent::LineBreak, InlineContent::Text(_), InlineContent::LineBreak, InlineContent::Text(_)]
+                    if first.inner() == "**not bold** and"
+            ),
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_macros() {
+    assert_xwiki_reads(
+        "{{toc/}}\n\n{{code language=\"rust\"}}\nfn main() {}\n{{/code}}\n\n{{comment}}\nNot metadata.\n{{/comment}}\n\n{{info}}\nSomething\n{{/info}}\n\nSome {{unknown/}} macro.\n",
+        |doc| match doc.inner().as_slice() {
+            [BlockContent::FrontMatter(FrontMatter::TableOfContents), BlockContent::CodeBlock(code), BlockContent::Comment(comment), BlockContent::Comment(info), BlockContent::Paragraph(paragraph)] => {
+                code.code() == "fn main() {}"
+                    && code.language() == &Some("rust".to_string())
+                    && comment == "Not metadata."
+                    && info == "{{info}}\nSomething\n{{/info}}"
+                    && paragraph.unformatted_string() == "Some {{unknown/}} macro."
+            }
+            _ => false,
+        },
+    );
+}
+
+#[test]
+fn test_metadata() {
+    let doc = Document::default()
+        .set_title("A Title")
+        .add_metadata(Metadata::Author(Author {
+            name: "Simon".to_string(),
+            email: Some("simon@example.org".to_string()),
+            organization: Some("Example Org".to_string()),
+        }))
+        .add_paragraph(Paragraph::plain_str("Text."))
+        .clone();
+    let doc = round_trip(|| doc.clone());
+    assert_eq!(
+        doc.metadata(),
+        &vec![
+            Metadata::Title("A Title".to_string()),
+            Metadata::Author(Author {
+                name: "Simon".to_string(),
+                email: Some("simon@example.org".to_string()),
+                organization: Some("Example Org".to_string()),
+            }),
+        ]
+    );
+    assert!(matches!(
+        doc.inner().as_slice(),
+        [BlockContent::Paragraph(_)]
+    ));
+}
+
+#[test]
+fn test_round_trip_skos() {
+    let doc = round_trip(common::skos::document);
+    assert_eq!(doc.metadata().len(), 2);
+    assert!(matches!(
+        &doc.inner()[1],
+        BlockContent::Comment(comment) if comment == "TODO:\n- more nested lists\n- tables"
+    ));
+    assert!(matches!(
+        &doc.inner()[4],
+        BlockContent::Heading(heading) if heading.label().as_ref().unwrap().to_string() == "Labels"
+    ));
+    assert!(matches!(&doc.inner()[5], BlockContent::Quote(quote) if quote.inner().len() == 2));
+    let table = doc
+        .inner()
+        .iter()
+        .find_map(|block| match block {
+            BlockContent::Table(table) => Some(table),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(table.columns()[0].text(), "Label text");
+    assert_eq!(table.rows().len(), 1);
+    assert!(matches!(
+        table.rows()[0].cells()[1].inner().as_slice(),
+        [InlineContent::Span(span)] if span.styles() == &vec![SpanStyle::Bold]
+    ));
+    let code = doc
+        .inner()
+        .iter()
+        .find_map(|block| match block {
+            BlockContent::CodeBlock(code) => Some(code),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(code.language(), &Some("turtle".to_string()));
+    assert_eq!(code.code(), "@prefix foo: <...>\nfoo:bar foo:baz 12.");
+    assert!(matches!(
+        doc.inner().last().unwrap(),
+        BlockContent::Formatted(formatted) if formatted.inner() == "@prefix foo: <...>\nfoo:bar foo:baz 12."
+    ));
+}
+
+#[test]
+fn test_round_trip_nested_mixed_lists() {
+    let doc = round_trip(common::parts::nested_mixed_lists);
+    match doc.inner().as_slice() {
+        [BlockContent::List(list)] => {
+            assert_eq!(list.inner().len(), 4);
+            match &list.inner()[2] {
+                ListItem::List(inner) => {
+                    assert_eq!(*inner.kind(), ListKind::Ordered);
+                    assert_eq!(inner.inner().len(), 3);
+                    assert!(matches!(
+                        &inner.inner()[1],
+                        ListItem::List(inner) if *inner.kind() == ListKind::Unordered
+                    ));
+                }
+                _ => panic!("expected a sub-list"),
+            }
+        }
+        _ => panic!("expected a single list"),
+    }
+}
+
+#[test]
+fn test_round_trip_definition_list() {
+    let doc = round_trip(common::parts::definition_list);
+    assert!(matches!(
+        doc.inner().as_slice(),
+        [BlockContent::DefinitionList(list)] if list.inner().len() == 1
+            && list.inner()[0].term() == "Universe"
+            && list.inner()[0].text().unformatted_string() == "Big, really big"
+    ));
+}
+
+#[test]
+fn test_round_trip_nested_block_quotes() {
+    let doc = round_trip(common::parts::nested_block_quotes);
+    match doc.inner().as_slice() {
+        [BlockContent::Quote(outer)] => {
+            assert!(matches!(
+                outer.inner().as_slice(),
+                [BlockContent::Paragraph(_), BlockContent::Quote(inner)] if inner.has_inner()
+            ));
+        }
+        _ => panic!("expected a single quote"),
+    }
+}
+
+#[test]
+fn test_round_trip_image_and_math_blocks() {
+    let doc = round_trip(common::parts::image_block_with_label_and_caption);
+    assert!(matches!(
+        doc.inner().as_slice(),
+        [BlockContent::ImageBlock(image)] if image.label().as_ref().unwrap().to_string() == "img:example"
+            && image.inner().inner() == "https://example.org/example.png"
+    ));
+    let doc = round_trip(common::parts::math_block_with_label_and_caption);
+    assert!(matches!(
+        doc.inner().as_slice(),
+        [BlockContent::MathBlock(math)] if math.label().as_ref().unwrap().to_string() == "math:example"
+            && math.inner().inner() == "x=2+2^2"
+    ));
+}
+
+#[test]
+fn test_round_trip_hyper_links() {
+    let doc = round_trip(common::parts::hyper_links);
+    let links: Vec<(HyperLinkTarget, bool)> = doc
+        .inner()
+        .iter()
+        .filter_map(|block| match block {
+            BlockContent::Paragraph(paragraph) => match paragraph.inner().as_slice() {
+                [InlineContent::HyperLink(link)] => {
+                    Some((link.target().clone(), link.caption().is_some()))
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(links.len(), 4);
+    assert!(matches!(&links[0], (HyperLinkTarget::External(_), false)));
+    assert!(matches!(&links[1], (HyperLinkTarget::External(_), true)));
+    assert!(matches!(&links[2], (HyperLinkTarget::Internal(_), false)));
+    assert!(matches!(&links[3], (HyperLinkTarget::Internal(_), true)));
+}
+
+#[test]
+fn test_round_trip_complex_paragraph() {
+    let doc = round_trip(common::parts::complex_paragraph);
+    match doc.inner().as_slice() {
+        [BlockContent::Paragraph(paragraph)] => {
+            let inner = paragraph.inner();
+            assert!(inner.iter().any(
+                |inline| matches!(inline, InlineContent::Math(math) if math.inner() == "x=2+2^2")
+            ));
+            assert!(inner
+                .iter()
+                .any(|inline| matches!(inline, InlineContent::LineBreak)));
+            assert!(inner.iter().any(|inline| matches!(
+                inline,
+                InlineContent::Image(image) if image.inner() == "https://example.org/favicon.png"
+            )));
+            assert!(inner
+                .iter()
+                .any(|inline| matches!(inline, InlineContent::Character(Character::EmDash))));
+        }
+        _ => panic!("expected a single paragraph"),
+    }
+}
+
+#[test]
+fn test_round_trip_text_styles() {
+    let original = common::parts::text_styles();
+    let doc = round_trip(common::parts::text_styles);
+    match (original.inner().as_slice(), doc.inner().as_slice()) {
+        ([BlockContent::Paragraph(original)], [BlockContent::Paragraph(paragraph)]) => {
+            let styles: Vec<Vec<SpanStyle>> = paragraph
+                .inner()
+                .iter()
+                .filter_map(|inline| match inline {
+                    InlineContent::Span(span) => Some(span.styles().clone()),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(
+                styles,
+                vec![
+                    vec![SpanStyle::Bold],
+                    vec![SpanStyle::Italic],
+                    vec![SpanStyle::Mono],
+                    vec![SpanStyle::Mono],
+                    vec![SpanStyle::Strikethrough],
+                    vec![SpanStyle::Underline],
+                    vec![SpanStyle::Superscript],
+                    vec![SpanStyle::Subscript],
+                ]
+            );
+            assert_eq!(
+                paragraph.unformatted_string(),
+                original.unformatted_string()
+            );
+        }
+        _ => panic!("expected a single paragraph"),
+    }
+}